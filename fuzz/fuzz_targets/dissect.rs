@@ -0,0 +1,47 @@
+//! Feeds arbitrary frames through the Ethernet dissector and everything below it (VLAN,
+//! MPLS, ARP, LLDP, IP, the transports and the application protocols) to check that no
+//! input makes it panic. Run with `cargo +nightly fuzz run dissect` from the repository root.
+//!
+//! The sniffer lives in the binary crate, so its modules are compiled in here directly.
+#![no_main]
+#![allow(dead_code)]
+
+#[path = "../../src/app_protocol.rs"]
+mod app_protocol;
+#[path = "../../src/event.rs"]
+mod event;
+#[path = "../../src/expert.rs"]
+mod expert;
+#[path = "../../src/flows.rs"]
+mod flows;
+#[path = "../../src/packet_data.rs"]
+mod packet_data;
+#[path = "../../src/reassembly.rs"]
+mod reassembly;
+#[path = "../../src/sniffer.rs"]
+mod sniffer;
+#[path = "../../src/strings.rs"]
+mod strings;
+
+use libfuzzer_sys::fuzz_target;
+
+use app_protocol::DecodeAs;
+use packet_data::CompletePacket;
+use sniffer::{LinkType, Sniffer};
+
+// The first byte picks whether the frame ends with an FCS, so both paths get fuzzed.
+fuzz_target!(|input: &[u8]| {
+    let Some((&flags, frame)) = input.split_first() else {
+        return;
+    };
+    let fcs = flags & 1 != 0;
+    let mut packet = CompletePacket::new(1, "fuzz", frame, None);
+    Sniffer::handle_frame(
+        LinkType::Ethernet,
+        frame,
+        fcs,
+        &DecodeAs::default(),
+        &mut packet,
+    );
+    expert::ExpertAnalyzer::default().analyze(&packet);
+});
@@ -1,132 +1,1692 @@
 use crate::{
+    app_protocol::DecodeAs,
+    cli::Args,
+    clipboard::{self, ClipboardWorker},
+    config::Config,
     event::Event,
+    event_log::EventLog,
+    expert::{ExpertAnalyzer, ExpertInfo, Severity},
+    filter::{DirectionFilter, DisplayFilter, FilterPreset, HighlightRules, ProtocolToggles},
+    flows::{TcpFlowTracker, TcpRttEstimator, TcpStateTracker, UdpStream},
+    geoip::GeoIp,
+    interface_stats::{self, InterfaceStats},
+    json_export::JsonLinesWriter,
+    keymap::{Action, KeyContext, Keymap},
     packet_data::CompletePacket,
-    sniffer::Sniffer,
+    pcap_export::PcapWriter,
+    resolver::Resolver,
+    settings::Settings,
+    sniffer::{CaptureOptions, Sniffer},
+    stats::{CaptureSummary, PacketRate, TalkerStats},
+    strings,
+    theme::Theme,
     widgets::{
-        charts::ChartWidget, footer::Footer, interfaces::InterfacesWidget, layout_helper::LayoutHelper, popup::PopupWidget
+        charts::{ChartMode, ChartWidget},
+        columns::ColumnsWidget,
+        command_palette::CommandPaletteWidget,
+        comparison::ComparisonWidget,
+        conversations::{ConversationSort, ConversationsWidget},
+        error_dialog::ErrorDialog,
+        event_log::EventLogWidget,
+        expert_info::ExpertInfoWidget,
+        filter_presets::FilterPresetsWidget,
+        footer::Footer,
+        interfaces::InterfacesWidget,
+        layout_helper::{LayoutHelper, PanelLayout},
+        packet_table::{Column, PacketTable, PacketTableState},
+        popup::{PopupState, PopupWidget},
+        stream::StreamWidget,
+        top_talkers::TopTalkersWidget,
     },
 };
-use crossterm::event::{KeyCode, KeyEventKind};
+use chrono::Local;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
 use pnet::datalink::{self, NetworkInterface};
 use ratatui::{
-    widgets::{ScrollbarState, TableState},
+    layout::{Constraint, Flex, Layout, Position, Rect},
+    widgets::{Block, Borders, Paragraph, ScrollbarState, TableState, Wrap},
     DefaultTerminal, Frame,
 };
 use std::{
+    collections::{HashMap, HashSet},
+    io,
     net::IpAddr,
-    sync::mpsc,
-    thread::{self},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc, PoisonError,
+    },
+    thread::{self, JoinHandle},
     time::{Duration, Instant},
 };
 
+#[derive(PartialEq)]
+enum InputMode {
+    Normal,
+    Search,
+    Filter,
+    GoToPacket,
+    DecodeAs,
+    SavePcap,
+    /// Editing the comment of the packet with this id.
+    Comment(usize),
+}
+
 pub struct App {
     exit: bool,
+    input_mode: InputMode,
+    input_buffer: String,
+    last_search: Option<String>,
+    status_message: Option<String>,
     interfaces_table_state: TableState,
     interfaces_scroll_state: ScrollbarState,
+    /// Captured packets, oldest first so a new one is appended rather than shifting the
+    /// rest; the table lists them newest first by reading backwards.
     packets: Vec<CompletePacket>,
+    /// Indices in `packets` of the packets shown in the table. Extended as packets arrive
+    /// and worked out again only when the filters change, rather than on every frame.
+    visible_rows: Vec<usize>,
+    packet_table_state: PacketTableState,
+    packet_table_area: Rect,
+    popup_area: Rect,
     pub action_tx: mpsc::Sender<Event>,
     pub action_rx: mpsc::Receiver<Event>,
     pub interface: Option<NetworkInterface>,
     pub interfaces: Vec<NetworkInterface>,
+    /// MTU of the listed interfaces, where the system reports it.
+    interface_mtus: HashMap<String, usize>,
     show_popup: bool,
     selected_popup_packet: Option<CompletePacket>,
+    popup_state: PopupState,
+    popup_size: u16,
+    /// Whether the popup is enlarged to nearly the whole terminal, for packets with many
+    /// layers.
+    popup_maximized: bool,
+    /// Frame bytes the popup shows until all are asked for; 0 for no limit.
+    popup_max_bytes: usize,
     sniffer: Sniffer,
+    theme: Theme,
+    /// Which of `Theme::NAMES` is in use, and the config file's theme to go back to.
+    theme_name: &'static str,
+    configured_theme: Theme,
+    keymap: Keymap,
+    clipboard: Option<ClipboardWorker>,
+    show_conversations: bool,
+    conversation_sort: ConversationSort,
+    conversation_ports: bool,
+    talker_stats: TalkerStats,
+    packet_rate: PacketRate,
+    tcp_flows: TcpFlowTracker,
+    tcp_rtt: TcpRttEstimator,
+    tcp_states: TcpStateTracker,
+    show_top_talkers: bool,
+    /// Ids of the packets marked while triaging, so marks follow packets as rows shift.
+    bookmarks: HashSet<usize>,
+    expert_analyzer: ExpertAnalyzer,
+    /// Findings for every captured packet, appended like `packets` and listed newest
+    /// first. A packet's findings are added least severe first, so read backwards they
+    /// come most severe first.
+    expert_info: Vec<ExpertInfo>,
+    show_expert_info: bool,
+    expert_info_state: TableState,
+    highlight_expert_info: bool,
+    /// Rules coloring the rows of the packets their filters match.
+    highlighting: HighlightRules,
+    /// Errors and notable events, kept after the footer moved on to the next message.
+    event_log: EventLog,
+    show_event_log: bool,
+    event_log_state: TableState,
+    chart_mode: ChartMode,
+    resolver: Option<Resolver>,
+    geoip: Option<GeoIp>,
+    resolve_names: bool,
+    preferred_interface: Option<String>,
+    /// List interfaces without a private IPv4 address too.
+    all_interfaces: bool,
+    interface_stats: InterfaceStats,
+    sniffer_error: Option<String>,
+    display_filter: Option<DisplayFilter>,
+    /// Presets from the config file followed by the ones saved from the picker.
+    filter_presets: Vec<FilterPreset>,
+    /// How many of `filter_presets` come from the config file and can't be deleted.
+    config_preset_count: usize,
+    show_filter_presets: bool,
+    filter_presets_state: TableState,
+    /// Packet table columns in the order they're shown.
+    columns: Vec<Column>,
+    show_columns: bool,
+    columns_state: TableState,
+    show_command_palette: bool,
+    command_palette_query: String,
+    command_palette_state: TableState,
+    /// Id of the packet marked as the first one to compare.
+    compare_mark: Option<usize>,
+    compared_packets: [Option<CompletePacket>; 2],
+    show_comparison: bool,
+    comparison_state: TableState,
+    /// The UDP flow being followed, while its stream is shown, and the line it starts at.
+    followed_stream: Option<UdpStream>,
+    stream_scroll: u16,
+    comparison_differences_only: bool,
+    protocol_toggles: ProtocolToggles,
+    direction_filter: DirectionFilter,
+    dim_unmatched: bool,
+    verify_checksums: bool,
+    /// Stream of captured packets for `--json-out`, dropped after a write fails.
+    json_output: Option<JsonLinesWriter>,
+    /// Whether the capture starts with the app, or waits for the sniffer toggle.
+    auto_start: bool,
+    /// When the capture first started, which `--duration` counts from.
+    capture_started_at: Option<Instant>,
+    /// `--count` and `--duration`, cleared once either stops the capture.
+    packet_limit: Option<usize>,
+    duration_limit: Option<Duration>,
+    exit_on_limit: bool,
+    /// Set when a limit stopped the capture, so packets still in flight are dropped.
+    stopped_at_limit: bool,
+    /// Whether `run` returns a summary of the session, to print once the terminal is back.
+    print_summary: bool,
+    panel_layout: PanelLayout,
+    frame_interval: Duration,
+    shutdown: Arc<AtomicBool>,
+    background_threads: Vec<JoinHandle<()>>,
 }
 
 impl App {
-    pub fn new() -> Self {
+    /// Redraw at least this often even when nothing happens, so the charts and interface
+    /// counters keep moving on a quiet link.
+    const IDLE_REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+    /// Seconds of history in the footer's packet-rate sparkline, one column each.
+    const PACKET_RATE_SECONDS: usize = 20;
+    /// Rows a page key scrolls the TCP options in the packet details.
+    const OPTIONS_PAGE: isize = 4;
+    /// Lines a page key scrolls a followed stream.
+    const STREAM_PAGE: u16 = 20;
+    const ANY_INTERFACE: &'static str = "any";
+    /// Below this size the panels and popups can't fit, so only a notice is drawn.
+    const MIN_WIDTH: u16 = 80;
+    const MIN_HEIGHT: u16 = 24;
+
+    pub fn new(config: Config, args: &Args, settings: Settings) -> Self {
         let (action_tx, action_rx) = mpsc::channel();
+        let resolver = (!args.no_resolve).then(Resolver::new);
+        let mut sniffer = Sniffer::new();
+        sniffer.options = CaptureOptions {
+            snaplen: args
+                .snaplen
+                .map(|snaplen| snaplen as usize)
+                .or(config.snaplen),
+            promiscuous: config.promiscuous && !args.no_promisc,
+            read_buffer_size: args
+                .buffer_size
+                .map(|size| size as usize)
+                .or(config.read_buffer_size)
+                .unwrap_or(CaptureOptions::DEFAULT_BUFFER_SIZE),
+            write_buffer_size: config
+                .write_buffer_size
+                .unwrap_or(CaptureOptions::DEFAULT_BUFFER_SIZE),
+            read_timeout: config
+                .read_timeout_ms
+                .map_or(CaptureOptions::DEFAULT_READ_TIMEOUT, Duration::from_millis),
+            sample: args.sample.filter(|&sample| sample > 1),
+            fcs: args.fcs || config.fcs,
+        };
+        let theme_name = settings
+            .theme
+            .as_deref()
+            .and_then(|saved| Theme::NAMES.into_iter().find(|&name| name == saved))
+            .unwrap_or(Theme::NAMES[0]);
         App {
             exit: false,
+            input_mode: InputMode::Normal,
+            input_buffer: String::new(),
+            last_search: None,
+            status_message: None,
             interfaces_table_state: TableState::default().with_selected(0),
             interfaces_scroll_state: ScrollbarState::new(0),
             packets: Vec::new(),
+            visible_rows: Vec::new(),
+            packet_table_state: PacketTableState {
+                compact: settings.compact_rows,
+                oldest_first: settings.oldest_first,
+                ..PacketTableState::new(config.wrap_navigation)
+            },
+            packet_table_area: Rect::default(),
+            popup_area: Rect::default(),
             action_tx,
             action_rx,
             interface: None,
             interfaces: Vec::new(),
+            interface_mtus: HashMap::new(),
             show_popup: false,
             selected_popup_packet: None,
-            sniffer: Sniffer::new(),
+            popup_state: PopupState::default(),
+            popup_size: config
+                .popup_size
+                .unwrap_or(PopupWidget::DEFAULT_SIZE_PERCENT),
+            popup_maximized: false,
+            popup_max_bytes: config.popup_max_bytes,
+            sniffer,
+            theme: Theme::named(theme_name, &config.theme).unwrap_or_default(),
+            theme_name,
+            configured_theme: config.theme,
+            keymap: config.keymap,
+            clipboard: None,
+            show_conversations: false,
+            conversation_sort: ConversationSort::default(),
+            conversation_ports: false,
+            talker_stats: TalkerStats::default(),
+            packet_rate: PacketRate::default(),
+            tcp_flows: TcpFlowTracker::default(),
+            tcp_rtt: TcpRttEstimator::default(),
+            tcp_states: TcpStateTracker::default(),
+            show_top_talkers: false,
+            bookmarks: HashSet::new(),
+            expert_analyzer: ExpertAnalyzer::default(),
+            expert_info: Vec::new(),
+            show_expert_info: false,
+            expert_info_state: TableState::default().with_selected(0),
+            highlight_expert_info: config.highlight_expert_info,
+            highlighting: config.highlighting,
+            event_log: EventLog::default(),
+            show_event_log: false,
+            event_log_state: TableState::default().with_selected(0),
+            chart_mode: ChartMode::default(),
+            resolve_names: settings.resolve_names && resolver.is_some(),
+            resolver,
+            geoip: None,
+            preferred_interface: args.interface.clone().or(settings.interface),
+            all_interfaces: args.all_interfaces,
+            interface_stats: InterfaceStats::default(),
+            sniffer_error: None,
+            display_filter: settings
+                .display_filter
+                .and_then(|expression| DisplayFilter::parse(&expression).ok()),
+            config_preset_count: config.filter_presets.len(),
+            filter_presets: config
+                .filter_presets
+                .into_iter()
+                .chain(settings.filter_presets)
+                .collect(),
+            show_filter_presets: false,
+            filter_presets_state: TableState::default().with_selected(0),
+            columns: config
+                .columns
+                .or(settings.columns)
+                .filter(|columns| !columns.is_empty())
+                .unwrap_or_else(|| Column::DEFAULTS.to_vec()),
+            show_columns: false,
+            columns_state: TableState::default().with_selected(0),
+            show_command_palette: false,
+            command_palette_query: String::new(),
+            command_palette_state: TableState::default().with_selected(0),
+            compare_mark: None,
+            compared_packets: [None, None],
+            show_comparison: false,
+            comparison_state: TableState::default().with_selected(0),
+            followed_stream: None,
+            stream_scroll: 0,
+            comparison_differences_only: false,
+            protocol_toggles: ProtocolToggles::default(),
+            direction_filter: config.direction,
+            dim_unmatched: false,
+            verify_checksums: args.verify_checksums || config.verify_checksums,
+            json_output: None,
+            auto_start: !args.no_start && config.auto_start,
+            capture_started_at: None,
+            packet_limit: args.count.map(|count| count as usize),
+            duration_limit: args.duration,
+            exit_on_limit: args.exit_on_limit,
+            stopped_at_limit: false,
+            print_summary: args.summary,
+            panel_layout: config.layout.or(settings.layout).unwrap_or_default(),
+            frame_interval: Duration::from_secs(1)
+                / args.max_fps.unwrap_or(config.max_fps).clamp(1, 240),
+            shutdown: Arc::new(AtomicBool::new(false)),
+            background_threads: Vec::new(),
         }
     }
 
+    /// Shows `message` in the footer and records it in the event log.
+    pub fn notify(&mut self, severity: Severity, message: String) {
+        self.event_log.push(severity, message.clone());
+        self.status_message = Some(message);
+    }
+
+    pub fn stream_json(&mut self, writer: JsonLinesWriter) {
+        self.json_output = Some(writer);
+    }
+
+    pub fn annotate_geoip(&mut self, geoip: GeoIp) {
+        self.geoip = Some(geoip);
+    }
+
     fn handle_key_event(
         &mut self,
         key_event: crossterm::event::KeyEvent,
     ) -> color_eyre::Result<()> {
         if key_event.kind == KeyEventKind::Press {
-            match key_event.code {
-                KeyCode::Char('q') => self.exit = true,
-                KeyCode::Char('j') | KeyCode::Down => self.sniffer.next_row(),
-                KeyCode::Char('k') | KeyCode::Up => self.sniffer.previous_row(),
-                KeyCode::Char('i') => self.next_active_interface(),
-                KeyCode::Char('p') => self.toggle_sniffer(),
-                KeyCode::Enter => self.toggle_popup(),
-                _ => {}
+            match self.input_mode {
+                InputMode::Normal => self.handle_normal_key(key_event),
+                InputMode::Search => self.handle_search_key(key_event.code),
+                InputMode::Filter => self.handle_filter_key(key_event.code),
+                InputMode::GoToPacket => self.handle_go_to_packet_key(key_event.code),
+                InputMode::DecodeAs => self.handle_decode_as_key(key_event.code),
+                InputMode::SavePcap => self.handle_save_pcap_key(key_event.code),
+                InputMode::Comment(packet_id) => self.handle_comment_key(packet_id, key_event.code),
             }
         }
         Ok(())
     }
 
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) {
+        if self.input_mode != InputMode::Normal
+            || self.sniffer_error.is_some()
+            || self.show_conversations
+            || self.show_expert_info
+            || self.show_event_log
+            || self.show_filter_presets
+            || self.show_columns
+            || self.show_comparison
+            || self.followed_stream.is_some()
+            || self.show_command_palette
+        {
+            return;
+        }
+        let position = Position::new(mouse_event.column, mouse_event.row);
+
+        if self.show_popup {
+            if mouse_event.kind == MouseEventKind::Down(MouseButton::Left)
+                && !self.popup_area.contains(position)
+            {
+                self.show_popup = false;
+            }
+            return;
+        }
+
+        match mouse_event.kind {
+            MouseEventKind::ScrollDown => self.packet_table_state.next_row(self.visible_rows.len()),
+            MouseEventKind::ScrollUp => self
+                .packet_table_state
+                .previous_row(self.visible_rows.len()),
+            MouseEventKind::Down(MouseButton::Left)
+                if self.packet_table_area.contains(position) =>
+            {
+                let clicked_row = self
+                    .packet_table_state
+                    .row_at(self.packet_table_area, mouse_event.row)
+                    .filter(|&index| index < self.visible_rows.len());
+                if let Some(index) = clicked_row {
+                    self.packet_table_state.select(index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_normal_key(&mut self, key_event: KeyEvent) {
+        self.status_message = None;
+        let action = self.keymap.action(KeyContext::PacketList, key_event);
+        let panel_action = |context| self.keymap.action(context, key_event);
+        if self.sniffer_error.is_some() {
+            match (action, key_event.code) {
+                (Some(Action::Quit), _) => self.exit = true,
+                (_, KeyCode::Esc | KeyCode::Enter) => self.sniffer_error = None,
+                (Some(Action::NextInterface), _) => {
+                    self.sniffer_error = None;
+                    self.next_active_interface();
+                }
+                _ => {}
+            }
+            return;
+        }
+        if self.show_command_palette {
+            self.handle_command_palette_key(action, key_event.code);
+            return;
+        }
+        if self.show_conversations {
+            self.handle_conversations_key(panel_action(KeyContext::Conversations), key_event.code);
+            return;
+        }
+        if self.show_expert_info {
+            self.handle_expert_info_key(action, key_event.code);
+            return;
+        }
+        if self.show_filter_presets {
+            self.handle_filter_presets_key(panel_action(KeyContext::FilterPresets), key_event.code);
+            return;
+        }
+        if self.show_event_log {
+            self.handle_event_log_key(action, key_event.code);
+            return;
+        }
+        if self.show_columns {
+            self.handle_columns_key(panel_action(KeyContext::Columns), key_event.code);
+            return;
+        }
+        if self.show_comparison {
+            self.handle_comparison_key(panel_action(KeyContext::Comparison), key_event.code);
+            return;
+        }
+        if self.followed_stream.is_some() {
+            self.handle_stream_key(action, key_event.code);
+            return;
+        }
+        if self.show_popup {
+            let context = if self.popup_state.is_selecting() {
+                KeyContext::ByteSelection
+            } else {
+                KeyContext::Popup
+            };
+            self.handle_popup_key(panel_action(context), key_event.code);
+            return;
+        }
+        let Some(action) = action else {
+            return;
+        };
+        self.run_action(action);
+    }
+
+    /// Does what `action` is bound to in the packet list, for its keys and for the
+    /// command palette alike.
+    fn run_action(&mut self, action: Action) {
+        match action {
+            Action::Quit => self.exit = true,
+            Action::NextRow => {
+                let visible_len = self.visible_rows.len();
+                self.packet_table_state.next_row(visible_len)
+            }
+            Action::PreviousRow => {
+                let visible_len = self.visible_rows.len();
+                self.packet_table_state.previous_row(visible_len)
+            }
+            Action::NextInterface => self.next_active_interface(),
+            Action::RefreshInterfaces => {
+                if self.refresh_interfaces() {
+                    return;
+                }
+                // Start capturing once an interface shows up after none were found.
+                if self.interface.is_none() && !self.interfaces.is_empty() {
+                    self.next_active_interface();
+                }
+                self.notify(
+                    Severity::Note,
+                    strings::get("status.interfaces_refreshed")
+                        .replace("{count}", &self.interfaces.len().to_string()),
+                );
+            }
+            Action::ToggleSniffer => self.toggle_sniffer(),
+            Action::ToggleFreeze => {
+                let packets_len = self.visible_rows.len();
+                self.packet_table_state.toggle_frozen(packets_len);
+            }
+            Action::Search => self.input_mode = InputMode::Search,
+            Action::NextMatch => self.search(false),
+            Action::PreviousMatch => self.search(true),
+            Action::Filter => {
+                self.input_buffer = self
+                    .display_filter
+                    .as_ref()
+                    .map(|filter| filter.expression().to_string())
+                    .unwrap_or_default();
+                self.input_mode = InputMode::Filter;
+            }
+            Action::FilterPresets => self.show_filter_presets = true,
+            Action::Columns => self.show_columns = true,
+            Action::Compare => self.mark_for_comparison(),
+            Action::GoToPacket => self.input_mode = InputMode::GoToPacket,
+            Action::DecodeAs => self.input_mode = InputMode::DecodeAs,
+            Action::SavePcap => {
+                self.input_buffer = Local::now()
+                    .format("wirepenguin-%Y%m%d-%H%M%S.pcapng")
+                    .to_string();
+                self.input_mode = InputMode::SavePcap;
+            }
+            Action::ToggleDim => self.toggle_dim_unmatched(),
+            Action::ToggleDirection => self.toggle_direction_filter(),
+            Action::Copy => self.copy_selected_packet(),
+            Action::Conversations => self.show_conversations = true,
+            Action::TopTalkers => self.show_top_talkers = !self.show_top_talkers,
+            Action::ChartMode => self.chart_mode = self.chart_mode.next(),
+            Action::FirstRow => self.packet_table_state.select_first(),
+            Action::LastRow => {
+                let visible_len = self.visible_rows.len();
+                self.packet_table_state.select_last(visible_len)
+            }
+            Action::PageDown => self.move_page(1),
+            Action::PageUp => self.move_page(-1),
+            Action::ToggleNameResolution => self.toggle_name_resolution(),
+            Action::TogglePopup => self.toggle_popup(),
+            Action::ShrinkTopPanel => self.panel_layout.resize_top(-1),
+            Action::GrowTopPanel => self.panel_layout.resize_top(1),
+            Action::ShrinkChart => self.panel_layout.resize_chart(-1),
+            Action::GrowChart => self.panel_layout.resize_chart(1),
+            Action::ToggleChart => self.panel_layout.show_chart = !self.panel_layout.show_chart,
+            Action::ToggleCompact => {
+                self.packet_table_state.compact = !self.packet_table_state.compact;
+            }
+            Action::ToggleOrder => self.toggle_packet_order(),
+            Action::FollowStream => self.follow_stream(),
+            Action::Comment => self.start_comment(),
+            Action::CycleTheme => self.cycle_theme(),
+            Action::CommandPalette => {
+                self.command_palette_query.clear();
+                self.command_palette_state.select(Some(0));
+                self.show_command_palette = true;
+            }
+            Action::ToggleInterfaces => {
+                self.panel_layout.show_interfaces = !self.panel_layout.show_interfaces
+            }
+            Action::ToggleFullscreen => {
+                self.panel_layout.fullscreen = !self.panel_layout.fullscreen
+            }
+            Action::ExpertInfo => self.show_expert_info = true,
+            Action::EventLog => {
+                self.event_log_state.select(Some(0));
+                self.show_event_log = true;
+            }
+            Action::ToggleBookmark => self.toggle_bookmark(),
+            Action::NextBookmark => self.jump_to_bookmark(false),
+            Action::PreviousBookmark => self.jump_to_bookmark(true),
+            Action::ToggleProtocol1
+            | Action::ToggleProtocol2
+            | Action::ToggleProtocol3
+            | Action::ToggleProtocol4
+            | Action::ToggleProtocol5
+            | Action::ToggleProtocol6
+            | Action::ToggleProtocol7
+            | Action::ToggleProtocol8
+            | Action::ToggleProtocol9 => {
+                if let Some(index) = Action::TOGGLE_PROTOCOL.iter().position(|&a| a == action) {
+                    self.toggle_protocol(index);
+                }
+            }
+            // The panels' own actions, run by their key handlers.
+            Action::SortConversations
+            | Action::ToggleConversationPorts
+            | Action::ToggleDifferencesOnly
+            | Action::ToggleColumn
+            | Action::MoveColumnDown
+            | Action::MoveColumnUp
+            | Action::SavePreset
+            | Action::DeletePreset
+            | Action::ApplyPreset1
+            | Action::ApplyPreset2
+            | Action::ApplyPreset3
+            | Action::ApplyPreset4
+            | Action::ApplyPreset5
+            | Action::ApplyPreset6
+            | Action::ApplyPreset7
+            | Action::ApplyPreset8
+            | Action::ApplyPreset9
+            | Action::ExpandLayer
+            | Action::CollapseLayer
+            | Action::ToggleLayer
+            | Action::ToggleBytesView
+            | Action::InspectBytes
+            | Action::ToggleAllBytes
+            | Action::MaximizePopup
+            | Action::SelectNextByte
+            | Action::SelectPreviousByte
+            | Action::RestartSelection
+            | Action::EndSelection => {}
+        }
+    }
+
+    fn move_page(&mut self, direction: isize) {
+        let page = self.packet_table_state.visible_rows(self.packet_table_area) as isize;
+        let visible_len = self.visible_rows.len();
+        self.packet_table_state
+            .move_by(direction * page, visible_len);
+    }
+
+    fn cycle_theme(&mut self) {
+        let position = Theme::NAMES
+            .iter()
+            .position(|&name| name == self.theme_name)
+            .unwrap_or(0);
+        self.theme_name = Theme::NAMES[(position + 1) % Theme::NAMES.len()];
+        self.theme = Theme::named(self.theme_name, &self.configured_theme).unwrap_or_default();
+        self.status_message =
+            Some(strings::get("status.theme").replace("{theme}", self.theme_name));
+    }
+
+    fn toggle_name_resolution(&mut self) {
+        if self.resolver.is_none() {
+            self.status_message = Some(strings::get("status.resolve_disabled").to_string());
+            return;
+        }
+        self.resolve_names = !self.resolve_names;
+    }
+
+    fn copy_selected_packet(&mut self) {
+        let selected_packet = self.selected_packet();
+
+        let Some(packet) = selected_packet else {
+            self.status_message = Some(strings::get("status.no_packet_selected").to_string());
+            return;
+        };
+
+        let summary = clipboard::packet_summary(packet);
+        self.clipboard
+            .get_or_insert_with(|| ClipboardWorker::new(self.action_tx.clone()))
+            .copy(summary);
+    }
+
+    /// Typing narrows the list down; Enter runs the selected action once the palette is
+    /// closed, so actions opening a prompt or another popup work as from their keys.
+    fn handle_command_palette_key(&mut self, action: Option<Action>, key_code: KeyCode) {
+        let actions = CommandPaletteWidget::matching_actions(&self.command_palette_query);
+        let selected = self.command_palette_state.selected().unwrap_or(0);
+        match (action, key_code) {
+            (Some(Action::CommandPalette), _) | (_, KeyCode::Esc) => {
+                self.show_command_palette = false
+            }
+            (_, KeyCode::Enter) => {
+                self.show_command_palette = false;
+                if let Some(&action) = actions.get(selected) {
+                    self.run_action(action);
+                }
+            }
+            (_, KeyCode::Down) => self
+                .command_palette_state
+                .select(Some((selected + 1).min(actions.len().saturating_sub(1)))),
+            (_, KeyCode::Up) => self
+                .command_palette_state
+                .select(Some(selected.saturating_sub(1))),
+            (_, KeyCode::Backspace) => {
+                self.command_palette_query.pop();
+                self.command_palette_state.select(Some(0));
+            }
+            (_, KeyCode::Char(c)) => {
+                self.command_palette_query.push(c);
+                self.command_palette_state.select(Some(0));
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_conversations_key(&mut self, action: Option<Action>, key_code: KeyCode) {
+        match (action, key_code) {
+            (Some(Action::Quit), _) => self.exit = true,
+            (Some(Action::Conversations), _) | (_, KeyCode::Esc) => self.show_conversations = false,
+            (Some(Action::SortConversations), _) => {
+                self.conversation_sort = self.conversation_sort.next()
+            }
+            (Some(Action::ToggleConversationPorts), _) => {
+                self.conversation_ports = !self.conversation_ports
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_expert_info_key(&mut self, action: Option<Action>, key_code: KeyCode) {
+        let len = self.expert_info.len();
+        let state = &mut self.expert_info_state;
+        match (action, key_code) {
+            (Some(Action::Quit), _) => self.exit = true,
+            (Some(Action::ExpertInfo), _) | (_, KeyCode::Esc) => self.show_expert_info = false,
+            (Some(Action::NextRow), _) => state.select(Some(
+                (state.selected().unwrap_or(0) + 1).min(len.saturating_sub(1)),
+            )),
+            (Some(Action::PreviousRow), _) => {
+                state.select(Some(state.selected().unwrap_or(0).saturating_sub(1)))
+            }
+            (_, KeyCode::Enter) => self.go_to_expert_info_packet(),
+            _ => {}
+        }
+    }
+
+    fn handle_event_log_key(&mut self, action: Option<Action>, key_code: KeyCode) {
+        let len = self.event_log.len();
+        let state = &mut self.event_log_state;
+        match (action, key_code) {
+            (Some(Action::Quit), _) => self.exit = true,
+            (Some(Action::EventLog), _) | (_, KeyCode::Esc) => self.show_event_log = false,
+            (Some(Action::NextRow), _) => state.select(Some(
+                (state.selected().unwrap_or(0) + 1).min(len.saturating_sub(1)),
+            )),
+            (Some(Action::PreviousRow), _) => {
+                state.select(Some(state.selected().unwrap_or(0).saturating_sub(1)))
+            }
+            _ => {}
+        }
+    }
+
+    /// The first press marks the selected packet; the second, on another packet, opens
+    /// the comparison. Pressing it again on the marked packet unmarks it.
+    fn mark_for_comparison(&mut self) {
+        let Some(packet) = self.selected_packet() else {
+            return;
+        };
+        let packet_id = packet.id;
+        let Some(marked_id) = self.compare_mark else {
+            self.compare_mark = Some(packet_id);
+            self.status_message =
+                Some(strings::get("status.compare_marked").replace("{id}", &packet_id.to_string()));
+            return;
+        };
+        if marked_id == packet_id {
+            self.compare_mark = None;
+            self.status_message = Some(strings::get("status.compare_unmarked").to_string());
+            return;
+        }
+        let second = packet.clone();
+        let Some(first) = self.packets.iter().find(|packet| packet.id == marked_id) else {
+            self.compare_mark = Some(packet_id);
+            self.status_message = Some(
+                strings::get("status.packet_not_found").replace("{id}", &marked_id.to_string()),
+            );
+            return;
+        };
+        self.compared_packets = [Some(first.clone()), Some(second)];
+        self.compare_mark = None;
+        self.comparison_state.select(Some(0));
+        self.show_comparison = true;
+    }
+
+    fn follow_stream(&mut self) {
+        match self.selected_packet().and_then(UdpStream::of) {
+            Some(stream) => {
+                self.followed_stream = Some(stream);
+                self.stream_scroll = 0;
+            }
+            None => self.status_message = Some(strings::get("status.not_udp").to_string()),
+        }
+    }
+
+    fn handle_stream_key(&mut self, action: Option<Action>, key_code: KeyCode) {
+        let scroll = &mut self.stream_scroll;
+        match (action, key_code) {
+            (Some(Action::Quit), _) => self.exit = true,
+            (Some(Action::FollowStream), _) | (_, KeyCode::Esc) => self.followed_stream = None,
+            (Some(Action::NextRow), _) => *scroll = scroll.saturating_add(1),
+            (Some(Action::PreviousRow), _) => *scroll = scroll.saturating_sub(1),
+            (Some(Action::PageDown), _) => *scroll = scroll.saturating_add(Self::STREAM_PAGE),
+            (Some(Action::PageUp), _) => *scroll = scroll.saturating_sub(Self::STREAM_PAGE),
+            _ => {}
+        }
+    }
+
+    fn handle_comparison_key(&mut self, action: Option<Action>, key_code: KeyCode) {
+        let len = self.comparison_widget().row_count();
+        let state = &mut self.comparison_state;
+        match (action, key_code) {
+            (Some(Action::Quit), _) => self.exit = true,
+            (Some(Action::Compare), _) | (_, KeyCode::Esc) => self.show_comparison = false,
+            (Some(Action::NextRow), _) => state.select(Some(
+                (state.selected().unwrap_or(0) + 1).min(len.saturating_sub(1)),
+            )),
+            (Some(Action::PreviousRow), _) => {
+                state.select(Some(state.selected().unwrap_or(0).saturating_sub(1)))
+            }
+            (Some(Action::ToggleDifferencesOnly), _) => {
+                self.comparison_differences_only = !self.comparison_differences_only;
+                state.select(Some(0));
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_columns_key(&mut self, action: Option<Action>, key_code: KeyCode) {
+        let selected = self.columns_state.selected().unwrap_or(0);
+        match (action, key_code) {
+            (Some(Action::Quit), _) => self.exit = true,
+            (Some(Action::Columns), _) | (_, KeyCode::Esc) => self.show_columns = false,
+            (Some(Action::NextRow), _) => self
+                .columns_state
+                .select(Some((selected + 1).min(Column::ALL.len() - 1))),
+            (Some(Action::PreviousRow), _) => {
+                self.columns_state.select(Some(selected.saturating_sub(1)))
+            }
+            (Some(Action::ToggleColumn), _) => self.toggle_column(selected),
+            (Some(Action::MoveColumnDown), _) => self.move_column(selected, selected + 1),
+            (Some(Action::MoveColumnUp), _) if selected > 0 => {
+                self.move_column(selected, selected - 1)
+            }
+            _ => {}
+        }
+    }
+
+    /// Hides the shown column at `index` of the picker, or shows the hidden one there
+    /// after the other shown columns. The last shown column stays.
+    fn toggle_column(&mut self, index: usize) {
+        if index < self.columns.len() {
+            if self.columns.len() > 1 {
+                self.columns.remove(index);
+            }
+            return;
+        }
+        let hidden = Column::hidden(&self.columns).nth(index - self.columns.len());
+        if let Some(column) = hidden {
+            self.columns.push(column);
+            self.columns_state.select(Some(self.columns.len() - 1));
+        }
+    }
+
+    /// Swaps two shown columns, keeping the moved one selected.
+    fn move_column(&mut self, from: usize, to: usize) {
+        if from < self.columns.len() && to < self.columns.len() {
+            self.columns.swap(from, to);
+            self.columns_state.select(Some(to));
+        }
+    }
+
+    fn handle_filter_presets_key(&mut self, action: Option<Action>, key_code: KeyCode) {
+        let len = self.filter_presets.len();
+        let state = &mut self.filter_presets_state;
+        match (action, key_code) {
+            (Some(Action::Quit), _) => self.exit = true,
+            (Some(Action::FilterPresets), _) | (_, KeyCode::Esc) => {
+                self.show_filter_presets = false
+            }
+            (Some(Action::NextRow), _) => state.select(Some(
+                (state.selected().unwrap_or(0) + 1).min(len.saturating_sub(1)),
+            )),
+            (Some(Action::PreviousRow), _) => {
+                state.select(Some(state.selected().unwrap_or(0).saturating_sub(1)))
+            }
+            (_, KeyCode::Enter) => {
+                if let Some(selected) = state.selected() {
+                    self.apply_filter_preset(selected);
+                }
+            }
+            (Some(Action::SavePreset), _) => self.save_filter_preset(),
+            (Some(Action::DeletePreset), _) => self.delete_filter_preset(),
+            (Some(action), _) => {
+                if let Some(index) = Action::APPLY_PRESET.iter().position(|&a| a == action) {
+                    self.apply_filter_preset(index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_filter_preset(&mut self, index: usize) {
+        let Some(preset) = self.filter_presets.get(index).cloned() else {
+            return;
+        };
+        self.show_filter_presets = false;
+        self.apply_filter(&preset.expression);
+        if self.status_message.is_none() {
+            self.status_message =
+                Some(strings::get("status.preset_applied").replace("{name}", &preset.name));
+        }
+    }
+
+    /// Saves the current display filter, named after its expression.
+    fn save_filter_preset(&mut self) {
+        let Some(expression) = self
+            .display_filter
+            .as_ref()
+            .map(|filter| filter.expression().to_string())
+        else {
+            self.status_message = Some(strings::get("status.preset_no_filter").to_string());
+            return;
+        };
+        if let Some(index) = self
+            .filter_presets
+            .iter()
+            .position(|preset| preset.expression == expression)
+        {
+            self.filter_presets_state.select(Some(index));
+            self.status_message =
+                Some(strings::get("status.preset_exists").replace("{filter}", &expression));
+            return;
+        }
+        self.status_message =
+            Some(strings::get("status.preset_saved").replace("{filter}", &expression));
+        self.filter_presets.push(FilterPreset {
+            name: expression.clone(),
+            expression,
+        });
+        self.filter_presets_state
+            .select(Some(self.filter_presets.len() - 1));
+    }
+
+    fn delete_filter_preset(&mut self) {
+        let Some(selected) = self.filter_presets_state.selected() else {
+            return;
+        };
+        if selected < self.config_preset_count {
+            self.status_message = Some(strings::get("status.preset_not_saved").to_string());
+            return;
+        }
+        if selected < self.filter_presets.len() {
+            self.filter_presets.remove(selected);
+            self.filter_presets_state.select(Some(
+                selected.min(self.filter_presets.len().saturating_sub(1)),
+            ));
+        }
+    }
+
+    /// Closes the panel and selects the packet of the highlighted finding in the table.
+    fn go_to_expert_info_packet(&mut self) {
+        let Some(finding) = self
+            .expert_info_state
+            .selected()
+            .and_then(|selected| self.expert_info.iter().rev().nth(selected))
+        else {
+            return;
+        };
+        let packet_id = finding.packet_id;
+        self.show_expert_info = false;
+        self.go_to_packet(packet_id);
+    }
+
+    /// Selects the packet with `packet_id` and freezes the table so new packets don't
+    /// scroll it away, or says why it can't be shown.
+    fn go_to_packet(&mut self, packet_id: usize) {
+        match self.visible_row_of(packet_id) {
+            Some(index) => {
+                self.packet_table_state.frozen = true;
+                self.packet_table_state.jump_to(index);
+            }
+            None => {
+                let message = if self.packets.iter().any(|packet| packet.id == packet_id) {
+                    "status.packet_hidden"
+                } else {
+                    "status.packet_not_found"
+                };
+                self.status_message =
+                    Some(strings::get(message).replace("{id}", &packet_id.to_string()));
+            }
+        }
+    }
+
+    /// Row of the packet with `packet_id` in the table. Packets are listed newest first
+    /// and filters leave gaps, so the row can't be worked out from the id alone.
+    fn visible_row_of(&self, packet_id: usize) -> Option<usize> {
+        self.visible_packets()
+            .iter()
+            .position(|packet| packet.id == packet_id)
+    }
+
+    /// The popup has its own keys for the layer tree and the byte selection, on top of the
+    /// packet list's for quitting, moving between layers and paging the TCP options.
+    fn handle_popup_key(&mut self, action: Option<Action>, key_code: KeyCode) {
+        let max_bytes = self.popup_max_bytes;
+        let popup_state = &mut self.popup_state;
+        if popup_state.is_selecting() {
+            match (action, key_code) {
+                (Some(Action::Quit), _) => self.exit = true,
+                (Some(Action::EndSelection), _) | (_, KeyCode::Esc) => popup_state.end_selection(),
+                (Some(Action::NextRow), _) | (_, KeyCode::Down) => {
+                    popup_state.move_selection(16, max_bytes)
+                }
+                (Some(Action::PreviousRow), _) | (_, KeyCode::Up) => {
+                    popup_state.move_selection(-16, max_bytes)
+                }
+                (Some(Action::SelectNextByte), _) => popup_state.move_selection(1, max_bytes),
+                (Some(Action::SelectPreviousByte), _) => popup_state.move_selection(-1, max_bytes),
+                (Some(Action::RestartSelection), _) => popup_state.restart_selection(),
+                _ => {}
+            }
+            return;
+        }
+        match (action, key_code) {
+            (Some(Action::Quit), _) => self.exit = true,
+            (_, KeyCode::Esc) => self.toggle_popup(),
+            (Some(Action::NextRow), _) | (_, KeyCode::Down) => popup_state.next(),
+            (Some(Action::PreviousRow), _) | (_, KeyCode::Up) => popup_state.previous(),
+            (Some(Action::PageDown), _) => popup_state.scroll_options(Self::OPTIONS_PAGE),
+            (Some(Action::PageUp), _) => popup_state.scroll_options(-Self::OPTIONS_PAGE),
+            (Some(Action::ExpandLayer), _) => popup_state.expand(),
+            (Some(Action::CollapseLayer), _) => popup_state.collapse(),
+            (Some(Action::ToggleLayer), _) => popup_state.toggle(),
+            (Some(Action::ToggleBytesView), _) => popup_state.toggle_bytes_view(),
+            (Some(Action::InspectBytes), _) => popup_state.start_selection(),
+            (Some(Action::ToggleAllBytes), _) => popup_state.toggle_all_bytes(),
+            (Some(Action::MaximizePopup), _) => self.popup_maximized = !self.popup_maximized,
+            _ => {}
+        }
+    }
+
+    fn handle_search_key(&mut self, key_code: KeyCode) {
+        match key_code {
+            KeyCode::Esc => {
+                self.input_buffer.clear();
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Enter => {
+                let query = std::mem::take(&mut self.input_buffer);
+                self.input_mode = InputMode::Normal;
+                if !query.is_empty() {
+                    self.last_search = Some(query);
+                    self.search(false);
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => self.input_buffer.push(c),
+            _ => {}
+        }
+    }
+
+    fn handle_filter_key(&mut self, key_code: KeyCode) {
+        match key_code {
+            KeyCode::Esc => {
+                self.input_buffer.clear();
+                self.input_mode = InputMode::Normal;
+            }
+            // An invalid filter stays in the prompt to be fixed, its error shown next to it.
+            KeyCode::Enter if DisplayFilter::validate(&self.input_buffer).is_ok() => {
+                let expression = std::mem::take(&mut self.input_buffer);
+                self.input_mode = InputMode::Normal;
+                self.apply_filter(&expression);
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => self.input_buffer.push(c),
+            _ => {}
+        }
+    }
+
+    fn handle_go_to_packet_key(&mut self, key_code: KeyCode) {
+        match key_code {
+            KeyCode::Esc => {
+                self.input_buffer.clear();
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Enter => {
+                let input = std::mem::take(&mut self.input_buffer);
+                self.input_mode = InputMode::Normal;
+                match input.trim().parse() {
+                    Ok(packet_id) => self.go_to_packet(packet_id),
+                    Err(_) if input.trim().is_empty() => {}
+                    Err(_) => {
+                        self.status_message = Some(
+                            strings::get("status.invalid_packet_id").replace("{input}", &input),
+                        )
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => self.input_buffer.push(c),
+            _ => {}
+        }
+    }
+
+    fn handle_decode_as_key(&mut self, key_code: KeyCode) {
+        match key_code {
+            KeyCode::Esc => {
+                self.input_buffer.clear();
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Enter => {
+                let rule = std::mem::take(&mut self.input_buffer);
+                self.input_mode = InputMode::Normal;
+                if !rule.trim().is_empty() {
+                    self.apply_decode_as(&rule);
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => self.input_buffer.push(c),
+            _ => {}
+        }
+    }
+
+    fn handle_save_pcap_key(&mut self, key_code: KeyCode) {
+        match key_code {
+            KeyCode::Esc => {
+                self.input_buffer.clear();
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Enter => {
+                let path = std::mem::take(&mut self.input_buffer);
+                self.input_mode = InputMode::Normal;
+                if !path.trim().is_empty() {
+                    self.save_filtered_pcap(Path::new(path.trim()));
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => self.input_buffer.push(c),
+            _ => {}
+        }
+    }
+
+    /// Opens the comment prompt for the selected packet, with its comment to edit.
+    fn start_comment(&mut self) {
+        let Some((packet_id, comment)) = self
+            .selected_packet()
+            .map(|packet| (packet.id, packet.comment.clone()))
+        else {
+            self.status_message = Some(strings::get("status.no_packet_selected").to_string());
+            return;
+        };
+        self.input_buffer = comment.unwrap_or_default();
+        self.input_mode = InputMode::Comment(packet_id);
+    }
+
+    /// Enter saves the comment, or removes it when left empty.
+    fn handle_comment_key(&mut self, packet_id: usize, key_code: KeyCode) {
+        match key_code {
+            KeyCode::Esc => {
+                self.input_buffer.clear();
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Enter => {
+                let comment = std::mem::take(&mut self.input_buffer);
+                self.input_mode = InputMode::Normal;
+                let Some(packet) = self
+                    .packets
+                    .iter_mut()
+                    .find(|packet| packet.id == packet_id)
+                else {
+                    return;
+                };
+                let comment = comment.trim();
+                let message = if comment.is_empty() {
+                    packet.comment = None;
+                    strings::get("status.comment_removed")
+                } else {
+                    packet.comment = Some(comment.to_string());
+                    strings::get("status.comment_set")
+                };
+                self.status_message = Some(message.replace("{id}", &packet_id.to_string()));
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => self.input_buffer.push(c),
+            _ => {}
+        }
+    }
+
+    /// Saves the packets passing the display filter, protocol switches and direction
+    /// filter, oldest first. Dimming doesn't matter: dimmed packets failed the filter.
+    fn save_filtered_pcap(&mut self, path: &Path) {
+        let rows = self.filter_packets(self.display_filter.as_ref());
+        let packets = Self::rows(&self.packets, &rows, true);
+        match PcapWriter::save(path, &packets) {
+            Ok(summary) => {
+                let mut message = strings::get("status.pcap_saved")
+                    .replace("{count}", &summary.written.to_string())
+                    .replace("{path}", &path.display().to_string());
+                if summary.truncated > 0 {
+                    message.push_str(
+                        &strings::get("status.pcap_truncated")
+                            .replace("{count}", &summary.truncated.to_string()),
+                    );
+                }
+                if summary.dropped_comments > 0 {
+                    message.push_str(
+                        &strings::get("status.pcap_comments_dropped")
+                            .replace("{count}", &summary.dropped_comments.to_string()),
+                    );
+                }
+                self.notify(Severity::Note, message);
+            }
+            Err(error) => self.notify(Severity::Error, error),
+        }
+    }
+
+    /// Packets already captured keep the layers they were decoded with.
+    fn apply_decode_as(&mut self, rule: &str) {
+        let (transport, port, protocol) = match DecodeAs::parse_rule(rule) {
+            Ok(rule) => rule,
+            Err(error) => {
+                self.notify(
+                    Severity::Warn,
+                    strings::get("status.decode_as_invalid").replace("{error}", &error),
+                );
+                return;
+            }
+        };
+        self.sniffer
+            .decode_as
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .set(transport, port, protocol);
+        let message = match protocol {
+            Some(protocol) => {
+                strings::get("status.decode_as_set").replace("{protocol}", protocol.name())
+            }
+            None => strings::get("status.decode_as_removed").to_string(),
+        };
+        self.notify(
+            Severity::Note,
+            message
+                .replace("{transport}", transport.name())
+                .replace("{port}", &port.to_string()),
+        );
+    }
+
+    /// An empty expression clears the filter; an invalid one keeps the current filter.
+    fn apply_filter(&mut self, expression: &str) {
+        let selected_id = self.selected_packet().map(|packet| packet.id);
+        if expression.trim().is_empty() {
+            self.display_filter = None;
+        } else {
+            match DisplayFilter::parse(expression) {
+                Ok(filter) => self.display_filter = Some(filter),
+                Err(error) => {
+                    self.notify(
+                        Severity::Warn,
+                        strings::get("status.filter_invalid").replace("{error}", &error),
+                    );
+                    return;
+                }
+            }
+        }
+        self.refilter();
+        self.reselect_packet(selected_id);
+    }
+
+    fn toggle_protocol(&mut self, index: usize) {
+        let selected_id = self.selected_packet().map(|packet| packet.id);
+        self.protocol_toggles.toggle(index);
+        self.refilter();
+        self.reselect_packet(selected_id);
+    }
+
+    fn toggle_direction_filter(&mut self) {
+        let selected_id = self.selected_packet().map(|packet| packet.id);
+        self.direction_filter = self.direction_filter.next();
+        self.refilter();
+        self.reselect_packet(selected_id);
+    }
+
+    fn toggle_packet_order(&mut self) {
+        let selected_id = self.selected_packet().map(|packet| packet.id);
+        self.packet_table_state.oldest_first = !self.packet_table_state.oldest_first;
+        self.reselect_packet(selected_id);
+    }
+
+    fn toggle_dim_unmatched(&mut self) {
+        let selected_id = self.selected_packet().map(|packet| packet.id);
+        self.dim_unmatched = !self.dim_unmatched;
+        self.refilter();
+        self.reselect_packet(selected_id);
+    }
+
+    /// Keeps the packet with `packet_id` selected after the visible packets change, or
+    /// goes back to the newest packet when it is no longer shown.
+    fn reselect_packet(&mut self, packet_id: Option<usize>) {
+        match packet_id.and_then(|packet_id| self.visible_row_of(packet_id)) {
+            Some(index) => self.packet_table_state.jump_to(index),
+            None => {
+                let packets_len = self.visible_rows.len();
+                self.packet_table_state.select_newest(packets_len);
+            }
+        }
+    }
+
+    /// Packets shown in the table: the ones of protocols switched on and in the chosen
+    /// direction, and of those only the ones passing the display filter unless it is set
+    /// to dim the rest.
+    fn visible_packets(&self) -> Vec<&CompletePacket> {
+        Self::rows(
+            &self.packets,
+            &self.visible_rows,
+            self.packet_table_state.oldest_first,
+        )
+    }
+
+    /// The packets at the indices `rows`. Borrows only the packets, for callers that also
+    /// need the table state mutably.
+    fn rows<'p>(
+        packets: &'p [CompletePacket],
+        rows: &[usize],
+        oldest_first: bool,
+    ) -> Vec<&'p CompletePacket> {
+        let packet = |row: &usize| &packets[*row];
+        if oldest_first {
+            rows.iter().map(packet).collect()
+        } else {
+            rows.iter().rev().map(packet).collect()
+        }
+    }
+
+    /// Whether `packet` is of a protocol switched on, in the chosen direction and passes
+    /// `display_filter`.
+    fn shows(&self, packet: &CompletePacket, display_filter: Option<&DisplayFilter>) -> bool {
+        self.protocol_toggles.allows(packet)
+            && self.direction_filter.allows(packet)
+            && display_filter.is_none_or(|filter| filter.matches(packet))
+    }
+
+    /// Indices of the packets `shows` lets through.
+    fn filter_packets(&self, display_filter: Option<&DisplayFilter>) -> Vec<usize> {
+        self.packets
+            .iter()
+            .enumerate()
+            .filter(|(_, packet)| self.shows(packet, display_filter))
+            .map(|(row, _)| row)
+            .collect()
+    }
+
+    /// Works out the visible packets again after the filters changed.
+    fn refilter(&mut self) {
+        self.visible_rows =
+            self.filter_packets(self.display_filter.as_ref().filter(|_| !self.dim_unmatched));
+    }
+
+    fn selected_packet(&self) -> Option<&CompletePacket> {
+        let selected = self.packet_table_state.selected()?;
+        let row = if self.packet_table_state.oldest_first {
+            self.visible_rows.get(selected)?
+        } else {
+            self.visible_rows.iter().rev().nth(selected)?
+        };
+        self.packets.get(*row)
+    }
+
+    fn search(&mut self, backward: bool) {
+        if let Some(query) = &self.last_search {
+            let visible_packets = Self::rows(
+                &self.packets,
+                &self.visible_rows,
+                self.packet_table_state.oldest_first,
+            );
+            let found = self
+                .packet_table_state
+                .search(&visible_packets, query, backward);
+            if !found {
+                self.status_message =
+                    Some(strings::get("status.search_no_match").replace("{query}", query));
+            }
+        }
+    }
+
+    fn toggle_bookmark(&mut self) {
+        let Some(packet_id) = self.selected_packet().map(|packet| packet.id) else {
+            self.status_message = Some(strings::get("status.no_packet_selected").to_string());
+            return;
+        };
+        let message = if self.bookmarks.insert(packet_id) {
+            strings::get("status.bookmark_added")
+        } else {
+            self.bookmarks.remove(&packet_id);
+            strings::get("status.bookmark_removed")
+        };
+        self.status_message = Some(message.replace("{id}", &packet_id.to_string()));
+    }
+
+    /// Selects the next bookmarked packet below the selection, or the previous one above
+    /// it, wrapping around.
+    fn jump_to_bookmark(&mut self, backward: bool) {
+        let visible_packets = Self::rows(
+            &self.packets,
+            &self.visible_rows,
+            self.packet_table_state.oldest_first,
+        );
+        let from_index = self.packet_table_state.selected().unwrap_or(0);
+        let is_bookmarked = |packet: &CompletePacket| self.bookmarks.contains(&packet.id);
+        let found = if backward {
+            self.packet_table_state
+                .find_previous(&visible_packets, is_bookmarked, from_index)
+        } else {
+            self.packet_table_state
+                .find_next(&visible_packets, is_bookmarked, from_index)
+        };
+        match found {
+            Some(index) => self.packet_table_state.jump_to(index),
+            None => self.status_message = Some(strings::get("status.no_bookmarks").to_string()),
+        }
+    }
+
     fn toggle_sniffer(&mut self) {
         if !self.sniffer.sniffer_paused {
             self.sniffer.stop();
         } else {
-            self.sniffer.start();
+            self.start_capture();
+        }
+    }
+
+    /// Starts the sniffer, logging the interface the first time.
+    fn start_capture(&mut self) {
+        self.sniffer.start();
+        if self.capture_started_at.is_none() {
+            self.capture_started_at = Some(Instant::now());
+            self.log_capture_interface();
         }
     }
 
     fn toggle_popup(&mut self) {
         self.show_popup = !self.show_popup;
-        if let Some(selected_idx) = self.sniffer.selected_packet_index() {
-            self.selected_popup_packet = self.packets.get(selected_idx).cloned();
+        if let Some(packet) = self.selected_packet() {
+            self.selected_popup_packet = Some(packet.clone());
         }
+        self.popup_state = self
+            .selected_popup_packet
+            .as_ref()
+            .map(PopupState::new)
+            .unwrap_or_default();
     }
 
-    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> color_eyre::Result<()> {
+    pub fn run(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+    ) -> color_eyre::Result<Option<CaptureSummary>> {
+        let started_at = Instant::now();
         self.setup_interfaces()?;
         self.start_background_threads();
 
+        // Redraw only after something changed, at most once per frame interval so bursts
+        // of packets are coalesced, and otherwise once per idle interval.
+        terminal.draw(|frame| self.draw(frame))?;
+        let mut needs_redraw = false;
+        let mut last_draw = Instant::now();
         while !self.exit {
-            match self.action_rx.recv().unwrap() {
-                Event::PacketCaptured(packet) => self.handle_packet_captured(packet),
-                Event::Input(key_event) => self.handle_key_event(key_event)?,
-                Event::Render => {
-                    terminal.draw(|frame| self.draw(frame))?;
+            let redraw_interval = if needs_redraw {
+                self.frame_interval
+            } else {
+                Self::IDLE_REDRAW_INTERVAL
+            };
+            let mut timeout = redraw_interval.saturating_sub(last_draw.elapsed());
+            if let (Some(limit), Some(capture_started_at)) =
+                (self.duration_limit, self.capture_started_at)
+            {
+                timeout = timeout.min(limit.saturating_sub(capture_started_at.elapsed()));
+            }
+            match self.action_rx.recv_timeout(timeout) {
+                Ok(event) => {
+                    self.handle_event(event)?;
+                    needs_redraw = true;
                 }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            if self.check_capture_limits(started_at) {
+                needs_redraw = true;
+            }
+
+            let redraw_interval = if needs_redraw {
+                self.frame_interval
+            } else {
+                Self::IDLE_REDRAW_INTERVAL
+            };
+            if last_draw.elapsed() >= redraw_interval {
+                let interface_name = self.interface.as_ref().map(|intf| intf.name.as_str());
+                self.interface_stats.refresh(interface_name);
+                self.write_json(|writer| writer.flush_if_due());
+                terminal.draw(|frame| self.draw(frame))?;
+                last_draw = Instant::now();
+                needs_redraw = false;
             }
         }
+        self.stop_background_threads();
+        self.write_json(JsonLinesWriter::flush);
+        Ok(self
+            .print_summary
+            .then(|| CaptureSummary::new(&self.packets, &self.talker_stats, started_at.elapsed())))
+    }
+
+    fn handle_event(&mut self, event: Event) -> color_eyre::Result<()> {
+        match event {
+            Event::PacketCaptured(packet) => self.handle_packet_captured(*packet),
+            Event::Input(key_event) => self.handle_key_event(key_event)?,
+            Event::Mouse(mouse_event) => self.handle_mouse_event(mouse_event),
+            Event::Resize => {}
+            Event::StatusMessage(message) => self.notify(Severity::Note, message),
+            Event::SnifferError(message) => {
+                self.event_log.push(Severity::Error, message.clone());
+                self.sniffer_error = Some(message);
+            }
+            Event::SnifferStopped => self.handle_sniffer_stopped(),
+        }
         Ok(())
     }
 
-    fn setup_interfaces(&mut self) -> color_eyre::Result<()> {
-        let interfaces = datalink::interfaces();
-        if interfaces.is_empty() {
-            self.exit = true;
-            return Ok(());
+    /// Saves the session state for the next run. Left to the caller after the terminal is
+    /// restored, where a failure can still be reported.
+    pub fn save_settings(&self) -> std::io::Result<()> {
+        self.settings().save()
+    }
+
+    fn settings(&self) -> Settings {
+        Settings {
+            interface: self.interface.as_ref().map(|intf| intf.name.clone()),
+            theme: Some(self.theme_name.to_string()),
+            resolve_names: self.resolve_names,
+            compact_rows: self.packet_table_state.compact,
+            oldest_first: self.packet_table_state.oldest_first,
+            display_filter: self
+                .display_filter
+                .as_ref()
+                .map(|filter| filter.expression().to_string()),
+            layout: Some(self.panel_layout),
+            filter_presets: self.filter_presets[self.config_preset_count..].to_vec(),
+            columns: Some(self.columns.clone()),
         }
+    }
 
-        self.interfaces = self.filter_valid_interfaces(&interfaces);
-        self.interface = self.interfaces.get(0).cloned();
+    fn setup_interfaces(&mut self) -> color_eyre::Result<()> {
+        self.interfaces = self.filter_valid_interfaces(&datalink::interfaces());
+        self.read_interface_mtus();
+        if self.interfaces.is_empty() {
+            // Keep running so the interfaces panel can say why nothing is captured.
+            self.notify(Severity::Error, strings::get("interfaces.none").to_string());
+        }
+        let preferred_idx = self
+            .preferred_interface
+            .as_ref()
+            .and_then(|name| self.interfaces.iter().position(|intf| &intf.name == name));
+        if let (None, Some(name)) = (preferred_idx, &self.preferred_interface) {
+            self.notify(
+                Severity::Warn,
+                strings::get("status.interface_not_found").replace("{interface}", name),
+            );
+        }
+        let initial_idx = preferred_idx.unwrap_or(0);
+        self.interfaces_table_state.select(Some(initial_idx));
+        self.interface = self.interfaces.get(initial_idx).cloned();
 
         let tx_to_sniffer = self.action_tx.clone();
-        self.sniffer.network_interface = self.interfaces.get(0).cloned();
+        self.sniffer.network_interfaces = self.capture_interfaces();
         self.sniffer.register_event_handler(tx_to_sniffer);
-        self.sniffer.start();
+        if self.auto_start {
+            self.start_capture();
+        }
         Ok(())
     }
 
+    /// Re-enumerates the interfaces so ones that appeared since startup can be selected,
+    /// keeping the current interface selected by name while it still exists. When it is
+    /// gone, moves to the first interface left, capturing there if it was capturing, and
+    /// returns true after saying so in the status line.
+    fn refresh_interfaces(&mut self) -> bool {
+        self.interfaces = self.filter_valid_interfaces(&datalink::interfaces());
+        self.read_interface_mtus();
+        let current_idx = self.interface.as_ref().and_then(|current| {
+            self.interfaces
+                .iter()
+                .position(|intf| intf.name == current.name)
+        });
+        self.interfaces_table_state.select(current_idx);
+        let Some(gone) = self.interface.take_if(|_| current_idx.is_none()) else {
+            return false;
+        };
+
+        let was_capturing = !self.sniffer.sniffer_paused;
+        self.sniffer.stop();
+        self.interface = self.interfaces.first().cloned();
+        self.interfaces_table_state
+            .select(self.interface.as_ref().map(|_| 0));
+        self.interface_stats.reset();
+        self.sniffer.network_interfaces = self.capture_interfaces();
+        if was_capturing && self.interface.is_some() {
+            self.sniffer.start();
+            self.log_capture_interface();
+        }
+        let message = match &self.interface {
+            Some(interface) => strings::get("status.interface_gone_switched")
+                .replace("{interface}", &gone.name)
+                .replace("{new}", &interface.name),
+            None => strings::get("status.interface_gone").replace("{interface}", &gone.name),
+        };
+        self.notify(Severity::Warn, message);
+        true
+    }
+
+    /// MTU of every listed interface, for the interfaces panel and the expert analyzer's
+    /// frame size check.
+    fn read_interface_mtus(&mut self) {
+        self.interface_mtus = self
+            .interfaces
+            .iter()
+            .filter_map(|intf| Some((intf.name.clone(), interface_stats::read_mtu(&intf.name)?)))
+            .collect();
+        self.expert_analyzer.interface_mtus = self.interface_mtus.clone();
+    }
+
+    /// Loopback is listed last so it is never picked by default over a real interface,
+    /// followed by "any" when there is more than one interface to capture from.
     fn filter_valid_interfaces(&self, interfaces: &[NetworkInterface]) -> Vec<NetworkInterface> {
-        interfaces
+        let mut valid: Vec<NetworkInterface> = interfaces
             .iter()
             .filter(|intf| self.is_valid_interface(intf))
             .cloned()
-            .collect()
+            .collect();
+        valid.sort_by_key(|intf| intf.is_loopback());
+        if valid.len() > 1 {
+            valid.push(Self::any_interface());
+        }
+        valid
+    }
+
+    /// Pseudo-interface standing for every listed interface at once, like `tcpdump -i any`.
+    fn any_interface() -> NetworkInterface {
+        NetworkInterface {
+            name: Self::ANY_INTERFACE.to_string(),
+            description: Self::ANY_INTERFACE.to_string(),
+            index: 0,
+            mac: None,
+            ips: Vec::new(),
+            flags: 0,
+        }
+    }
+
+    /// The interfaces the sniffer should open for the selected one: all real interfaces
+    /// for "any", otherwise just that one.
+    fn capture_interfaces(&self) -> Vec<NetworkInterface> {
+        match &self.interface {
+            Some(interface) if interface.name == Self::ANY_INTERFACE => self
+                .interfaces
+                .iter()
+                .filter(|intf| intf.name != Self::ANY_INTERFACE)
+                .cloned()
+                .collect(),
+            Some(interface) => vec![interface.clone()],
+            None => Vec::new(),
+        }
     }
 
     fn is_valid_interface(&self, intf: &NetworkInterface) -> bool {
-        intf.is_up() && !intf.ips.is_empty() && self.has_private_ipv4(intf)
+        if self.all_interfaces {
+            return intf.is_up();
+        }
+        intf.is_up() && !intf.ips.is_empty() && (intf.is_loopback() || self.has_private_ipv4(intf))
     }
 
     fn has_private_ipv4(&self, intf: &NetworkInterface) -> bool {
@@ -139,64 +1699,306 @@ impl App {
         })
     }
 
-    fn start_background_threads(&self) {
+    fn start_background_threads(&mut self) {
         let tx_key_events = self.action_tx.clone();
-        let tx_render = self.action_tx.clone();
+        let input_shutdown = self.shutdown.clone();
 
-        thread::spawn(move || handle_input_events(tx_key_events));
+        let input_thread =
+            thread::spawn(move || handle_input_events(tx_key_events, input_shutdown));
 
-        thread::spawn(move || {
-            let tick_rate = Duration::from_secs_f64(1.0 / 22.0);
-            let mut last_tick = Instant::now();
+        self.background_threads = vec![input_thread];
+    }
 
-            loop {
-                let now = Instant::now();
-                if now.duration_since(last_tick) >= tick_rate {
-                    if tx_render.send(Event::Render).is_err() {
-                        break;
-                    }
-                    last_tick = now;
-                } else {
-                    thread::sleep(Duration::from_millis(1));
-                }
-            }
-        });
+    fn stop_background_threads(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        self.sniffer.stop();
+        for handle in self.background_threads.drain(..) {
+            let _ = handle.join();
+        }
     }
 
-    fn handle_packet_captured(&mut self, packet: CompletePacket) {
-        self.packets.insert(0, packet.clone());
-        self.sniffer.packets.insert(0, packet);
+    /// Stops the capture once `--count` packets arrived or `--duration` passed since it
+    /// started, and reports the totals, exiting with `--exit-on-limit`. Returns
+    /// whether it stopped the capture.
+    fn check_capture_limits(&mut self, started_at: Instant) -> bool {
+        let count_reached = self
+            .packet_limit
+            .is_some_and(|limit| self.packets.len() >= limit);
+        let duration_reached = self
+            .duration_limit
+            .zip(self.capture_started_at)
+            .is_some_and(|(limit, capture_started_at)| capture_started_at.elapsed() >= limit);
+        if !count_reached && !duration_reached {
+            return false;
+        }
+        self.packet_limit = None;
+        self.duration_limit = None;
+        self.stopped_at_limit = true;
+        self.sniffer.stop();
+        if self.exit_on_limit {
+            self.print_summary = true;
+            self.exit = true;
+        } else {
+            let summary =
+                CaptureSummary::new(&self.packets, &self.talker_stats, started_at.elapsed());
+            self.notify(
+                Severity::Note,
+                strings::get("status.capture_limit")
+                    .replace("{packets}", &summary.packets.to_string())
+                    .replace("{bytes}", &summary.bytes.to_string())
+                    .replace("{seconds}", &summary.duration.as_secs().to_string()),
+            );
+        }
+        true
+    }
+
+    fn handle_sniffer_stopped(&mut self) {
+        // A capture ends on its own when its interface goes away, which the refresh
+        // notices and moves on from.
+        if self.sniffer.is_finished() && !self.refresh_interfaces() {
+            self.sniffer.stop();
+        }
+    }
+
+    fn handle_packet_captured(&mut self, mut packet: CompletePacket) {
+        if self.stopped_at_limit && self.sniffer.sniffer_paused {
+            return;
+        }
+        self.talker_stats.record(&packet);
+        self.packet_rate.record(&packet);
+        packet.tcp_direction = self.tcp_flows.record(&packet);
+        packet.tcp_rtt = self.tcp_rtt.record(&packet);
+        packet.tcp_state = self.tcp_states.record(&packet);
+        let findings = self.expert_analyzer.analyze(&packet);
+        packet.expert_severity = findings.first().map(|finding| finding.severity);
+        if !findings.is_empty() && !self.expert_info.is_empty() && self.show_expert_info {
+            // Keep the selected finding in place while new ones are added above it.
+            let selected = self.expert_info_state.selected().unwrap_or(0);
+            self.expert_info_state
+                .select(Some(selected + findings.len()));
+        }
+        self.expert_info.extend(findings.into_iter().rev());
+        self.write_json(|writer| writer.write(&packet));
+        let visible = self.shows(
+            &packet,
+            self.display_filter.as_ref().filter(|_| !self.dim_unmatched),
+        );
+        self.packets.push(packet);
+        if visible {
+            self.visible_rows.push(self.packets.len() - 1);
+            self.packet_table_state.packet_inserted();
+        }
+    }
+
+    /// Runs `operation` on the JSON stream, if any, and stops streaming when it fails
+    /// rather than erroring out of the capture.
+    fn write_json(&mut self, operation: impl FnOnce(&mut JsonLinesWriter) -> io::Result<()>) {
+        let Some(writer) = self.json_output.as_mut() else {
+            return;
+        };
+        if let Err(error) = operation(writer) {
+            self.json_output = None;
+            self.notify(
+                Severity::Error,
+                strings::get("status.json_out_error").replace("{error}", &error.to_string()),
+            );
+        }
     }
 
     fn draw(&mut self, frame: &mut Frame) {
-        let (top_area, packets_area, footer_area) = LayoutHelper::create_main_layout(frame.area());
-        let (chart_area, interfaces_area) = LayoutHelper::create_top_layout(top_area);
+        let area = frame.area();
+        if area.width < Self::MIN_WIDTH || area.height < Self::MIN_HEIGHT {
+            self.render_too_small(frame, area);
+            return;
+        }
 
-        self.render_sniffer(frame, packets_area);
-        self.render_chart(frame, chart_area);
-        self.render_interfaces(frame, interfaces_area);
+        let (top_area, packets_area, footer_area) =
+            LayoutHelper::create_main_layout(frame.area(), &self.panel_layout);
+        let (chart_area, interfaces_area) = top_area.map_or((None, None), |top_area| {
+            LayoutHelper::create_top_layout(top_area, &self.panel_layout)
+        });
+
+        self.render_packet_table(frame, packets_area);
+        if let Some(chart_area) = chart_area {
+            self.render_chart(frame, chart_area);
+        }
+        if let Some(interfaces_area) = interfaces_area {
+            self.render_interfaces(frame, interfaces_area);
+        }
         self.render_footer(frame, footer_area);
+        if self.show_top_talkers {
+            self.render_top_talkers(frame, packets_area);
+        }
 
         if self.show_popup {
             self.render_popup(frame);
         }
+        if self.show_conversations {
+            self.render_conversations(frame);
+        }
+        if self.show_expert_info {
+            self.render_expert_info(frame);
+        }
+        if self.show_filter_presets {
+            self.render_filter_presets(frame);
+        }
+        if self.show_comparison {
+            self.render_comparison(frame);
+        }
+        if let Some(stream) = self.followed_stream {
+            let (endpoints, segments) = stream.segments(&self.packets);
+            StreamWidget::new(endpoints, &segments, &self.keymap, &self.theme).render(
+                frame,
+                frame.area(),
+                &mut self.stream_scroll,
+            );
+        }
+        if self.show_columns {
+            let columns_widget = ColumnsWidget::new(&self.columns, &self.keymap, &self.theme);
+            columns_widget.render(frame, frame.area(), &mut self.columns_state);
+        }
+        if self.show_command_palette {
+            let command_palette_widget =
+                CommandPaletteWidget::new(&self.keymap, &self.command_palette_query, &self.theme);
+            command_palette_widget.render(frame, frame.area(), &mut self.command_palette_state);
+        }
+        if self.show_event_log {
+            let event_log_widget = EventLogWidget::new(&self.event_log, &self.keymap, &self.theme);
+            event_log_widget.render(frame, frame.area(), &mut self.event_log_state);
+        }
+        if let Some(message) = &self.sniffer_error {
+            ErrorDialog::new(message, &self.theme).render(frame, frame.area());
+        }
     }
 
-    fn render_sniffer(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
-        self.sniffer.draw(frame, area);
+    fn render_too_small(&mut self, frame: &mut Frame, area: Rect) {
+        // Nothing clickable is on screen until the terminal grows again.
+        self.packet_table_area = Rect::default();
+        self.popup_area = Rect::default();
+
+        let message = strings::get("app.too_small")
+            .replace("{min_width}", &Self::MIN_WIDTH.to_string())
+            .replace("{min_height}", &Self::MIN_HEIGHT.to_string())
+            .replace("{width}", &area.width.to_string())
+            .replace("{height}", &area.height.to_string());
+        // Word wrapping can take a line more than the characters alone need.
+        let lines = (message.chars().count() as u16).div_ceil(area.width.max(1)) + 1;
+        let [message_area] = Layout::vertical([Constraint::Length(lines)])
+            .flex(Flex::Center)
+            .areas(area);
+        frame.render_widget(
+            Paragraph::new(message).wrap(Wrap { trim: true }).centered(),
+            message_area,
+        );
+    }
+
+    fn render_packet_table(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let resolver = self.resolver.as_ref().filter(|_| self.resolve_names);
+        let visible_packets = Self::rows(
+            &self.packets,
+            &self.visible_rows,
+            self.packet_table_state.oldest_first,
+        );
+        let packet_table =
+            PacketTable::new(&visible_packets, self.sniffer.sniffer_paused, &self.theme)
+                .resolver(resolver)
+                .geoip(self.geoip.as_ref())
+                .display_filter(self.display_filter.as_ref(), self.dim_unmatched)
+                .verify_checksums(self.verify_checksums)
+                .highlight_expert_info(self.highlight_expert_info)
+                .highlight_rules(&self.highlighting)
+                .bookmarks(&self.bookmarks)
+                .protocol_toggles(
+                    &self.protocol_toggles,
+                    Action::TOGGLE_PROTOCOL.map(|action| self.keymap.key_label(action)),
+                )
+                .direction_filter(self.direction_filter)
+                .total_captured(self.packets.len())
+                .columns(&self.columns)
+                .start_key(
+                    self.capture_started_at
+                        .is_none()
+                        .then(|| self.keymap.key_label(Action::ToggleSniffer)),
+                )
+                .block(Block::default().borders(Borders::ALL));
+        frame.render_stateful_widget(packet_table, area, &mut self.packet_table_state);
+        self.packet_table_area = area;
     }
 
     fn render_chart(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
-        let chart_widget = ChartWidget::new(&self.packets);
+        let chart_widget = ChartWidget::new(&self.packets, &self.theme)
+            .mode(self.chart_mode)
+            .protocol_toggles(&self.protocol_toggles);
         chart_widget.render(frame, area);
-    }    
+    }
     fn render_footer(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
-        let footer = Footer::new();
+        let packet_rate = self
+            .packet_rate
+            .history(Local::now(), Self::PACKET_RATE_SECONDS);
+        let mut footer = Footer::new(&self.keymap, &self.theme).packet_rate(&packet_rate);
+        if self.input_mode == InputMode::Search {
+            footer = footer.prompt(format!("/{}", self.input_buffer));
+        } else if self.input_mode == InputMode::Filter {
+            footer = footer.prompt(format!(
+                "{} {}",
+                strings::get("footer.filter_prompt"),
+                self.input_buffer
+            ));
+            // An empty filter is valid too, but only clears the current one.
+            if !self.input_buffer.trim().is_empty() {
+                footer = footer.prompt_feedback(DisplayFilter::validate(&self.input_buffer));
+            }
+        } else if self.input_mode == InputMode::GoToPacket {
+            footer = footer.prompt(format!(
+                "{} {}",
+                strings::get("footer.go_to_prompt"),
+                self.input_buffer
+            ));
+        } else if self.input_mode == InputMode::DecodeAs {
+            footer = footer.prompt(format!(
+                "{} {}",
+                strings::get("footer.decode_as_prompt"),
+                self.input_buffer
+            ));
+        } else if self.input_mode == InputMode::SavePcap {
+            footer = footer.prompt(format!(
+                "{} {}",
+                strings::get("footer.save_pcap_prompt"),
+                self.input_buffer
+            ));
+        } else if let InputMode::Comment(packet_id) = self.input_mode {
+            footer = footer.prompt(format!(
+                "{} {}",
+                strings::get("footer.comment_prompt").replace("{id}", &packet_id.to_string()),
+                self.input_buffer
+            ));
+        } else if let Some(status) = &self.status_message {
+            footer = footer.status(status.clone());
+        }
+        let mut capture_stats = Vec::new();
+        if let Some(sample) = self.sniffer.options.sample {
+            capture_stats.push(strings::get("footer.sampling").replace("{n}", &sample.to_string()));
+        }
+        if let (Some(interface), Some(counters)) =
+            (&self.interface, self.interface_stats.counters())
+        {
+            capture_stats.push(
+                strings::get("footer.interface_stats")
+                    .replace("{interface}", &interface.name)
+                    .replace("{received}", &counters.rx_packets.to_string())
+                    .replace("{dropped}", &counters.rx_dropped.to_string()),
+            );
+        }
+        if !capture_stats.is_empty() {
+            footer = footer.interface_stats(capture_stats.join("  "));
+        }
         footer.render(frame, area);
     }
 
     fn render_interfaces(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
-        let interfaces_widget = InterfacesWidget::new(&self.interfaces, &self.interface);
+        let interfaces_widget =
+            InterfacesWidget::new(&self.interfaces, &self.interface, &self.theme)
+                .mtus(&self.interface_mtus);
         interfaces_widget.render(
             frame,
             area,
@@ -205,12 +2007,78 @@ impl App {
         );
     }
 
-    fn render_popup(&self, frame: &mut Frame) {
-        let popup_widget = PopupWidget::new(&self.selected_popup_packet);
-        popup_widget.render(frame, frame.area());
+    fn render_top_talkers(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let top_talkers_widget = TopTalkersWidget::new(&self.talker_stats, &self.theme, 5);
+        top_talkers_widget.render(frame, area);
+    }
+
+    fn render_conversations(&self, frame: &mut Frame) {
+        let conversations_widget = ConversationsWidget::new(
+            &self.packets,
+            &self.keymap,
+            &self.theme,
+            self.conversation_sort,
+            self.conversation_ports,
+        );
+        conversations_widget.render(frame, frame.area());
+    }
+
+    fn render_expert_info(&mut self, frame: &mut Frame) {
+        let expert_info_widget =
+            ExpertInfoWidget::new(&self.expert_info, &self.keymap, &self.theme);
+        expert_info_widget.render(frame, frame.area(), &mut self.expert_info_state);
+    }
+
+    fn render_filter_presets(&mut self, frame: &mut Frame) {
+        let filter_presets_widget =
+            FilterPresetsWidget::new(&self.filter_presets, &self.keymap, &self.theme);
+        filter_presets_widget.render(frame, frame.area(), &mut self.filter_presets_state);
+    }
+
+    fn comparison_widget(&self) -> ComparisonWidget<'_> {
+        let [first, second] = &self.compared_packets;
+        ComparisonWidget::new([first, second], &self.keymap, &self.theme)
+            .verify_checksums(self.verify_checksums)
+            .geoip(self.geoip.as_ref())
+            .differences_only(self.comparison_differences_only)
+    }
+
+    fn render_comparison(&mut self, frame: &mut Frame) {
+        // The widget borrows the compared packets, so the state is moved out meanwhile.
+        let mut state = std::mem::take(&mut self.comparison_state);
+        self.comparison_widget()
+            .render(frame, frame.area(), &mut state);
+        self.comparison_state = state;
+    }
+
+    fn render_popup(&mut self, frame: &mut Frame) {
+        let popup_widget = PopupWidget::new(&self.selected_popup_packet, &self.theme)
+            .keymap(&self.keymap)
+            .verify_checksums(self.verify_checksums)
+            .geoip(self.geoip.as_ref())
+            .size_percent(self.popup_size_percent())
+            .max_bytes(self.popup_max_bytes)
+            .connection_state(
+                self.selected_popup_packet
+                    .as_ref()
+                    .and_then(|packet| self.tcp_states.state(packet)),
+            );
+        popup_widget.render(frame, frame.area(), &self.popup_state);
+        self.popup_area = PopupWidget::area(frame.area(), self.popup_size_percent());
+    }
+
+    fn popup_size_percent(&self) -> u16 {
+        if self.popup_maximized {
+            PopupWidget::MAXIMIZED_SIZE_PERCENT
+        } else {
+            self.popup_size
+        }
     }
 
     fn next_active_interface(&mut self) {
+        if self.refresh_interfaces() {
+            return;
+        }
         self.sniffer.stop();
 
         if self.interfaces.is_empty() {
@@ -219,26 +2087,49 @@ impl App {
             return;
         }
 
-        let current_idx = self.interfaces_table_state.selected().unwrap_or(0);
-        let new_idx = (current_idx + 1) % self.interfaces.len();
+        let new_idx = match self.interfaces_table_state.selected() {
+            Some(current_idx) => (current_idx + 1) % self.interfaces.len(),
+            None => 0,
+        };
 
         self.interfaces_table_state.select(Some(new_idx));
         self.interface = self.interfaces.get(new_idx).cloned();
-        self.sniffer.network_interface = self.interfaces.get(new_idx).cloned();
+        self.interface_stats.reset();
+        self.sniffer.network_interfaces = self.capture_interfaces();
 
-        if self.interface.is_some() {
+        if self.interface.is_some() && self.capture_started_at.is_some() {
             self.sniffer.start();
+            self.log_capture_interface();
+        }
+    }
+
+    fn log_capture_interface(&mut self) {
+        if let Some(interface) = &self.interface {
+            let message = strings::get("log.capturing").replace("{interface}", &interface.name);
+            self.event_log.push(Severity::Note, message);
         }
     }
 }
 
-pub fn handle_input_events(tx: mpsc::Sender<Event>) {
-    loop {
-        match crossterm::event::read().unwrap() {
-            crossterm::event::Event::Key(key_event) => {
-                tx.send(Event::Input(key_event)).unwrap();
+/// Forwards key and mouse events to the app. Polls with a timeout instead of blocking on `read` so
+/// the thread notices `shutdown` and exits with the app.
+pub fn handle_input_events(tx: mpsc::Sender<Event>, shutdown: Arc<AtomicBool>) {
+    while !shutdown.load(Ordering::Relaxed) {
+        match crossterm::event::poll(Duration::from_millis(100)) {
+            Ok(true) => {
+                let event = match crossterm::event::read() {
+                    Ok(crossterm::event::Event::Key(key_event)) => Event::Input(key_event),
+                    Ok(crossterm::event::Event::Mouse(mouse_event)) => Event::Mouse(mouse_event),
+                    Ok(crossterm::event::Event::Resize(..)) => Event::Resize,
+                    Ok(_) => continue,
+                    Err(_) => break,
+                };
+                if tx.send(event).is_err() {
+                    break;
+                }
             }
-            _ => {}
+            Ok(false) => {}
+            Err(_) => break,
         }
     }
 }
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+
+use crate::{
+    packet_data::{CompletePacket, PacketsData},
+    strings,
+};
+
+/// Well-known ports, used when the payload has no recognizable signature.
+const TCP_PORTS: [(u16, &str); 17] = [
+    (21, "FTP"),
+    (22, "SSH"),
+    (23, "Telnet"),
+    (25, "SMTP"),
+    (53, "DNS"),
+    (80, "HTTP"),
+    (110, "POP3"),
+    (143, "IMAP"),
+    (443, "HTTPS"),
+    (445, "SMB"),
+    (587, "SMTP"),
+    (993, "IMAPS"),
+    (995, "POP3S"),
+    (3306, "MySQL"),
+    (3389, "RDP"),
+    (5432, "PostgreSQL"),
+    (6379, "Redis"),
+];
+
+const UDP_PORTS: [(u16, &str); 12] = [
+    (53, "DNS"),
+    (67, "DHCP"),
+    (68, "DHCP"),
+    (69, "TFTP"),
+    (123, "NTP"),
+    (161, "SNMP"),
+    (162, "SNMP"),
+    (443, "QUIC"),
+    (1812, "RADIUS"),
+    (1813, "RADIUS"),
+    (1900, "SSDP"),
+    (5353, "mDNS"),
+];
+
+const HTTP_PREFIXES: [&[u8]; 8] = [
+    b"GET ",
+    b"POST ",
+    b"PUT ",
+    b"HEAD ",
+    b"DELETE ",
+    b"OPTIONS ",
+    b"PATCH ",
+    b"HTTP/1.",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Transport {
+    Tcp,
+    Udp,
+}
+
+impl Transport {
+    pub fn name(self) -> &'static str {
+        match self {
+            Transport::Tcp => "TCP",
+            Transport::Udp => "UDP",
+        }
+    }
+}
+
+/// Application protocols with a dissector, which can be run on any port with "decode as".
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppProto {
+    Dns,
+    Mdns,
+    Http,
+    Ssdp,
+    Radius,
+    Gtp,
+    Vxlan,
+}
+
+impl AppProto {
+    const ALL: [AppProto; 7] = [
+        AppProto::Dns,
+        AppProto::Mdns,
+        AppProto::Http,
+        AppProto::Ssdp,
+        AppProto::Radius,
+        AppProto::Gtp,
+        AppProto::Vxlan,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AppProto::Dns => "DNS",
+            AppProto::Mdns => "mDNS",
+            AppProto::Http => "HTTP",
+            AppProto::Ssdp => "SSDP",
+            AppProto::Radius => "RADIUS",
+            AppProto::Gtp => "GTP",
+            AppProto::Vxlan => "VXLAN",
+        }
+    }
+
+    /// Whether the dissector can decode this protocol over `transport`.
+    pub fn runs_over(self, transport: Transport) -> bool {
+        match self {
+            AppProto::Dns => true,
+            AppProto::Http => transport == Transport::Tcp,
+            AppProto::Mdns
+            | AppProto::Ssdp
+            | AppProto::Radius
+            | AppProto::Gtp
+            | AppProto::Vxlan => transport == Transport::Udp,
+        }
+    }
+}
+
+/// Ports the user asked to decode as a given protocol, checked before the well-known
+/// ports of each dissector.
+#[derive(Clone, Default)]
+pub struct DecodeAs {
+    ports: HashMap<(Transport, u16), AppProto>,
+}
+
+impl DecodeAs {
+    /// The protocol either port is decoded as. The lower port is usually the server
+    /// side, so it wins when both have one.
+    pub fn get(&self, transport: Transport, source: u16, destination: u16) -> Option<AppProto> {
+        let mut candidates = [source, destination];
+        candidates.sort_unstable();
+        candidates
+            .iter()
+            .find_map(|port| self.ports.get(&(transport, *port)).copied())
+    }
+
+    /// Decodes `port` as `protocol`, or by its well-known port again when `None`.
+    pub fn set(&mut self, transport: Transport, port: u16, protocol: Option<AppProto>) {
+        match protocol {
+            Some(protocol) => self.ports.insert((transport, port), protocol),
+            None => self.ports.remove(&(transport, port)),
+        };
+    }
+
+    /// Parses a rule like "tcp 8080 http". Leaving out the protocol removes the rule for
+    /// the port.
+    pub fn parse_rule(rule: &str) -> Result<(Transport, u16, Option<AppProto>), String> {
+        let mut words = rule.split_whitespace();
+        let transport = match words.next().map(str::to_lowercase).as_deref() {
+            Some("tcp") => Transport::Tcp,
+            Some("udp") => Transport::Udp,
+            _ => return Err(strings::get("decode_as.missing_transport").to_string()),
+        };
+        let port = words
+            .next()
+            .ok_or_else(|| strings::get("decode_as.missing_port").to_string())?;
+        let port = port
+            .parse()
+            .map_err(|_| strings::get("filter.invalid_port").replace("{value}", port))?;
+        let Some(name) = words.next() else {
+            return Ok((transport, port, None));
+        };
+        let protocol = AppProto::ALL
+            .into_iter()
+            .find(|protocol| protocol.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| strings::get("decode_as.unknown_protocol").replace("{value}", name))?;
+        if !protocol.runs_over(transport) {
+            return Err(strings::get("decode_as.wrong_transport")
+                .replace("{protocol}", protocol.name())
+                .replace("{transport}", transport.name()));
+        }
+        Ok((transport, port, Some(protocol)))
+    }
+}
+
+/// The application protocol of `packet`: the one a dissector decoded, otherwise the
+/// guess made from ports and payload when the packet was captured.
+pub fn classify_app_protocol(packet: &CompletePacket) -> Option<&'static str> {
+    match &packet.layer_4 {
+        Some(PacketsData::DnsPacket(_)) => Some("DNS"),
+        Some(PacketsData::MdnsPacket(_)) => Some("mDNS"),
+        Some(PacketsData::HttpPacket(_)) => Some("HTTP"),
+        Some(PacketsData::SsdpPacket(_)) => Some("SSDP"),
+        Some(PacketsData::RadiusPacket(_)) => Some("RADIUS"),
+        Some(PacketsData::SshPacket(_)) => Some("SSH"),
+        _ => packet.app_protocol_guess,
+    }
+}
+
+/// Guesses the application protocol from the first payload bytes, falling back to the
+/// well-known port on either side.
+pub fn guess(
+    transport: Transport,
+    source: u16,
+    destination: u16,
+    payload: &[u8],
+) -> Option<&'static str> {
+    signature(transport, source, destination, payload).or_else(|| {
+        let ports: &[(u16, &str)] = match transport {
+            Transport::Tcp => &TCP_PORTS,
+            Transport::Udp => &UDP_PORTS,
+        };
+        // The lower port is usually the server side, so it wins when both are known.
+        let mut candidates = [source, destination];
+        candidates.sort_unstable();
+        candidates.iter().find_map(|port| {
+            ports
+                .iter()
+                .find(|(known, _)| known == port)
+                .map(|(_, name)| *name)
+        })
+    })
+}
+
+fn signature(
+    transport: Transport,
+    source: u16,
+    destination: u16,
+    payload: &[u8],
+) -> Option<&'static str> {
+    let uses_port = |port: u16| source == port || destination == port;
+    match transport {
+        Transport::Tcp => {
+            if is_tls_record(payload) {
+                Some("TLS")
+            } else if payload.starts_with(b"SSH-") {
+                Some("SSH")
+            } else if HTTP_PREFIXES
+                .iter()
+                .any(|prefix| payload.starts_with(prefix))
+            {
+                Some("HTTP")
+            } else {
+                None
+            }
+        }
+        Transport::Udp => {
+            // BOOTP fixed header, then the DHCP magic cookie.
+            if (uses_port(67) || uses_port(68))
+                && payload.get(236..240) == Some(&[0x63, 0x82, 0x53, 0x63])
+            {
+                Some("DHCP")
+            } else if uses_port(123) && payload.len() >= 48 {
+                Some("NTP")
+            } else if uses_port(443) && payload.first().is_some_and(|byte| byte & 0xc0 == 0xc0) {
+                // QUIC long header: header form and fixed bits set.
+                Some("QUIC")
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// A TLS record header: content type 20-23, then a 3.x protocol version.
+fn is_tls_record(payload: &[u8]) -> bool {
+    matches!(payload, [20..=23, 0x03, 0x00..=0x04, ..])
+}
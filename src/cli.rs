@@ -0,0 +1,151 @@
+use std::{path::PathBuf, time::Duration};
+
+use clap::Parser;
+
+use crate::strings::Language;
+
+#[derive(Parser)]
+#[command(version, about = "Terminal packet sniffer")]
+pub struct Args {
+    /// Interface language; defaults to the one in the LANG environment variable
+    #[arg(long, value_enum)]
+    pub lang: Option<Language>,
+
+    /// Interface to capture on, e.g. `lo`; overrides the one remembered from the last run
+    #[arg(short, long, value_name = "NAME")]
+    pub interface: Option<String>,
+
+    /// List every interface that is up, not only loopback and those with a private IPv4
+    /// address
+    #[arg(long)]
+    pub all_interfaces: bool,
+
+    /// Keep only the first N bytes of each frame, overriding `snaplen` from the config file
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub snaplen: Option<u64>,
+
+    /// Recompute IPv4, TCP, UDP and ICMP checksums and flag the packets that don't match
+    #[arg(long)]
+    pub verify_checksums: bool,
+
+    /// Capture read buffer size in bytes, overriding `read_buffer_size` from the config file.
+    /// On macOS and the BSDs a larger buffer drops fewer packets in bursts; on Linux it is
+    /// the largest frame that can be read
+    #[arg(long, value_name = "BYTES", value_parser = clap::value_parser!(u64).range(1024..=64 * 1024 * 1024))]
+    pub buffer_size: Option<u64>,
+
+    /// Ethernet frames end with their 4-byte frame check sequence, as some NICs and capture
+    /// drivers deliver them: decode them without it and flag frames whose FCS doesn't
+    /// match; overrides `fcs` from the config file
+    #[arg(long)]
+    pub fcs: bool,
+
+    /// Capture only traffic to and from this host instead of putting the interface in
+    /// promiscuous mode, which can need extra privileges and is visible to IDS sensors
+    #[arg(long)]
+    pub no_promisc: bool,
+
+    /// Maximum redraws per second, overriding `max_fps` from the config file
+    #[arg(long, value_name = "FPS", value_parser = clap::value_parser!(u32).range(1..=240))]
+    pub max_fps: Option<u32>,
+
+    /// Disable reverse DNS resolution of IP addresses entirely
+    #[arg(long)]
+    pub no_resolve: bool,
+
+    /// Annotate public addresses with their country and autonomous system from a MaxMind DB
+    /// file, such as GeoLite2-Country.mmdb or GeoLite2-ASN.mmdb; repeat to load both
+    #[arg(long, value_name = "PATH")]
+    pub geoip: Vec<PathBuf>,
+
+    /// Print a summary of the session on exit: duration, packet and byte totals, packets
+    /// per protocol and the top talkers
+    #[arg(long)]
+    pub summary: bool,
+
+    /// Append every captured packet to this file or named pipe as a JSON object per line,
+    /// as it is captured
+    #[arg(long, value_name = "PATH")]
+    pub json_out: Option<PathBuf>,
+
+    /// Stop capturing after N packets
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub count: Option<u64>,
+
+    /// Stop capturing after this long, e.g. `30s`, `5m` or `1h`; plain numbers are seconds
+    #[arg(long, value_name = "TIME", value_parser = parse_duration)]
+    pub duration: Option<Duration>,
+
+    /// Keep only one of every N captured frames, e.g. `1/100`, to follow a busy link
+    /// without every packet; the footer shows the rate so counts aren't read as totals
+    #[arg(long, value_name = "1/N", value_parser = parse_sample)]
+    pub sample: Option<u32>,
+
+    /// Launch with the capture paused, to pick an interface or set a filter before starting
+    /// it with the sniffer toggle key; overrides `auto_start` from the config file
+    #[arg(long)]
+    pub no_start: bool,
+
+    /// Exit and print the capture summary once `--count` or `--duration` stops the capture
+    #[arg(long)]
+    pub exit_on_limit: bool,
+}
+
+/// Parses a `1/N` sampling rate, or just `N`, into N.
+fn parse_sample(value: &str) -> Result<u32, String> {
+    let denominator = value.strip_prefix("1/").unwrap_or(value);
+    match denominator.parse() {
+        Ok(0) => Err("the sampling rate must be 1/N with N of at least 1".to_string()),
+        Ok(n) => Ok(n),
+        Err(_) => Err(format!("expected a rate like `1/100`, got `{value}`")),
+    }
+}
+
+/// Parses a whole number followed by `ms`, `s`, `m` or `h`, or no unit for seconds.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("expected a number with an optional unit, got `{value}`"))?;
+    let seconds = |factor: u64| {
+        number
+            .checked_mul(factor)
+            .map(Duration::from_secs)
+            .ok_or_else(|| "duration too large".to_string())
+    };
+    let duration = match unit {
+        "ms" => Duration::from_millis(number),
+        "" | "s" => Duration::from_secs(number),
+        "m" => seconds(60)?,
+        "h" => seconds(3600)?,
+        _ => return Err(format!("unknown unit `{unit}`, expected ms, s, m or h")),
+    };
+    if duration.is_zero() {
+        return Err("the duration must be greater than zero".to_string());
+    }
+    Ok(duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::parse_duration;
+
+    #[test]
+    fn durations_take_a_unit_and_reject_overflow() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("2h"), Ok(Duration::from_secs(7200)));
+        assert!(parse_duration("0m").is_err());
+        assert!(parse_duration("5d").is_err());
+        assert_eq!(
+            parse_duration("999999999999999999h"),
+            Err("duration too large".to_string())
+        );
+        assert!(parse_duration("99999999999999999999").is_err());
+    }
+}
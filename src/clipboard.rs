@@ -0,0 +1,210 @@
+use std::{
+    sync::mpsc::{self, Sender},
+    thread,
+};
+
+use arboard::Clipboard;
+
+use crate::{
+    event::Event,
+    packet_data::{CompletePacket, DnsPacketInfo, MplsPacketInfo, PacketsData, VlanPacketInfo},
+    strings,
+    widgets::{packet_table::PacketTable, popup::PopupWidget},
+};
+
+/// Owns the system clipboard on a dedicated thread, so slow or unavailable clipboard
+/// backends never block the UI and copied contents outlive the copy request.
+pub struct ClipboardWorker {
+    tx: Sender<String>,
+}
+
+impl ClipboardWorker {
+    pub fn new(event_tx: Sender<Event>) -> Self {
+        let (tx, rx) = mpsc::channel::<String>();
+
+        thread::spawn(move || {
+            let mut clipboard = None;
+            for text in rx {
+                if clipboard.is_none() {
+                    clipboard = Clipboard::new().ok();
+                }
+                let message = match clipboard.as_mut().map(|c| c.set_text(text)) {
+                    Some(Ok(())) => strings::get("status.clipboard_copied").to_string(),
+                    Some(Err(e)) => {
+                        strings::get("status.clipboard_error").replace("{error}", &e.to_string())
+                    }
+                    None => strings::get("status.clipboard_unavailable").to_string(),
+                };
+                if event_tx.send(Event::StatusMessage(message)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self { tx }
+    }
+
+    pub fn copy(&self, text: String) {
+        let _ = self.tx.send(text);
+    }
+}
+
+pub fn packet_summary(packet: &CompletePacket) -> String {
+    let mut lines = Vec::new();
+
+    if let Some([id, protocol, source, destination, length]) =
+        PacketTable::generate_ref_array(packet)
+    {
+        lines.push(format!(
+            "#{id} {protocol} {source} -> {destination} ({length} bytes) on {}",
+            packet.interface
+        ));
+    }
+
+    lines.extend(packet.layer_1.iter().map(layer_summary));
+    lines.extend(packet.vlan_tags.iter().map(vlan_summary));
+    lines.extend(packet.mpls.iter().map(mpls_summary));
+    lines.extend(
+        [&packet.layer_2, &packet.layer_3, &packet.layer_4]
+            .into_iter()
+            .flatten()
+            .map(layer_summary),
+    );
+    lines.extend(packet.tunneled_layers().iter().map(layer_summary));
+
+    lines.join("\n")
+}
+
+pub fn vlan_summary(vlan: &VlanPacketInfo) -> String {
+    format!(
+        "VLAN: id {}, priority {}, EtherType {}",
+        vlan.vlan_id, vlan.priority, vlan.ethertype
+    )
+}
+
+pub fn mpls_summary(mpls: &MplsPacketInfo) -> String {
+    format!(
+        "MPLS: labels {}",
+        mpls.labels
+            .iter()
+            .map(|label| format!("{} (TTL {})", label.label, label.ttl))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+pub fn layer_summary(layer: &PacketsData) -> String {
+    match layer {
+        PacketsData::Ieee80211Packet(wifi) => format!(
+            "802.11: {} -> {}, {}",
+            wifi.transmitter()
+                .map_or("-".to_string(), |mac| mac.to_string()),
+            wifi.receiver()
+                .map_or("-".to_string(), |mac| mac.to_string()),
+            wifi.summary()
+        ),
+        PacketsData::EthernetPacket(ethernet) => format!(
+            "Ethernet: {} -> {}, EtherType {}",
+            ethernet.source, ethernet.destination, ethernet.ethertype
+        ),
+        PacketsData::ArpPacket(arp) => format!(
+            "ARP: {}, {} ({}) -> {} ({})",
+            PopupWidget::arp_operation_description(arp.operation),
+            arp.sender_proto_addr,
+            arp.sender_hw_addr,
+            arp.target_proto_addr,
+            arp.target_hw_addr
+        ),
+        PacketsData::LldpPacket(lldp) => format!(
+            "LLDP: system {}, chassis {}, port {}",
+            lldp.system_name.as_deref().unwrap_or("-"),
+            lldp.chassis_id.as_deref().unwrap_or("-"),
+            lldp.port_id.as_deref().unwrap_or("-")
+        ),
+        PacketsData::Ipv4Packet(ipv4) => format!(
+            "IPv4: {} -> {}, protocol {}, TTL {}, ID {}",
+            ipv4.source, ipv4.destination, ipv4.next_level_protocol, ipv4.ttl, ipv4.identification
+        ),
+        PacketsData::Ipv6Packet(ipv6) => format!(
+            "IPv6: {} -> {}, next header {}, hop limit {}",
+            ipv6.source, ipv6.destination, ipv6.next_header, ipv6.hop_limit
+        ),
+        PacketsData::TcpPacket(tcp) => format!(
+            "TCP: {} -> {}, seq {}, ack {}, flags 0x{:02x}, window {}",
+            tcp.source, tcp.destination, tcp.sequence, tcp.acknowledgement, tcp.flags, tcp.window
+        ),
+        PacketsData::UdpPacket(udp) => format!(
+            "UDP: {} -> {}, length {}",
+            udp.source, udp.destination, udp.length
+        ),
+        PacketsData::SctpPacket(sctp) => format!(
+            "SCTP: {} -> {}, verification tag 0x{:08x}, {} chunks",
+            sctp.source,
+            sctp.destination,
+            sctp.verification_tag,
+            sctp.chunks.len()
+        ),
+        PacketsData::IcmpPacket(icmp) => match &icmp.original {
+            Some(original) => format!(
+                "ICMP: type {}, code {}, original packet {}",
+                icmp.icmp_type.0, icmp.icmp_code.0, original
+            ),
+            None => format!("ICMP: type {}, code {}", icmp.icmp_type.0, icmp.icmp_code.0),
+        },
+        PacketsData::Icmpv6Packet(icmpv6) => match &icmpv6.ndp {
+            Some(ndp) => format!(
+                "ICMPv6: type {}, code {}, {}",
+                icmpv6.icmpv6_type.0,
+                icmpv6.icmpv6_code.0,
+                ndp.summary(icmpv6.icmpv6_type)
+            ),
+            None => format!(
+                "ICMPv6: type {}, code {}",
+                icmpv6.icmpv6_type.0, icmpv6.icmpv6_code.0
+            ),
+        },
+        PacketsData::HttpPacket(http) => match (&http.method, http.status_code) {
+            (Some(method), _) => format!(
+                "HTTP: {} {} {}, host {}",
+                method,
+                http.path.as_deref().unwrap_or("-"),
+                http.version,
+                http.host.as_deref().unwrap_or("-")
+            ),
+            (None, status_code) => format!(
+                "HTTP: {} {} {}",
+                http.version,
+                status_code.unwrap_or_default(),
+                http.reason.as_deref().unwrap_or_default()
+            ),
+        },
+        PacketsData::DnsPacket(dns) => dns_summary("DNS", dns),
+        PacketsData::MdnsPacket(mdns) => dns_summary("mDNS", mdns),
+        PacketsData::SsdpPacket(ssdp) => format!("SSDP: {}", ssdp.summary()),
+        PacketsData::RadiusPacket(radius) => format!("RADIUS: {}", radius.summary()),
+        PacketsData::SshPacket(ssh) => format!("SSH: {}", ssh.summary()),
+        PacketsData::GtpPacket(gtp) => format!(
+            "GTP-U: {}, TEID 0x{:08x}, length {}",
+            PopupWidget::gtp_message_type_description(gtp.message_type),
+            gtp.teid,
+            gtp.length
+        ),
+        PacketsData::VxlanPacket(vxlan) => format!(
+            "VXLAN: VNI {}, flags 0x{:02x}, length {}",
+            vxlan.vni, vxlan.flags, vxlan.length
+        ),
+    }
+}
+
+fn dns_summary(label: &str, dns: &DnsPacketInfo) -> String {
+    format!(
+        "{label}: {} 0x{:04x}, {}",
+        if dns.is_response { "response" } else { "query" },
+        dns.id,
+        dns.questions
+            .iter()
+            .map(|question| question.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
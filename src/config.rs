@@ -0,0 +1,196 @@
+use std::{collections::HashMap, env, fs, path::PathBuf};
+
+use serde::Deserialize;
+
+use crate::{
+    filter::{DirectionFilter, FilterPreset, HighlightRule, HighlightRules},
+    keymap::{Action, KeyNames, Keymap},
+    sniffer::CaptureOptions,
+    strings,
+    theme::Theme,
+    widgets::{layout_helper::PanelLayout, packet_table::Column, popup::PopupWidget},
+};
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub theme: Theme,
+    /// Upper bound on redraws per second while packets or input keep arriving.
+    pub max_fps: u32,
+    /// Bytes kept per captured frame; the whole frame when unset.
+    pub snaplen: Option<usize>,
+    /// Capture buffer sizes in bytes and read timeout in milliseconds; out-of-range values
+    /// fall back to the defaults with a warning.
+    pub read_buffer_size: Option<usize>,
+    pub write_buffer_size: Option<usize>,
+    pub read_timeout_ms: Option<u64>,
+    /// Start capturing as soon as the app launches, instead of waiting for the sniffer
+    /// toggle key.
+    pub auto_start: bool,
+    /// Ethernet frames are captured with their frame check sequence at the end.
+    pub fcs: bool,
+    /// Put interfaces in promiscuous mode to see traffic between other hosts too.
+    pub promiscuous: bool,
+    /// Flag packets whose checksums don't match. Off by default, since checksum offload
+    /// leaves outgoing packets with checksums the NIC fills in only after capture.
+    pub verify_checksums: bool,
+    /// Color packet rows by their most severe expert info finding.
+    pub highlight_expert_info: bool,
+    /// Whether moving past the last packet row goes back to the first and vice versa,
+    /// instead of stopping at the ends.
+    pub wrap_navigation: bool,
+    /// List inbound, outbound or both kinds of packets at startup.
+    pub direction: DirectionFilter,
+    /// Display filters offered in the preset picker, ahead of the ones saved from it.
+    pub filter_presets: Vec<FilterPreset>,
+    /// Colors for the table rows of the packets matching a display filter; the first
+    /// matching rule wins.
+    highlight_rules: Vec<HighlightRule>,
+    #[serde(skip)]
+    pub highlighting: HighlightRules,
+    /// Percentage of the terminal's width and height the packet details popup covers.
+    pub popup_size: Option<u16>,
+    /// Frame bytes the packet details popup shows before asking for a key to show the
+    /// rest; 0 shows every captured byte.
+    pub popup_max_bytes: usize,
+    /// Panel sizes to start with, instead of the ones remembered from the last run.
+    pub layout: Option<PanelLayout>,
+    /// Packet table columns to start with, instead of the ones remembered from the last run.
+    pub columns: Option<Vec<Column>>,
+    keys: HashMap<Action, KeyNames>,
+    #[serde(skip)]
+    pub keymap: Keymap,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            max_fps: 22,
+            snaplen: None,
+            read_buffer_size: None,
+            write_buffer_size: None,
+            read_timeout_ms: None,
+            auto_start: true,
+            fcs: false,
+            promiscuous: true,
+            verify_checksums: false,
+            highlight_expert_info: true,
+            wrap_navigation: true,
+            direction: DirectionFilter::Both,
+            filter_presets: FilterPreset::defaults(),
+            highlight_rules: HighlightRule::defaults(),
+            highlighting: HighlightRules::new(&HighlightRule::defaults()).0,
+            popup_size: None,
+            popup_max_bytes: PopupWidget::DEFAULT_MAX_BYTES,
+            layout: None,
+            columns: None,
+            keys: HashMap::new(),
+            keymap: Keymap::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Loads `~/.config/wirepenguin/config.toml`. A missing file yields the defaults; an
+    /// unreadable or invalid one also yields the defaults, together with a warning to show.
+    pub fn load() -> (Self, Option<String>) {
+        let Some(path) = Self::path() else {
+            return (Self::default(), None);
+        };
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return (Self::default(), None);
+            }
+            Err(e) => {
+                let warning = strings::get("status.config_read_error")
+                    .replace("{path}", &path.display().to_string())
+                    .replace("{error}", &e.to_string());
+                return (Self::default(), Some(warning));
+            }
+        };
+
+        match toml::from_str::<Config>(&contents) {
+            Ok(mut config) => {
+                config.theme = config.theme.with_default_protocols();
+                let (keymap, mut warnings) = Keymap::new(&config.keys);
+                config.keymap = keymap;
+                let (highlighting, highlight_warnings) =
+                    HighlightRules::new(&config.highlight_rules);
+                config.highlighting = highlighting;
+                warnings.extend(highlight_warnings);
+                warnings.extend(config.validate_capture_options());
+                let (min, max) = PopupWidget::SIZE_PERCENT_RANGE;
+                config.popup_size =
+                    Self::in_range("popup_size", config.popup_size, min, max, &mut warnings);
+                let warning = (!warnings.is_empty()).then(|| warnings.join("; "));
+                (config, warning)
+            }
+            Err(e) => {
+                let warning = strings::get("status.config_invalid")
+                    .replace("{path}", &path.display().to_string())
+                    .replace("{error}", e.message());
+                (Self::default(), Some(warning))
+            }
+        }
+    }
+
+    /// Drops capture settings outside the ranges the sniffer accepts, returning a
+    /// translated warning for each.
+    fn validate_capture_options(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let (min, max) = CaptureOptions::BUFFER_SIZE_RANGE;
+        self.read_buffer_size = Self::in_range(
+            "read_buffer_size",
+            self.read_buffer_size,
+            min,
+            max,
+            &mut warnings,
+        );
+        self.write_buffer_size = Self::in_range(
+            "write_buffer_size",
+            self.write_buffer_size,
+            min,
+            max,
+            &mut warnings,
+        );
+        let (min, max) = CaptureOptions::READ_TIMEOUT_MS_RANGE;
+        self.read_timeout_ms = Self::in_range(
+            "read_timeout_ms",
+            self.read_timeout_ms,
+            min,
+            max,
+            &mut warnings,
+        );
+        warnings
+    }
+
+    fn in_range<T: Copy + PartialOrd + ToString>(
+        name: &str,
+        value: Option<T>,
+        min: T,
+        max: T,
+        warnings: &mut Vec<String>,
+    ) -> Option<T> {
+        let value = value?;
+        if (min..=max).contains(&value) {
+            return Some(value);
+        }
+        warnings.push(
+            strings::get("status.config_out_of_range")
+                .replace("{option}", name)
+                .replace("{min}", &min.to_string())
+                .replace("{max}", &max.to_string()),
+        );
+        None
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        let config_dir = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(config_dir.join("wirepenguin").join("config.toml"))
+    }
+}
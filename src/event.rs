@@ -2,6 +2,11 @@ use crate::packet_data::CompletePacket;
 
 pub enum Event {
     Input(crossterm::event::KeyEvent),
-    PacketCaptured(CompletePacket),
-    Render,
+    Mouse(crossterm::event::MouseEvent),
+    /// The terminal was resized; only asks for a redraw.
+    Resize,
+    PacketCaptured(Box<CompletePacket>),
+    StatusMessage(String),
+    SnifferError(String),
+    SnifferStopped,
 }
@@ -0,0 +1,42 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+
+use crate::expert::Severity;
+
+/// A notice shown in the footer, kept so it can be read again after the footer moved on.
+pub struct LogEntry {
+    pub timestamp: DateTime<Local>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// The most recent errors and notable events of the session, newest first.
+#[derive(Default)]
+pub struct EventLog {
+    entries: VecDeque<LogEntry>,
+}
+
+impl EventLog {
+    const CAPACITY: usize = 200;
+
+    /// Records `message`, dropping the oldest entry once the log is full.
+    pub fn push(&mut self, severity: Severity, message: String) {
+        if self.entries.len() == Self::CAPACITY {
+            self.entries.pop_back();
+        }
+        self.entries.push_front(LogEntry {
+            timestamp: Local::now(),
+            severity,
+            message,
+        });
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
@@ -0,0 +1,433 @@
+use std::{cmp::Reverse, collections::HashMap, net::Ipv4Addr};
+
+use pnet::{
+    datalink::MacAddr,
+    packet::{
+        ethernet::{EtherType, EtherTypes},
+        icmp::{IcmpType, IcmpTypes},
+        icmpv6::{Icmpv6Type, Icmpv6Types},
+        ip::IpNextHeaderProtocols,
+        tcp::TcpFlags,
+    },
+};
+
+use crate::{
+    flows::{SegmentKind, TcpSequenceTracker, TrafficDirection},
+    packet_data::{ArpAddress, CompletePacket, PacketsData},
+    sniffer::LinkType,
+    strings,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Note,
+    Warn,
+    Error,
+}
+
+impl Severity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Note => strings::get("expert.note"),
+            Severity::Warn => strings::get("expert.warn"),
+            Severity::Error => strings::get("expert.error"),
+        }
+    }
+}
+
+/// A notable condition found in a captured packet.
+#[derive(Clone)]
+pub struct ExpertInfo {
+    pub packet_id: usize,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Looks at every captured packet for conditions worth a closer look, like Wireshark's
+/// expert info: retransmissions, resets, zero windows, expiring TTLs, ICMP errors,
+/// Ethernet frames too large for the interface MTU, too small or with a bad FCS, gratuitous ARP and IP
+/// addresses claimed by two MACs, and headers that are cut short or inconsistent.
+#[derive(Default)]
+pub struct ExpertAnalyzer {
+    sequences: TcpSequenceTracker,
+    /// MTU of each capture interface by name; frames from the others aren't size-checked.
+    pub interface_mtus: HashMap<String, usize>,
+    /// MAC address last seen announcing each IPv4 address in ARP.
+    arp_table: HashMap<Ipv4Addr, MacAddr>,
+}
+
+impl ExpertAnalyzer {
+    const ETHERNET_HEADER_LENGTH: usize = 14;
+    const VLAN_TAG_LENGTH: usize = 4;
+    /// Smallest Ethernet frame without its FCS; senders pad shorter ones up to it.
+    const ETHERNET_MIN_LENGTH: usize = 60;
+
+    /// Findings for `packet`, most severe first. Packets must be passed in capture order
+    /// for retransmissions to be told apart from new data.
+    pub fn analyze(&mut self, packet: &CompletePacket) -> Vec<ExpertInfo> {
+        let mut findings = Vec::new();
+        Self::check_malformed(packet, &mut findings);
+        self.check_tcp(packet, &mut findings);
+        Self::check_ttl(packet, &mut findings);
+        Self::check_icmp(packet, &mut findings);
+        self.check_frame_size(packet, &mut findings);
+        self.check_arp(packet, &mut findings);
+
+        findings.sort_by_key(|(severity, _)| Reverse(*severity));
+        findings
+            .into_iter()
+            .map(|(severity, message)| ExpertInfo {
+                packet_id: packet.id,
+                severity,
+                message,
+            })
+            .collect()
+    }
+
+    /// Headers too short to parse and IP packets shorter than their length field says.
+    fn check_malformed(packet: &CompletePacket, findings: &mut Vec<(Severity, String)>) {
+        let malformed = |protocol: &str| {
+            (
+                Severity::Error,
+                strings::get("expert.malformed").replace("{protocol}", protocol),
+            )
+        };
+        let truncated = |protocol: &str, captured: usize, expected: usize| {
+            (
+                Severity::Error,
+                strings::get("expert.truncated")
+                    .replace("{protocol}", protocol)
+                    .replace("{captured}", &captured.to_string())
+                    .replace("{expected}", &expected.to_string()),
+            )
+        };
+
+        if packet.layer_2.is_none() && packet.mpls.is_none() {
+            if let Some(protocol) = Self::ip_ethertype_name(Self::innermost_ethertype(packet)) {
+                findings.push(malformed(protocol));
+            }
+        }
+
+        match &packet.layer_2 {
+            Some(PacketsData::Ipv4Packet(ipv4)) => {
+                let header_length = ipv4.header_length as usize * 4;
+                let total_length = ipv4.total_length as usize;
+                if ipv4.header_length < 5 || total_length < header_length {
+                    findings.push(malformed("IPv4"));
+                    return;
+                }
+                if ipv4.length < total_length - header_length {
+                    findings.push(truncated("IPv4", header_length + ipv4.length, total_length));
+                }
+                // Later fragments carry no transport header of their own.
+                if !ipv4.is_fragment() || packet.reassembled_fragments.is_some() {
+                    Self::check_transport(packet, ipv4.next_level_protocol, findings);
+                }
+            }
+            Some(PacketsData::Ipv6Packet(ipv6)) => {
+                // A zero payload length announces a jumbogram, sized by its options.
+                if ipv6.payload_length != 0 && ipv6.length < ipv6.payload_length as usize {
+                    findings.push(truncated("IPv6", ipv6.length, ipv6.payload_length as usize));
+                }
+                Self::check_transport(packet, ipv6.next_header, findings);
+            }
+            _ => {}
+        }
+    }
+
+    /// A transport protocol announced by the IP header whose own header didn't parse.
+    fn check_transport(
+        packet: &CompletePacket,
+        protocol: pnet::packet::ip::IpNextHeaderProtocol,
+        findings: &mut Vec<(Severity, String)>,
+    ) {
+        let name = match protocol {
+            IpNextHeaderProtocols::Tcp => "TCP",
+            IpNextHeaderProtocols::Udp => "UDP",
+            IpNextHeaderProtocols::Sctp => "SCTP",
+            IpNextHeaderProtocols::Icmp => "ICMP",
+            IpNextHeaderProtocols::Icmpv6 => "ICMPv6",
+            _ => return,
+        };
+        let data_offset_too_small = matches!(
+            &packet.layer_3,
+            Some(PacketsData::TcpPacket(tcp)) if tcp.data_offset < 5
+        );
+        if packet.layer_3.is_none() || data_offset_too_small {
+            findings.push((
+                Severity::Error,
+                strings::get("expert.malformed").replace("{protocol}", name),
+            ));
+        }
+    }
+
+    fn check_tcp(&mut self, packet: &CompletePacket, findings: &mut Vec<(Severity, String)>) {
+        let Some(PacketsData::TcpPacket(tcp)) = &packet.layer_3 else {
+            return;
+        };
+        match self.sequences.record(packet) {
+            Some(SegmentKind::Retransmission) => findings.push((
+                Severity::Warn,
+                strings::get("expert.retransmission").to_string(),
+            )),
+            Some(SegmentKind::KeepAlive) => findings.push((
+                Severity::Note,
+                strings::get("expert.keep_alive").to_string(),
+            )),
+            _ => {}
+        }
+
+        // The window of SYN and FIN segments says nothing about a stalled receiver.
+        let opening_or_closing = tcp.flags & (TcpFlags::SYN | TcpFlags::FIN) != 0;
+        if tcp.flags & TcpFlags::RST != 0 {
+            findings.push((Severity::Warn, strings::get("expert.reset").to_string()));
+        } else if tcp.window == 0 && !opening_or_closing {
+            findings.push((
+                Severity::Warn,
+                strings::get("expert.zero_window").to_string(),
+            ));
+        }
+    }
+
+    fn check_ttl(packet: &CompletePacket, findings: &mut Vec<(Severity, String)>) {
+        let ttl = match &packet.layer_2 {
+            Some(PacketsData::Ipv4Packet(ipv4)) => ipv4.ttl,
+            Some(PacketsData::Ipv6Packet(ipv6)) => ipv6.hop_limit,
+            _ => return,
+        };
+        // Multicast and link-local protocols send with a TTL of 1 on purpose.
+        let link_local = packet
+            .ip_addresses()
+            .is_some_and(|(_, destination)| match destination {
+                std::net::IpAddr::V4(ip) => ip.is_multicast() || ip.is_broadcast(),
+                std::net::IpAddr::V6(ip) => ip.is_multicast(),
+            });
+        if ttl == 1 && !link_local {
+            findings.push((Severity::Note, strings::get("expert.ttl_one").to_string()));
+        }
+    }
+
+    fn check_icmp(packet: &CompletePacket, findings: &mut Vec<(Severity, String)>) {
+        let error = match &packet.layer_3 {
+            Some(PacketsData::IcmpPacket(icmp)) => {
+                Self::icmp_error(icmp.icmp_type).map(|(severity, error)| (severity, "ICMP", error))
+            }
+            Some(PacketsData::Icmpv6Packet(icmpv6)) => Self::icmpv6_error(icmpv6.icmpv6_type)
+                .map(|(severity, error)| (severity, "ICMPv6", error)),
+            _ => None,
+        };
+        if let Some((severity, protocol, error)) = error {
+            findings.push((
+                severity,
+                strings::get("expert.icmp_error")
+                    .replace("{protocol}", protocol)
+                    .replace("{error}", error),
+            ));
+        }
+    }
+
+    fn icmp_error(icmp_type: IcmpType) -> Option<(Severity, &'static str)> {
+        match icmp_type {
+            IcmpTypes::DestinationUnreachable => Some((Severity::Warn, "Destination Unreachable")),
+            IcmpTypes::TimeExceeded => Some((Severity::Warn, "Time Exceeded")),
+            IcmpTypes::ParameterProblem => Some((Severity::Warn, "Parameter Problem")),
+            IcmpTypes::SourceQuench => Some((Severity::Note, "Source Quench")),
+            IcmpTypes::RedirectMessage => Some((Severity::Note, "Redirect")),
+            _ => None,
+        }
+    }
+
+    fn icmpv6_error(icmpv6_type: Icmpv6Type) -> Option<(Severity, &'static str)> {
+        match icmpv6_type {
+            Icmpv6Types::DestinationUnreachable => {
+                Some((Severity::Warn, "Destination Unreachable"))
+            }
+            Icmpv6Types::PacketTooBig => Some((Severity::Warn, "Packet Too Big")),
+            Icmpv6Types::TimeExceeded => Some((Severity::Warn, "Time Exceeded")),
+            Icmpv6Types::ParameterProblem => Some((Severity::Warn, "Parameter Problem")),
+            _ => None,
+        }
+    }
+
+    /// Ethernet frames carrying more than the interface MTU, which points at an MTU
+    /// mismatch (or at segmentation offload handing the capture oversized frames), and
+    /// runts below the minimum frame size. Outgoing frames are captured before the NIC
+    /// pads them, so only received ones can be runts.
+    fn check_frame_size(&self, packet: &CompletePacket, findings: &mut Vec<(Severity, String)>) {
+        let is_ethernet = matches!(packet.layer_1, Some(PacketsData::EthernetPacket(_)));
+        if packet.link_type != LinkType::Ethernet || !is_ethernet {
+            return;
+        }
+        if let Some(PacketsData::EthernetPacket(ethernet)) = &packet.layer_1 {
+            if let (Some(fcs), Some(expected)) = (ethernet.fcs, ethernet.expected_fcs) {
+                if fcs != expected {
+                    findings.push((
+                        Severity::Error,
+                        strings::get("expert.bad_fcs")
+                            .replace("{fcs}", &format!("0x{fcs:08x}"))
+                            .replace("{expected}", &format!("0x{expected:08x}")),
+                    ));
+                }
+            }
+        }
+        // Sizes are without the FCS, when the frame was captured with one.
+        let length = packet.length.saturating_sub(packet.fcs_length());
+        if length < Self::ETHERNET_MIN_LENGTH
+            && packet.traffic_direction != TrafficDirection::Outbound
+        {
+            findings.push((
+                Severity::Warn,
+                strings::get("expert.runt")
+                    .replace("{size}", &length.to_string())
+                    .replace("{minimum}", &Self::ETHERNET_MIN_LENGTH.to_string()),
+            ));
+        }
+        let Some(&mtu) = self.interface_mtus.get(&packet.interface) else {
+            return;
+        };
+        let header_length =
+            Self::ETHERNET_HEADER_LENGTH + Self::VLAN_TAG_LENGTH * packet.vlan_tags.len();
+        let payload = length.saturating_sub(header_length);
+        if payload > mtu {
+            findings.push((
+                Severity::Warn,
+                strings::get("expert.over_mtu")
+                    .replace("{payload}", &payload.to_string())
+                    .replace("{mtu}", &mtu.to_string())
+                    .replace("{interface}", &packet.interface),
+            ));
+        }
+    }
+
+    /// ARP announcing the sender's own address, and an address announced by a MAC other
+    /// than the one that announced it before, which usually means two hosts share an IP.
+    /// Probes from 0.0.0.0 claim nothing yet.
+    fn check_arp(&mut self, packet: &CompletePacket, findings: &mut Vec<(Severity, String)>) {
+        let Some(PacketsData::ArpPacket(arp)) = &packet.layer_2 else {
+            return;
+        };
+        let (ArpAddress::Ipv4(ip), ArpAddress::Mac(mac)) =
+            (&arp.sender_proto_addr, &arp.sender_hw_addr)
+        else {
+            return;
+        };
+        if ip.is_unspecified() {
+            return;
+        }
+        if arp.target_proto_addr == arp.sender_proto_addr {
+            findings.push((
+                Severity::Note,
+                strings::get("expert.gratuitous_arp").replace("{ip}", &ip.to_string()),
+            ));
+        }
+        match self.arp_table.insert(*ip, *mac) {
+            Some(previous) if previous != *mac => findings.push((
+                Severity::Warn,
+                strings::get("expert.duplicate_ip")
+                    .replace("{ip}", &ip.to_string())
+                    .replace("{previous}", &previous.to_string())
+                    .replace("{mac}", &mac.to_string()),
+            )),
+            _ => {}
+        }
+    }
+
+    /// The EtherType of the payload after the Ethernet header and any VLAN tags.
+    fn innermost_ethertype(packet: &CompletePacket) -> Option<EtherType> {
+        match (packet.vlan_tags.last(), &packet.layer_1) {
+            (Some(vlan), _) => Some(vlan.ethertype),
+            (None, Some(PacketsData::EthernetPacket(ethernet))) => Some(ethernet.ethertype),
+            (None, Some(PacketsData::Ieee80211Packet(wifi))) => wifi.ethertype,
+            _ => None,
+        }
+    }
+
+    fn ip_ethertype_name(ethertype: Option<EtherType>) -> Option<&'static str> {
+        match ethertype? {
+            EtherTypes::Ipv4 => Some("IPv4"),
+            EtherTypes::Ipv6 => Some("IPv6"),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pnet::packet::{
+        arp::{ArpHardwareTypes, ArpOperations, ArpPacket, MutableArpPacket},
+        ethernet::{EthernetPacket, MutableEthernetPacket},
+    };
+
+    use super::*;
+    use crate::packet_data::{ArpPacketInfo, EthernetPacketInfo};
+
+    /// An Ethernet frame `length` bytes long, header included, of a protocol that isn't
+    /// decoded further.
+    fn ethernet(ethertype: EtherType, length: usize) -> CompletePacket {
+        let mut frame = vec![0; length];
+        MutableEthernetPacket::new(&mut frame)
+            .unwrap()
+            .set_ethertype(ethertype);
+        let mut packet = CompletePacket::new(1, "test", &frame, None);
+        packet.layer_1 = Some(PacketsData::EthernetPacket(EthernetPacketInfo::from(
+            &EthernetPacket::new(&frame).unwrap(),
+        )));
+        packet
+    }
+
+    fn messages(findings: Vec<ExpertInfo>) -> Vec<String> {
+        findings
+            .into_iter()
+            .map(|finding| finding.message)
+            .collect()
+    }
+
+    #[test]
+    fn frames_over_the_mtu_and_runts_are_flagged() {
+        let mut analyzer = ExpertAnalyzer::default();
+        analyzer.interface_mtus.insert("test".to_string(), 1500);
+        let mut findings =
+            |length| messages(analyzer.analyze(&ethernet(EtherType(0x88b5), length)));
+
+        assert!(findings(14 + 1500).is_empty());
+        let over_mtu = findings(14 + 1501);
+        assert_eq!(over_mtu.len(), 1);
+        assert!(over_mtu[0].contains("1501"));
+        let runt = findings(46);
+        assert_eq!(runt.len(), 1);
+        assert!(runt[0].contains("46"));
+    }
+
+    #[test]
+    fn gratuitous_arp_and_duplicate_ips_are_flagged() {
+        let mut analyzer = ExpertAnalyzer::default();
+        let mut findings = |sender_mac: u8, sender_ip: [u8; 4], target_ip: [u8; 4]| {
+            let mut buffer = [0; 28];
+            let mut arp = MutableArpPacket::new(&mut buffer).unwrap();
+            arp.set_hardware_type(ArpHardwareTypes::Ethernet);
+            arp.set_protocol_type(EtherTypes::Ipv4);
+            arp.set_hw_addr_len(6);
+            arp.set_proto_addr_len(4);
+            arp.set_operation(ArpOperations::Reply);
+            arp.set_sender_hw_addr(MacAddr::new(2, 0, 0, 0, 0, sender_mac));
+            arp.set_sender_proto_addr(sender_ip.into());
+            arp.set_target_proto_addr(target_ip.into());
+            // Padded to the Ethernet minimum, as it arrives on the wire.
+            let mut packet = ethernet(EtherTypes::Arp, 60);
+            packet.layer_2 = Some(PacketsData::ArpPacket(ArpPacketInfo::from(
+                &ArpPacket::new(&buffer).unwrap(),
+            )));
+            messages(analyzer.analyze(&packet))
+        };
+
+        assert!(findings(1, [10, 0, 0, 1], [10, 0, 0, 2]).is_empty());
+        let gratuitous = findings(1, [10, 0, 0, 1], [10, 0, 0, 1]);
+        assert_eq!(gratuitous.len(), 1);
+        assert!(gratuitous[0].contains("10.0.0.1"));
+        assert!(findings(2, [0, 0, 0, 0], [10, 0, 0, 1]).is_empty());
+        let duplicate = findings(2, [10, 0, 0, 1], [10, 0, 0, 3]);
+        assert_eq!(duplicate.len(), 1);
+        assert!(duplicate[0].contains("02:00:00:00:00:01"));
+        assert!(duplicate[0].contains("02:00:00:00:00:02"));
+    }
+}
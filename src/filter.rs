@@ -0,0 +1,488 @@
+use std::{collections::HashSet, net::IpAddr};
+
+use pnet::packet::tcp::TcpFlags;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app_protocol::classify_app_protocol,
+    flows::TrafficDirection,
+    packet_data::{CompletePacket, PacketsData},
+    strings,
+    widgets::{charts::ChartWidget, packet_table::PacketTable},
+};
+
+/// On/off switches for the most common protocols, bound to the number keys: a quicker way
+/// than a display filter to hide noise such as ARP. A packet is classified by its
+/// innermost protocol, as in the chart, so hiding TCP leaves HTTP visible; packets of
+/// protocols without a switch are always shown.
+#[derive(Clone)]
+pub struct ProtocolToggles {
+    enabled: HashSet<&'static str>,
+}
+
+impl Default for ProtocolToggles {
+    fn default() -> Self {
+        Self {
+            enabled: Self::PROTOCOLS.into_iter().collect(),
+        }
+    }
+}
+
+impl ProtocolToggles {
+    /// Protocols in the order of their number keys, starting at 1.
+    pub const PROTOCOLS: [&'static str; 9] = [
+        "TCP", "UDP", "ICMP", "ICMPv6", "ARP", "DNS", "HTTP", "SCTP", "LLDP",
+    ];
+
+    /// Switches the protocol at `index` (0 for the first) on or off.
+    pub fn toggle(&mut self, index: usize) {
+        if let Some(protocol) = Self::PROTOCOLS.get(index) {
+            if !self.enabled.remove(protocol) {
+                self.enabled.insert(protocol);
+            }
+        }
+    }
+
+    pub fn is_enabled(&self, protocol: &str) -> bool {
+        self.enabled.contains(protocol)
+    }
+
+    pub fn allows(&self, packet: &CompletePacket) -> bool {
+        ChartWidget::get_protocol_name(packet).is_none_or(|protocol| {
+            !Self::PROTOCOLS.contains(&protocol) || self.enabled.contains(protocol)
+        })
+    }
+}
+
+/// A display filter saved under a name, to apply from the preset picker.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct FilterPreset {
+    pub name: String,
+    pub expression: String,
+}
+
+impl FilterPreset {
+    /// Offered when the config file doesn't list any presets.
+    pub fn defaults() -> Vec<Self> {
+        [
+            ("No ARP", "!arp"),
+            ("DNS only", "dns"),
+            ("HTTPS", "tcp port 443"),
+            ("No discovery", "!mdns !ssdp !lldp"),
+        ]
+        .into_iter()
+        .map(|(name, expression)| FilterPreset {
+            name: name.to_string(),
+            expression: expression.to_string(),
+        })
+        .collect()
+    }
+}
+
+/// A display filter and the color of the table rows of the packets it matches, like
+/// Wireshark's coloring rules.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct HighlightRule {
+    pub filter: String,
+    pub color: Color,
+}
+
+impl HighlightRule {
+    /// Used when the config file doesn't list any rules.
+    pub fn defaults() -> Vec<Self> {
+        [
+            ("flag rst", Color::LightRed),
+            ("flag syn", Color::Gray),
+            ("flag fin", Color::Gray),
+        ]
+        .into_iter()
+        .map(|(filter, color)| HighlightRule {
+            filter: filter.to_string(),
+            color,
+        })
+        .collect()
+    }
+}
+
+/// Highlight rules with their filters parsed, tried in order against each packet.
+#[derive(Clone, Debug, Default)]
+pub struct HighlightRules {
+    rules: Vec<(DisplayFilter, Color)>,
+}
+
+impl HighlightRules {
+    /// Parses the filter of every rule, leaving out the invalid ones with a translated
+    /// warning for each.
+    pub fn new(rules: &[HighlightRule]) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        let rules = rules
+            .iter()
+            .filter_map(|rule| match DisplayFilter::parse(&rule.filter) {
+                Ok(filter) => Some((filter, rule.color)),
+                Err(error) => {
+                    warnings.push(
+                        strings::get("status.invalid_highlight_rule")
+                            .replace("{filter}", &rule.filter)
+                            .replace("{error}", &error),
+                    );
+                    None
+                }
+            })
+            .collect();
+        (Self { rules }, warnings)
+    }
+
+    /// The color of the first rule matching `packet`.
+    pub fn color(&self, packet: &CompletePacket) -> Option<Color> {
+        self.rules
+            .iter()
+            .find(|(filter, _)| filter.matches(packet))
+            .map(|(_, color)| *color)
+    }
+}
+
+/// Which packets to list by their direction relative to the capturing host.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DirectionFilter {
+    #[default]
+    Both,
+    Inbound,
+    Outbound,
+}
+
+impl DirectionFilter {
+    pub fn next(self) -> Self {
+        match self {
+            DirectionFilter::Both => DirectionFilter::Inbound,
+            DirectionFilter::Inbound => DirectionFilter::Outbound,
+            DirectionFilter::Outbound => DirectionFilter::Both,
+        }
+    }
+
+    pub fn allows(self, packet: &CompletePacket) -> bool {
+        match self {
+            DirectionFilter::Both => true,
+            DirectionFilter::Inbound => packet.traffic_direction == TrafficDirection::Inbound,
+            DirectionFilter::Outbound => packet.traffic_direction == TrafficDirection::Outbound,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DirectionFilter::Both => strings::get("direction.both"),
+            DirectionFilter::Inbound => strings::get("direction.inbound"),
+            DirectionFilter::Outbound => strings::get("direction.outbound"),
+        }
+    }
+}
+
+/// Protocol names accepted as filter terms, matched against every layer of a packet.
+const PROTOCOLS: [&str; 23] = [
+    "eth", "wlan", "vlan", "mpls", "arp", "lldp", "ip", "ipv4", "ipv6", "frag", "tcp", "udp",
+    "sctp", "icmp", "icmpv6", "dns", "mdns", "http", "ssdp", "radius", "ssh", "gtp", "vxlan",
+];
+
+/// TCP flag names accepted after `flag`.
+const TCP_FLAGS: [(&str, u8); 8] = [
+    ("fin", TcpFlags::FIN),
+    ("syn", TcpFlags::SYN),
+    ("rst", TcpFlags::RST),
+    ("psh", TcpFlags::PSH),
+    ("ack", TcpFlags::ACK),
+    ("urg", TcpFlags::URG),
+    ("ece", TcpFlags::ECE),
+    ("cwr", TcpFlags::CWR),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    Protocol(String),
+    Port(u16),
+    Host(IpAddr),
+    TcpFlag(u8),
+    /// Anything else is looked up in the text of the packet's table row, like a search.
+    Text(String),
+    Not(Box<Term>),
+}
+
+impl Term {
+    fn matches(&self, packet: &CompletePacket) -> bool {
+        match self {
+            Term::Protocol(protocol) => has_protocol(packet, protocol),
+            Term::Port(port) => packet
+                .ports()
+                .is_some_and(|(source, destination)| source == *port || destination == *port),
+            Term::Host(host) => packet
+                .ip_addresses()
+                .is_some_and(|(source, destination)| source == *host || destination == *host),
+            Term::TcpFlag(flag) => {
+                matches!(&packet.layer_3, Some(PacketsData::TcpPacket(tcp)) if tcp.flags & flag != 0)
+            }
+            Term::Text(text) => PacketTable::matches_query(packet, text),
+            Term::Not(term) => !term.matches(packet),
+        }
+    }
+}
+
+/// A display filter such as `tcp port 443 !host 10.0.0.1`: whitespace-separated terms
+/// that must all match. Terms are protocol names, `port N`, `host ADDRESS`, `flag NAME`
+/// for a TCP flag such as `rst`, any other word to look for in the table row, and `!`
+/// before a term to negate it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayFilter {
+    expression: String,
+    terms: Vec<Term>,
+}
+
+impl DisplayFilter {
+    /// Parses `expression`, returning a translated message on error.
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let mut words = expression.split_whitespace();
+        let mut terms = Vec::new();
+        while let Some(word) = words.next() {
+            terms.push(Self::parse_term(word, &mut words)?);
+        }
+        Ok(Self {
+            expression: expression.trim().to_string(),
+            terms,
+        })
+    }
+
+    /// Checks `expression` without building the filter, for feedback while it is typed.
+    pub fn validate(expression: &str) -> Result<(), String> {
+        Self::parse(expression).map(|_| ())
+    }
+
+    fn parse_term<'e>(
+        word: &'e str,
+        words: &mut impl Iterator<Item = &'e str>,
+    ) -> Result<Term, String> {
+        if let Some(negated) = word.strip_prefix('!') {
+            let negated = match negated {
+                "" => words
+                    .next()
+                    .ok_or_else(|| strings::get("filter.empty_negation").to_string())?,
+                negated => negated,
+            };
+            return Ok(Term::Not(Box::new(Self::parse_term(negated, words)?)));
+        }
+
+        let word_lowercase = word.to_lowercase();
+        match word_lowercase.as_str() {
+            "port" => {
+                let port = words
+                    .next()
+                    .ok_or_else(|| strings::get("filter.missing_port").to_string())?;
+                port.parse()
+                    .map(Term::Port)
+                    .map_err(|_| strings::get("filter.invalid_port").replace("{value}", port))
+            }
+            "host" => {
+                let host = words
+                    .next()
+                    .ok_or_else(|| strings::get("filter.missing_host").to_string())?;
+                host.parse()
+                    .map(Term::Host)
+                    .map_err(|_| strings::get("filter.invalid_host").replace("{value}", host))
+            }
+            "flag" => {
+                let flag = words
+                    .next()
+                    .ok_or_else(|| strings::get("filter.missing_flag").to_string())?;
+                TCP_FLAGS
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(flag))
+                    .map(|(_, bit)| Term::TcpFlag(*bit))
+                    .ok_or_else(|| strings::get("filter.invalid_flag").replace("{value}", flag))
+            }
+            protocol if PROTOCOLS.contains(&protocol) => Ok(Term::Protocol(word_lowercase)),
+            _ => Ok(Term::Text(word.to_string())),
+        }
+    }
+
+    pub fn matches(&self, packet: &CompletePacket) -> bool {
+        self.terms.iter().all(|term| term.matches(packet))
+    }
+
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+}
+
+fn has_protocol(packet: &CompletePacket, protocol: &str) -> bool {
+    if protocol == "vlan" {
+        return !packet.vlan_tags.is_empty();
+    }
+    if protocol == "mpls" {
+        return packet.mpls.is_some();
+    }
+    // Only the banner is decoded; the encrypted packets after it are known by their port.
+    if protocol == "ssh" {
+        return classify_app_protocol(packet) == Some("SSH");
+    }
+    if protocol == "frag" {
+        return matches!(&packet.layer_2, Some(PacketsData::Ipv4Packet(ipv4)) if ipv4.is_fragment());
+    }
+    // Layers tunneled over GTP-U or VXLAN match too, so "tcp" also finds subscriber and
+    // tenant TCP traffic.
+    [
+        &packet.layer_1,
+        &packet.layer_2,
+        &packet.layer_3,
+        &packet.layer_4,
+    ]
+    .into_iter()
+    .flatten()
+    .chain(packet.tunneled_layers())
+    .any(|layer| {
+        matches!(
+            (protocol, layer),
+            ("eth", PacketsData::EthernetPacket(_))
+                | ("wlan", PacketsData::Ieee80211Packet(_))
+                | ("arp", PacketsData::ArpPacket(_))
+                | ("lldp", PacketsData::LldpPacket(_))
+                | ("ip" | "ipv4", PacketsData::Ipv4Packet(_))
+                | ("ip" | "ipv6", PacketsData::Ipv6Packet(_))
+                | ("tcp", PacketsData::TcpPacket(_))
+                | ("udp", PacketsData::UdpPacket(_))
+                | ("sctp", PacketsData::SctpPacket(_))
+                | ("icmp", PacketsData::IcmpPacket(_))
+                | ("icmpv6", PacketsData::Icmpv6Packet(_))
+                | ("dns", PacketsData::DnsPacket(_))
+                | ("mdns", PacketsData::MdnsPacket(_))
+                | ("http", PacketsData::HttpPacket(_))
+                | ("ssdp", PacketsData::SsdpPacket(_))
+                | ("radius", PacketsData::RadiusPacket(_))
+                | ("gtp", PacketsData::GtpPacket(_))
+                | ("vxlan", PacketsData::VxlanPacket(_))
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pnet::packet::{
+        tcp::{MutableTcpPacket, TcpPacket},
+        udp::{MutableUdpPacket, UdpPacket},
+    };
+
+    use super::*;
+    use crate::packet_data::{TcpPacketInfo, UdpPacketInfo};
+
+    fn terms(expression: &str) -> Vec<Term> {
+        DisplayFilter::parse(expression).unwrap().terms
+    }
+
+    fn tcp_packet(source: u16, destination: u16, flags: u8) -> CompletePacket {
+        let mut buffer = [0; 20];
+        let mut tcp = MutableTcpPacket::new(&mut buffer).unwrap();
+        tcp.set_source(source);
+        tcp.set_destination(destination);
+        tcp.set_data_offset(5);
+        tcp.set_flags(flags);
+        let mut packet = CompletePacket::new(1, "test", &[], None);
+        packet.layer_3 = Some(PacketsData::TcpPacket(TcpPacketInfo::from(
+            &TcpPacket::new(&buffer).unwrap(),
+        )));
+        packet
+    }
+
+    fn udp_packet(source: u16, destination: u16) -> CompletePacket {
+        let mut buffer = [0; 8];
+        let mut udp = MutableUdpPacket::new(&mut buffer).unwrap();
+        udp.set_source(source);
+        udp.set_destination(destination);
+        udp.set_length(8);
+        let mut packet = CompletePacket::new(1, "test", &[], None);
+        packet.layer_3 = Some(PacketsData::UdpPacket(UdpPacketInfo::from(
+            &UdpPacket::new(&buffer).unwrap(),
+        )));
+        packet
+    }
+
+    #[test]
+    fn terms_are_parsed_by_keyword() {
+        assert_eq!(
+            terms("TCP port 443 host 10.0.0.1 flag RST GET"),
+            [
+                Term::Protocol("tcp".to_string()),
+                Term::Port(443),
+                Term::Host("10.0.0.1".parse().unwrap()),
+                Term::TcpFlag(TcpFlags::RST),
+                Term::Text("GET".to_string()),
+            ]
+        );
+        assert_eq!(terms("host ::1"), [Term::Host("::1".parse().unwrap())]);
+        assert!(terms("  ").is_empty());
+    }
+
+    #[test]
+    fn a_bang_negates_the_term_after_it() {
+        let not = |term| Term::Not(Box::new(term));
+        assert_eq!(
+            terms("!arp ! port 53 !flag syn"),
+            [
+                not(Term::Protocol("arp".to_string())),
+                not(Term::Port(53)),
+                not(Term::TcpFlag(TcpFlags::SYN)),
+            ]
+        );
+        assert_eq!(
+            terms("!!tcp"),
+            [not(not(Term::Protocol("tcp".to_string())))]
+        );
+    }
+
+    #[test]
+    fn missing_and_invalid_values_are_rejected() {
+        for (expression, key) in [
+            ("!", "filter.empty_negation"),
+            ("tcp port", "filter.missing_port"),
+            ("host", "filter.missing_host"),
+            ("! flag", "filter.missing_flag"),
+        ] {
+            assert_eq!(
+                DisplayFilter::parse(expression),
+                Err(strings::get(key).to_string()),
+                "{expression}"
+            );
+        }
+        for (expression, key, value) in [
+            ("port 65536", "filter.invalid_port", "65536"),
+            ("port http", "filter.invalid_port", "http"),
+            ("host 10.0.0", "filter.invalid_host", "10.0.0"),
+            ("flag nope", "filter.invalid_flag", "nope"),
+        ] {
+            assert_eq!(
+                DisplayFilter::validate(expression),
+                Err(strings::get(key).replace("{value}", value)),
+                "{expression}"
+            );
+        }
+    }
+
+    #[test]
+    fn highlight_rules_color_packets_by_the_first_matching_filter() {
+        let rule = |filter: &str, color| HighlightRule {
+            filter: filter.to_string(),
+            color,
+        };
+        let (rules, warnings) = HighlightRules::new(&[
+            rule("flag rst", Color::Red),
+            rule("flag", Color::Green),
+            rule("flag nope", Color::Green),
+            rule("port 53", Color::Blue),
+            rule("tcp", Color::Yellow),
+        ]);
+        assert_eq!(warnings.len(), 2);
+
+        let reset = tcp_packet(5000, 80, TcpFlags::RST | TcpFlags::ACK);
+        assert_eq!(rules.color(&reset), Some(Color::Red));
+        let push = tcp_packet(5000, 80, TcpFlags::PSH | TcpFlags::ACK);
+        assert_eq!(rules.color(&push), Some(Color::Yellow));
+        assert_eq!(rules.color(&udp_packet(5353, 53)), Some(Color::Blue));
+        assert_eq!(rules.color(&udp_packet(5353, 5354)), None);
+    }
+}
@@ -0,0 +1,799 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    net::IpAddr,
+    time::Duration,
+};
+
+use chrono::{DateTime, Local, TimeDelta};
+use pnet::{datalink::NetworkInterface, packet::tcp::TcpFlags};
+
+use crate::packet_data::{CompletePacket, PacketsData};
+
+type Endpoint = (IpAddr, u16);
+
+/// Per-connection state of a tracker, keyed by endpoint pair. Bounded so a long capture
+/// or a scan opening many connections can't grow it without end: once full, connections
+/// idle for a while are dropped, then the least recently seen ones.
+struct FlowTable<V> {
+    entries: HashMap<(Endpoint, Endpoint), (V, DateTime<Local>)>,
+    capacity: usize,
+}
+
+impl<V> Default for FlowTable<V> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity: Self::MAX_FLOWS,
+        }
+    }
+}
+
+impl<V> FlowTable<V> {
+    const MAX_FLOWS: usize = 65_536;
+    const IDLE_TIMEOUT: TimeDelta = TimeDelta::minutes(5);
+
+    /// The state of the connection `key`, created with `default` if it is new, marked as
+    /// seen at `now`.
+    fn entry(
+        &mut self,
+        key: (Endpoint, Endpoint),
+        now: DateTime<Local>,
+        default: impl FnOnce() -> V,
+    ) -> &mut V {
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            self.make_room(now);
+        }
+        let (value, last_seen) = self.entries.entry(key).or_insert_with(|| (default(), now));
+        *last_seen = now;
+        value
+    }
+
+    fn get(&self, key: &(Endpoint, Endpoint)) -> Option<&V> {
+        self.entries.get(key).map(|(value, _)| value)
+    }
+
+    fn get_mut(&mut self, key: &(Endpoint, Endpoint)) -> Option<&mut V> {
+        self.entries.get_mut(key).map(|(value, _)| value)
+    }
+
+    fn remove(&mut self, key: &(Endpoint, Endpoint)) {
+        self.entries.remove(key);
+    }
+
+    /// Drops the idle connections, then the least recently seen until an eighth of the
+    /// table is free, so this doesn't run again for every new connection.
+    fn make_room(&mut self, now: DateTime<Local>) {
+        self.entries
+            .retain(|_, (_, last_seen)| now - *last_seen < Self::IDLE_TIMEOUT);
+        let keep = self.capacity - self.capacity.div_ceil(8);
+        if self.entries.len() > keep {
+            let mut last_seen: Vec<_> = self.entries.values().map(|(_, seen)| *seen).collect();
+            let dropped = last_seen.len() - keep;
+            let (_, &mut cutoff, _) = last_seen.select_nth_unstable(dropped - 1);
+            self.entries.retain(|_, (_, seen)| *seen > cutoff);
+        }
+    }
+}
+
+/// Which side of a TCP connection sent a packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlowDirection {
+    ClientToServer,
+    ServerToClient,
+}
+
+impl FlowDirection {
+    pub fn indicator(self) -> &'static str {
+        match self {
+            FlowDirection::ClientToServer => "C→S",
+            FlowDirection::ServerToClient => "S→C",
+        }
+    }
+}
+
+/// Whether a packet was sent or received by the capturing host.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TrafficDirection {
+    Inbound,
+    Outbound,
+    /// Between other hosts, seen in promiscuous mode, or on an interface without
+    /// addresses to compare against.
+    #[default]
+    Other,
+}
+
+impl TrafficDirection {
+    /// Compares the IP addresses against the interface's own, then the Ethernet
+    /// addresses against its MAC for traffic without IP such as ARP.
+    pub fn classify(packet: &CompletePacket, interface: &NetworkInterface) -> Self {
+        let is_own_ip = |ip: IpAddr| interface.ips.iter().any(|network| network.ip() == ip);
+        if let Some((source, destination)) = packet.ip_addresses() {
+            if is_own_ip(source) {
+                return TrafficDirection::Outbound;
+            }
+            if is_own_ip(destination) {
+                return TrafficDirection::Inbound;
+            }
+        }
+        match (&packet.layer_1, interface.mac) {
+            (Some(PacketsData::EthernetPacket(ethernet)), Some(mac)) if !mac.is_zero() => {
+                if ethernet.source == mac {
+                    TrafficDirection::Outbound
+                } else if ethernet.destination == mac {
+                    TrafficDirection::Inbound
+                } else {
+                    TrafficDirection::Other
+                }
+            }
+            _ => TrafficDirection::Other,
+        }
+    }
+
+    pub fn indicator(self) -> &'static str {
+        match self {
+            TrafficDirection::Inbound => "IN",
+            TrafficDirection::Outbound => "OUT",
+            TrafficDirection::Other => "",
+        }
+    }
+}
+
+/// Remembers the client of every TCP connection seen, keyed by its endpoint pair with
+/// the lower endpoint first so both directions share an entry.
+#[derive(Default)]
+pub struct TcpFlowTracker {
+    clients: FlowTable<Endpoint>,
+}
+
+impl TcpFlowTracker {
+    /// Returns the direction of a TCP `packet`, learning the client from its SYN. For
+    /// connections already open when the capture started, the endpoint on the lower port
+    /// is taken as the server, or the first sender as the client when both ports match.
+    pub fn record(&mut self, packet: &CompletePacket) -> Option<FlowDirection> {
+        let Some(PacketsData::TcpPacket(tcp)) = &packet.layer_3 else {
+            return None;
+        };
+        let (source_ip, destination_ip) = packet.ip_addresses()?;
+        let source = (source_ip, tcp.source);
+        let destination = (destination_ip, tcp.destination);
+        let key = if source <= destination {
+            (source, destination)
+        } else {
+            (destination, source)
+        };
+
+        let now = packet.timestamp;
+        let client = match (
+            tcp.flags & TcpFlags::SYN != 0,
+            tcp.flags & TcpFlags::ACK != 0,
+        ) {
+            // A new SYN also replaces the client of a reused endpoint pair.
+            (true, false) => {
+                *self.clients.entry(key, now, || source) = source;
+                source
+            }
+            (true, true) => *self.clients.entry(key, now, || destination),
+            _ => *self.clients.entry(key, now, || {
+                if tcp.source < tcp.destination {
+                    destination
+                } else {
+                    source
+                }
+            }),
+        };
+
+        Some(if client == source {
+            FlowDirection::ClientToServer
+        } else {
+            FlowDirection::ServerToClient
+        })
+    }
+}
+
+/// State of a TCP connection as far as the flags seen from both sides tell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TcpConnectionState {
+    /// The client's SYN, not answered yet.
+    SynSent,
+    /// The server's SYN-ACK, waiting for the client's ACK.
+    SynReceived,
+    Established,
+    /// One side sent a FIN, the other can still send.
+    FinWait,
+    /// Both sides sent a FIN, waiting for the last ACK.
+    Closing,
+    Closed,
+    Reset,
+}
+
+impl TcpConnectionState {
+    pub fn name(self) -> &'static str {
+        match self {
+            TcpConnectionState::SynSent => "SYN_SENT",
+            TcpConnectionState::SynReceived => "SYN_RECEIVED",
+            TcpConnectionState::Established => "ESTABLISHED",
+            TcpConnectionState::FinWait => "FIN_WAIT",
+            TcpConnectionState::Closing => "CLOSING",
+            TcpConnectionState::Closed => "CLOSED",
+            TcpConnectionState::Reset => "RESET",
+        }
+    }
+}
+
+struct TcpConnection {
+    state: TcpConnectionState,
+    /// Sender of the first FIN, so its retransmissions don't count as the second one.
+    fin_from: Option<Endpoint>,
+}
+
+/// Follows the handshake and teardown of every TCP connection, keyed like
+/// `TcpFlowTracker`. Connections already open when the capture started are taken as
+/// established from their first segment.
+#[derive(Default)]
+pub struct TcpStateTracker {
+    connections: FlowTable<TcpConnection>,
+}
+
+impl TcpStateTracker {
+    /// Returns the state of the connection of a TCP `packet` once it is accounted for.
+    /// Packets must be passed in capture order.
+    pub fn record(&mut self, packet: &CompletePacket) -> Option<TcpConnectionState> {
+        let Some(PacketsData::TcpPacket(tcp)) = &packet.layer_3 else {
+            return None;
+        };
+        let (source, key) = Self::endpoints(packet)?;
+        let syn = tcp.flags & TcpFlags::SYN != 0;
+        let ack = tcp.flags & TcpFlags::ACK != 0;
+        let fin = tcp.flags & TcpFlags::FIN != 0;
+        let rst = tcp.flags & TcpFlags::RST != 0;
+
+        let connection = self
+            .connections
+            .entry(key, packet.timestamp, || TcpConnection {
+                state: TcpConnectionState::Established,
+                fin_from: None,
+            });
+        connection.state = match connection.state {
+            _ if rst => TcpConnectionState::Reset,
+            // A SYN opens a new connection on a reused endpoint pair, whatever came before.
+            _ if syn && !ack => {
+                connection.fin_from = None;
+                TcpConnectionState::SynSent
+            }
+            _ if syn => TcpConnectionState::SynReceived,
+            TcpConnectionState::SynReceived if ack => TcpConnectionState::Established,
+            _ if fin => match connection.fin_from {
+                Some(first) if first != source => TcpConnectionState::Closing,
+                Some(_) => connection.state,
+                None => {
+                    connection.fin_from = Some(source);
+                    TcpConnectionState::FinWait
+                }
+            },
+            TcpConnectionState::Closing if ack => TcpConnectionState::Closed,
+            state => state,
+        };
+        Some(connection.state)
+    }
+
+    /// The state of the connection `packet` belongs to after every packet recorded.
+    pub fn state(&self, packet: &CompletePacket) -> Option<TcpConnectionState> {
+        let (_, key) = Self::endpoints(packet)?;
+        self.connections
+            .get(&key)
+            .map(|connection| connection.state)
+    }
+
+    /// The sender of a TCP `packet` and its connection key, the lower endpoint first.
+    fn endpoints(packet: &CompletePacket) -> Option<(Endpoint, (Endpoint, Endpoint))> {
+        let Some(PacketsData::TcpPacket(tcp)) = &packet.layer_3 else {
+            return None;
+        };
+        let (source_ip, destination_ip) = packet.ip_addresses()?;
+        let source = (source_ip, tcp.source);
+        let destination = (destination_ip, tcp.destination);
+        let key = if source <= destination {
+            (source, destination)
+        } else {
+            (destination, source)
+        };
+        Some((source, key))
+    }
+}
+
+/// How a TCP segment relates to the data already seen in its direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SegmentKind {
+    /// Carries new data, or nothing that occupies sequence space.
+    New,
+    Retransmission,
+    /// An empty or one-byte segment just below the next sequence number.
+    KeepAlive,
+}
+
+/// Remembers, for each direction of every TCP connection, the sequence number after the
+/// highest byte sent so far, to spot segments that repeat data.
+#[derive(Default)]
+pub struct TcpSequenceTracker {
+    next_sequences: FlowTable<u32>,
+}
+
+impl TcpSequenceTracker {
+    pub fn record(&mut self, packet: &CompletePacket) -> Option<SegmentKind> {
+        let Some(PacketsData::TcpPacket(tcp)) = &packet.layer_3 else {
+            return None;
+        };
+        let (source_ip, destination_ip) = packet.ip_addresses()?;
+        let key = ((source_ip, tcp.source), (destination_ip, tcp.destination));
+        let syn = tcp.flags & TcpFlags::SYN != 0;
+        let fin = tcp.flags & TcpFlags::FIN != 0;
+        let rst = tcp.flags & TcpFlags::RST != 0;
+        // SYN and FIN take up one sequence number each.
+        let length = tcp.length as u32 + u32::from(syn) + u32::from(fin);
+        let end = tcp.sequence.wrapping_add(length);
+
+        let mut first = false;
+        let next = self.next_sequences.entry(key, packet.timestamp, || {
+            first = true;
+            end
+        });
+        if first {
+            return Some(SegmentKind::New);
+        }
+        if syn {
+            // A repeated SYN is a retransmission; any other one opens a new connection.
+            let kind = if *next == end {
+                SegmentKind::Retransmission
+            } else {
+                SegmentKind::New
+            };
+            *next = end;
+            return Some(kind);
+        }
+        if tcp.length <= 1 && !fin && !rst && tcp.sequence == next.wrapping_sub(1) {
+            return Some(SegmentKind::KeepAlive);
+        }
+        if length == 0 {
+            return Some(SegmentKind::New);
+        }
+        // Sequence numbers wrap around, so compare them by their distance.
+        if (end.wrapping_sub(*next) as i32) > 0 {
+            *next = end;
+            Some(SegmentKind::New)
+        } else {
+            Some(SegmentKind::Retransmission)
+        }
+    }
+}
+
+/// Whether sequence number `a` comes before `b`, or is `b`, allowing for wrap-around.
+fn sequence_at_or_before(a: u32, b: u32) -> bool {
+    (b.wrapping_sub(a) as i32) >= 0
+}
+
+/// A segment waiting for the acknowledgement that covers it.
+struct OutstandingSegment {
+    start: u32,
+    end: u32,
+    sent_at: DateTime<Local>,
+    retransmitted: bool,
+}
+
+/// The segments one side of a connection has sent and the other hasn't acknowledged yet.
+#[derive(Default)]
+struct UnackedSegments {
+    segments: VecDeque<OutstandingSegment>,
+    /// The sequence number after the highest byte sent so far.
+    next: Option<u32>,
+}
+
+/// Estimates the round-trip time of every TCP connection from the time between a segment
+/// and the first acknowledgement that covers it, cumulatively or with a SACK block,
+/// smoothed as in RFC 6298. Retransmitted segments give no sample (Karn's algorithm),
+/// since it can't be told which copy was acknowledged.
+#[derive(Default)]
+pub struct TcpRttEstimator {
+    unacked: FlowTable<UnackedSegments>,
+    /// Keyed like `TcpFlowTracker`, with the lower endpoint first.
+    smoothed: FlowTable<Duration>,
+}
+
+impl TcpRttEstimator {
+    /// Formats an RTT estimate for display, like "~12ms".
+    pub fn format(rtt: Duration) -> String {
+        let millis = rtt.as_secs_f64() * 1000.0;
+        if millis >= 1000.0 {
+            format!("~{:.1}s", millis / 1000.0)
+        } else if millis >= 10.0 {
+            format!("~{millis:.0}ms")
+        } else {
+            format!("~{millis:.1}ms")
+        }
+    }
+
+    /// Bounds the memory of connections whose acknowledgements aren't captured.
+    const MAX_UNACKED_SEGMENTS: usize = 1024;
+
+    /// Returns the smoothed RTT of the connection of `packet` once it has a sample. Packets
+    /// must be passed in capture order.
+    pub fn record(&mut self, packet: &CompletePacket) -> Option<Duration> {
+        let Some(PacketsData::TcpPacket(tcp)) = &packet.layer_3 else {
+            return None;
+        };
+        let (source_ip, destination_ip) = packet.ip_addresses()?;
+        let source = (source_ip, tcp.source);
+        let destination = (destination_ip, tcp.destination);
+        let flow = if source <= destination {
+            (source, destination)
+        } else {
+            (destination, source)
+        };
+        let syn = tcp.flags & TcpFlags::SYN != 0;
+        let fin = tcp.flags & TcpFlags::FIN != 0;
+
+        if tcp.flags & TcpFlags::ACK != 0 {
+            if let Some(sent_at) = self.acknowledge(destination, source, tcp) {
+                if let Ok(sample) = (packet.timestamp - sent_at).to_std() {
+                    let smoothed = self.smoothed.entry(flow, packet.timestamp, || sample);
+                    *smoothed = (*smoothed * 7 + sample) / 8;
+                }
+            }
+        }
+
+        // SYN and FIN take up one sequence number each.
+        let length = tcp.length as u32 + u32::from(syn) + u32::from(fin);
+        if length > 0 && tcp.flags & TcpFlags::RST == 0 {
+            let unacked = self.unacked.entry(
+                (source, destination),
+                packet.timestamp,
+                UnackedSegments::default,
+            );
+            if syn && tcp.flags & TcpFlags::ACK == 0 {
+                // A new SYN also starts over on a reused endpoint pair.
+                unacked
+                    .segments
+                    .retain(|segment| segment.end == tcp.sequence.wrapping_add(1));
+                if unacked.segments.is_empty() {
+                    unacked.next = None;
+                    self.smoothed.remove(&flow);
+                }
+            }
+            Self::send(unacked, tcp.sequence, length, packet.timestamp);
+        }
+
+        self.smoothed.get(&flow).copied()
+    }
+
+    /// Records a segment from `start` taking `length` sequence numbers, marking the data
+    /// it repeats as retransmitted.
+    fn send(unacked: &mut UnackedSegments, start: u32, length: u32, sent_at: DateTime<Local>) {
+        let end = start.wrapping_add(length);
+        let mut repeats = false;
+        for segment in &mut unacked.segments {
+            let overlaps = !sequence_at_or_before(end, segment.start)
+                && !sequence_at_or_before(segment.end, start);
+            if overlaps {
+                segment.retransmitted = true;
+                repeats = true;
+            }
+        }
+        let new_data = unacked
+            .next
+            .is_none_or(|next| !sequence_at_or_before(end, next));
+        if !new_data {
+            return;
+        }
+        unacked.next = Some(end);
+        if unacked.segments.len() == Self::MAX_UNACKED_SEGMENTS {
+            unacked.segments.pop_front();
+        }
+        unacked.segments.push_back(OutstandingSegment {
+            start,
+            end,
+            sent_at,
+            retransmitted: repeats,
+        });
+    }
+
+    /// Drops the segments from `sender` to `receiver` that `tcp` acknowledges, returning
+    /// when the latest of them that was sent only once went out.
+    fn acknowledge(
+        &mut self,
+        sender: Endpoint,
+        receiver: Endpoint,
+        tcp: &crate::packet_data::TcpPacketInfo,
+    ) -> Option<DateTime<Local>> {
+        let unacked = self.unacked.get_mut(&(sender, receiver))?;
+        let sack_blocks = tcp.sack_blocks();
+        let mut sent_at = None;
+        unacked.segments.retain(|segment| {
+            let acknowledged = sequence_at_or_before(segment.end, tcp.acknowledgement)
+                || sack_blocks.iter().any(|&(left, right)| {
+                    sequence_at_or_before(left, segment.start)
+                        && sequence_at_or_before(segment.end, right)
+                });
+            if acknowledged && !segment.retransmitted {
+                sent_at = sent_at.max(Some(segment.sent_at));
+            }
+            !acknowledged
+        });
+        sent_at
+    }
+}
+
+/// A datagram's payload in a followed stream, and which way it went.
+pub struct StreamSegment<'p> {
+    pub direction: FlowDirection,
+    pub timestamp: DateTime<Local>,
+    pub payload: &'p [u8],
+}
+
+/// The UDP flow between two endpoints, in both directions, picked to be followed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct UdpStream {
+    endpoints: [Endpoint; 2],
+}
+
+impl UdpStream {
+    /// The flow `packet` belongs to, if it is a UDP datagram.
+    pub fn of(packet: &CompletePacket) -> Option<Self> {
+        let Some(PacketsData::UdpPacket(udp)) = &packet.layer_3 else {
+            return None;
+        };
+        let (source, destination) = packet.ip_addresses()?;
+        Some(Self {
+            endpoints: [(source, udp.source), (destination, udp.destination)],
+        })
+    }
+
+    /// The client and server endpoints, and the payloads of the flow's datagrams among
+    /// `packets`, oldest first. Without connection setup to go by, the endpoint that sent
+    /// the oldest one is taken as the client.
+    pub fn segments<'p>(
+        &self,
+        packets: impl IntoIterator<Item = &'p CompletePacket>,
+    ) -> ([Endpoint; 2], Vec<StreamSegment<'p>>) {
+        let mut datagrams: Vec<(&CompletePacket, &[u8], Endpoint)> = packets
+            .into_iter()
+            .filter_map(|packet| {
+                let Some(PacketsData::UdpPacket(udp)) = &packet.layer_3 else {
+                    return None;
+                };
+                let (source, destination) = packet.ip_addresses()?;
+                let sender = (source, udp.source);
+                let receiver = (destination, udp.destination);
+                let [first, second] = self.endpoints;
+                ((sender, receiver) == (first, second) || (sender, receiver) == (second, first))
+                    .then_some((packet, udp.payload.as_slice(), sender))
+            })
+            .collect();
+        datagrams.sort_by_key(|(packet, ..)| packet.timestamp);
+        let mut endpoints = self.endpoints;
+        if datagrams
+            .first()
+            .is_some_and(|&(_, _, sender)| sender != endpoints[0])
+        {
+            endpoints.reverse();
+        }
+        let segments = datagrams
+            .into_iter()
+            .map(|(packet, payload, sender)| StreamSegment {
+                direction: if sender == endpoints[0] {
+                    FlowDirection::ClientToServer
+                } else {
+                    FlowDirection::ServerToClient
+                },
+                timestamp: packet.timestamp,
+                payload,
+            })
+            .collect();
+        (endpoints, segments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use pnet::packet::{
+        ipv4::{Ipv4Packet, MutableIpv4Packet},
+        tcp::{MutableTcpPacket, TcpOption, TcpPacket},
+        udp::{MutableUdpPacket, UdpPacket},
+    };
+
+    use super::*;
+    use crate::packet_data::{Ipv4PacketInfo, TcpPacketInfo, UdpPacketInfo};
+
+    const CLIENT: Ipv4Addr = Ipv4Addr::new(192, 168, 1, 10);
+    const SERVER: Ipv4Addr = Ipv4Addr::new(192, 168, 1, 1);
+
+    /// A packet between the client and the server carrying `transport`.
+    fn packet(to_server: bool, transport: PacketsData) -> CompletePacket {
+        let (source, destination) = if to_server {
+            (CLIENT, SERVER)
+        } else {
+            (SERVER, CLIENT)
+        };
+        let mut buffer = [0; 20];
+        let mut ipv4 = MutableIpv4Packet::new(&mut buffer).unwrap();
+        ipv4.set_version(4);
+        ipv4.set_header_length(5);
+        ipv4.set_source(source);
+        ipv4.set_destination(destination);
+        let mut packet = CompletePacket::new(1, "test", &[], None);
+        packet.layer_2 = Some(PacketsData::Ipv4Packet(Ipv4PacketInfo::from(
+            &Ipv4Packet::new(&buffer).unwrap(),
+        )));
+        packet.layer_3 = Some(transport);
+        packet
+    }
+
+    /// A TCP segment between the client's port 40000 and the server's port 80.
+    fn tcp(
+        to_server: bool,
+        sequence: u32,
+        acknowledgement: u32,
+        flags: u8,
+        payload: &[u8],
+    ) -> CompletePacket {
+        let (source, destination) = if to_server { (40000, 80) } else { (80, 40000) };
+        let mut buffer = vec![0; 20 + payload.len()];
+        let mut tcp = MutableTcpPacket::new(&mut buffer).unwrap();
+        tcp.set_source(source);
+        tcp.set_destination(destination);
+        tcp.set_sequence(sequence);
+        tcp.set_acknowledgement(acknowledgement);
+        tcp.set_data_offset(5);
+        tcp.set_flags(flags);
+        tcp.set_payload(payload);
+        let tcp = TcpPacketInfo::from(&TcpPacket::new(&buffer).unwrap());
+        packet(to_server, PacketsData::TcpPacket(tcp))
+    }
+
+    /// A UDP datagram, sent by the client unless it comes from the server's port 53.
+    fn udp(source: u16, destination: u16, payload: &[u8]) -> CompletePacket {
+        let mut buffer = vec![0; 8 + payload.len()];
+        let mut udp = MutableUdpPacket::new(&mut buffer).unwrap();
+        udp.set_source(source);
+        udp.set_destination(destination);
+        udp.set_length(8 + payload.len() as u16);
+        udp.set_payload(payload);
+        let udp = UdpPacketInfo::from(&UdpPacket::new(&buffer).unwrap());
+        packet(source != 53, PacketsData::UdpPacket(udp))
+    }
+
+    #[test]
+    fn flow_tables_drop_idle_then_least_recently_seen_connections() {
+        let start = Local::now();
+        let at = |seconds| start + TimeDelta::seconds(seconds);
+        let key = |port| ((CLIENT.into(), port), (SERVER.into(), 80));
+        let mut table = FlowTable {
+            capacity: 4,
+            ..FlowTable::default()
+        };
+        let ports = |table: &FlowTable<u16>| {
+            let mut ports: Vec<u16> = table.entries.values().map(|(port, _)| *port).collect();
+            ports.sort();
+            ports
+        };
+
+        for port in 1..=4 {
+            table.entry(key(port), at(port.into()), || port);
+        }
+        table.entry(key(1), at(10), || 1);
+        table.entry(key(5), at(11), || 5);
+        assert_eq!(ports(&table), [1, 3, 4, 5]);
+
+        table.entry(key(3), at(900), || 3);
+        table.entry(key(6), at(1000), || 6);
+        assert_eq!(ports(&table), [3, 6]);
+    }
+
+    #[test]
+    fn tcp_rtt_from_acknowledgements() {
+        let start = Local::now();
+        let segment = |to_server, sequence, acknowledgement, flags, payload: &[u8], millis| {
+            let mut packet = tcp(to_server, sequence, acknowledgement, flags, payload);
+            packet.timestamp = start + chrono::TimeDelta::milliseconds(millis);
+            packet
+        };
+        let ack = TcpFlags::ACK | TcpFlags::PSH;
+        let mut rtt = TcpRttEstimator::default();
+        let millis = |rtt: Option<Duration>| rtt.map(|rtt| rtt.as_millis());
+
+        assert_eq!(
+            rtt.record(&segment(true, 100, 0, TcpFlags::SYN, &[], 0)),
+            None
+        );
+        let syn_ack = segment(false, 500, 101, TcpFlags::SYN | TcpFlags::ACK, &[], 10);
+        assert_eq!(millis(rtt.record(&syn_ack)), Some(10));
+        assert_eq!(
+            millis(rtt.record(&segment(true, 101, 501, TcpFlags::ACK, &[], 30))),
+            Some(11)
+        );
+
+        // A retransmitted segment gives no sample.
+        rtt.record(&segment(true, 101, 501, ack, &[0; 10], 40));
+        rtt.record(&segment(true, 101, 501, ack, &[0; 10], 240));
+        let estimate = rtt.record(&segment(false, 501, 111, ack, &[], 250));
+        assert_eq!(millis(estimate), Some(11));
+
+        // Data past a hole, acknowledged with SACK before the hole is filled.
+        rtt.record(&segment(true, 111, 501, ack, &[0; 10], 300));
+        rtt.record(&segment(true, 121, 501, ack, &[0; 10], 301));
+        let mut sack = segment(false, 501, 111, ack, &[], 320);
+        if let Some(PacketsData::TcpPacket(tcp)) = &mut sack.layer_3 {
+            tcp.options.push(TcpOption::selective_ack(&[121, 131]));
+        }
+        assert_eq!(millis(rtt.record(&sack)), Some(12));
+    }
+
+    #[test]
+    fn tcp_connection_state_follows_handshake_and_teardown() {
+        use TcpConnectionState::*;
+
+        let segment = |flags, from_server: bool| tcp(!from_server, 0, 0, flags, &[]);
+        let mut tracker = TcpStateTracker::default();
+        let states: Vec<_> = [
+            (TcpFlags::SYN, false),
+            (TcpFlags::SYN | TcpFlags::ACK, true),
+            (TcpFlags::ACK, false),
+            (TcpFlags::FIN | TcpFlags::ACK, true),
+            (TcpFlags::FIN | TcpFlags::ACK, true),
+            (TcpFlags::FIN | TcpFlags::ACK, false),
+            (TcpFlags::ACK, true),
+        ]
+        .into_iter()
+        .map(|(flags, from_server)| tracker.record(&segment(flags, from_server)))
+        .collect();
+        assert_eq!(
+            states,
+            [
+                SynSent,
+                SynReceived,
+                Established,
+                FinWait,
+                FinWait,
+                Closing,
+                Closed
+            ]
+            .map(Some)
+        );
+
+        assert_eq!(tracker.record(&segment(TcpFlags::RST, true)), Some(Reset));
+        assert_eq!(
+            tracker.record(&segment(TcpFlags::SYN, false)),
+            Some(SynSent)
+        );
+    }
+
+    #[test]
+    fn udp_streams_are_ordered_by_time_and_split_by_direction() {
+        let datagram = |source, destination, payload: &[u8], at| {
+            let mut packet = udp(source, destination, payload);
+            packet.timestamp = DateTime::from_timestamp(at, 0).unwrap().into();
+            packet
+        };
+        // Oldest first, as the app keeps them, with another flow in between.
+        let packets = [
+            datagram(5000, 53, b"query", 1),
+            datagram(5001, 53, b"other", 2),
+            datagram(53, 5000, b"answer", 3),
+        ];
+
+        let stream = UdpStream::of(&packets[2]).unwrap();
+        let (endpoints, segments) = stream.segments(&packets);
+        assert_eq!(endpoints[0].1, 5000);
+        let segments: Vec<_> = segments
+            .iter()
+            .map(|segment| (segment.direction, segment.payload))
+            .collect();
+        assert_eq!(
+            segments,
+            [
+                (FlowDirection::ClientToServer, &b"query"[..]),
+                (FlowDirection::ServerToClient, &b"answer"[..]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,478 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    path::Path,
+    sync::{
+        mpsc::{self, Sender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use crate::{resolver::LookupCache, strings};
+
+/// Where an address is registered, as far as the loaded databases know.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GeoInfo {
+    /// ISO 3166 code, like "US".
+    pub country: Option<String>,
+    pub asn: Option<u32>,
+    pub organization: Option<String>,
+}
+
+impl GeoInfo {
+    /// "US, AS15169 Google LLC", or whichever parts are known.
+    pub fn describe(&self) -> Option<String> {
+        let autonomous_system = match (self.asn, &self.organization) {
+            (Some(asn), Some(organization)) => Some(format!("AS{asn} {organization}")),
+            (Some(asn), None) => Some(format!("AS{asn}")),
+            (None, organization) => organization.clone(),
+        };
+        let parts: Vec<String> = self
+            .country
+            .iter()
+            .cloned()
+            .chain(autonomous_system)
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    fn merge(&mut self, other: GeoInfo) {
+        self.country = self.country.take().or(other.country);
+        self.asn = self.asn.or(other.asn);
+        self.organization = self.organization.take().or(other.organization);
+    }
+}
+
+/// A value of the MaxMind DB data section, keeping only the types GeoIP records use.
+#[derive(Debug, PartialEq)]
+enum Value {
+    String(String),
+    Uint(u64),
+    Map(Vec<(String, Value)>),
+    Array(Vec<Value>),
+    Other,
+}
+
+impl Value {
+    fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Map(entries) => entries
+                .iter()
+                .find(|(entry_key, _)| entry_key == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn as_uint(&self) -> Option<u64> {
+        match self {
+            Value::Uint(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// A database in the MaxMind DB format (GeoLite2/GeoIP2 Country, City or ASN, and the
+/// free DB-IP equivalents), read whole into memory. See
+/// https://maxmind.github.io/MaxMind-DB/ for the layout.
+struct MaxMindDb {
+    data: Vec<u8>,
+    node_count: usize,
+    record_size: usize,
+    ip_version: u64,
+    /// Start of the data section, after the search tree and its 16-byte separator.
+    data_start: usize,
+    /// Node reached after the 96 zero bits that IPv4 addresses sit under in an IPv6 tree.
+    ipv4_start: usize,
+}
+
+impl MaxMindDb {
+    const METADATA_MARKER: &'static [u8] = b"\xab\xcd\xefMaxMind.com";
+    /// The metadata is within this many bytes of the end of the file.
+    const METADATA_MAX_SIZE: usize = 128 * 1024;
+    /// Bounds the nesting of maps and arrays, and of pointers within them, so a corrupted
+    /// file can't recurse forever.
+    const MAX_DEPTH: usize = 32;
+
+    fn parse(data: Vec<u8>) -> Option<Self> {
+        let search_from = data.len().saturating_sub(Self::METADATA_MAX_SIZE);
+        let marker = data[search_from..]
+            .windows(Self::METADATA_MARKER.len())
+            .rposition(|window| window == Self::METADATA_MARKER)?;
+        let metadata_start = search_from + marker + Self::METADATA_MARKER.len();
+        let (metadata, _) = Self::decode(&data[metadata_start..], 0, 0)?;
+
+        let node_count = metadata.get("node_count")?.as_uint()? as usize;
+        let record_size = metadata.get("record_size")?.as_uint()? as usize;
+        let ip_version = metadata.get("ip_version")?.as_uint()?;
+        if !matches!(record_size, 24 | 28 | 32) || !matches!(ip_version, 4 | 6) {
+            return None;
+        }
+        let data_start = node_count.checked_mul(record_size / 4)? + 16;
+        if data_start > search_from + marker {
+            return None;
+        }
+
+        let mut db = Self {
+            data,
+            node_count,
+            record_size,
+            ip_version,
+            data_start,
+            ipv4_start: 0,
+        };
+        if ip_version == 6 {
+            let mut node = 0;
+            for _ in 0..96 {
+                if node >= node_count {
+                    break;
+                }
+                node = db.read_record(node, false)?;
+            }
+            db.ipv4_start = node;
+        }
+        Some(db)
+    }
+
+    /// The left (bit 0) or right (bit 1) record of `node`.
+    fn read_record(&self, node: usize, right: bool) -> Option<usize> {
+        let node_size = self.record_size / 4;
+        let bytes = self.data.get(node * node_size..(node + 1) * node_size)?;
+        let be = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .fold(0, |value, &byte| value << 8 | byte as usize)
+        };
+        Some(match (self.record_size, right) {
+            (24, false) => be(&bytes[0..3]),
+            (24, true) => be(&bytes[3..6]),
+            (28, false) => (bytes[3] as usize & 0xf0) << 20 | be(&bytes[0..3]),
+            (28, true) => (bytes[3] as usize & 0x0f) << 24 | be(&bytes[4..7]),
+            (_, false) => be(&bytes[0..4]),
+            (_, true) => be(&bytes[4..8]),
+        })
+    }
+
+    fn lookup(&self, ip: IpAddr) -> Option<Value> {
+        let (bits, mut node): (Vec<bool>, usize) = match ip {
+            IpAddr::V4(ip) if self.ip_version == 6 => (Self::bits(&ip.octets()), self.ipv4_start),
+            IpAddr::V4(ip) => (Self::bits(&ip.octets()), 0),
+            IpAddr::V6(ip) if self.ip_version == 6 => (Self::bits(&ip.octets()), 0),
+            IpAddr::V6(ip) => (Self::bits(&ip.to_ipv4_mapped()?.octets()), 0),
+        };
+        for bit in bits {
+            if node >= self.node_count {
+                break;
+            }
+            node = self.read_record(node, bit)?;
+        }
+        // Equal to the node count means the address isn't in the database.
+        let offset = node.checked_sub(self.node_count + 16)?;
+        let section = self.data.get(self.data_start..)?;
+        Self::decode(section, offset, 0).map(|(value, _)| value)
+    }
+
+    fn bits(octets: &[u8]) -> Vec<bool> {
+        octets
+            .iter()
+            .flat_map(|octet| (0..8).rev().map(move |bit| octet >> bit & 1 == 1))
+            .collect()
+    }
+
+    /// Decodes the value at `offset` of `section`, returning it with the offset after it.
+    /// Pointers are relative to the start of `section`.
+    fn decode(section: &[u8], offset: usize, depth: usize) -> Option<(Value, usize)> {
+        if depth > Self::MAX_DEPTH {
+            return None;
+        }
+        let control = *section.get(offset)?;
+        let mut offset = offset + 1;
+        let mut value_type = control >> 5;
+        if value_type == 1 {
+            let size = (control >> 3 & 0x3) as usize;
+            let bytes = section.get(offset..offset + size + 1)?;
+            let low = bytes
+                .iter()
+                .fold(0, |value, &byte| value << 8 | byte as usize);
+            let pointer = match size {
+                0 => (control as usize & 0x7) << 8 | low,
+                1 => ((control as usize & 0x7) << 16 | low) + 2048,
+                2 => ((control as usize & 0x7) << 24 | low) + 526336,
+                _ => low,
+            };
+            let (value, _) = Self::decode(section, pointer, depth + 1)?;
+            return Some((value, offset + size + 1));
+        }
+        if value_type == 0 {
+            value_type = section.get(offset)?.checked_add(7)?;
+            offset += 1;
+        }
+
+        let mut size = (control & 0x1f) as usize;
+        if size >= 29 {
+            let extra = size - 28;
+            let bytes = section.get(offset..offset + extra)?;
+            let value = bytes
+                .iter()
+                .fold(0, |value, &byte| value << 8 | byte as usize);
+            size = match extra {
+                1 => 29 + value,
+                2 => 285 + value,
+                _ => 65821 + value,
+            };
+            offset += extra;
+        }
+
+        match value_type {
+            // Booleans keep their value in the size bits.
+            14 => Some((Value::Other, offset)),
+            7 => {
+                let mut entries = Vec::with_capacity(size.min(64));
+                for _ in 0..size {
+                    let (key, next) = Self::decode(section, offset, depth + 1)?;
+                    let (value, next) = Self::decode(section, next, depth + 1)?;
+                    let Value::String(key) = key else {
+                        return None;
+                    };
+                    entries.push((key, value));
+                    offset = next;
+                }
+                Some((Value::Map(entries), offset))
+            }
+            11 => {
+                let mut values = Vec::with_capacity(size.min(64));
+                for _ in 0..size {
+                    let (value, next) = Self::decode(section, offset, depth + 1)?;
+                    values.push(value);
+                    offset = next;
+                }
+                Some((Value::Array(values), offset))
+            }
+            _ => {
+                let bytes = section.get(offset..offset + size)?;
+                let value = match value_type {
+                    2 => Value::String(String::from_utf8_lossy(bytes).into_owned()),
+                    5 | 6 | 9 if size <= 8 => Value::Uint(
+                        bytes
+                            .iter()
+                            .fold(0, |value, &byte| value << 8 | byte as u64),
+                    ),
+                    _ => Value::Other,
+                };
+                Some((value, offset + size))
+            }
+        }
+    }
+}
+
+/// Looks up the country and autonomous system of public addresses in MaxMind-style
+/// databases on a background thread. Like `Resolver`, lookups from the UI only read the
+/// cache and queue misses.
+pub struct GeoIp {
+    cache: Arc<Mutex<LookupCache<GeoInfo>>>,
+    tx: Sender<IpAddr>,
+}
+
+impl GeoIp {
+    /// Loads every database in `paths`; a Country or City database gives the country and
+    /// an ASN database the autonomous system, so both can be passed at once.
+    pub fn open(paths: &[impl AsRef<Path>]) -> Result<Self, String> {
+        let databases = paths
+            .iter()
+            .map(|path| {
+                let path = path.as_ref();
+                let data =
+                    std::fs::read(path).map_err(|error| format!("{}: {error}", path.display()))?;
+                MaxMindDb::parse(data).ok_or_else(|| {
+                    strings::get("geoip.invalid").replace("{path}", &path.display().to_string())
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(databases))
+    }
+
+    fn new(databases: Vec<MaxMindDb>) -> Self {
+        let cache = Arc::new(Mutex::new(LookupCache::new()));
+        let (tx, rx) = mpsc::channel::<IpAddr>();
+
+        let worker_cache = cache.clone();
+        thread::spawn(move || {
+            for ip in rx {
+                let info = Self::lookup_all(&databases, ip);
+                if let Ok(mut cache) = worker_cache.lock() {
+                    cache.insert(ip, info);
+                }
+            }
+        });
+
+        Self { cache, tx }
+    }
+
+    fn lookup_all(databases: &[MaxMindDb], ip: IpAddr) -> GeoInfo {
+        let mut info = GeoInfo::default();
+        for record in databases.iter().filter_map(|db| db.lookup(ip)) {
+            let country = record
+                .get("country")
+                .or_else(|| record.get("registered_country"))
+                .and_then(|country| country.get("iso_code"))
+                .and_then(Value::as_str);
+            info.merge(GeoInfo {
+                country: country.map(str::to_string),
+                asn: record
+                    .get("autonomous_system_number")
+                    .and_then(Value::as_uint)
+                    .map(|asn| asn as u32),
+                organization: record
+                    .get("autonomous_system_organization")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            });
+        }
+        info
+    }
+
+    /// What the databases say about `ip`, once looked up. Private and reserved addresses
+    /// are never looked up.
+    pub fn lookup(&self, ip: IpAddr) -> Option<GeoInfo> {
+        if !is_public(ip) {
+            return None;
+        }
+        let mut cache = self.cache.lock().ok()?;
+        if let Some(info) = cache.get(&ip) {
+            return Some(info.clone());
+        }
+        if cache.start_lookup(ip) {
+            let _ = self.tx.send(ip);
+        }
+        None
+    }
+}
+
+/// Whether `ip` is routable on the internet, and so can be in a GeoIP database.
+pub fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_v4(ip),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    let shared = a == 100 && (64..128).contains(&b);
+    let benchmarking = a == 198 && (b == 18 || b == 19);
+    let reserved = a == 0 || a >= 240;
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_multicast()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || shared
+        || benchmarking
+        || reserved)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let documentation = ip.segments()[..2] == [0x2001, 0xdb8];
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        || ip.is_unique_local()
+        || ip.is_unicast_link_local()
+        || documentation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(value: &str) -> Vec<u8> {
+        let size = match value.len() {
+            length @ 0..29 => vec![0x40 | length as u8],
+            length => vec![0x40 | 29, (length - 29) as u8],
+        };
+        [&size[..], value.as_bytes()].concat()
+    }
+
+    fn uint32(value: u32) -> Vec<u8> {
+        [&[0xc4][..], &value.to_be_bytes()].concat()
+    }
+
+    fn map(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut encoded = vec![0xe0 | entries.len() as u8];
+        for (key, value) in entries {
+            encoded.extend(string(key));
+            encoded.extend(value);
+        }
+        encoded
+    }
+
+    /// An IPv4 database with one node: 0.0.0.0/1 points at `record`, 128.0.0.0/1 at nothing.
+    fn database(record: Vec<u8>) -> MaxMindDb {
+        let tree = [0, 0, 1 + 16, 0, 0, 1];
+        let metadata = map(&[
+            ("node_count", uint32(1)),
+            ("record_size", uint32(24)),
+            ("ip_version", uint32(4)),
+        ]);
+        let file = [
+            &tree[..],
+            &[0; 16],
+            &record,
+            MaxMindDb::METADATA_MARKER,
+            &metadata,
+        ]
+        .concat();
+        MaxMindDb::parse(file).unwrap()
+    }
+
+    #[test]
+    fn looks_up_country_and_autonomous_system() {
+        let country = database(map(&[("country", map(&[("iso_code", string("US"))]))]));
+        let asn = database(map(&[
+            ("autonomous_system_number", uint32(15169)),
+            ("autonomous_system_organization", string("Google LLC")),
+        ]));
+        let databases = [country, asn];
+
+        let info = GeoIp::lookup_all(&databases, "8.8.8.8".parse().unwrap());
+        assert_eq!(info.describe().as_deref(), Some("US, AS15169 Google LLC"));
+        let info = GeoIp::lookup_all(&databases, "203.0.113.1".parse().unwrap());
+        assert_eq!(info, GeoInfo::default());
+
+        assert!(MaxMindDb::parse(b"not a database".to_vec()).is_none());
+    }
+
+    #[test]
+    fn skips_private_and_reserved_addresses() {
+        for ip in ["8.8.8.8", "2606:4700::1111", "::ffff:1.1.1.1"] {
+            assert!(is_public(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in [
+            "10.1.2.3",
+            "192.168.0.1",
+            "100.64.0.1",
+            "127.0.0.1",
+            "169.254.1.1",
+            "224.0.0.251",
+            "255.255.255.255",
+            "fe80::1",
+            "fd00::1",
+            "::1",
+            "2001:db8::1",
+            "::ffff:10.0.0.1",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{ip}");
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::time::{Duration, Instant};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Receive counters the operating system keeps for a network interface.
+#[derive(Clone, Copy)]
+pub struct InterfaceCounters {
+    pub rx_packets: u64,
+    pub rx_dropped: u64,
+}
+
+impl InterfaceCounters {
+    /// Reads the counters from `/sys/class/net/<name>/statistics`. Packets the NIC
+    /// missed because its ring buffer was full are counted as dropped.
+    #[cfg(target_os = "linux")]
+    pub fn read(name: &str) -> Option<Self> {
+        let statistics = std::path::Path::new("/sys/class/net")
+            .join(name)
+            .join("statistics");
+        let counter = |file: &str| -> Option<u64> {
+            std::fs::read_to_string(statistics.join(file))
+                .ok()?
+                .trim()
+                .parse()
+                .ok()
+        };
+
+        Some(Self {
+            rx_packets: counter("rx_packets")?,
+            rx_dropped: counter("rx_dropped")?
+                .saturating_add(counter("rx_missed_errors").unwrap_or(0)),
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn read(_name: &str) -> Option<Self> {
+        None
+    }
+}
+
+/// The MTU of interface `name`, from `/sys/class/net/<name>/mtu`.
+#[cfg(target_os = "linux")]
+pub fn read_mtu(name: &str) -> Option<usize> {
+    std::fs::read_to_string(
+        std::path::Path::new("/sys/class/net")
+            .join(name)
+            .join("mtu"),
+    )
+    .ok()?
+    .trim()
+    .parse()
+    .ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_mtu(_name: &str) -> Option<usize> {
+    None
+}
+
+/// Polls the counters of the active interface at most once per `REFRESH_INTERVAL`.
+#[derive(Default)]
+pub struct InterfaceStats {
+    counters: Option<InterfaceCounters>,
+    last_read: Option<Instant>,
+}
+
+impl InterfaceStats {
+    pub fn refresh(&mut self, interface_name: Option<&str>) {
+        let due = self
+            .last_read
+            .is_none_or(|last_read| last_read.elapsed() >= REFRESH_INTERVAL);
+        if !due {
+            return;
+        }
+        self.last_read = Some(Instant::now());
+        self.counters = interface_name.and_then(InterfaceCounters::read);
+    }
+
+    /// Forces the next `refresh` to read again, e.g. after switching interfaces.
+    pub fn reset(&mut self) {
+        self.counters = None;
+        self.last_read = None;
+    }
+
+    pub fn counters(&self) -> Option<InterfaceCounters> {
+        self.counters
+    }
+}
@@ -0,0 +1,99 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use chrono::SecondsFormat;
+use serde::Serialize;
+
+use crate::{
+    flows::TrafficDirection, packet_data::CompletePacket, widgets::packet_table::PacketTable,
+};
+
+/// One captured packet as written to the JSON-lines stream, with the same fields the
+/// packet table shows.
+#[derive(Serialize)]
+struct PacketRecord<'a> {
+    id: usize,
+    timestamp: String,
+    interface: &'a str,
+    length: usize,
+    captured_length: usize,
+    direction: Option<&'static str>,
+    protocol: String,
+    source: String,
+    destination: String,
+    source_port: Option<u16>,
+    destination_port: Option<u16>,
+    info: String,
+}
+
+impl<'a> PacketRecord<'a> {
+    fn new(packet: &'a CompletePacket) -> Self {
+        let [_, protocol, source, destination, _] =
+            PacketTable::generate_ref_array(packet).unwrap_or_default();
+        let ports = packet.ports();
+        Self {
+            id: packet.id,
+            timestamp: packet
+                .timestamp
+                .to_rfc3339_opts(SecondsFormat::Micros, false),
+            interface: &packet.interface,
+            length: packet.length,
+            captured_length: packet.data.len(),
+            direction: match packet.traffic_direction {
+                TrafficDirection::Inbound => Some("inbound"),
+                TrafficDirection::Outbound => Some("outbound"),
+                TrafficDirection::Other => None,
+            },
+            protocol,
+            source,
+            destination,
+            source_port: ports.map(|(source, _)| source),
+            destination_port: ports.map(|(_, destination)| destination),
+            info: PacketTable::info(packet),
+        }
+    }
+}
+
+/// Streams every captured packet to a file or named pipe as one JSON object per line.
+/// Writes are buffered and flushed at least once per `FLUSH_INTERVAL`, so readers on the
+/// other end see packets shortly after they arrive without a syscall per packet.
+pub struct JsonLinesWriter {
+    writer: BufWriter<File>,
+    last_flush: Instant,
+}
+
+impl JsonLinesWriter {
+    const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Appends to `path`, creating it if needed. Opening a named pipe waits for a reader.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            last_flush: Instant::now(),
+        })
+    }
+
+    pub fn write(&mut self, packet: &CompletePacket) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, &PacketRecord::new(packet))?;
+        self.writer.write_all(b"\n")?;
+        self.flush_if_due()
+    }
+
+    /// Flushes buffered packets once the flush interval has passed since the last flush.
+    pub fn flush_if_due(&mut self) -> io::Result<()> {
+        if self.last_flush.elapsed() < Self::FLUSH_INTERVAL {
+            return Ok(());
+        }
+        self.last_flush = Instant::now();
+        self.writer.flush()
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,626 @@
+use std::{collections::HashMap, fmt};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+use crate::strings;
+
+/// Where the keys of an action work: the packet list, or a panel drawn over it. The packet
+/// list's keys keep working in a panel unless the panel binds the same key to its own
+/// action.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyContext {
+    PacketList,
+    Conversations,
+    Comparison,
+    Columns,
+    FilterPresets,
+    Popup,
+    /// Picking a range of bytes in the popup's hex dump.
+    ByteSelection,
+}
+
+/// Everything a key can be bound to, in the packet list or in one of the panels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    NextRow,
+    PreviousRow,
+    FirstRow,
+    LastRow,
+    PageDown,
+    PageUp,
+    NextInterface,
+    RefreshInterfaces,
+    ToggleSniffer,
+    ToggleFreeze,
+    Search,
+    NextMatch,
+    PreviousMatch,
+    Filter,
+    ToggleDim,
+    Copy,
+    Conversations,
+    TopTalkers,
+    ChartMode,
+    ToggleNameResolution,
+    TogglePopup,
+    ShrinkTopPanel,
+    GrowTopPanel,
+    ShrinkChart,
+    GrowChart,
+    ToggleChart,
+    ToggleInterfaces,
+    ExpertInfo,
+    ToggleBookmark,
+    NextBookmark,
+    PreviousBookmark,
+    GoToPacket,
+    ToggleDirection,
+    FilterPresets,
+    ToggleCompact,
+    DecodeAs,
+    EventLog,
+    ToggleFullscreen,
+    Columns,
+    SavePcap,
+    Compare,
+    ToggleOrder,
+    CommandPalette,
+    FollowStream,
+    Comment,
+    CycleTheme,
+    #[serde(rename = "toggle_protocol_1")]
+    ToggleProtocol1,
+    #[serde(rename = "toggle_protocol_2")]
+    ToggleProtocol2,
+    #[serde(rename = "toggle_protocol_3")]
+    ToggleProtocol3,
+    #[serde(rename = "toggle_protocol_4")]
+    ToggleProtocol4,
+    #[serde(rename = "toggle_protocol_5")]
+    ToggleProtocol5,
+    #[serde(rename = "toggle_protocol_6")]
+    ToggleProtocol6,
+    #[serde(rename = "toggle_protocol_7")]
+    ToggleProtocol7,
+    #[serde(rename = "toggle_protocol_8")]
+    ToggleProtocol8,
+    #[serde(rename = "toggle_protocol_9")]
+    ToggleProtocol9,
+    SortConversations,
+    ToggleConversationPorts,
+    ToggleDifferencesOnly,
+    ToggleColumn,
+    MoveColumnDown,
+    MoveColumnUp,
+    SavePreset,
+    DeletePreset,
+    #[serde(rename = "apply_preset_1")]
+    ApplyPreset1,
+    #[serde(rename = "apply_preset_2")]
+    ApplyPreset2,
+    #[serde(rename = "apply_preset_3")]
+    ApplyPreset3,
+    #[serde(rename = "apply_preset_4")]
+    ApplyPreset4,
+    #[serde(rename = "apply_preset_5")]
+    ApplyPreset5,
+    #[serde(rename = "apply_preset_6")]
+    ApplyPreset6,
+    #[serde(rename = "apply_preset_7")]
+    ApplyPreset7,
+    #[serde(rename = "apply_preset_8")]
+    ApplyPreset8,
+    #[serde(rename = "apply_preset_9")]
+    ApplyPreset9,
+    ExpandLayer,
+    CollapseLayer,
+    ToggleLayer,
+    ToggleBytesView,
+    InspectBytes,
+    ToggleAllBytes,
+    MaximizePopup,
+    SelectNextByte,
+    SelectPreviousByte,
+    RestartSelection,
+    EndSelection,
+}
+
+impl Action {
+    /// Default bindings, in the order actions are listed.
+    const DEFAULTS: [(Action, &'static [&'static str]); 84] = [
+        (Action::Quit, &["q"]),
+        (Action::NextRow, &["j", "down"]),
+        (Action::PreviousRow, &["k", "up"]),
+        (Action::FirstRow, &["g", "home"]),
+        (Action::LastRow, &["G", "end"]),
+        (Action::PageDown, &["pagedown"]),
+        (Action::PageUp, &["pageup"]),
+        (Action::NextInterface, &["i"]),
+        (Action::RefreshInterfaces, &["r"]),
+        (Action::ToggleSniffer, &["p"]),
+        (Action::ToggleFreeze, &["space"]),
+        (Action::Search, &["/"]),
+        (Action::NextMatch, &["n"]),
+        (Action::PreviousMatch, &["N"]),
+        (Action::Filter, &["f"]),
+        (Action::ToggleDim, &["d"]),
+        (Action::Copy, &["y"]),
+        (Action::Conversations, &["c"]),
+        (Action::TopTalkers, &["t"]),
+        (Action::ChartMode, &["h"]),
+        (Action::ToggleNameResolution, &["R"]),
+        (Action::TogglePopup, &["enter"]),
+        (Action::ShrinkTopPanel, &["ctrl+up"]),
+        (Action::GrowTopPanel, &["ctrl+down"]),
+        (Action::ShrinkChart, &["ctrl+left"]),
+        (Action::GrowChart, &["ctrl+right"]),
+        (Action::ToggleChart, &["C"]),
+        (Action::ToggleInterfaces, &["I"]),
+        (Action::ExpertInfo, &["e"]),
+        (Action::ToggleBookmark, &["m"]),
+        (Action::NextBookmark, &["b"]),
+        (Action::PreviousBookmark, &["B"]),
+        (Action::GoToPacket, &[":"]),
+        (Action::ToggleDirection, &["D"]),
+        (Action::FilterPresets, &["F"]),
+        (Action::ToggleCompact, &["v"]),
+        (Action::DecodeAs, &["A"]),
+        (Action::EventLog, &["L"]),
+        (Action::ToggleFullscreen, &["z"]),
+        (Action::Columns, &["T"]),
+        (Action::SavePcap, &["w"]),
+        (Action::Compare, &["="]),
+        (Action::ToggleOrder, &["o"]),
+        (Action::CommandPalette, &["ctrl+p"]),
+        (Action::FollowStream, &["S"]),
+        (Action::Comment, &["a"]),
+        (Action::CycleTheme, &["ctrl+t"]),
+        (Action::ToggleProtocol1, &["1"]),
+        (Action::ToggleProtocol2, &["2"]),
+        (Action::ToggleProtocol3, &["3"]),
+        (Action::ToggleProtocol4, &["4"]),
+        (Action::ToggleProtocol5, &["5"]),
+        (Action::ToggleProtocol6, &["6"]),
+        (Action::ToggleProtocol7, &["7"]),
+        (Action::ToggleProtocol8, &["8"]),
+        (Action::ToggleProtocol9, &["9"]),
+        (Action::SortConversations, &["s"]),
+        (Action::ToggleConversationPorts, &["P"]),
+        (Action::ToggleDifferencesOnly, &["d"]),
+        (Action::ToggleColumn, &["space", "enter"]),
+        (Action::MoveColumnDown, &["J"]),
+        (Action::MoveColumnUp, &["K"]),
+        (Action::SavePreset, &["a"]),
+        (Action::DeletePreset, &["x"]),
+        (Action::ApplyPreset1, &["1"]),
+        (Action::ApplyPreset2, &["2"]),
+        (Action::ApplyPreset3, &["3"]),
+        (Action::ApplyPreset4, &["4"]),
+        (Action::ApplyPreset5, &["5"]),
+        (Action::ApplyPreset6, &["6"]),
+        (Action::ApplyPreset7, &["7"]),
+        (Action::ApplyPreset8, &["8"]),
+        (Action::ApplyPreset9, &["9"]),
+        (Action::ExpandLayer, &["l", "right"]),
+        (Action::CollapseLayer, &["h", "left"]),
+        (Action::ToggleLayer, &["enter", "space"]),
+        (Action::ToggleBytesView, &["x"]),
+        (Action::InspectBytes, &["v"]),
+        (Action::ToggleAllBytes, &["a"]),
+        (Action::MaximizePopup, &["z"]),
+        (Action::SelectNextByte, &["l", "right"]),
+        (Action::SelectPreviousByte, &["h", "left"]),
+        (Action::RestartSelection, &["space"]),
+        (Action::EndSelection, &["v"]),
+    ];
+
+    /// The protocol switches, in the order of `ProtocolToggles::PROTOCOLS`.
+    pub const TOGGLE_PROTOCOL: [Action; 9] = [
+        Action::ToggleProtocol1,
+        Action::ToggleProtocol2,
+        Action::ToggleProtocol3,
+        Action::ToggleProtocol4,
+        Action::ToggleProtocol5,
+        Action::ToggleProtocol6,
+        Action::ToggleProtocol7,
+        Action::ToggleProtocol8,
+        Action::ToggleProtocol9,
+    ];
+
+    /// The first nine presets of the preset picker, in order.
+    pub const APPLY_PRESET: [Action; 9] = [
+        Action::ApplyPreset1,
+        Action::ApplyPreset2,
+        Action::ApplyPreset3,
+        Action::ApplyPreset4,
+        Action::ApplyPreset5,
+        Action::ApplyPreset6,
+        Action::ApplyPreset7,
+        Action::ApplyPreset8,
+        Action::ApplyPreset9,
+    ];
+
+    /// Every action, in the order they're listed.
+    pub fn all() -> impl Iterator<Item = Action> {
+        Self::DEFAULTS.into_iter().map(|(action, _)| action)
+    }
+
+    pub fn context(self) -> KeyContext {
+        match self {
+            Action::SortConversations | Action::ToggleConversationPorts => {
+                KeyContext::Conversations
+            }
+            Action::ToggleDifferencesOnly => KeyContext::Comparison,
+            Action::ToggleColumn | Action::MoveColumnDown | Action::MoveColumnUp => {
+                KeyContext::Columns
+            }
+            Action::SavePreset
+            | Action::DeletePreset
+            | Action::ApplyPreset1
+            | Action::ApplyPreset2
+            | Action::ApplyPreset3
+            | Action::ApplyPreset4
+            | Action::ApplyPreset5
+            | Action::ApplyPreset6
+            | Action::ApplyPreset7
+            | Action::ApplyPreset8
+            | Action::ApplyPreset9 => KeyContext::FilterPresets,
+            Action::ExpandLayer
+            | Action::CollapseLayer
+            | Action::ToggleLayer
+            | Action::ToggleBytesView
+            | Action::InspectBytes
+            | Action::ToggleAllBytes
+            | Action::MaximizePopup => KeyContext::Popup,
+            Action::SelectNextByte
+            | Action::SelectPreviousByte
+            | Action::RestartSelection
+            | Action::EndSelection => KeyContext::ByteSelection,
+            _ => KeyContext::PacketList,
+        }
+    }
+
+    /// What the action does, as listed in the command palette.
+    pub fn description(self) -> &'static str {
+        strings::get(self.string_key())
+    }
+
+    /// The name of the action in `[keys]`, such as "next_row".
+    pub fn name(self) -> &'static str {
+        self.string_key().trim_start_matches("action.")
+    }
+
+    fn string_key(self) -> &'static str {
+        match self {
+            Action::Quit => "action.quit",
+            Action::NextRow => "action.next_row",
+            Action::PreviousRow => "action.previous_row",
+            Action::FirstRow => "action.first_row",
+            Action::LastRow => "action.last_row",
+            Action::PageDown => "action.page_down",
+            Action::PageUp => "action.page_up",
+            Action::NextInterface => "action.next_interface",
+            Action::RefreshInterfaces => "action.refresh_interfaces",
+            Action::ToggleSniffer => "action.toggle_sniffer",
+            Action::ToggleFreeze => "action.toggle_freeze",
+            Action::Search => "action.search",
+            Action::NextMatch => "action.next_match",
+            Action::PreviousMatch => "action.previous_match",
+            Action::Filter => "action.filter",
+            Action::ToggleDim => "action.toggle_dim",
+            Action::Copy => "action.copy",
+            Action::Conversations => "action.conversations",
+            Action::TopTalkers => "action.top_talkers",
+            Action::ChartMode => "action.chart_mode",
+            Action::ToggleNameResolution => "action.toggle_name_resolution",
+            Action::TogglePopup => "action.toggle_popup",
+            Action::ShrinkTopPanel => "action.shrink_top_panel",
+            Action::GrowTopPanel => "action.grow_top_panel",
+            Action::ShrinkChart => "action.shrink_chart",
+            Action::GrowChart => "action.grow_chart",
+            Action::ToggleChart => "action.toggle_chart",
+            Action::ToggleInterfaces => "action.toggle_interfaces",
+            Action::ExpertInfo => "action.expert_info",
+            Action::ToggleBookmark => "action.toggle_bookmark",
+            Action::NextBookmark => "action.next_bookmark",
+            Action::PreviousBookmark => "action.previous_bookmark",
+            Action::GoToPacket => "action.go_to_packet",
+            Action::ToggleDirection => "action.toggle_direction",
+            Action::FilterPresets => "action.filter_presets",
+            Action::ToggleCompact => "action.toggle_compact",
+            Action::DecodeAs => "action.decode_as",
+            Action::EventLog => "action.event_log",
+            Action::ToggleFullscreen => "action.toggle_fullscreen",
+            Action::Columns => "action.columns",
+            Action::SavePcap => "action.save_pcap",
+            Action::Compare => "action.compare",
+            Action::ToggleOrder => "action.toggle_order",
+            Action::CommandPalette => "action.command_palette",
+            Action::FollowStream => "action.follow_stream",
+            Action::Comment => "action.comment",
+            Action::CycleTheme => "action.cycle_theme",
+            Action::ToggleProtocol1 => "action.toggle_protocol_1",
+            Action::ToggleProtocol2 => "action.toggle_protocol_2",
+            Action::ToggleProtocol3 => "action.toggle_protocol_3",
+            Action::ToggleProtocol4 => "action.toggle_protocol_4",
+            Action::ToggleProtocol5 => "action.toggle_protocol_5",
+            Action::ToggleProtocol6 => "action.toggle_protocol_6",
+            Action::ToggleProtocol7 => "action.toggle_protocol_7",
+            Action::ToggleProtocol8 => "action.toggle_protocol_8",
+            Action::ToggleProtocol9 => "action.toggle_protocol_9",
+            Action::SortConversations => "action.sort_conversations",
+            Action::ToggleConversationPorts => "action.toggle_conversation_ports",
+            Action::ToggleDifferencesOnly => "action.toggle_differences_only",
+            Action::ToggleColumn => "action.toggle_column",
+            Action::MoveColumnDown => "action.move_column_down",
+            Action::MoveColumnUp => "action.move_column_up",
+            Action::SavePreset => "action.save_preset",
+            Action::DeletePreset => "action.delete_preset",
+            Action::ApplyPreset1 => "action.apply_preset_1",
+            Action::ApplyPreset2 => "action.apply_preset_2",
+            Action::ApplyPreset3 => "action.apply_preset_3",
+            Action::ApplyPreset4 => "action.apply_preset_4",
+            Action::ApplyPreset5 => "action.apply_preset_5",
+            Action::ApplyPreset6 => "action.apply_preset_6",
+            Action::ApplyPreset7 => "action.apply_preset_7",
+            Action::ApplyPreset8 => "action.apply_preset_8",
+            Action::ApplyPreset9 => "action.apply_preset_9",
+            Action::ExpandLayer => "action.expand_layer",
+            Action::CollapseLayer => "action.collapse_layer",
+            Action::ToggleLayer => "action.toggle_layer",
+            Action::ToggleBytesView => "action.toggle_bytes_view",
+            Action::InspectBytes => "action.inspect_bytes",
+            Action::ToggleAllBytes => "action.toggle_all_bytes",
+            Action::MaximizePopup => "action.maximize_popup",
+            Action::SelectNextByte => "action.select_next_byte",
+            Action::SelectPreviousByte => "action.select_previous_byte",
+            Action::RestartSelection => "action.restart_selection",
+            Action::EndSelection => "action.end_selection",
+        }
+    }
+}
+
+/// A key with the Ctrl/Alt modifiers that must be held. Shift is part of the character
+/// itself ("G" rather than "shift+g"), since terminals disagree on reporting it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    const MODIFIERS: KeyModifiers = KeyModifiers::CONTROL.union(KeyModifiers::ALT);
+
+    /// Parses names like "q", "G", "enter", "pagedown", "f5" or "ctrl+p".
+    pub fn parse(name: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut key = name;
+        while let Some((modifier, rest)) = key.split_once('+').filter(|(_, rest)| !rest.is_empty())
+        {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                _ => return None,
+            };
+            key = rest;
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_lowercase().as_str() {
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "space" => KeyCode::Char(' '),
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "insert" => KeyCode::Insert,
+                "delete" => KeyCode::Delete,
+                function => KeyCode::F(function.strip_prefix('f')?.parse().ok()?),
+            },
+        };
+        Some(Self { code, modifiers })
+    }
+
+    fn matches(&self, key_event: KeyEvent) -> bool {
+        self.code == key_event.code && self.modifiers == key_event.modifiers & Self::MODIFIERS
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Home => write!(f, "home"),
+            KeyCode::End => write!(f, "end"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            KeyCode::Insert => write!(f, "insert"),
+            KeyCode::Delete => write!(f, "delete"),
+            KeyCode::F(number) => write!(f, "f{number}"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// Keys for an action in the config file: one key name or a list of them.
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+pub enum KeyNames {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyNames {
+    fn names(&self) -> Vec<&str> {
+        match self {
+            KeyNames::One(name) => vec![name.as_str()],
+            KeyNames::Many(names) => names.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Keymap {
+    bindings: Vec<(KeyBinding, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&HashMap::new()).0
+    }
+}
+
+impl Keymap {
+    /// Builds the keymap from the defaults, replacing the keys of every action present
+    /// in `overrides`. Returns translated warnings for unknown key names and for keys
+    /// bound to more than one action of the same context, in which case the configured
+    /// binding wins.
+    pub fn new(overrides: &HashMap<Action, KeyNames>) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        let mut configured = Vec::new();
+        let mut defaults = Vec::new();
+        for (action, default_names) in Action::DEFAULTS {
+            match overrides.get(&action) {
+                Some(names) => {
+                    for name in names.names() {
+                        match KeyBinding::parse(name) {
+                            Some(binding) => configured.push((binding, action)),
+                            None => warnings
+                                .push(strings::get("keymap.unknown_key").replace("{key}", name)),
+                        }
+                    }
+                }
+                None => defaults.extend(
+                    default_names
+                        .iter()
+                        .filter_map(|name| KeyBinding::parse(name))
+                        .map(|binding| (binding, action)),
+                ),
+            }
+        }
+
+        let mut bindings: Vec<(KeyBinding, Action)> = Vec::new();
+        for (binding, action) in configured.into_iter().chain(defaults) {
+            match bindings.iter().find(|(bound, bound_action)| {
+                *bound == binding && bound_action.context() == action.context()
+            }) {
+                Some((_, bound_action)) => warnings.push(
+                    strings::get("keymap.conflict")
+                        .replace("{key}", &binding.to_string())
+                        .replace("{action}", &format!("{bound_action:?}"))
+                        .replace("{other}", &format!("{action:?}")),
+                ),
+                None => bindings.push((binding, action)),
+            }
+        }
+        (Self { bindings }, warnings)
+    }
+
+    /// The action `key_event` runs in `context`, falling back to the packet list's.
+    pub fn action(&self, context: KeyContext, key_event: KeyEvent) -> Option<Action> {
+        let find = |context: KeyContext| {
+            self.bindings
+                .iter()
+                .find(|(binding, action)| action.context() == context && binding.matches(key_event))
+                .map(|(_, action)| *action)
+        };
+        find(context).or_else(|| find(KeyContext::PacketList))
+    }
+
+    /// The first key bound to `action`, for help texts; "-" when it has none.
+    pub fn key_label(&self, action: Action) -> String {
+        self.bindings
+            .iter()
+            .find(|(_, bound)| *bound == action)
+            .map_or_else(|| "-".to_string(), |(binding, _)| binding.to_string())
+    }
+
+    /// `template` with every `{action}` placeholder, such as `{quit}`, replaced by the
+    /// first key bound to that action, for the help lines of the panels.
+    pub fn fill_keys(&self, template: &str) -> String {
+        Action::all().fold(template.to_string(), |text, action| {
+            text.replace(&format!("{{{}}}", action.name()), &self.key_label(action))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{Action, KeyContext, KeyNames, Keymap};
+
+    #[test]
+    fn panel_keys_shadow_the_packet_list_and_conflict_only_within_their_panel() {
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let (keymap, warnings) = Keymap::new(&HashMap::new());
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert_eq!(
+            keymap.action(KeyContext::PacketList, key('1')),
+            Some(Action::ToggleProtocol1)
+        );
+        assert_eq!(
+            keymap.action(KeyContext::FilterPresets, key('1')),
+            Some(Action::ApplyPreset1)
+        );
+        assert_eq!(
+            keymap.action(KeyContext::Popup, key('a')),
+            Some(Action::ToggleAllBytes)
+        );
+        assert_eq!(
+            keymap.action(KeyContext::Popup, key('q')),
+            Some(Action::Quit)
+        );
+
+        let overrides = HashMap::from([
+            (Action::SortConversations, KeyNames::One("o".to_string())),
+            (
+                Action::ToggleConversationPorts,
+                KeyNames::One("o".to_string()),
+            ),
+        ]);
+        let (keymap, warnings) = Keymap::new(&overrides);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            keymap.action(KeyContext::Conversations, key('o')),
+            Some(Action::SortConversations)
+        );
+        assert_eq!(
+            keymap.action(KeyContext::PacketList, key('o')),
+            Some(Action::ToggleOrder)
+        );
+        assert_eq!(
+            keymap.fill_keys("{sort_conversations}/{toggle_protocol_2}"),
+            "o/2"
+        );
+    }
+}
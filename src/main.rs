@@ -1,15 +1,94 @@
 use app::App;
+use clap::Parser;
+use cli::Args;
+use color_eyre::eyre::WrapErr;
+use config::Config;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use expert::Severity;
+use geoip::GeoIp;
+use json_export::JsonLinesWriter;
+use settings::Settings;
+use strings::Language;
 mod app;
+mod app_protocol;
+mod cli;
+mod clipboard;
+mod config;
 mod event;
+mod event_log;
+mod expert;
+mod filter;
+mod flows;
+mod geoip;
+mod interface_stats;
+mod json_export;
+mod keymap;
 mod packet_data;
+mod pcap_export;
+mod reassembly;
+mod resolver;
+mod settings;
 mod sniffer;
+mod stats;
+mod strings;
+mod theme;
 mod widgets;
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
+    let args = Args::parse();
+    strings::init(args.lang.unwrap_or_else(Language::from_env));
+
+    let (config, config_warning) = Config::load();
+    // Opened before the terminal is taken over, since a named pipe blocks until read.
+    let json_output = args
+        .json_out
+        .as_deref()
+        .map(|path| {
+            JsonLinesWriter::open(path)
+                .wrap_err_with(|| format!("cannot open {} for --json-out", path.display()))
+        })
+        .transpose()?;
     let mut terminal = ratatui::init();
 
-    let mut app = App::new();
+    let mut app = App::new(config, &args, Settings::load());
+    if let Some(warning) = config_warning {
+        app.notify(Severity::Warn, warning);
+    }
+    // The keyboard works without the mouse, so this isn't worth leaving the terminal
+    // raw for by returning early.
+    if let Err(error) = crossterm::execute!(std::io::stdout(), EnableMouseCapture) {
+        app.notify(
+            Severity::Warn,
+            strings::get("status.mouse_unavailable").replace("{error}", &error.to_string()),
+        );
+    }
+    if !args.geoip.is_empty() {
+        match GeoIp::open(&args.geoip) {
+            Ok(geoip) => app.annotate_geoip(geoip),
+            Err(error) => app.notify(
+                Severity::Warn,
+                strings::get("geoip.disabled").replace("{error}", &error),
+            ),
+        }
+    }
+    if let Some(json_output) = json_output {
+        app.stream_json(json_output);
+    }
     let app_result = app.run(&mut terminal);
+    // Restoring the terminal matters more than a failure to turn mouse reporting off.
+    let _ = crossterm::execute!(std::io::stdout(), DisableMouseCapture);
     ratatui::restore();
-    app_result
+    if let Err(error) = app.save_settings() {
+        let path = Settings::path().unwrap_or_default();
+        eprintln!(
+            "{}",
+            strings::get("settings.save_failed")
+                .replace("{path}", &path.display().to_string())
+                .replace("{error}", &error.to_string())
+        );
+    }
+    if let Some(summary) = app_result? {
+        print!("{summary}");
+    }
+    Ok(())
 }
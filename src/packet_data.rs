@@ -1,19 +1,27 @@
+use chrono::{DateTime, Local};
 use pnet::{
     packet::{
         arp::{ArpHardwareType, ArpOperation, ArpPacket},
-        ethernet::{EtherType, EthernetPacket},
-        icmp::{IcmpCode, IcmpPacket, IcmpType},
-        icmpv6::{Icmpv6Code, Icmpv6Packet, Icmpv6Type},
-        ip::IpNextHeaderProtocol,
-        ipv4::Ipv4Packet,
+        ethernet::{EtherType, EtherTypes, EthernetPacket},
+        icmp::{self, IcmpCode, IcmpPacket, IcmpType, IcmpTypes},
+        icmpv6::{Icmpv6Code, Icmpv6Packet, Icmpv6Type, Icmpv6Types},
+        ip::{IpNextHeaderProtocol, IpNextHeaderProtocols},
+        ipv4::{self, Ipv4Flags, Ipv4Packet},
         ipv6::Ipv6Packet,
-        tcp::{TcpOption, TcpPacket},
+        tcp::{TcpOption, TcpOptionNumbers, TcpPacket},
         udp::UdpPacket,
         Packet,
     },
     util::MacAddr,
 };
-use std::net::{Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::{
+    expert::Severity,
+    flows::{FlowDirection, TcpConnectionState, TrafficDirection},
+    reassembly::Ipv4Fragment,
+    sniffer::LinkType,
+};
 
 #[derive(Clone)]
 pub struct TcpPacketInfo {
@@ -29,6 +37,9 @@ pub struct TcpPacketInfo {
     pub urgent_ptr: u16,
     pub options: Vec<TcpOption>,
     pub length: usize,
+    /// Checksum recomputed over the pseudo-header and segment; set by the sniffer, which
+    /// knows the IP addresses.
+    pub expected_checksum: Option<u16>,
 }
 impl<'a> From<&TcpPacket<'a>> for TcpPacketInfo {
     fn from(packet: &TcpPacket<'a>) -> Self {
@@ -45,9 +56,26 @@ impl<'a> From<&TcpPacket<'a>> for TcpPacketInfo {
             urgent_ptr: packet.get_urgent_ptr(),
             options: packet.get_options(),
             length: packet.payload().len(),
+            expected_checksum: None,
         }
     }
 }
+impl TcpPacketInfo {
+    /// The left and right edges of the blocks of the SACK option, if any.
+    pub fn sack_blocks(&self) -> Vec<(u32, u32)> {
+        self.options
+            .iter()
+            .filter(|option| option.number == TcpOptionNumbers::SACK)
+            .flat_map(|option| option.data.chunks_exact(8))
+            .map(|block| {
+                (
+                    u32::from_be_bytes([block[0], block[1], block[2], block[3]]),
+                    u32::from_be_bytes([block[4], block[5], block[6], block[7]]),
+                )
+            })
+            .collect()
+    }
+}
 
 #[derive(Clone)]
 pub struct UdpPacketInfo {
@@ -55,6 +83,11 @@ pub struct UdpPacketInfo {
     pub destination: u16,
     pub length: u16,
     pub checksum: u16,
+    /// Checksum recomputed over the pseudo-header and datagram; set by the sniffer, which
+    /// knows the IP addresses. None when the sender left it out (zero over IPv4).
+    pub expected_checksum: Option<u16>,
+    /// Datagram payload, kept for following the flow's stream.
+    pub payload: Vec<u8>,
 }
 impl<'a> From<&UdpPacket<'a>> for UdpPacketInfo {
     fn from(packet: &UdpPacket<'a>) -> Self {
@@ -63,6 +96,8 @@ impl<'a> From<&UdpPacket<'a>> for UdpPacketInfo {
             destination: packet.get_destination(),
             length: packet.get_length(),
             checksum: packet.get_checksum(),
+            expected_checksum: None,
+            payload: packet.payload().to_vec(),
         }
     }
 }
@@ -72,42 +107,406 @@ pub struct Icmpv6PacketInfo {
     pub icmpv6_type: Icmpv6Type,
     pub icmpv6_code: Icmpv6Code,
     pub checksum: u16,
+    pub ndp: Option<NdpInfo>,
     pub length: usize,
 }
 impl<'a> From<&Icmpv6Packet<'a>> for Icmpv6PacketInfo {
     fn from(packet: &Icmpv6Packet<'a>) -> Self {
+        let icmpv6_type = packet.get_icmpv6_type();
         Icmpv6PacketInfo {
-            icmpv6_type: packet.get_icmpv6_type(),
+            icmpv6_type,
             icmpv6_code: packet.get_icmpv6_code(),
             checksum: packet.get_checksum(),
+            ndp: NdpInfo::parse(icmpv6_type, packet.payload()),
             length: packet.payload().len(),
         }
     }
 }
+impl Icmpv6PacketInfo {
+    /// What a type and code mean together (RFC 4443, 4861, 2710 and 3810): the reason
+    /// for an error, or the message name for types that don't use the code.
+    pub fn describe_icmpv6(icmpv6_type: Icmpv6Type, icmpv6_code: Icmpv6Code) -> &'static str {
+        match (icmpv6_type, icmpv6_code.0) {
+            (Icmpv6Types::DestinationUnreachable, 0) => "No Route to Destination",
+            (Icmpv6Types::DestinationUnreachable, 1) => "Communication Administratively Prohibited",
+            (Icmpv6Types::DestinationUnreachable, 2) => "Beyond Scope of Source Address",
+            (Icmpv6Types::DestinationUnreachable, 3) => "Address Unreachable",
+            (Icmpv6Types::DestinationUnreachable, 4) => "Port Unreachable",
+            (Icmpv6Types::DestinationUnreachable, 5) => "Source Address Failed Policy",
+            (Icmpv6Types::DestinationUnreachable, 6) => "Reject Route to Destination",
+            (Icmpv6Types::PacketTooBig, _) => "Packet Too Big",
+            (Icmpv6Types::TimeExceeded, 0) => "Hop Limit Exceeded in Transit",
+            (Icmpv6Types::TimeExceeded, 1) => "Fragment Reassembly Time Exceeded",
+            (Icmpv6Types::ParameterProblem, 0) => "Erroneous Header Field",
+            (Icmpv6Types::ParameterProblem, 1) => "Unrecognized Next Header Type",
+            (Icmpv6Types::ParameterProblem, 2) => "Unrecognized IPv6 Option",
+            (Icmpv6Types::EchoRequest, _) => "Echo Request",
+            (Icmpv6Types::EchoReply, _) => "Echo Reply",
+            (Icmpv6Type(130), _) => "Multicast Listener Query",
+            (Icmpv6Type(131), _) => "Multicast Listener Report",
+            (Icmpv6Type(132), _) => "Multicast Listener Done",
+            (Icmpv6Types::RouterSolicit, _) => "Router Solicitation",
+            (Icmpv6Types::RouterAdvert, _) => "Router Advertisement",
+            (Icmpv6Types::NeighborSolicit, _) => "Neighbor Solicitation",
+            (Icmpv6Types::NeighborAdvert, _) => "Neighbor Advertisement",
+            (Icmpv6Types::Redirect, _) => "Redirect",
+            (Icmpv6Type(143), _) => "Multicast Listener Report v2",
+            _ => "Unknown",
+        }
+    }
+}
+
+/// Body of an ICMPv6 Neighbor Discovery message (RFC 4861): router and neighbor
+/// solicitations and advertisements, which IPv6 uses instead of ARP and DHCP.
+#[derive(Clone)]
+pub struct NdpInfo {
+    /// Address being resolved or advertised; only neighbor messages carry one.
+    pub target: Option<Ipv6Addr>,
+    /// M and O bits of a router advertisement, or R, S and O of a neighbor advertisement.
+    pub flags: u8,
+    pub router: Option<NdpRouterInfo>,
+    pub options: Vec<NdpOption>,
+    /// An option whose length is zero or runs past the end of the message; parsing
+    /// stops there, as the rest can't be delimited.
+    pub malformed_options: bool,
+}
+
+/// Parameters a router advertises to the hosts on its link.
+#[derive(Clone)]
+pub struct NdpRouterInfo {
+    pub cur_hop_limit: u8,
+    pub lifetime: u16,
+    pub reachable_time: u32,
+    pub retrans_timer: u32,
+}
+
+#[derive(Clone)]
+pub enum NdpOption {
+    SourceLinkLayerAddress(MacAddr),
+    TargetLinkLayerAddress(MacAddr),
+    PrefixInformation {
+        prefix: Ipv6Addr,
+        prefix_length: u8,
+        on_link: bool,
+        autonomous: bool,
+        valid_lifetime: u32,
+        preferred_lifetime: u32,
+    },
+    Mtu(u32),
+    Other {
+        option_type: u8,
+        length: usize,
+    },
+}
+
+impl NdpInfo {
+    /// Options are measured in units of 8 bytes, type and length included.
+    const OPTION_UNIT: usize = 8;
+    const PREFIX_INFORMATION_LENGTH: usize = 32;
+
+    /// `body` is the ICMPv6 payload after the type, code and checksum.
+    fn parse(icmpv6_type: Icmpv6Type, body: &[u8]) -> Option<Self> {
+        let read_u32 = |bytes: &[u8], at: usize| {
+            u32::from_be_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+        };
+        let read_target = |body: &[u8]| {
+            body.get(4..20)
+                .and_then(|bytes| <[u8; 16]>::try_from(bytes).ok())
+                .map(Ipv6Addr::from)
+        };
+
+        let (target, flags, router, options_offset) = match icmpv6_type {
+            Icmpv6Types::RouterSolicit => (None, 0, None, 4),
+            Icmpv6Types::RouterAdvert => {
+                let fixed = body.get(..12)?;
+                let router = NdpRouterInfo {
+                    cur_hop_limit: fixed[0],
+                    lifetime: u16::from_be_bytes([fixed[2], fixed[3]]),
+                    reachable_time: read_u32(fixed, 4),
+                    retrans_timer: read_u32(fixed, 8),
+                };
+                (None, fixed[1], Some(router), 12)
+            }
+            Icmpv6Types::NeighborSolicit => (Some(read_target(body)?), 0, None, 20),
+            Icmpv6Types::NeighborAdvert => (Some(read_target(body)?), body[0], None, 20),
+            _ => return None,
+        };
+
+        let (options, malformed_options) =
+            Self::parse_options(body.get(options_offset..).unwrap_or_default());
+        Some(NdpInfo {
+            target,
+            flags,
+            router,
+            options,
+            malformed_options,
+        })
+    }
+
+    fn parse_options(mut bytes: &[u8]) -> (Vec<NdpOption>, bool) {
+        let mut options = Vec::new();
+        while !bytes.is_empty() {
+            let length = bytes
+                .get(1)
+                .map_or(0, |&units| units as usize * Self::OPTION_UNIT);
+            if length == 0 || length > bytes.len() {
+                return (options, true);
+            }
+            let (option, rest) = bytes.split_at(length);
+            bytes = rest;
+
+            let mac = || {
+                MacAddr::new(
+                    option[2], option[3], option[4], option[5], option[6], option[7],
+                )
+            };
+            options.push(match option[0] {
+                1 => NdpOption::SourceLinkLayerAddress(mac()),
+                2 => NdpOption::TargetLinkLayerAddress(mac()),
+                3 if length >= Self::PREFIX_INFORMATION_LENGTH => {
+                    let prefix: [u8; 16] = option[16..32].try_into().unwrap_or_default();
+                    NdpOption::PrefixInformation {
+                        prefix: Ipv6Addr::from(prefix),
+                        prefix_length: option[2],
+                        on_link: option[3] & 0x80 != 0,
+                        autonomous: option[3] & 0x40 != 0,
+                        valid_lifetime: u32::from_be_bytes([
+                            option[4], option[5], option[6], option[7],
+                        ]),
+                        preferred_lifetime: u32::from_be_bytes([
+                            option[8], option[9], option[10], option[11],
+                        ]),
+                    }
+                }
+                5 => NdpOption::Mtu(u32::from_be_bytes([
+                    option[4], option[5], option[6], option[7],
+                ])),
+                option_type => NdpOption::Other {
+                    option_type,
+                    length,
+                },
+            });
+        }
+        (options, false)
+    }
+
+    pub fn message_name(icmpv6_type: Icmpv6Type) -> &'static str {
+        match icmpv6_type {
+            Icmpv6Types::RouterSolicit => "Router Solicitation",
+            Icmpv6Types::RouterAdvert => "Router Advertisement",
+            Icmpv6Types::NeighborSolicit => "Neighbor Solicitation",
+            Icmpv6Types::NeighborAdvert => "Neighbor Advertisement",
+            _ => "Neighbor Discovery",
+        }
+    }
+
+    /// Names of the flags set, for the message type they were read from.
+    pub fn flag_names(&self, icmpv6_type: Icmpv6Type) -> Vec<&'static str> {
+        let names: &[(u8, &str)] = match icmpv6_type {
+            Icmpv6Types::RouterAdvert => &[(0x80, "Managed"), (0x40, "Other Configuration")],
+            Icmpv6Types::NeighborAdvert => {
+                &[(0x80, "Router"), (0x40, "Solicited"), (0x20, "Override")]
+            }
+            _ => &[],
+        };
+        names
+            .iter()
+            .filter(|(bit, _)| self.flags & bit != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    /// The link-layer address the sender announces: its own in solicitations and router
+    /// advertisements, the target's in neighbor advertisements.
+    pub fn link_layer_address(&self) -> Option<MacAddr> {
+        self.options.iter().find_map(|option| match option {
+            NdpOption::SourceLinkLayerAddress(mac) | NdpOption::TargetLinkLayerAddress(mac) => {
+                Some(*mac)
+            }
+            _ => None,
+        })
+    }
+
+    pub fn prefixes(&self) -> impl Iterator<Item = String> + '_ {
+        self.options.iter().filter_map(|option| match option {
+            NdpOption::PrefixInformation {
+                prefix,
+                prefix_length,
+                ..
+            } => Some(format!("{prefix}/{prefix_length}")),
+            _ => None,
+        })
+    }
+
+    /// One-line description naming the neighbor, such as "Neighbor Solicitation for
+    /// fe80::1 from 00:11:22:33:44:55".
+    pub fn summary(&self, icmpv6_type: Icmpv6Type) -> String {
+        let mut parts = vec![Self::message_name(icmpv6_type).to_string()];
+        if let Some(target) = self.target {
+            parts.push(target.to_string());
+        }
+        if let Some(mac) = self.link_layer_address() {
+            let link = match icmpv6_type {
+                Icmpv6Types::NeighborAdvert => "is at",
+                _ => "from",
+            };
+            parts.push(format!("{link} {mac}"));
+        }
+        parts.extend(self.prefixes());
+        parts.join(" ")
+    }
+}
 #[derive(Clone)]
 pub struct IcmpPacketInfo {
     pub icmp_type: IcmpType,
     pub icmp_code: IcmpCode,
     pub checksum: u16,
+    pub expected_checksum: Option<u16>,
+    pub original: Option<IcmpOriginalPacket>,
     pub length: usize,
 }
 impl<'a> From<&IcmpPacket<'a>> for IcmpPacketInfo {
     fn from(packet: &IcmpPacket<'a>) -> Self {
+        let icmp_type = packet.get_icmp_type();
+        let original = if IcmpOriginalPacket::is_error_type(icmp_type) {
+            // The 4 bytes after the checksum are type specific; the offending packet follows.
+            packet
+                .payload()
+                .get(4..)
+                .and_then(IcmpOriginalPacket::parse)
+        } else {
+            None
+        };
+
         IcmpPacketInfo {
-            icmp_type: packet.get_icmp_type(),
+            icmp_type,
             icmp_code: packet.get_icmp_code(),
             checksum: packet.get_checksum(),
+            expected_checksum: Some(icmp::checksum(packet)),
+            original,
             length: packet.payload().len(),
         }
     }
 }
+impl IcmpPacketInfo {
+    /// What a type and code mean together (RFC 792 and 1812): the reason for an error,
+    /// or the message name for types that don't use the code.
+    pub fn describe_icmp(icmp_type: IcmpType, icmp_code: IcmpCode) -> &'static str {
+        match (icmp_type, icmp_code.0) {
+            (IcmpTypes::EchoReply, _) => "Echo Reply",
+            (IcmpTypes::DestinationUnreachable, 0) => "Network Unreachable",
+            (IcmpTypes::DestinationUnreachable, 1) => "Host Unreachable",
+            (IcmpTypes::DestinationUnreachable, 2) => "Protocol Unreachable",
+            (IcmpTypes::DestinationUnreachable, 3) => "Port Unreachable",
+            (IcmpTypes::DestinationUnreachable, 4) => "Fragmentation Needed",
+            (IcmpTypes::DestinationUnreachable, 5) => "Source Route Failed",
+            (IcmpTypes::DestinationUnreachable, 6) => "Destination Network Unknown",
+            (IcmpTypes::DestinationUnreachable, 7) => "Destination Host Unknown",
+            (IcmpTypes::DestinationUnreachable, 8) => "Source Host Isolated",
+            (IcmpTypes::DestinationUnreachable, 9) => "Network Administratively Prohibited",
+            (IcmpTypes::DestinationUnreachable, 10) => "Host Administratively Prohibited",
+            (IcmpTypes::DestinationUnreachable, 11) => "Network Unreachable for ToS",
+            (IcmpTypes::DestinationUnreachable, 12) => "Host Unreachable for ToS",
+            (IcmpTypes::DestinationUnreachable, 13) => "Communication Administratively Prohibited",
+            (IcmpTypes::DestinationUnreachable, 14) => "Host Precedence Violation",
+            (IcmpTypes::DestinationUnreachable, 15) => "Precedence Cutoff in Effect",
+            (IcmpTypes::SourceQuench, _) => "Source Quench",
+            (IcmpTypes::RedirectMessage, 0) => "Redirect for Network",
+            (IcmpTypes::RedirectMessage, 1) => "Redirect for Host",
+            (IcmpTypes::RedirectMessage, 2) => "Redirect for ToS and Network",
+            (IcmpTypes::RedirectMessage, 3) => "Redirect for ToS and Host",
+            (IcmpTypes::EchoRequest, _) => "Echo Request",
+            (IcmpTypes::RouterAdvertisement, _) => "Router Advertisement",
+            (IcmpTypes::RouterSolicitation, _) => "Router Solicitation",
+            (IcmpTypes::TimeExceeded, 0) => "TTL Exceeded in Transit",
+            (IcmpTypes::TimeExceeded, 1) => "Fragment Reassembly Time Exceeded",
+            (IcmpTypes::ParameterProblem, 0) => "Pointer Indicates the Error",
+            (IcmpTypes::ParameterProblem, 1) => "Missing a Required Option",
+            (IcmpTypes::ParameterProblem, 2) => "Bad Length",
+            (IcmpTypes::Timestamp, _) => "Timestamp",
+            (IcmpTypes::TimestampReply, _) => "Timestamp Reply",
+            _ => "Unknown",
+        }
+    }
+}
+
+/// The IPv4 header and first bytes of the packet that triggered an ICMP error.
+#[derive(Clone)]
+pub struct IcmpOriginalPacket {
+    pub source: Ipv4Addr,
+    pub destination: Ipv4Addr,
+    pub protocol: IpNextHeaderProtocol,
+    pub ports: Option<(u16, u16)>,
+}
+impl IcmpOriginalPacket {
+    fn is_error_type(icmp_type: IcmpType) -> bool {
+        matches!(
+            icmp_type,
+            IcmpTypes::DestinationUnreachable
+                | IcmpTypes::SourceQuench
+                | IcmpTypes::RedirectMessage
+                | IcmpTypes::TimeExceeded
+                | IcmpTypes::ParameterProblem
+        )
+    }
+
+    fn parse(embedded: &[u8]) -> Option<Self> {
+        let ipv4 = Ipv4Packet::new(embedded)?;
+        let header_length = ipv4.get_header_length() as usize * 4;
+        if ipv4.get_version() != 4 || header_length < Ipv4Packet::minimum_packet_size() {
+            return None;
+        }
+
+        let protocol = ipv4.get_next_level_protocol();
+        let ports = match protocol {
+            IpNextHeaderProtocols::Tcp
+            | IpNextHeaderProtocols::Udp
+            | IpNextHeaderProtocols::Sctp => {
+                embedded.get(header_length..header_length + 4).map(|ports| {
+                    (
+                        u16::from_be_bytes([ports[0], ports[1]]),
+                        u16::from_be_bytes([ports[2], ports[3]]),
+                    )
+                })
+            }
+            _ => None,
+        };
+
+        Some(IcmpOriginalPacket {
+            source: ipv4.get_source(),
+            destination: ipv4.get_destination(),
+            protocol,
+            ports,
+        })
+    }
+}
+impl std::fmt::Display for IcmpOriginalPacket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.ports {
+            Some((source_port, destination_port)) => write!(
+                f,
+                "{}:{} -> {}:{} ({})",
+                self.source, source_port, self.destination, destination_port, self.protocol
+            ),
+            None => write!(
+                f,
+                "{} -> {} ({})",
+                self.source, self.destination, self.protocol
+            ),
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct EthernetPacketInfo {
     pub destination: MacAddr,
     pub source: MacAddr,
     pub ethertype: EtherType,
-    pub payload: Vec<u8>,
+    pub payload_length: usize,
+    /// Frame check sequence the frame ended with and the one computed over the rest of
+    /// it; only read when capturing with `--fcs`.
+    pub fcs: Option<u32>,
+    pub expected_fcs: Option<u32>,
 }
 impl<'p> From<&EthernetPacket<'p>> for EthernetPacketInfo {
     fn from(packet: &EthernetPacket) -> Self {
@@ -115,7 +514,449 @@ impl<'p> From<&EthernetPacket<'p>> for EthernetPacketInfo {
             destination: packet.get_destination(),
             source: packet.get_source(),
             ethertype: packet.get_ethertype(),
-            payload: packet.payload().to_vec(),
+            payload_length: packet.payload().len(),
+            fcs: None,
+            expected_fcs: None,
+        }
+    }
+}
+impl EthernetPacketInfo {
+    pub const FCS_LENGTH: usize = 4;
+
+    /// The CRC-32 Ethernet puts in the FCS, sent least significant byte first.
+    pub fn crc32(bytes: &[u8]) -> u32 {
+        let mut crc = !0u32;
+        for &byte in bytes {
+            crc ^= u32::from(byte);
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 {
+                    (crc >> 1) ^ 0xedb8_8320
+                } else {
+                    crc >> 1
+                };
+            }
+        }
+        !crc
+    }
+}
+
+/// Capture metadata a WiFi driver in monitor mode puts in front of each 802.11 frame.
+/// Only the fields up to the antenna noise are decoded; the rest are skipped.
+#[derive(Clone)]
+pub struct RadiotapInfo {
+    /// Length of the whole radiotap header; the 802.11 frame starts right after it.
+    pub length: usize,
+    pub flags: u8,
+    /// In units of 500 kb/s.
+    pub rate: Option<u8>,
+    pub frequency: Option<u16>,
+    pub signal_dbm: Option<i8>,
+    pub noise_dbm: Option<i8>,
+}
+impl RadiotapInfo {
+    const HEADER_LENGTH: usize = 8;
+    const EXTENDED_PRESENCE: u32 = 1 << 31;
+    /// Alignment and size of the fields for presence bits 0 to 6: TSFT, flags, rate,
+    /// channel, FHSS, antenna signal and antenna noise.
+    const FIELDS: [(usize, usize); 7] = [(8, 8), (1, 1), (1, 1), (2, 4), (1, 2), (1, 1), (1, 1)];
+    /// Set in the flags when the frame ends with its 4-byte frame check sequence.
+    const FLAG_FCS: u8 = 0x10;
+    pub const FCS_LENGTH: usize = 4;
+
+    pub fn parse(frame: &[u8]) -> Option<Self> {
+        let fixed = frame.get(..Self::HEADER_LENGTH)?;
+        let length = u16::from_le_bytes([fixed[2], fixed[3]]) as usize;
+        if fixed[0] != 0 || length < Self::HEADER_LENGTH {
+            return None;
+        }
+        let header = frame.get(..length)?;
+        let read_u32 = |at: usize| {
+            header
+                .get(at..at + 4)
+                .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        };
+
+        // Further presence words follow while the extension bit is set; the fields come
+        // after the last one.
+        let present = read_u32(4)?;
+        let mut offset = 8;
+        let mut word = present;
+        while word & Self::EXTENDED_PRESENCE != 0 {
+            word = read_u32(offset)?;
+            offset += 4;
+        }
+
+        let mut info = RadiotapInfo {
+            length,
+            flags: 0,
+            rate: None,
+            frequency: None,
+            signal_dbm: None,
+            noise_dbm: None,
+        };
+        for (bit, (align, size)) in Self::FIELDS.into_iter().enumerate() {
+            if present & (1 << bit) == 0 {
+                continue;
+            }
+            offset = offset.next_multiple_of(align);
+            let Some(field) = header.get(offset..offset + size) else {
+                break;
+            };
+            match bit {
+                1 => info.flags = field[0],
+                2 => info.rate = Some(field[0]),
+                3 => info.frequency = Some(u16::from_le_bytes([field[0], field[1]])),
+                5 => info.signal_dbm = Some(field[0] as i8),
+                6 => info.noise_dbm = Some(field[0] as i8),
+                _ => {}
+            }
+            offset += size;
+        }
+        Some(info)
+    }
+
+    pub fn has_fcs(&self) -> bool {
+        self.flags & Self::FLAG_FCS != 0
+    }
+
+    /// The channel number for a frequency in the 2.4, 5 or 6 GHz band.
+    pub fn channel(&self) -> Option<u16> {
+        match self.frequency? {
+            2484 => Some(14),
+            frequency @ 2412..=2472 => Some((frequency - 2407) / 5),
+            frequency @ 5955..=7115 => Some((frequency - 5950) / 5),
+            frequency @ 5000..=5925 => Some((frequency - 5000) / 5),
+            _ => None,
+        }
+    }
+}
+
+/// An IEEE 802.11 (WiFi) frame as seen on a monitor-mode interface.
+#[derive(Clone)]
+pub struct Ieee80211PacketInfo {
+    pub radiotap: Option<RadiotapInfo>,
+    /// 0 for management, 1 for control and 2 for data frames.
+    pub frame_type: u8,
+    pub subtype: u8,
+    pub flags: u8,
+    pub duration: u16,
+    /// Receiver, transmitter, then the BSSID or source/destination depending on the
+    /// To DS and From DS flags; control frames carry only one or two.
+    pub addresses: Vec<MacAddr>,
+    pub sequence: Option<u16>,
+    /// Network name from beacons and probes; empty for hidden networks.
+    pub ssid: Option<String>,
+    /// EtherType of the LLC/SNAP header of an unencrypted data frame.
+    pub ethertype: Option<EtherType>,
+    pub length: usize,
+}
+impl Ieee80211PacketInfo {
+    pub const MANAGEMENT: u8 = 0;
+    pub const CONTROL: u8 = 1;
+    pub const DATA: u8 = 2;
+
+    const FLAG_TO_DS: u8 = 0x01;
+    const FLAG_FROM_DS: u8 = 0x02;
+    const FLAG_PROTECTED: u8 = 0x40;
+    const FLAG_ORDER: u8 = 0x80;
+    const MANAGEMENT_HEADER_LENGTH: usize = 24;
+    const SUBTYPE_PROBE_REQUEST: u8 = 4;
+    const SUBTYPE_PROBE_RESPONSE: u8 = 5;
+    const SUBTYPE_BEACON: u8 = 8;
+    /// Timestamp, beacon interval and capabilities before the tagged parameters.
+    const BEACON_FIXED_LENGTH: usize = 12;
+    const TAG_SSID: u8 = 0;
+    const LLC_SNAP_PREFIXES: [[u8; 6]; 2] = [
+        [0xaa, 0xaa, 0x03, 0x00, 0x00, 0x00],
+        [0xaa, 0xaa, 0x03, 0x00, 0x00, 0xf8],
+    ];
+
+    /// Parses `frame` without its FCS. The offset returned is where the payload of the
+    /// LLC/SNAP header starts, meaningful when `ethertype` is set.
+    pub fn parse(frame: &[u8]) -> Option<(Self, usize)> {
+        let header = frame.get(..4)?;
+        let frame_type = (header[0] >> 2) & 0x03;
+        let subtype = header[0] >> 4;
+        let flags = header[1];
+        let read_mac = |at: usize| {
+            frame.get(at..at + 6).map(|bytes| {
+                MacAddr::new(bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5])
+            })
+        };
+
+        let mut info = Ieee80211PacketInfo {
+            radiotap: None,
+            frame_type,
+            subtype,
+            flags,
+            duration: u16::from_le_bytes([header[2], header[3]]),
+            addresses: Vec::new(),
+            sequence: None,
+            ssid: None,
+            ethertype: None,
+            length: frame.len(),
+        };
+
+        if frame_type == Self::CONTROL {
+            // CTS and ACK only name the receiver.
+            let address_count = if matches!(subtype, 12 | 13) { 1 } else { 2 };
+            info.addresses = (0..address_count)
+                .map_while(|index| read_mac(4 + index * 6))
+                .collect();
+            return Some((info, frame.len()));
+        }
+
+        info.addresses = [4, 10, 16].into_iter().map_while(read_mac).collect();
+        if info.addresses.len() < 3 {
+            return None;
+        }
+        let sequence = frame.get(22..24)?;
+        info.sequence = Some(u16::from_le_bytes([sequence[0], sequence[1]]) >> 4);
+        let mut body = Self::MANAGEMENT_HEADER_LENGTH;
+
+        if frame_type == Self::MANAGEMENT {
+            if flags & Self::FLAG_ORDER != 0 {
+                body += 4;
+            }
+            let tags = match subtype {
+                Self::SUBTYPE_BEACON | Self::SUBTYPE_PROBE_RESPONSE => {
+                    Some(body + Self::BEACON_FIXED_LENGTH)
+                }
+                Self::SUBTYPE_PROBE_REQUEST => Some(body),
+                _ => None,
+            };
+            info.ssid = tags.and_then(|tags| Self::parse_ssid(frame.get(tags..)?));
+            return Some((info, frame.len()));
+        }
+
+        if flags & Self::FLAG_TO_DS != 0 && flags & Self::FLAG_FROM_DS != 0 {
+            info.addresses.extend(read_mac(body));
+            body += 6;
+        }
+        let is_qos = subtype & 0x08 != 0;
+        if is_qos {
+            body += 2;
+            if flags & Self::FLAG_ORDER != 0 {
+                body += 4;
+            }
+        }
+        // Null data frames carry no body, and encrypted bodies can't be read.
+        let has_body = subtype & 0x04 == 0 && flags & Self::FLAG_PROTECTED == 0;
+        if has_body {
+            if let Some(llc) = frame.get(body..body + 8) {
+                if Self::LLC_SNAP_PREFIXES
+                    .iter()
+                    .any(|prefix| llc[..6] == *prefix)
+                {
+                    info.ethertype = Some(EtherType(u16::from_be_bytes([llc[6], llc[7]])));
+                    return Some((info, body + 8));
+                }
+            }
+        }
+        Some((info, frame.len()))
+    }
+
+    fn parse_ssid(mut tags: &[u8]) -> Option<String> {
+        while tags.len() >= 2 {
+            let (id, length) = (tags[0], tags[1] as usize);
+            let value = tags.get(2..2 + length)?;
+            if id == Self::TAG_SSID {
+                return Some(String::from_utf8_lossy(value).into_owned());
+            }
+            tags = &tags[2 + length..];
+        }
+        None
+    }
+
+    pub fn is_protected(&self) -> bool {
+        self.flags & Self::FLAG_PROTECTED != 0
+    }
+
+    pub fn receiver(&self) -> Option<MacAddr> {
+        self.addresses.first().copied()
+    }
+
+    pub fn transmitter(&self) -> Option<MacAddr> {
+        self.addresses.get(1).copied()
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self.frame_type {
+            Self::MANAGEMENT => "Management",
+            Self::CONTROL => "Control",
+            Self::DATA => "Data",
+            _ => "Extension",
+        }
+    }
+
+    pub fn subtype_name(&self) -> &'static str {
+        match (self.frame_type, self.subtype) {
+            (Self::MANAGEMENT, 0) => "Association Request",
+            (Self::MANAGEMENT, 1) => "Association Response",
+            (Self::MANAGEMENT, 2) => "Reassociation Request",
+            (Self::MANAGEMENT, 3) => "Reassociation Response",
+            (Self::MANAGEMENT, 4) => "Probe Request",
+            (Self::MANAGEMENT, 5) => "Probe Response",
+            (Self::MANAGEMENT, 6) => "Timing Advertisement",
+            (Self::MANAGEMENT, 8) => "Beacon",
+            (Self::MANAGEMENT, 9) => "ATIM",
+            (Self::MANAGEMENT, 10) => "Disassociation",
+            (Self::MANAGEMENT, 11) => "Authentication",
+            (Self::MANAGEMENT, 12) => "Deauthentication",
+            (Self::MANAGEMENT, 13) => "Action",
+            (Self::MANAGEMENT, 14) => "Action No Ack",
+            (Self::CONTROL, 4) => "Beamforming Report Poll",
+            (Self::CONTROL, 5) => "NDP Announcement",
+            (Self::CONTROL, 7) => "Control Wrapper",
+            (Self::CONTROL, 8) => "Block Ack Request",
+            (Self::CONTROL, 9) => "Block Ack",
+            (Self::CONTROL, 10) => "PS-Poll",
+            (Self::CONTROL, 11) => "RTS",
+            (Self::CONTROL, 12) => "CTS",
+            (Self::CONTROL, 13) => "ACK",
+            (Self::CONTROL, 14) => "CF-End",
+            (Self::CONTROL, 15) => "CF-End + CF-Ack",
+            (Self::DATA, 4) => "Null",
+            (Self::DATA, 8) => "QoS Data",
+            (Self::DATA, 12) => "QoS Null",
+            (Self::DATA, _) => "Data",
+            _ => "Reserved",
+        }
+    }
+
+    /// Such as `Beacon "HomeNet" ch 6 -47 dBm`.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![self.subtype_name().to_string()];
+        if let Some(ssid) = &self.ssid {
+            parts.push(format!("\"{ssid}\""));
+        }
+        if self.is_protected() {
+            parts.push("[protected]".to_string());
+        }
+        if let Some(radiotap) = &self.radiotap {
+            parts.extend(radiotap.channel().map(|channel| format!("ch {channel}")));
+            parts.extend(radiotap.signal_dbm.map(|signal| format!("{signal} dBm")));
+        }
+        parts.join(" ")
+    }
+}
+
+/// An 802.1Q (or 802.1ad outer) tag sitting between the Ethernet header and the payload.
+#[derive(Clone)]
+pub struct VlanPacketInfo {
+    pub tpid: EtherType,
+    pub priority: u8,
+    pub drop_eligible: bool,
+    pub vlan_id: u16,
+    pub ethertype: EtherType,
+}
+impl VlanPacketInfo {
+    pub const HEADER_LENGTH: usize = 4;
+
+    pub fn is_vlan_ethertype(ethertype: EtherType) -> bool {
+        matches!(
+            ethertype,
+            EtherTypes::Vlan | EtherTypes::PBridge | EtherTypes::QinQ
+        )
+    }
+
+    /// Parses the tag at the start of `payload`; `tpid` is the EtherType that announced it.
+    pub fn parse(payload: &[u8], tpid: EtherType) -> Option<Self> {
+        let header = payload.get(..Self::HEADER_LENGTH)?;
+        let tci = u16::from_be_bytes([header[0], header[1]]);
+        Some(VlanPacketInfo {
+            tpid,
+            priority: (tci >> 13) as u8,
+            drop_eligible: tci & 0x1000 != 0,
+            vlan_id: tci & 0x0fff,
+            ethertype: EtherType(u16::from_be_bytes([header[2], header[3]])),
+        })
+    }
+}
+
+/// One entry of an MPLS label stack.
+#[derive(Clone)]
+pub struct MplsLabel {
+    pub label: u32,
+    pub traffic_class: u8,
+    pub bottom_of_stack: bool,
+    pub ttl: u8,
+}
+
+/// An MPLS label stack, top label first, between the Ethernet header and the payload.
+#[derive(Clone)]
+pub struct MplsPacketInfo {
+    pub ethertype: EtherType,
+    pub labels: Vec<MplsLabel>,
+    /// Bytes after the bottom of the stack.
+    pub length: usize,
+}
+impl MplsPacketInfo {
+    const ENTRY_LENGTH: usize = 4;
+    /// Real stacks rarely go past three or four labels.
+    const MAX_LABELS: usize = 8;
+
+    pub fn is_mpls_ethertype(ethertype: EtherType) -> bool {
+        matches!(ethertype, EtherTypes::Mpls | EtherTypes::MplsMcast)
+    }
+
+    /// Parses the label stack at the start of `payload` up to the entry with the
+    /// bottom-of-stack bit; `ethertype` is the unicast or multicast one that announced it.
+    pub fn parse(payload: &[u8], ethertype: EtherType) -> Option<Self> {
+        let mut labels = Vec::new();
+        for entry in payload
+            .chunks_exact(Self::ENTRY_LENGTH)
+            .take(Self::MAX_LABELS)
+        {
+            let entry = u32::from_be_bytes([entry[0], entry[1], entry[2], entry[3]]);
+            let label = MplsLabel {
+                label: entry >> 12,
+                traffic_class: ((entry >> 9) & 0x7) as u8,
+                bottom_of_stack: entry & 0x100 != 0,
+                ttl: entry as u8,
+            };
+            let bottom_of_stack = label.bottom_of_stack;
+            labels.push(label);
+            if bottom_of_stack {
+                return Some(MplsPacketInfo {
+                    ethertype,
+                    length: payload.len() - labels.len() * Self::ENTRY_LENGTH,
+                    labels,
+                });
+            }
+        }
+        None
+    }
+
+    pub fn header_length(&self) -> usize {
+        self.labels.len() * Self::ENTRY_LENGTH
+    }
+}
+
+/// An ARP address. Only 6-byte hardware and 4-byte IPv4 protocol addresses are decoded;
+/// any other length is kept as raw bytes.
+#[derive(Clone, PartialEq)]
+pub enum ArpAddress {
+    Mac(MacAddr),
+    Ipv4(Ipv4Addr),
+    Raw(Vec<u8>),
+}
+
+impl ArpAddress {
+    pub fn is_raw(&self) -> bool {
+        matches!(self, ArpAddress::Raw(_))
+    }
+}
+
+impl std::fmt::Display for ArpAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArpAddress::Mac(mac) => write!(f, "{mac}"),
+            ArpAddress::Ipv4(ip) => write!(f, "{ip}"),
+            ArpAddress::Raw(bytes) => {
+                let hex: Vec<String> = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+                write!(f, "{}", hex.join(":"))
+            }
         }
     }
 }
@@ -127,28 +968,196 @@ pub struct ArpPacketInfo {
     pub hw_addr_len: u8,
     pub proto_addr_len: u8,
     pub operation: ArpOperation,
-    pub sender_hw_addr: MacAddr,
-    pub sender_proto_addr: Ipv4Addr,
-    pub target_hw_addr: MacAddr,
-    pub target_proto_addr: Ipv4Addr,
+    pub sender_hw_addr: ArpAddress,
+    pub sender_proto_addr: ArpAddress,
+    pub target_hw_addr: ArpAddress,
+    pub target_proto_addr: ArpAddress,
     pub length: usize,
 }
+impl ArpPacketInfo {
+    /// Fixed part of the header, before the variable-length addresses.
+    const HEADER_LENGTH: usize = 8;
+
+    /// Reads the address at `offset`, truncated if the packet ends early.
+    fn address(
+        bytes: &[u8],
+        offset: usize,
+        len: u8,
+        kind: fn(&[u8]) -> Option<ArpAddress>,
+    ) -> ArpAddress {
+        let end = (offset + len as usize).min(bytes.len());
+        let address = bytes.get(offset..end).unwrap_or_default();
+        if address.len() == len as usize {
+            if let Some(address) = kind(address) {
+                return address;
+            }
+        }
+        ArpAddress::Raw(address.to_vec())
+    }
+
+    fn hardware_address(bytes: &[u8]) -> Option<ArpAddress> {
+        let bytes: [u8; 6] = bytes.try_into().ok()?;
+        Some(ArpAddress::Mac(MacAddr::from(bytes)))
+    }
+
+    fn ipv4_address(bytes: &[u8]) -> Option<ArpAddress> {
+        let bytes: [u8; 4] = bytes.try_into().ok()?;
+        Some(ArpAddress::Ipv4(Ipv4Addr::from(bytes)))
+    }
+}
 impl<'p> From<&ArpPacket<'p>> for ArpPacketInfo {
+    /// pnet always reads 6-byte and 4-byte addresses, so they are taken from the raw
+    /// packet using the lengths the header declares instead.
     fn from(packet: &ArpPacket) -> Self {
+        let bytes = packet.packet();
+        let hw_addr_len = packet.get_hw_addr_len();
+        let proto_addr_len = packet.get_proto_addr_len();
+        let protocol_address: fn(&[u8]) -> Option<ArpAddress> =
+            if packet.get_protocol_type() == EtherTypes::Ipv4 {
+                Self::ipv4_address
+            } else {
+                |_| None
+            };
+        let sender_hw_offset = Self::HEADER_LENGTH;
+        let sender_proto_offset = sender_hw_offset + hw_addr_len as usize;
+        let target_hw_offset = sender_proto_offset + proto_addr_len as usize;
+        let target_proto_offset = target_hw_offset + hw_addr_len as usize;
         ArpPacketInfo {
             hardware_type: packet.get_hardware_type(),
             protocol_type: packet.get_protocol_type(),
-            hw_addr_len: packet.get_hw_addr_len(),
-            proto_addr_len: packet.get_proto_addr_len(),
+            hw_addr_len,
+            proto_addr_len,
             operation: packet.get_operation(),
-            sender_hw_addr: packet.get_sender_hw_addr(),
-            sender_proto_addr: packet.get_sender_proto_addr(),
-            target_hw_addr: packet.get_target_hw_addr(),
-            target_proto_addr: packet.get_target_proto_addr(),
+            sender_hw_addr: Self::address(
+                bytes,
+                sender_hw_offset,
+                hw_addr_len,
+                Self::hardware_address,
+            ),
+            sender_proto_addr: Self::address(
+                bytes,
+                sender_proto_offset,
+                proto_addr_len,
+                protocol_address,
+            ),
+            target_hw_addr: Self::address(
+                bytes,
+                target_hw_offset,
+                hw_addr_len,
+                Self::hardware_address,
+            ),
+            target_proto_addr: Self::address(
+                bytes,
+                target_proto_offset,
+                proto_addr_len,
+                protocol_address,
+            ),
             length: packet.payload().len(),
         }
     }
 }
+#[derive(Clone)]
+pub struct LldpPacketInfo {
+    pub chassis_id: Option<String>,
+    pub port_id: Option<String>,
+    pub ttl: Option<u16>,
+    pub port_description: Option<String>,
+    pub system_name: Option<String>,
+    pub system_description: Option<String>,
+    pub system_capabilities: Option<u16>,
+    pub enabled_capabilities: Option<u16>,
+    pub length: usize,
+}
+impl LldpPacketInfo {
+    const TLV_END: u8 = 0;
+    const TLV_CHASSIS_ID: u8 = 1;
+    const TLV_PORT_ID: u8 = 2;
+    const TLV_TTL: u8 = 3;
+    const TLV_PORT_DESCRIPTION: u8 = 4;
+    const TLV_SYSTEM_NAME: u8 = 5;
+    const TLV_SYSTEM_DESCRIPTION: u8 = 6;
+    const TLV_SYSTEM_CAPABILITIES: u8 = 7;
+
+    const CHASSIS_SUBTYPE_MAC: u8 = 4;
+    const PORT_SUBTYPE_MAC: u8 = 3;
+
+    pub fn parse(payload: &[u8]) -> Option<Self> {
+        if payload.len() < 2 {
+            return None;
+        }
+
+        let mut info = LldpPacketInfo {
+            chassis_id: None,
+            port_id: None,
+            ttl: None,
+            port_description: None,
+            system_name: None,
+            system_description: None,
+            system_capabilities: None,
+            enabled_capabilities: None,
+            length: payload.len(),
+        };
+
+        let mut offset = 0;
+        while offset + 2 <= payload.len() {
+            let header = u16::from_be_bytes([payload[offset], payload[offset + 1]]);
+            let tlv_type = (header >> 9) as u8;
+            let tlv_length = (header & 0x01ff) as usize;
+            offset += 2;
+
+            if tlv_type == Self::TLV_END {
+                break;
+            }
+            let Some(value) = payload.get(offset..offset + tlv_length) else {
+                break;
+            };
+            offset += tlv_length;
+
+            match tlv_type {
+                Self::TLV_CHASSIS_ID => {
+                    info.chassis_id = Self::parse_id(value, Self::CHASSIS_SUBTYPE_MAC)
+                }
+                Self::TLV_PORT_ID => info.port_id = Self::parse_id(value, Self::PORT_SUBTYPE_MAC),
+                Self::TLV_TTL if value.len() >= 2 => {
+                    info.ttl = Some(u16::from_be_bytes([value[0], value[1]]))
+                }
+                Self::TLV_PORT_DESCRIPTION => info.port_description = Some(Self::parse_text(value)),
+                Self::TLV_SYSTEM_NAME => info.system_name = Some(Self::parse_text(value)),
+                Self::TLV_SYSTEM_DESCRIPTION => {
+                    info.system_description = Some(Self::parse_text(value))
+                }
+                Self::TLV_SYSTEM_CAPABILITIES if value.len() >= 4 => {
+                    info.system_capabilities = Some(u16::from_be_bytes([value[0], value[1]]));
+                    info.enabled_capabilities = Some(u16::from_be_bytes([value[2], value[3]]));
+                }
+                _ => {}
+            }
+        }
+
+        Some(info)
+    }
+
+    fn parse_id(value: &[u8], mac_subtype: u8) -> Option<String> {
+        let (&subtype, id) = value.split_first()?;
+        if subtype == mac_subtype && id.len() == 6 {
+            Some(MacAddr::new(id[0], id[1], id[2], id[3], id[4], id[5]).to_string())
+        } else {
+            Some(Self::parse_text(id))
+        }
+    }
+
+    fn parse_text(value: &[u8]) -> String {
+        if value
+            .iter()
+            .all(|byte| byte.is_ascii_graphic() || *byte == b' ')
+        {
+            String::from_utf8_lossy(value).into_owned()
+        } else {
+            value.iter().map(|byte| format!("{byte:02x}")).collect()
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Ipv6PacketInfo {
     pub version: u8,
@@ -190,6 +1199,8 @@ pub struct Ipv4PacketInfo {
     pub ttl: u8,
     pub next_level_protocol: IpNextHeaderProtocol,
     pub checksum: u16,
+    /// Header checksum recomputed from the captured bytes; None when the header is cut short.
+    pub expected_checksum: Option<u16>,
     pub source: Ipv4Addr,
     pub destination: Ipv4Addr,
     pub length: usize,
@@ -208,6 +1219,8 @@ impl<'a> From<&Ipv4Packet<'a>> for Ipv4PacketInfo {
             ttl: packet.get_ttl(),
             next_level_protocol: packet.get_next_level_protocol(),
             checksum: packet.get_checksum(),
+            expected_checksum: (packet.packet().len() >= packet.get_header_length() as usize * 4)
+                .then(|| ipv4::checksum(packet)),
             source: packet.get_source(),
             destination: packet.get_destination(),
             length: packet.payload().len(),
@@ -215,33 +1228,894 @@ impl<'a> From<&Ipv4Packet<'a>> for Ipv4PacketInfo {
     }
 }
 
+impl Ipv4PacketInfo {
+    pub fn more_fragments(&self) -> bool {
+        self.flags & Ipv4Flags::MoreFragments != 0
+    }
+
+    /// Part of a fragmented datagram: more fragments follow, or this one isn't the first.
+    pub fn is_fragment(&self) -> bool {
+        self.more_fragments() || self.fragment_offset != 0
+    }
+
+    /// Offset of the payload in the original datagram, in bytes.
+    pub fn fragment_offset_bytes(&self) -> usize {
+        self.fragment_offset as usize * 8
+    }
+
+    /// The standard name of the DSCP codepoint: class selectors (RFC 2474), assured
+    /// forwarding (RFC 2597), expedited forwarding (RFC 3246) and lower effort (RFC 8622).
+    pub fn dscp_name(&self) -> Option<&'static str> {
+        Some(match self.dscp {
+            0 => "Default",
+            1 => "LE",
+            8 => "CS1",
+            10 => "AF11",
+            12 => "AF12",
+            14 => "AF13",
+            16 => "CS2",
+            18 => "AF21",
+            20 => "AF22",
+            22 => "AF23",
+            24 => "CS3",
+            26 => "AF31",
+            28 => "AF32",
+            30 => "AF33",
+            32 => "CS4",
+            34 => "AF41",
+            36 => "AF42",
+            38 => "AF43",
+            40 => "CS5",
+            44 => "VOICE-ADMIT",
+            46 => "EF",
+            48 => "CS6",
+            56 => "CS7",
+            _ => return None,
+        })
+    }
+
+    /// The ECN state (RFC 3168): whether the endpoints support congestion notification
+    /// and whether a router marked congestion.
+    pub fn ecn_name(&self) -> &'static str {
+        match self.ecn & 0x03 {
+            0 => "Not-ECT",
+            1 => "ECT(1)",
+            2 => "ECT(0)",
+            _ => "CE",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SctpChunk {
+    pub chunk_type: u8,
+    pub flags: u8,
+    pub length: u16,
+}
+
+#[derive(Clone)]
+pub struct SctpPacketInfo {
+    pub source: u16,
+    pub destination: u16,
+    pub verification_tag: u32,
+    pub checksum: u32,
+    pub chunks: Vec<SctpChunk>,
+    pub length: usize,
+}
+impl SctpPacketInfo {
+    const HEADER_LENGTH: usize = 12;
+    const CHUNK_HEADER_LENGTH: usize = 4;
+
+    pub fn parse(payload: &[u8]) -> Option<Self> {
+        let header = payload.get(..Self::HEADER_LENGTH)?;
+
+        let mut chunks = Vec::new();
+        let mut offset = Self::HEADER_LENGTH;
+        while let Some(chunk_header) = payload.get(offset..offset + Self::CHUNK_HEADER_LENGTH) {
+            let length = u16::from_be_bytes([chunk_header[2], chunk_header[3]]);
+            if (length as usize) < Self::CHUNK_HEADER_LENGTH {
+                break;
+            }
+            chunks.push(SctpChunk {
+                chunk_type: chunk_header[0],
+                flags: chunk_header[1],
+                length,
+            });
+            // Chunks are padded to a multiple of four bytes.
+            offset += (length as usize).next_multiple_of(4);
+        }
+
+        Some(SctpPacketInfo {
+            source: u16::from_be_bytes([header[0], header[1]]),
+            destination: u16::from_be_bytes([header[2], header[3]]),
+            verification_tag: u32::from_be_bytes([header[4], header[5], header[6], header[7]]),
+            checksum: u32::from_be_bytes([header[8], header[9], header[10], header[11]]),
+            chunks,
+            length: payload.len(),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct DnsQuestion {
+    pub name: String,
+    pub record_type: u16,
+}
+
+#[derive(Clone)]
+pub struct DnsRecord {
+    pub name: String,
+    pub record_type: u16,
+    pub ttl: u32,
+    pub data: String,
+}
+
+#[derive(Clone)]
+pub struct DnsPacketInfo {
+    pub id: u16,
+    pub flags: u16,
+    pub is_response: bool,
+    pub opcode: u8,
+    pub rcode: u8,
+    pub questions: Vec<DnsQuestion>,
+    pub answers: Vec<DnsRecord>,
+    pub authority_count: u16,
+    pub additional_count: u16,
+    pub over_tcp: bool,
+    pub length: usize,
+}
+impl DnsPacketInfo {
+    pub const PORT: u16 = 53;
+    /// Multicast DNS uses the same message format on its own port.
+    pub const MDNS_PORT: u16 = 5353;
+
+    const HEADER_LENGTH: usize = 12;
+    const MAX_POINTER_JUMPS: usize = 16;
+    const MAX_RECORDS: u16 = 64;
+
+    pub fn parse(message: &[u8]) -> Option<Self> {
+        if message.len() < Self::HEADER_LENGTH {
+            return None;
+        }
+
+        let read_u16 = |offset: usize| u16::from_be_bytes([message[offset], message[offset + 1]]);
+        let flags = read_u16(2);
+        let question_count = read_u16(4);
+        let answer_count = read_u16(6);
+        if question_count > Self::MAX_RECORDS || answer_count > Self::MAX_RECORDS {
+            return None;
+        }
+
+        let mut offset = Self::HEADER_LENGTH;
+        let mut questions = Vec::new();
+        for _ in 0..question_count {
+            let (name, next) = Self::parse_name(message, offset)?;
+            let record_type = u16::from_be_bytes([*message.get(next)?, *message.get(next + 1)?]);
+            // QTYPE and QCLASS.
+            offset = next + 4;
+            if offset > message.len() {
+                return None;
+            }
+            questions.push(DnsQuestion { name, record_type });
+        }
+
+        let mut answers = Vec::new();
+        for _ in 0..answer_count {
+            match Self::parse_record(message, offset) {
+                Some((record, next)) => {
+                    answers.push(record);
+                    offset = next;
+                }
+                None => break,
+            }
+        }
+
+        Some(DnsPacketInfo {
+            id: read_u16(0),
+            flags,
+            is_response: flags & 0x8000 != 0,
+            opcode: ((flags >> 11) & 0x0f) as u8,
+            rcode: (flags & 0x000f) as u8,
+            questions,
+            answers,
+            authority_count: read_u16(8),
+            additional_count: read_u16(10),
+            over_tcp: false,
+            length: message.len(),
+        })
+    }
+
+    /// DNS over TCP prefixes each message with its 2-byte length. Only messages that
+    /// fit entirely in this segment are decoded.
+    pub fn parse_tcp(payload: &[u8]) -> Option<Self> {
+        if payload.len() < 2 {
+            return None;
+        }
+        let message_length = u16::from_be_bytes([payload[0], payload[1]]) as usize;
+        let message = payload.get(2..2 + message_length)?;
+
+        let mut info = Self::parse(message)?;
+        info.over_tcp = true;
+        Some(info)
+    }
+
+    fn parse_record(message: &[u8], offset: usize) -> Option<(DnsRecord, usize)> {
+        let (name, offset) = Self::parse_name(message, offset)?;
+        let fixed = message.get(offset..offset + 10)?;
+        let record_type = u16::from_be_bytes([fixed[0], fixed[1]]);
+        let ttl = u32::from_be_bytes([fixed[4], fixed[5], fixed[6], fixed[7]]);
+        let data_length = u16::from_be_bytes([fixed[8], fixed[9]]) as usize;
+        let data_offset = offset + 10;
+        let data = message.get(data_offset..data_offset + data_length)?;
+
+        let data = match (record_type, data.len()) {
+            // A
+            (1, 4) => Ipv4Addr::new(data[0], data[1], data[2], data[3]).to_string(),
+            // AAAA
+            (28, 16) => {
+                let octets: [u8; 16] = data.try_into().ok()?;
+                Ipv6Addr::from(octets).to_string()
+            }
+            // NS, CNAME, PTR
+            (2 | 5 | 12, _) => Self::parse_name(message, data_offset)?.0,
+            // MX
+            (15, 3..) => format!(
+                "{} {}",
+                u16::from_be_bytes([data[0], data[1]]),
+                Self::parse_name(message, data_offset + 2)?.0
+            ),
+            // TXT
+            (16, _) => String::from_utf8_lossy(data.get(1..).unwrap_or_default()).into_owned(),
+            _ => format!("{} bytes", data.len()),
+        };
+
+        Some((
+            DnsRecord {
+                name,
+                record_type,
+                ttl,
+                data,
+            },
+            data_offset + data_length,
+        ))
+    }
+
+    /// Reads a possibly compressed domain name, returning it and the offset right after
+    /// it in the original (uncompressed) position.
+    fn parse_name(message: &[u8], mut offset: usize) -> Option<(String, usize)> {
+        let mut labels = Vec::new();
+        let mut end = None;
+        let mut jumps = 0;
+
+        loop {
+            let length = *message.get(offset)? as usize;
+            match length {
+                0 => {
+                    end.get_or_insert(offset + 1);
+                    break;
+                }
+                _ if length & 0xc0 == 0xc0 => {
+                    let pointer = ((length & 0x3f) << 8) | *message.get(offset + 1)? as usize;
+                    end.get_or_insert(offset + 2);
+                    jumps += 1;
+                    if jumps > Self::MAX_POINTER_JUMPS {
+                        return None;
+                    }
+                    offset = pointer;
+                }
+                _ => {
+                    let label = message.get(offset + 1..offset + 1 + length)?;
+                    labels.push(String::from_utf8_lossy(label).into_owned());
+                    offset += 1 + length;
+                }
+            }
+        }
+
+        let name = if labels.is_empty() {
+            "<root>".to_string()
+        } else {
+            labels.join(".")
+        };
+        Some((name, end?))
+    }
+}
+
+/// A VXLAN header, with the layers of the Ethernet frame it carries over UDP.
+#[derive(Clone)]
+pub struct VxlanPacketInfo {
+    pub flags: u8,
+    /// VXLAN Network Identifier, the 24-bit segment the frame belongs to.
+    pub vni: u32,
+    /// Length of the encapsulated frame.
+    pub length: usize,
+    /// The tunneled frame's layers, Ethernet first.
+    pub inner: Vec<PacketsData>,
+}
+impl VxlanPacketInfo {
+    pub const PORT: u16 = 4789;
+
+    const HEADER_LENGTH: usize = 8;
+    /// Set when the VNI is valid, as RFC 7348 requires.
+    const VNI_FLAG: u8 = 0x08;
+
+    /// Parses the header at the start of a UDP payload, returning it with the offset of
+    /// the encapsulated frame. `inner` is left for the caller to fill.
+    pub fn parse(payload: &[u8]) -> Option<(Self, usize)> {
+        let header = payload.get(..Self::HEADER_LENGTH)?;
+        if header[0] & Self::VNI_FLAG == 0 {
+            return None;
+        }
+        Some((
+            VxlanPacketInfo {
+                flags: header[0],
+                vni: u32::from_be_bytes([0, header[4], header[5], header[6]]),
+                length: payload.len() - Self::HEADER_LENGTH,
+                inner: Vec::new(),
+            },
+            Self::HEADER_LENGTH,
+        ))
+    }
+}
+
+/// A GTP-U header, with the layers of the subscriber packet it tunnels.
+#[derive(Clone)]
+pub struct GtpPacketInfo {
+    pub version: u8,
+    pub message_type: u8,
+    /// Bytes after the first 8 of the header: optional fields, extensions and payload.
+    pub length: u16,
+    pub teid: u32,
+    pub sequence: Option<u16>,
+    /// The tunneled IP packet, transport and application layers, outermost first; empty
+    /// for messages other than G-PDUs.
+    pub inner: Vec<PacketsData>,
+}
+impl GtpPacketInfo {
+    pub const PORT: u16 = 2152;
+    /// Message type of a G-PDU, the one carrying subscriber traffic.
+    pub const G_PDU: u8 = 255;
+
+    const HEADER_LENGTH: usize = 8;
+    const OPTIONAL_FIELDS_LENGTH: usize = 4;
+    const PROTOCOL_TYPE: u8 = 0x10;
+    const EXTENSION_HEADER: u8 = 0x04;
+    const SEQUENCE_NUMBER: u8 = 0x02;
+    const OPTIONAL_FIELDS: u8 = 0x07;
+
+    /// Parses the header at the start of a UDP payload, returning it with the offset
+    /// of the tunneled packet. `inner` is left for the caller to fill.
+    pub fn parse(payload: &[u8]) -> Option<(Self, usize)> {
+        let header = payload.get(..Self::HEADER_LENGTH)?;
+        let flags = header[0];
+        let version = flags >> 5;
+        // GTP' shares the port but clears the protocol type bit.
+        if version != 1 || flags & Self::PROTOCOL_TYPE == 0 {
+            return None;
+        }
+
+        let mut offset = Self::HEADER_LENGTH;
+        let mut sequence = None;
+        if flags & Self::OPTIONAL_FIELDS != 0 {
+            let optional = payload.get(offset..offset + Self::OPTIONAL_FIELDS_LENGTH)?;
+            if flags & Self::SEQUENCE_NUMBER != 0 {
+                sequence = Some(u16::from_be_bytes([optional[0], optional[1]]));
+            }
+            let mut next_extension = if flags & Self::EXTENSION_HEADER != 0 {
+                optional[3]
+            } else {
+                0
+            };
+            offset += Self::OPTIONAL_FIELDS_LENGTH;
+            while next_extension != 0 {
+                // Counted in 4-byte units, including the length and next type bytes.
+                let extension_length = *payload.get(offset)? as usize * 4;
+                if extension_length == 0 {
+                    return None;
+                }
+                next_extension = *payload.get(offset + extension_length - 1)?;
+                offset += extension_length;
+            }
+        }
+
+        Some((
+            GtpPacketInfo {
+                version,
+                message_type: header[1],
+                length: u16::from_be_bytes([header[2], header[3]]),
+                teid: u32::from_be_bytes([header[4], header[5], header[6], header[7]]),
+                sequence,
+                inner: Vec::new(),
+            },
+            offset,
+        ))
+    }
+}
+
+#[derive(Clone)]
+pub struct HttpPacketInfo {
+    pub method: Option<String>,
+    pub path: Option<String>,
+    pub version: String,
+    pub status_code: Option<u16>,
+    pub reason: Option<String>,
+    pub host: Option<String>,
+    pub length: usize,
+}
+impl HttpPacketInfo {
+    pub const PORT: u16 = 80;
+
+    const METHODS: [&'static str; 9] = [
+        "GET", "HEAD", "POST", "PUT", "DELETE", "CONNECT", "OPTIONS", "TRACE", "PATCH",
+    ];
+
+    /// Parses the start line and Host header of a request or response that begins in this
+    /// segment. Messages split across segments are not reassembled.
+    pub fn parse(payload: &[u8]) -> Option<Self> {
+        let head_end = payload
+            .windows(4)
+            .position(|window| window == b"\r\n\r\n")
+            .unwrap_or(payload.len());
+        let head = String::from_utf8_lossy(&payload[..head_end]);
+        let mut lines = head.lines();
+        let mut start_line = lines.next()?.splitn(3, ' ');
+        let first = start_line.next()?;
+
+        let mut info = HttpPacketInfo {
+            method: None,
+            path: None,
+            version: String::new(),
+            status_code: None,
+            reason: None,
+            host: None,
+            length: payload.len(),
+        };
+
+        if first.starts_with("HTTP/") {
+            info.version = first.to_string();
+            info.status_code = Some(start_line.next()?.parse().ok()?);
+            info.reason = start_line.next().map(str::to_string);
+        } else if Self::METHODS.contains(&first) {
+            info.method = Some(first.to_string());
+            info.path = Some(start_line.next()?.to_string());
+            info.version = start_line.next()?.to_string();
+            if !info.version.starts_with("HTTP/") {
+                return None;
+            }
+        } else {
+            return None;
+        }
+
+        info.host = lines.find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("host")
+                .then(|| value.trim().to_string())
+        });
+
+        Some(info)
+    }
+}
+
+/// Simple Service Discovery Protocol, UPnP's way of announcing and finding devices:
+/// HTTP-style messages sent over UDP multicast.
+#[derive(Clone)]
+pub struct SsdpPacketInfo {
+    /// NOTIFY or M-SEARCH; search responses have a status code instead.
+    pub method: Option<String>,
+    pub status_code: Option<u16>,
+    /// What is announced (NT) or searched for (ST).
+    pub target: Option<String>,
+    /// ssdp:alive, ssdp:byebye or ssdp:update, for announcements.
+    pub notification_sub_type: Option<String>,
+    pub usn: Option<String>,
+    pub location: Option<String>,
+    pub server: Option<String>,
+    pub length: usize,
+}
+impl SsdpPacketInfo {
+    pub const PORT: u16 = 1900;
+
+    const METHODS: [&'static str; 2] = ["NOTIFY", "M-SEARCH"];
+
+    pub fn parse(payload: &[u8]) -> Option<Self> {
+        let text = String::from_utf8_lossy(payload);
+        let mut lines = text.lines();
+        let mut start_line = lines.next()?.splitn(3, ' ');
+        let first = start_line.next()?;
+
+        let mut info = SsdpPacketInfo {
+            method: None,
+            status_code: None,
+            target: None,
+            notification_sub_type: None,
+            usn: None,
+            location: None,
+            server: None,
+            length: payload.len(),
+        };
+
+        if first.starts_with("HTTP/") {
+            info.status_code = Some(start_line.next()?.parse().ok()?);
+        } else if Self::METHODS.contains(&first) {
+            start_line.next()?;
+            if !start_line.next()?.starts_with("HTTP/") {
+                return None;
+            }
+            info.method = Some(first.to_string());
+        } else {
+            return None;
+        }
+
+        for line in lines.take_while(|line| !line.is_empty()) {
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = Some(value.trim().to_string());
+            match name.trim().to_ascii_uppercase().as_str() {
+                "NT" | "ST" => info.target = value,
+                "NTS" => info.notification_sub_type = value,
+                "USN" => info.usn = value,
+                "LOCATION" => info.location = value,
+                "SERVER" => info.server = value,
+                _ => {}
+            }
+        }
+
+        Some(info)
+    }
+
+    /// Such as "NOTIFY ssdp:alive upnp:rootdevice" or "200 urn:dial-multiscreen-org:service:dial:1".
+    pub fn summary(&self) -> String {
+        let start = match (&self.method, self.status_code) {
+            (Some(method), _) => method.clone(),
+            (None, status_code) => status_code.unwrap_or_default().to_string(),
+        };
+        [
+            Some(start),
+            self.notification_sub_type.clone(),
+            self.target.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
+/// The identification string both sides of an SSH connection send in the clear before
+/// the key exchange (RFC 4253): "SSH-protoversion-softwareversion comments". Everything
+/// after it is encrypted.
+#[derive(Clone)]
+pub struct SshPacketInfo {
+    pub protocol_version: String,
+    /// The implementation, such as "OpenSSH_9.6p1" or "dropbear_2022.83".
+    pub software_version: String,
+    pub comments: Option<String>,
+    /// Whether the server (port 22) or the client sent the banner; unknown on other ports.
+    pub from_server: Option<bool>,
+    pub length: usize,
+}
+
+impl SshPacketInfo {
+    pub const PORT: u16 = 22;
+    /// Including the CR LF, which the RFC limits the line to.
+    const MAX_BANNER_LENGTH: usize = 255;
+
+    pub fn parse(payload: &[u8]) -> Option<Self> {
+        let line = payload.split(|&byte| byte == b'\n').next()?;
+        if line.len() > Self::MAX_BANNER_LENGTH {
+            return None;
+        }
+        let line = std::str::from_utf8(line).ok()?.trim_end_matches('\r');
+        let (identification, comments) = match line.strip_prefix("SSH-")?.split_once(' ') {
+            Some((identification, comments)) => (identification, Some(comments.to_string())),
+            None => (line.strip_prefix("SSH-")?, None),
+        };
+        let (protocol_version, software_version) = identification.split_once('-')?;
+        if protocol_version.is_empty() || software_version.is_empty() {
+            return None;
+        }
+        Some(SshPacketInfo {
+            protocol_version: protocol_version.to_string(),
+            software_version: software_version.to_string(),
+            comments,
+            from_server: None,
+            length: payload.len(),
+        })
+    }
+
+    /// Such as "server SSH-2.0-OpenSSH_9.6p1 Ubuntu-3ubuntu13".
+    pub fn summary(&self) -> String {
+        let mut summary = format!("SSH-{}-{}", self.protocol_version, self.software_version);
+        if let Some(comments) = &self.comments {
+            summary.push_str(&format!(" {comments}"));
+        }
+        match self.from_server {
+            Some(true) => format!("server {summary}"),
+            Some(false) => format!("client {summary}"),
+            None => summary,
+        }
+    }
+}
+
+/// Remote Authentication Dial-In User Service, used by switches, access points and VPN
+/// gateways to ask a server whether a user may connect, and to report accounting.
+#[derive(Clone)]
+pub struct RadiusPacketInfo {
+    pub code: u8,
+    /// Matches a reply to its request.
+    pub identifier: u8,
+    /// The packet length announced in the header.
+    pub declared_length: u16,
+    pub authenticator: [u8; 16],
+    pub attributes: Vec<RadiusAttribute>,
+    /// Attributes were cut short by a length field running past the packet.
+    pub malformed_attributes: bool,
+    pub length: usize,
+}
+
+/// One attribute TLV. The values of password and authenticator attributes are not kept,
+/// only their length, so secrets never reach the screen or the clipboard.
+#[derive(Clone)]
+pub struct RadiusAttribute {
+    pub attribute_type: u8,
+    pub value: Vec<u8>,
+    pub value_length: usize,
+}
+
+impl RadiusPacketInfo {
+    pub const AUTH_PORT: u16 = 1812;
+    pub const ACCOUNTING_PORT: u16 = 1813;
+    const HEADER_LENGTH: usize = 20;
+
+    pub fn parse(payload: &[u8]) -> Option<Self> {
+        let header = payload.get(..Self::HEADER_LENGTH)?;
+        let code = header[0];
+        let declared_length = u16::from_be_bytes([header[2], header[3]]);
+        if Self::code_name(code).is_none() || (declared_length as usize) < Self::HEADER_LENGTH {
+            return None;
+        }
+        let end = (declared_length as usize).min(payload.len());
+
+        let mut attributes = Vec::new();
+        let mut malformed_attributes = false;
+        let mut rest = &payload[Self::HEADER_LENGTH..end];
+        while !rest.is_empty() {
+            let Some(&[attribute_type, length]) = rest.get(..2) else {
+                malformed_attributes = true;
+                break;
+            };
+            let length = length as usize;
+            if length < 2 || length > rest.len() {
+                malformed_attributes = true;
+                break;
+            }
+            let value = &rest[2..length];
+            attributes.push(RadiusAttribute {
+                attribute_type,
+                value: if RadiusAttribute::is_secret(attribute_type) {
+                    Vec::new()
+                } else {
+                    value.to_vec()
+                },
+                value_length: value.len(),
+            });
+            rest = &rest[length..];
+        }
+
+        Some(RadiusPacketInfo {
+            code,
+            identifier: header[1],
+            declared_length,
+            authenticator: header[4..Self::HEADER_LENGTH].try_into().ok()?,
+            attributes,
+            malformed_attributes,
+            length: payload.len(),
+        })
+    }
+
+    pub fn code_name(code: u8) -> Option<&'static str> {
+        Some(match code {
+            1 => "Access-Request",
+            2 => "Access-Accept",
+            3 => "Access-Reject",
+            4 => "Accounting-Request",
+            5 => "Accounting-Response",
+            11 => "Access-Challenge",
+            12 => "Status-Server",
+            13 => "Status-Client",
+            40 => "Disconnect-Request",
+            41 => "Disconnect-ACK",
+            42 => "Disconnect-NAK",
+            43 => "CoA-Request",
+            44 => "CoA-ACK",
+            45 => "CoA-NAK",
+            _ => return None,
+        })
+    }
+
+    pub fn user_name(&self) -> Option<String> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.attribute_type == RadiusAttribute::USER_NAME)
+            .map(RadiusAttribute::display_value)
+    }
+
+    /// Such as "Access-Request id=12 user=alice".
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} id={}",
+            Self::code_name(self.code).unwrap_or("Unknown"),
+            self.identifier
+        );
+        if let Some(user_name) = self.user_name() {
+            summary.push_str(&format!(" user={user_name}"));
+        }
+        summary
+    }
+}
+
+impl RadiusAttribute {
+    const USER_NAME: u8 = 1;
+    const USER_PASSWORD: u8 = 2;
+    const CHAP_PASSWORD: u8 = 3;
+    const TUNNEL_PASSWORD: u8 = 69;
+    const MESSAGE_AUTHENTICATOR: u8 = 80;
+
+    fn is_secret(attribute_type: u8) -> bool {
+        matches!(
+            attribute_type,
+            Self::USER_PASSWORD
+                | Self::CHAP_PASSWORD
+                | Self::TUNNEL_PASSWORD
+                | Self::MESSAGE_AUTHENTICATOR
+        )
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self.attribute_type {
+            1 => "User-Name",
+            2 => "User-Password",
+            3 => "CHAP-Password",
+            4 => "NAS-IP-Address",
+            5 => "NAS-Port",
+            6 => "Service-Type",
+            7 => "Framed-Protocol",
+            8 => "Framed-IP-Address",
+            11 => "Filter-Id",
+            18 => "Reply-Message",
+            24 => "State",
+            25 => "Class",
+            26 => "Vendor-Specific",
+            27 => "Session-Timeout",
+            30 => "Called-Station-Id",
+            31 => "Calling-Station-Id",
+            32 => "NAS-Identifier",
+            40 => "Acct-Status-Type",
+            44 => "Acct-Session-Id",
+            61 => "NAS-Port-Type",
+            69 => "Tunnel-Password",
+            79 => "EAP-Message",
+            80 => "Message-Authenticator",
+            95 => "NAS-IPv6-Address",
+            _ => "Unknown",
+        }
+    }
+
+    /// Text, addresses and numbers decoded by attribute type; anything else in hex.
+    pub fn display_value(&self) -> String {
+        if Self::is_secret(self.attribute_type) {
+            return format!("({} bytes hidden)", self.value_length);
+        }
+        let value = self.value.as_slice();
+        match (self.attribute_type, value) {
+            (1 | 11 | 18 | 30 | 31 | 32 | 44, _) => String::from_utf8_lossy(value).into_owned(),
+            (4 | 8, &[a, b, c, d]) => Ipv4Addr::new(a, b, c, d).to_string(),
+            (5 | 6 | 7 | 27 | 40 | 61, &[a, b, c, d]) => {
+                u32::from_be_bytes([a, b, c, d]).to_string()
+            }
+            (95, _) => <[u8; 16]>::try_from(value)
+                .map(|octets| Ipv6Addr::from(octets).to_string())
+                .unwrap_or_else(|_| Self::hex(value)),
+            _ => Self::hex(value),
+        }
+    }
+
+    fn hex(value: &[u8]) -> String {
+        value.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
+
 #[derive(Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum PacketsData {
     EthernetPacket(EthernetPacketInfo),
+    Ieee80211Packet(Ieee80211PacketInfo),
     ArpPacket(ArpPacketInfo),
+    LldpPacket(LldpPacketInfo),
     Ipv4Packet(Ipv4PacketInfo),
     Ipv6Packet(Ipv6PacketInfo),
     TcpPacket(TcpPacketInfo),
     UdpPacket(UdpPacketInfo),
     IcmpPacket(IcmpPacketInfo),
     Icmpv6Packet(Icmpv6PacketInfo),
+    SctpPacket(SctpPacketInfo),
+    DnsPacket(DnsPacketInfo),
+    MdnsPacket(DnsPacketInfo),
+    HttpPacket(HttpPacketInfo),
+    SsdpPacket(SsdpPacketInfo),
+    RadiusPacket(RadiusPacketInfo),
+    SshPacket(SshPacketInfo),
+    GtpPacket(GtpPacketInfo),
+    VxlanPacket(VxlanPacketInfo),
 }
 
 #[derive(Clone)]
 pub struct CompletePacket {
     pub id: usize,
+    pub timestamp: DateTime<Local>,
+    /// Name of the interface the packet was captured on.
+    pub interface: String,
+    pub length: usize,
+    /// The frame as captured, link-layer header included; only the first `snaplen` bytes
+    /// when a snap length is set, so it can be shorter than `length`.
+    pub data: Vec<u8>,
+    /// How `data` begins, for writing it back out as pcap.
+    pub link_type: LinkType,
     pub layer_1: Option<PacketsData>,
+    pub vlan_tags: Vec<VlanPacketInfo>,
+    pub mpls: Option<MplsPacketInfo>,
     pub layer_2: Option<PacketsData>,
     pub layer_3: Option<PacketsData>,
+    pub layer_4: Option<PacketsData>,
+    /// Application protocol guessed from ports and payload, for traffic no dissector decodes.
+    pub app_protocol_guess: Option<&'static str>,
+    /// Client/server direction of a TCP packet, filled in by the flow tracker.
+    pub tcp_direction: Option<FlowDirection>,
+    /// Smoothed round-trip time of the TCP connection as of this packet.
+    pub tcp_rtt: Option<std::time::Duration>,
+    /// State of the TCP connection once this packet is accounted for.
+    pub tcp_state: Option<TcpConnectionState>,
+    /// Sent or received by this host, compared against the capturing interface.
+    pub traffic_direction: TrafficDirection,
+    /// Most severe expert info finding for the packet, filled in by the analyzer.
+    pub expert_severity: Option<Severity>,
+    /// Payload of an IPv4 fragment, left for the capture loop to hand to the reassembler.
+    pub ipv4_fragment: Option<Ipv4Fragment>,
+    /// Set on the fragment that completed a datagram: how many fragments it was made of.
+    /// The transport layers of such a packet come from the reassembled datagram.
+    pub reassembled_fragments: Option<usize>,
+    /// How many tunnels the packet was found inside; zero for the captured frame itself.
+    pub tunnel_depth: usize,
+    /// Note the analyst attached to the packet, saved as its comment in pcapng files.
+    pub comment: Option<String>,
 }
 
 impl CompletePacket {
-    pub fn new(id: usize) -> Self {
+    pub fn new(id: usize, interface: &str, frame: &[u8], snaplen: Option<usize>) -> Self {
+        let stored = snaplen.map_or(frame.len(), |snaplen| snaplen.min(frame.len()));
         CompletePacket {
             id,
+            timestamp: Local::now(),
+            interface: interface.to_string(),
+            length: frame.len(),
+            data: frame[..stored].to_vec(),
+            link_type: LinkType::Ethernet,
             layer_1: None,
+            vlan_tags: Vec::new(),
+            mpls: None,
             layer_2: None,
             layer_3: None,
+            layer_4: None,
+            app_protocol_guess: None,
+            tcp_direction: None,
+            tcp_rtt: None,
+            tcp_state: None,
+            traffic_direction: TrafficDirection::Other,
+            expert_severity: None,
+            ipv4_fragment: None,
+            reassembled_fragments: None,
+            tunnel_depth: 0,
+            comment: None,
         }
     }
     pub fn set_layer1_packet(&mut self, packet: Option<PacketsData>) {
@@ -253,4 +2127,387 @@ impl CompletePacket {
     pub fn set_layer3_packet(&mut self, packet: Option<PacketsData>) {
         self.layer_3 = packet;
     }
+    pub fn set_layer4_packet(&mut self, packet: Option<PacketsData>) {
+        self.layer_4 = packet;
+    }
+
+    pub fn ip_addresses(&self) -> Option<(IpAddr, IpAddr)> {
+        match &self.layer_2 {
+            Some(PacketsData::Ipv4Packet(ipv4)) => {
+                Some((IpAddr::V4(ipv4.source), IpAddr::V4(ipv4.destination)))
+            }
+            Some(PacketsData::Ipv6Packet(ipv6)) => {
+                Some((IpAddr::V6(ipv6.source), IpAddr::V6(ipv6.destination)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Bytes of the Ethernet FCS at the end of `data`, when the frame was captured with one.
+    pub fn fcs_length(&self) -> usize {
+        match &self.layer_1 {
+            Some(PacketsData::EthernetPacket(ethernet)) if ethernet.fcs.is_some() => {
+                EthernetPacketInfo::FCS_LENGTH
+            }
+            _ => 0,
+        }
+    }
+
+    /// Whether a recomputed IPv4, TCP, UDP or ICMP checksum disagrees with the one carried.
+    pub fn has_bad_checksum(&self) -> bool {
+        [&self.layer_2, &self.layer_3]
+            .into_iter()
+            .flatten()
+            .filter_map(|layer| match layer {
+                PacketsData::Ipv4Packet(ipv4) => Some((ipv4.checksum, ipv4.expected_checksum)),
+                PacketsData::TcpPacket(tcp) => Some((tcp.checksum, tcp.expected_checksum)),
+                PacketsData::UdpPacket(udp) => Some((udp.checksum, udp.expected_checksum)),
+                PacketsData::IcmpPacket(icmp) => Some((icmp.checksum, icmp.expected_checksum)),
+                _ => None,
+            })
+            .any(|(checksum, expected)| expected.is_some_and(|expected| expected != checksum))
+    }
+
+    pub fn ports(&self) -> Option<(u16, u16)> {
+        match &self.layer_3 {
+            Some(PacketsData::TcpPacket(tcp)) => Some((tcp.source, tcp.destination)),
+            Some(PacketsData::UdpPacket(udp)) => Some((udp.source, udp.destination)),
+            Some(PacketsData::SctpPacket(sctp)) => Some((sctp.source, sctp.destination)),
+            _ => None,
+        }
+    }
+
+    /// Layers of the packet carried in a GTP-U or VXLAN tunnel, if any.
+    pub fn tunneled_layers(&self) -> &[PacketsData] {
+        match &self.layer_4 {
+            Some(PacketsData::GtpPacket(gtp)) => &gtp.inner,
+            Some(PacketsData::VxlanPacket(vxlan)) => &vxlan.inner,
+            _ => &[],
+        }
+    }
+
+    /// Source and destination of the IP packet carried in a tunnel.
+    pub fn tunneled_addresses(&self) -> Option<(IpAddr, IpAddr)> {
+        self.tunneled_layers().iter().find_map(|layer| match layer {
+            PacketsData::Ipv4Packet(ipv4) => {
+                Some((IpAddr::V4(ipv4.source), IpAddr::V4(ipv4.destination)))
+            }
+            PacketsData::Ipv6Packet(ipv6) => {
+                Some((IpAddr::V6(ipv6.source), IpAddr::V6(ipv6.destination)))
+            }
+            _ => None,
+        })
+    }
+
+    /// Source and destination ports of the transport layer carried in a tunnel.
+    pub fn tunneled_ports(&self) -> Option<(u16, u16)> {
+        self.tunneled_layers().iter().find_map(|layer| match layer {
+            PacketsData::TcpPacket(tcp) => Some((tcp.source, tcp.destination)),
+            PacketsData::UdpPacket(udp) => Some((udp.source, udp.destination)),
+            PacketsData::SctpPacket(sctp) => Some((sctp.source, sctp.destination)),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn icmp_codes_are_described_relative_to_their_type() {
+        let describe = |icmp_type, code| IcmpPacketInfo::describe_icmp(icmp_type, IcmpCode(code));
+        assert_eq!(
+            describe(IcmpTypes::DestinationUnreachable, 3),
+            "Port Unreachable"
+        );
+        assert_eq!(
+            describe(IcmpTypes::DestinationUnreachable, 4),
+            "Fragmentation Needed"
+        );
+        assert_eq!(
+            describe(IcmpTypes::TimeExceeded, 1),
+            "Fragment Reassembly Time Exceeded"
+        );
+        assert_eq!(describe(IcmpTypes::EchoRequest, 0), "Echo Request");
+        assert_eq!(describe(IcmpTypes::DestinationUnreachable, 99), "Unknown");
+
+        let describe_v6 =
+            |icmpv6_type, code| Icmpv6PacketInfo::describe_icmpv6(icmpv6_type, Icmpv6Code(code));
+        assert_eq!(
+            describe_v6(Icmpv6Types::DestinationUnreachable, 4),
+            "Port Unreachable"
+        );
+        assert_eq!(
+            describe_v6(Icmpv6Types::TimeExceeded, 0),
+            "Hop Limit Exceeded in Transit"
+        );
+    }
+
+    #[test]
+    fn dns_query_and_compressed_answer() {
+        let mut message = vec![0xab, 0xcd, 0x81, 0x80, 0, 1, 0, 1, 0, 0, 0, 0];
+        message.extend(b"\x07example\x03com\x00\x00\x01\x00\x01");
+        // Name pointer back to the question, type A, class IN, TTL 300, 4 bytes.
+        message.extend([
+            0xc0, 0x0c, 0, 1, 0, 1, 0, 0, 0x01, 0x2c, 0, 4, 93, 184, 216, 34,
+        ]);
+
+        let dns = DnsPacketInfo::parse(&message).expect("valid response");
+        assert_eq!(dns.id, 0xabcd);
+        assert!(dns.is_response);
+        assert_eq!(dns.questions[0].name, "example.com");
+        assert_eq!(dns.questions[0].record_type, 1);
+        assert_eq!(dns.answers[0].name, "example.com");
+        assert_eq!(dns.answers[0].ttl, 300);
+        assert_eq!(dns.answers[0].data, "93.184.216.34");
+
+        let tcp = [&(message.len() as u16).to_be_bytes()[..], &message].concat();
+        assert!(
+            DnsPacketInfo::parse_tcp(&tcp)
+                .expect("length-prefixed")
+                .over_tcp
+        );
+        assert!(DnsPacketInfo::parse_tcp(&tcp[..tcp.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn dns_over_tcp_needs_the_whole_length_prefixed_message() {
+        let mut message = vec![0x12, 0x34, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0];
+        message.extend(b"\x07example\x03com\x00\x00\x01\x00\x01");
+        let length = (message.len() as u16).to_be_bytes();
+
+        let segment = [&length[..], &message].concat();
+        let dns = DnsPacketInfo::parse_tcp(&segment).expect("whole message");
+        assert!(dns.over_tcp);
+        assert_eq!(dns.id, 0x1234);
+        assert_eq!(dns.questions[0].name, "example.com");
+
+        // The length prefix itself cut short.
+        assert!(DnsPacketInfo::parse_tcp(&[]).is_none());
+        assert!(DnsPacketInfo::parse_tcp(&length[..1]).is_none());
+        // A length running past the end of the segment.
+        let longer = (message.len() as u16 + 1).to_be_bytes();
+        assert!(DnsPacketInfo::parse_tcp(&[&longer[..], &message].concat()).is_none());
+    }
+
+    #[test]
+    fn dns_pointer_loop_is_rejected() {
+        let mut message = vec![0, 1, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0];
+        message.extend([0xc0, 0x0c, 0, 1, 0, 1]);
+        assert!(DnsPacketInfo::parse(&message).is_none());
+    }
+
+    #[test]
+    fn http_request_and_response() {
+        let request =
+            HttpPacketInfo::parse(b"GET /a HTTP/1.1\r\nHost: example.com\r\n\r\n").unwrap();
+        assert_eq!(request.method.as_deref(), Some("GET"));
+        assert_eq!(request.path.as_deref(), Some("/a"));
+        assert_eq!(request.host.as_deref(), Some("example.com"));
+
+        let response = HttpPacketInfo::parse(b"HTTP/1.1 404 Not Found\r\n\r\n").unwrap();
+        assert_eq!(response.status_code, Some(404));
+        assert_eq!(response.reason.as_deref(), Some("Not Found"));
+
+        assert!(HttpPacketInfo::parse(b"\x16\x03\x01\x02\x00").is_none());
+    }
+
+    #[test]
+    fn ssdp_notify() {
+        let ssdp = SsdpPacketInfo::parse(
+            b"NOTIFY * HTTP/1.1\r\nNT: upnp:rootdevice\r\nNTS: ssdp:alive\r\nLOCATION: http://10.0.0.2/\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(ssdp.method.as_deref(), Some("NOTIFY"));
+        assert_eq!(ssdp.location.as_deref(), Some("http://10.0.0.2/"));
+        assert_eq!(ssdp.summary(), "NOTIFY ssdp:alive upnp:rootdevice");
+    }
+
+    #[test]
+    fn ssh_banner() {
+        let ssh = SshPacketInfo::parse(b"SSH-2.0-OpenSSH_9.6p1 Ubuntu-3ubuntu13\r\n").unwrap();
+        assert_eq!(ssh.protocol_version, "2.0");
+        assert_eq!(ssh.software_version, "OpenSSH_9.6p1");
+        assert_eq!(ssh.comments.as_deref(), Some("Ubuntu-3ubuntu13"));
+
+        let ssh = SshPacketInfo::parse(b"SSH-1.99-dropbear_2022.83\r\n").unwrap();
+        assert_eq!(ssh.software_version, "dropbear_2022.83");
+        assert_eq!(ssh.comments, None);
+
+        assert!(SshPacketInfo::parse(b"SSH-2.0\r\n").is_none());
+        assert!(SshPacketInfo::parse(&[0, 0, 0, 0x1c, 0x0a, 0x14]).is_none());
+    }
+
+    #[test]
+    fn radius_hides_passwords() {
+        let attributes = [&[1, 7][..], b"alice", &[2, 18], &[0xaa; 16]].concat();
+        let length = (20 + attributes.len()) as u16;
+        let message = [&[1, 9][..], &length.to_be_bytes(), &[0; 16], &attributes].concat();
+
+        let radius = RadiusPacketInfo::parse(&message).unwrap();
+        assert_eq!(radius.identifier, 9);
+        assert_eq!(radius.user_name().as_deref(), Some("alice"));
+        assert_eq!(radius.summary(), "Access-Request id=9 user=alice");
+        let password = &radius.attributes[1];
+        assert!(password.value.is_empty());
+        assert_eq!(password.display_value(), "(16 bytes hidden)");
+        assert!(!radius.malformed_attributes);
+
+        let mut overrun = message.clone();
+        overrun[21] = 40;
+        assert!(
+            RadiusPacketInfo::parse(&overrun)
+                .unwrap()
+                .malformed_attributes
+        );
+    }
+
+    #[test]
+    fn gtp_with_sequence_number() {
+        let payload = [0x32, 0xff, 0, 8, 0, 0, 0, 7, 0x12, 0x34, 0, 0, 0x45];
+        let (gtp, offset) = GtpPacketInfo::parse(&payload).unwrap();
+        assert_eq!(gtp.teid, 7);
+        assert_eq!(gtp.sequence, Some(0x1234));
+        assert_eq!(offset, 12);
+        // GTP' clears the protocol type bit.
+        assert!(GtpPacketInfo::parse(&[0x22, 0xff, 0, 0, 0, 0, 0, 0]).is_none());
+    }
+
+    #[test]
+    fn sctp_chunks_are_padded() {
+        let payload = [
+            &[0, 1, 0, 2, 0, 0, 0, 9, 0, 0, 0, 0][..],
+            &[0, 3, 0, 5, 0xff, 0, 0, 0],
+            &[3, 0, 0, 4],
+        ]
+        .concat();
+        let sctp = SctpPacketInfo::parse(&payload).unwrap();
+        assert_eq!(sctp.verification_tag, 9);
+        let chunks: Vec<_> = sctp.chunks.iter().map(|chunk| chunk.chunk_type).collect();
+        assert_eq!(chunks, [0, 3]);
+    }
+
+    #[test]
+    fn lldp_identifiers() {
+        let payload = [
+            &[0x02, 0x07, 4, 0, 0x11, 0x22, 0x33, 0x44, 0x55][..],
+            &[0x04, 0x05, 5],
+            b"eth0",
+            &[0x06, 0x02, 0, 120, 0, 0],
+        ]
+        .concat();
+        let lldp = LldpPacketInfo::parse(&payload).unwrap();
+        assert_eq!(lldp.chassis_id.as_deref(), Some("00:11:22:33:44:55"));
+        assert_eq!(lldp.port_id.as_deref(), Some("eth0"));
+        assert_eq!(lldp.ttl, Some(120));
+    }
+
+    #[test]
+    fn vlan_and_mpls_headers() {
+        let vlan = VlanPacketInfo::parse(&[0xa0, 0x64, 0x08, 0x00], EtherTypes::Vlan).unwrap();
+        assert_eq!((vlan.priority, vlan.vlan_id), (5, 100));
+        assert_eq!(vlan.ethertype, EtherTypes::Ipv4);
+
+        let stack = [0x00, 0x01, 0x00, 0x40, 0x00, 0x02, 0x01, 0x3f, 0x45];
+        let mpls = MplsPacketInfo::parse(&stack, EtherTypes::Mpls).unwrap();
+        let labels: Vec<_> = mpls.labels.iter().map(|label| label.label).collect();
+        assert_eq!(labels, [16, 32]);
+        assert_eq!(mpls.header_length(), 8);
+        assert!(MplsPacketInfo::parse(&stack[..4], EtherTypes::Mpls).is_none());
+    }
+
+    #[test]
+    fn arp_with_unusual_address_lengths() {
+        let bytes = [
+            0, 1, 0x08, 0x00, 8, 4, 0, 1, 1, 2, 3, 4, 5, 6, 7, 8, 10, 0, 0, 1,
+        ];
+        let arp = ArpPacketInfo::from(&ArpPacket::new(&[&bytes[..], &[0; 12]].concat()).unwrap());
+        assert!(arp.sender_hw_addr.is_raw());
+        assert_eq!(arp.sender_proto_addr.to_string(), "10.0.0.1");
+    }
+
+    #[test]
+    fn ipv4_header_fields() {
+        let bytes = [
+            0x45, 0xb8, 0, 20, 0, 1, 0x20, 0x01, 64, 17, 0, 0, 10, 0, 0, 1, 10, 0, 0, 2,
+        ];
+        let ipv4 = Ipv4PacketInfo::from(&Ipv4Packet::new(&bytes).unwrap());
+        assert_eq!((ipv4.dscp, ipv4.ecn), (46, 0));
+        assert_eq!(ipv4.dscp_name(), Some("EF"));
+        assert!(ipv4.more_fragments());
+        assert_eq!(ipv4.fragment_offset_bytes(), 8);
+        assert_eq!(ipv4.destination, Ipv4Addr::new(10, 0, 0, 2));
+    }
+
+    #[test]
+    fn dscp_and_ecn_names() {
+        let bytes = [
+            0x45, 0, 0, 20, 0, 0, 0, 0, 64, 6, 0, 0, 10, 0, 0, 1, 10, 0, 0, 2,
+        ];
+        let mut ipv4 = Ipv4PacketInfo::from(&Ipv4Packet::new(&bytes).unwrap());
+        let names = |ipv4: &Ipv4PacketInfo| (ipv4.dscp_name(), ipv4.ecn_name());
+
+        assert_eq!(names(&ipv4), (Some("Default"), "Not-ECT"));
+        (ipv4.dscp, ipv4.ecn) = (10, 2);
+        assert_eq!(names(&ipv4), (Some("AF11"), "ECT(0)"));
+        (ipv4.dscp, ipv4.ecn) = (38, 1);
+        assert_eq!(names(&ipv4), (Some("AF43"), "ECT(1)"));
+        (ipv4.dscp, ipv4.ecn) = (56, 3);
+        assert_eq!(names(&ipv4), (Some("CS7"), "CE"));
+        ipv4.dscp = 63;
+        assert_eq!(ipv4.dscp_name(), None);
+    }
+
+    #[test]
+    fn neighbor_advertisement() {
+        let body = [
+            &[0x60, 0, 0, 0][..],
+            &Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1).octets(),
+            &[2, 1, 0x02, 0, 0, 0, 0, 0x0b],
+        ]
+        .concat();
+        let ndp = NdpInfo::parse(Icmpv6Types::NeighborAdvert, &body).unwrap();
+        assert_eq!(ndp.target, Some(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1)));
+        assert!(matches!(
+            ndp.options[..],
+            [NdpOption::TargetLinkLayerAddress(mac)] if mac == MacAddr::new(2, 0, 0, 0, 0, 0x0b)
+        ));
+
+        let zero_length = [&body[..20], &[1, 0]].concat();
+        assert!(
+            NdpInfo::parse(Icmpv6Types::NeighborAdvert, &zero_length)
+                .unwrap()
+                .malformed_options
+        );
+    }
+
+    #[test]
+    fn radiotap_and_beacon() {
+        // Flags, rate, channel and signal present.
+        let radiotap = [
+            0, 0, 18, 0, 0x2e, 0, 0, 0, 0x10, 0x0c, 0x85, 0x09, 0xa0, 0x00, 0xd1, 0, 0, 0,
+        ];
+        let info = RadiotapInfo::parse(&radiotap).unwrap();
+        assert!(info.has_fcs());
+        assert_eq!(info.frequency, Some(2437));
+        assert_eq!(info.channel(), Some(6));
+        assert_eq!(info.signal_dbm, Some(-47));
+
+        let beacon = [
+            &[0x80, 0, 0, 0][..],
+            &[0xff; 6],
+            &[2, 0, 0, 0, 0, 1],
+            &[2, 0, 0, 0, 0, 1],
+            &[0, 0],
+            &[0; 12],
+            &[0, 7],
+            b"HomeNet",
+        ]
+        .concat();
+        let (wifi, _) = Ieee80211PacketInfo::parse(&beacon).unwrap();
+        assert_eq!(wifi.subtype_name(), "Beacon");
+        assert_eq!(wifi.ssid.as_deref(), Some("HomeNet"));
+        assert_eq!(wifi.transmitter(), Some(MacAddr::new(2, 0, 0, 0, 0, 1)));
+    }
 }
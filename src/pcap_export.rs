@@ -0,0 +1,276 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::{packet_data::CompletePacket, strings};
+
+/// What a pcap export wrote, for the status message.
+pub struct PcapSummary {
+    pub written: usize,
+    /// Packets stored shorter than they were on the wire because of the snap length.
+    pub truncated: usize,
+    /// Packet comments left out because the classic format has nowhere to put them.
+    pub dropped_comments: usize,
+}
+
+/// Writes packets to a capture file readable by Wireshark and tcpdump: classic (libpcap)
+/// for a `.pcap` path, pcapng, which also keeps packet comments, for any other.
+pub struct PcapWriter;
+
+impl PcapWriter {
+    const MAGIC: u32 = 0xa1b2_c3d4;
+    const VERSION: (u16, u16) = (2, 4);
+    /// The snap length announced in the file header; no captured frame is longer.
+    const SNAPLEN: u32 = 262_144;
+
+    const SECTION_HEADER_BLOCK: u32 = 0x0a0d_0d0a;
+    const INTERFACE_DESCRIPTION_BLOCK: u32 = 1;
+    const ENHANCED_PACKET_BLOCK: u32 = 6;
+    const BYTE_ORDER_MAGIC: u32 = 0x1a2b_3c4d;
+    const OPTION_END: u16 = 0;
+    const OPTION_COMMENT: u16 = 1;
+    const OPTION_INTERFACE_NAME: u16 = 2;
+
+    /// Saves `packets`, in the order given, to `path`. Fails with a translated message when
+    /// there is nothing to save, when a packet kept none of its frame or when the packets
+    /// come from links of different types, which one classic pcap file can't hold.
+    pub fn save(path: &Path, packets: &[&CompletePacket]) -> Result<PcapSummary, String> {
+        let classic = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("pcap"));
+        let Some(first) = packets.first() else {
+            return Err(strings::get("pcap.empty").to_string());
+        };
+        let not_retained = packets
+            .iter()
+            .filter(|packet| packet.data.is_empty() && packet.length > 0)
+            .count();
+        if not_retained > 0 {
+            return Err(
+                strings::get("pcap.not_retained").replace("{count}", &not_retained.to_string())
+            );
+        }
+        let link_type = first.link_type.pcap_link_type(&first.data);
+        if let Some(other) = packets
+            .iter()
+            .filter(|_| classic)
+            .find(|packet| packet.link_type.pcap_link_type(&packet.data) != link_type)
+        {
+            return Err(strings::get("pcap.mixed_links")
+                .replace("{first}", &first.interface)
+                .replace("{other}", &other.interface));
+        }
+
+        let write_error = |error: io::Error| {
+            strings::get("pcap.write_error")
+                .replace("{path}", &path.display().to_string())
+                .replace("{error}", &error.to_string())
+        };
+        let mut writer = BufWriter::new(File::create(path).map_err(write_error)?);
+        if classic {
+            Self::write(&mut writer, link_type, packets)
+        } else {
+            Self::write_pcapng(&mut writer, packets)
+        }
+        .and_then(|()| writer.flush())
+        .map_err(write_error)?;
+        Ok(PcapSummary {
+            written: packets.len(),
+            truncated: packets
+                .iter()
+                .filter(|packet| packet.data.len() < packet.length)
+                .count(),
+            dropped_comments: packets
+                .iter()
+                .filter(|packet| classic && packet.comment.is_some())
+                .count(),
+        })
+    }
+
+    /// The file header followed by one record per packet, in host byte order as libpcap
+    /// writes them; readers tell the order from the magic number.
+    fn write(
+        writer: &mut impl Write,
+        link_type: u32,
+        packets: &[&CompletePacket],
+    ) -> io::Result<()> {
+        writer.write_all(&Self::MAGIC.to_ne_bytes())?;
+        writer.write_all(&Self::VERSION.0.to_ne_bytes())?;
+        writer.write_all(&Self::VERSION.1.to_ne_bytes())?;
+        // Time zone offset and timestamp accuracy, always zero in practice.
+        writer.write_all(&[0; 8])?;
+        writer.write_all(&Self::SNAPLEN.to_ne_bytes())?;
+        writer.write_all(&link_type.to_ne_bytes())?;
+
+        for packet in packets {
+            let captured = packet.data.len().min(Self::SNAPLEN as usize);
+            let seconds = packet.timestamp.timestamp() as u32;
+            let microseconds = packet.timestamp.timestamp_subsec_micros().min(999_999);
+            writer.write_all(&seconds.to_ne_bytes())?;
+            writer.write_all(&microseconds.to_ne_bytes())?;
+            writer.write_all(&(captured as u32).to_ne_bytes())?;
+            writer.write_all(&(packet.length as u32).to_ne_bytes())?;
+            writer.write_all(&packet.data[..captured])?;
+        }
+        Ok(())
+    }
+
+    /// A section header, then an interface description for each interface and link type
+    /// the packets came from, the first time one of its packets is written, and an
+    /// enhanced packet block per packet carrying its comment. Host byte order, as for pcap.
+    fn write_pcapng(writer: &mut impl Write, packets: &[&CompletePacket]) -> io::Result<()> {
+        let mut section = Self::BYTE_ORDER_MAGIC.to_ne_bytes().to_vec();
+        section.extend(1u16.to_ne_bytes());
+        section.extend(0u16.to_ne_bytes());
+        // Section length unknown.
+        section.extend((-1i64).to_ne_bytes());
+        Self::write_block(writer, Self::SECTION_HEADER_BLOCK, &section)?;
+
+        let mut interfaces: Vec<(&str, u32)> = Vec::new();
+        for packet in packets {
+            let link_type = packet.link_type.pcap_link_type(&packet.data);
+            let key = (packet.interface.as_str(), link_type);
+            let interface_id = match interfaces.iter().position(|interface| *interface == key) {
+                Some(id) => id,
+                None => {
+                    let mut description = (link_type as u16).to_ne_bytes().to_vec();
+                    description.extend(0u16.to_ne_bytes());
+                    description.extend(Self::SNAPLEN.to_ne_bytes());
+                    Self::push_text_option(
+                        &mut description,
+                        Self::OPTION_INTERFACE_NAME,
+                        &packet.interface,
+                    );
+                    Self::push_option(&mut description, Self::OPTION_END, &[]);
+                    Self::write_block(writer, Self::INTERFACE_DESCRIPTION_BLOCK, &description)?;
+                    interfaces.push(key);
+                    interfaces.len() - 1
+                }
+            };
+
+            let captured = packet.data.len().min(Self::SNAPLEN as usize);
+            // Microseconds, the default resolution of an interface without if_tsresol.
+            let timestamp = packet.timestamp.timestamp_micros() as u64;
+            let mut body = (interface_id as u32).to_ne_bytes().to_vec();
+            body.extend(((timestamp >> 32) as u32).to_ne_bytes());
+            body.extend((timestamp as u32).to_ne_bytes());
+            body.extend((captured as u32).to_ne_bytes());
+            body.extend((packet.length as u32).to_ne_bytes());
+            body.extend(&packet.data[..captured]);
+            body.resize(body.len().next_multiple_of(4), 0);
+            if let Some(comment) = &packet.comment {
+                Self::push_text_option(&mut body, Self::OPTION_COMMENT, comment);
+                Self::push_option(&mut body, Self::OPTION_END, &[]);
+            }
+            Self::write_block(writer, Self::ENHANCED_PACKET_BLOCK, &body)?;
+        }
+        Ok(())
+    }
+
+    /// A pcapng option: code, length and the value padded to 32 bits. The length field is
+    /// 16 bits, so longer values are cut short.
+    fn push_option(body: &mut Vec<u8>, code: u16, value: &[u8]) {
+        let value = &value[..value.len().min(u16::MAX as usize)];
+        body.extend(code.to_ne_bytes());
+        body.extend((value.len() as u16).to_ne_bytes());
+        body.extend(value);
+        body.resize(body.len().next_multiple_of(4), 0);
+    }
+
+    /// An option holding UTF-8 text, cut short at a character boundary if it doesn't fit.
+    fn push_text_option(body: &mut Vec<u8>, code: u16, text: &str) {
+        let mut length = text.len().min(u16::MAX as usize);
+        while !text.is_char_boundary(length) {
+            length -= 1;
+        }
+        Self::push_option(body, code, &text.as_bytes()[..length]);
+    }
+
+    /// A pcapng block: its type and total length around a body padded to 32 bits.
+    fn write_block(writer: &mut impl Write, block_type: u32, body: &[u8]) -> io::Result<()> {
+        let length = (12 + body.len()) as u32;
+        writer.write_all(&block_type.to_ne_bytes())?;
+        writer.write_all(&length.to_ne_bytes())?;
+        writer.write_all(body)?;
+        writer.write_all(&length.to_ne_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PcapWriter;
+    use crate::packet_data::CompletePacket;
+
+    #[test]
+    fn records_keep_the_wire_length_of_truncated_frames() {
+        let frame = [0xaa; 100];
+        let full = CompletePacket::new(1, "test", &frame, None);
+        let truncated = CompletePacket::new(2, "test", &frame, Some(60));
+
+        let mut file = Vec::new();
+        PcapWriter::write(&mut file, 1, &[&full, &truncated]).unwrap();
+
+        let word = |offset: usize| u32::from_ne_bytes(file[offset..offset + 4].try_into().unwrap());
+        assert_eq!(word(0), 0xa1b2_c3d4);
+        assert_eq!(word(20), 1);
+        // Each record header holds the seconds, microseconds, stored and wire lengths.
+        assert_eq!((word(24 + 8), word(24 + 12)), (100, 100));
+        let second = 24 + 16 + 100;
+        assert_eq!((word(second + 8), word(second + 12)), (60, 100));
+        assert_eq!(file.len(), second + 16 + 60);
+    }
+
+    #[test]
+    fn pcapng_blocks_carry_the_interface_and_packet_comments() {
+        let frame = [0xaa; 61];
+        let mut commented = CompletePacket::new(1, "eth0", &frame, None);
+        commented.comment = Some("look here".to_string());
+        let plain = CompletePacket::new(2, "eth0", &frame, None);
+
+        let mut file = Vec::new();
+        PcapWriter::write_pcapng(&mut file, &[&commented, &plain]).unwrap();
+
+        let word = |offset: usize| u32::from_ne_bytes(file[offset..offset + 4].try_into().unwrap());
+        let mut blocks = Vec::new();
+        let mut offset = 0;
+        while offset < file.len() {
+            let length = word(offset + 4) as usize;
+            assert_eq!(word(offset + length - 4) as usize, length);
+            blocks.push((word(offset), offset, length));
+            offset += length;
+        }
+        let types: Vec<u32> = blocks.iter().map(|(block_type, ..)| *block_type).collect();
+        assert_eq!(types, [0x0a0d_0d0a, 1, 6, 6]);
+
+        let (_, interface, _) = blocks[1];
+        assert_eq!(&file[interface + 20..interface + 24], b"eth0");
+        // 28 bytes of header, then the frame padded to 64 and the comment option.
+        let (_, first, first_length) = blocks[2];
+        let comment = first + 28 + 64;
+        assert_eq!(&file[comment + 4..comment + 13], b"look here");
+        let (_, _, second_length) = blocks[3];
+        assert_eq!(first_length - second_length, 4 + 12 + 4);
+    }
+
+    #[test]
+    fn comments_too_long_for_an_option_are_cut_at_a_character() {
+        let mut packet = CompletePacket::new(1, "eth0", &[0xaa; 60], None);
+        packet.comment = Some("é".repeat(40_000));
+
+        let mut file = Vec::new();
+        PcapWriter::write_pcapng(&mut file, &[&packet]).unwrap();
+
+        // Section header of 28 bytes and interface description of 32, then the packet
+        // block's 28 bytes of header and the frame.
+        let comment = 28 + 32 + 28 + 60;
+        let half = |offset: usize| u16::from_ne_bytes(file[offset..offset + 2].try_into().unwrap());
+        assert_eq!(half(comment), 1);
+        assert_eq!(half(comment + 2), u16::MAX - 1);
+        let text = &file[comment + 4..comment + 4 + (u16::MAX - 1) as usize];
+        assert!(std::str::from_utf8(text).is_ok());
+        let block_length = u32::from_ne_bytes(file[28 + 32 + 4..28 + 32 + 8].try_into().unwrap());
+        assert_eq!(file.len(), 28 + 32 + block_length as usize);
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    collections::HashMap,
+    net::Ipv4Addr,
+    time::{Duration, Instant},
+};
+
+use pnet::packet::ip::IpNextHeaderProtocol;
+
+/// The payload of one IPv4 fragment, set aside by the dissector for the reassembler.
+#[derive(Clone)]
+pub struct Ipv4Fragment {
+    pub source: Ipv4Addr,
+    pub destination: Ipv4Addr,
+    pub identification: u16,
+    pub protocol: IpNextHeaderProtocol,
+    /// Offset of `payload` in the datagram, in bytes.
+    pub offset: usize,
+    pub more_fragments: bool,
+    pub payload: Vec<u8>,
+}
+
+/// A datagram put back together from its fragments.
+pub struct Datagram {
+    pub protocol: IpNextHeaderProtocol,
+    pub payload: Vec<u8>,
+    pub fragments: usize,
+}
+
+type DatagramKey = (Ipv4Addr, Ipv4Addr, u16, IpNextHeaderProtocol);
+
+struct PendingDatagram {
+    /// (offset, payload) in arrival order.
+    fragments: Vec<(usize, Vec<u8>)>,
+    /// Known once the last fragment, the one without "more fragments", arrives.
+    total_length: Option<usize>,
+    buffered: usize,
+    first_seen: Instant,
+}
+
+/// Collects IPv4 fragments by (source, destination, identification, protocol) until a
+/// datagram is complete. Incomplete datagrams are dropped after `TIMEOUT`, and the
+/// number and size of the ones kept are capped so lost fragments can't pile up.
+#[derive(Default)]
+pub struct Ipv4Reassembler {
+    pending: HashMap<DatagramKey, PendingDatagram>,
+}
+
+impl Ipv4Reassembler {
+    const TIMEOUT: Duration = Duration::from_secs(30);
+    const MAX_PENDING: usize = 64;
+    const MAX_DATAGRAM_LENGTH: usize = 65_535;
+
+    /// Adds `fragment`, returning the datagram it completes.
+    pub fn push(&mut self, fragment: Ipv4Fragment) -> Option<Datagram> {
+        let now = Instant::now();
+        self.pending
+            .retain(|_, pending| now.duration_since(pending.first_seen) < Self::TIMEOUT);
+
+        let key = (
+            fragment.source,
+            fragment.destination,
+            fragment.identification,
+            fragment.protocol,
+        );
+        let end = fragment.offset + fragment.payload.len();
+        if end > Self::MAX_DATAGRAM_LENGTH {
+            self.pending.remove(&key);
+            return None;
+        }
+
+        if !self.pending.contains_key(&key) && self.pending.len() >= Self::MAX_PENDING {
+            let oldest = self
+                .pending
+                .iter()
+                .min_by_key(|(_, pending)| pending.first_seen)
+                .map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                self.pending.remove(&oldest);
+            }
+        }
+
+        let pending = self.pending.entry(key).or_insert_with(|| PendingDatagram {
+            fragments: Vec::new(),
+            total_length: None,
+            buffered: 0,
+            first_seen: now,
+        });
+        if !fragment.more_fragments {
+            pending.total_length = Some(end);
+        }
+        pending.buffered += fragment.payload.len();
+        pending.fragments.push((fragment.offset, fragment.payload));
+        if pending.buffered > Self::MAX_DATAGRAM_LENGTH {
+            self.pending.remove(&key);
+            return None;
+        }
+
+        let payload = Self::assemble(pending)?;
+        let fragments = pending.fragments.len();
+        self.pending.remove(&key);
+        Some(Datagram {
+            protocol: fragment.protocol,
+            payload,
+            fragments,
+        })
+    }
+
+    /// Joins the fragments once they cover the whole datagram without gaps. Overlapping
+    /// bytes are taken from the fragment that arrived first.
+    fn assemble(pending: &PendingDatagram) -> Option<Vec<u8>> {
+        let total_length = pending.total_length?;
+        let mut fragments: Vec<&(usize, Vec<u8>)> = pending.fragments.iter().collect();
+        fragments.sort_by_key(|(offset, _)| *offset);
+
+        let mut payload = Vec::with_capacity(total_length);
+        for (offset, bytes) in fragments {
+            if *offset > payload.len() {
+                return None;
+            }
+            let end = (offset + bytes.len()).min(total_length);
+            if end > payload.len() {
+                payload.extend_from_slice(&bytes[payload.len() - offset..end - offset]);
+            }
+        }
+        (payload.len() == total_length).then_some(payload)
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+    sync::{
+        mpsc::{self, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+const CACHE_CAPACITY: usize = 1024;
+/// How long an address pushed out of a full cache isn't looked up again, so a capture
+/// with more addresses than fit doesn't keep the worker busy, or send it DNS queries
+/// that get captured in turn.
+const REQUERY_DELAY: Duration = Duration::from_secs(60);
+
+/// Least-recently-used cache of lookups made on a background thread, keyed by address,
+/// with the addresses still waiting for an answer and the ones evicted lately.
+pub struct LookupCache<V> {
+    entries: HashMap<IpAddr, (V, u64)>,
+    pending: HashSet<IpAddr>,
+    evicted: HashMap<IpAddr, Instant>,
+    tick: u64,
+}
+
+impl<V> LookupCache<V> {
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            pending: HashSet::new(),
+            evicted: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    pub fn get(&mut self, ip: &IpAddr) -> Option<&V> {
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(ip).map(|(value, last_used)| {
+            *last_used = tick;
+            &*value
+        })
+    }
+
+    /// Marks `ip` as being looked up; false if it already was, or was evicted less than
+    /// `REQUERY_DELAY` ago.
+    pub fn start_lookup(&mut self, ip: IpAddr) -> bool {
+        if self
+            .evicted
+            .get(&ip)
+            .is_some_and(|evicted_at| evicted_at.elapsed() < REQUERY_DELAY)
+        {
+            return false;
+        }
+        self.evicted.remove(&ip);
+        self.pending.insert(ip)
+    }
+
+    pub fn insert(&mut self, ip: IpAddr, value: V) {
+        self.pending.remove(&ip);
+        if self.entries.len() >= CACHE_CAPACITY && !self.entries.contains_key(&ip) {
+            let least_recently_used = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(ip, _)| *ip);
+            if let Some(least_recently_used) = least_recently_used {
+                self.entries.remove(&least_recently_used);
+                self.evicted
+                    .retain(|_, evicted_at| evicted_at.elapsed() < REQUERY_DELAY);
+                self.evicted.insert(least_recently_used, Instant::now());
+            }
+        }
+        self.tick += 1;
+        self.entries.insert(ip, (value, self.tick));
+    }
+}
+
+/// Resolves addresses to host names on a background thread. Lookups from the UI only
+/// read the cache and queue misses, so rendering never waits on DNS.
+pub struct Resolver {
+    /// `None` records an address with no name.
+    cache: Arc<Mutex<LookupCache<Option<String>>>>,
+    tx: Sender<IpAddr>,
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        let cache = Arc::new(Mutex::new(LookupCache::new()));
+        let (tx, rx) = mpsc::channel::<IpAddr>();
+
+        let worker_cache = cache.clone();
+        thread::spawn(move || {
+            for ip in rx {
+                let name = dns_lookup::lookup_addr(&ip)
+                    .ok()
+                    .filter(|name| *name != ip.to_string());
+                if let Ok(mut cache) = worker_cache.lock() {
+                    cache.insert(ip, name);
+                }
+            }
+        });
+
+        Self { cache, tx }
+    }
+
+    pub fn lookup(&self, ip: IpAddr) -> Option<String> {
+        let mut cache = self.cache.lock().ok()?;
+        if let Some(name) = cache.get(&ip) {
+            return name.clone();
+        }
+        if cache.start_lookup(ip) {
+            let _ = self.tx.send(ip);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use super::{LookupCache, CACHE_CAPACITY};
+
+    #[test]
+    fn evicted_addresses_are_not_looked_up_again_right_away() {
+        let ip = |n: usize| IpAddr::V4(Ipv4Addr::from(n as u32));
+        let mut cache = LookupCache::new();
+        for n in 0..=CACHE_CAPACITY {
+            assert!(cache.start_lookup(ip(n)));
+            cache.insert(ip(n), n);
+        }
+        assert_eq!(cache.get(&ip(0)), None);
+        assert!(!cache.start_lookup(ip(0)));
+        assert_eq!(cache.get(&ip(CACHE_CAPACITY)), Some(&CACHE_CAPACITY));
+        assert!(cache.start_lookup(ip(CACHE_CAPACITY + 1)));
+        assert!(!cache.start_lookup(ip(CACHE_CAPACITY + 1)));
+    }
+}
@@ -0,0 +1,59 @@
+use std::{env, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    filter::FilterPreset,
+    widgets::{layout_helper::PanelLayout, packet_table::Column},
+};
+
+/// Session state remembered between runs, as opposed to the user-edited `Config`.
+#[derive(Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Settings {
+    pub interface: Option<String>,
+    /// One of `Theme::NAMES`, as last picked with the theme key.
+    pub theme: Option<String>,
+    pub resolve_names: bool,
+    /// Whether the packet table shows one line per packet.
+    pub compact_rows: bool,
+    /// Whether the packet table lists the oldest packet first.
+    pub oldest_first: bool,
+    pub display_filter: Option<String>,
+    pub layout: Option<PanelLayout>,
+    /// Filters saved from the preset picker.
+    pub filter_presets: Vec<FilterPreset>,
+    /// Packet table columns as left in the column picker.
+    pub columns: Option<Vec<Column>>,
+}
+
+impl Settings {
+    /// Loads `~/.local/state/wirepenguin/state.toml`. A missing or corrupt file yields the
+    /// defaults; the file is rewritten on exit anyway.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = toml::to_string(self).map_err(std::io::Error::other)?;
+        fs::write(path, contents)
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        let state_dir = env::var_os("XDG_STATE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("state"))
+            })?;
+        Some(state_dir.join("wirepenguin").join("state.toml"))
+    }
+}
@@ -1,16 +1,20 @@
 use std::{
+    net::IpAddr,
     sync::mpsc::Sender,
     thread::{self, JoinHandle},
 };
 
 use crate::{
+    app_protocol::{self, AppProto, DecodeAs, Transport},
     event::Event,
-    widgets::packet_table::{PacketTable, PacketTableState},
+    flows::TrafficDirection,
+    reassembly::{Ipv4Fragment, Ipv4Reassembler},
+    strings,
 };
 use std::{
     sync::{
-        atomic::{AtomicBool, Ordering},
-        mpsc, Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc, Arc, PoisonError, RwLock,
     },
     time::Duration,
 };
@@ -19,143 +23,444 @@ use pnet::{
     datalink::{Channel, ChannelType, NetworkInterface},
     packet::{
         arp::ArpPacket,
-        ethernet::{EtherTypes, EthernetPacket},
+        ethernet::{EtherType, EtherTypes, EthernetPacket},
         icmp::IcmpPacket,
         icmpv6::Icmpv6Packet,
         ip::{IpNextHeaderProtocol, IpNextHeaderProtocols},
         ipv4::Ipv4Packet,
         ipv6::Ipv6Packet,
-        tcp::TcpPacket,
-        udp::UdpPacket,
+        tcp::{self, TcpPacket},
+        udp::{self, UdpPacket},
         Packet,
     },
 };
-use ratatui::{
-    layout::Rect,
-    widgets::{Block, Borders},
-    Frame,
-};
 
 use crate::packet_data::{
-    ArpPacketInfo, CompletePacket, EthernetPacketInfo, IcmpPacketInfo, Icmpv6PacketInfo,
-    Ipv4PacketInfo, Ipv6PacketInfo, PacketsData, TcpPacketInfo, UdpPacketInfo,
+    ArpPacketInfo, CompletePacket, DnsPacketInfo, EthernetPacketInfo, GtpPacketInfo,
+    HttpPacketInfo, IcmpPacketInfo, Icmpv6PacketInfo, Ieee80211PacketInfo, Ipv4PacketInfo,
+    Ipv6PacketInfo, LldpPacketInfo, MplsPacketInfo, PacketsData, RadiotapInfo, RadiusPacketInfo,
+    SctpPacketInfo, SsdpPacketInfo, SshPacketInfo, TcpPacketInfo, UdpPacketInfo, VlanPacketInfo,
+    VxlanPacketInfo,
 };
 
+/// How captured frames begin, chosen per interface when the capture starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkType {
+    Ethernet,
+    /// Linux `lo` delivers Ethernet frames with zeroed addresses, while BSD and macOS
+    /// prefix packets with a 4-byte address family instead of an Ethernet header.
+    Loopback,
+    /// Tunnels such as WireGuard or OpenVPN `tun` and some cellular modems have no
+    /// link-layer header: frames start at the IP header.
+    RawIp,
+    /// WiFi interfaces in monitor mode deliver 802.11 frames behind a radiotap header.
+    Radiotap,
+}
+
+impl LinkType {
+    /// `AF_INET` is 2 everywhere; `AF_INET6` is 10 on Linux and 24, 28 or 30 on the BSDs.
+    const NULL_ADDRESS_FAMILIES: [u32; 5] = [2, 10, 24, 28, 30];
+    const NULL_HEADER_LENGTH: usize = 4;
+
+    /// `ARPHRD_IEEE80211_RADIOTAP`, the device type Linux gives monitor-mode interfaces.
+    const ARPHRD_IEEE80211_RADIOTAP: &'static str = "803";
+
+    /// The pcap `LINKTYPE_*` value for `frame` as captured on this kind of link.
+    pub fn pcap_link_type(self, frame: &[u8]) -> u32 {
+        match self {
+            LinkType::Loopback | LinkType::RawIp if Self::has_null_header(frame) => 0,
+            LinkType::RawIp => 101,
+            LinkType::Radiotap => 127,
+            LinkType::Ethernet | LinkType::Loopback => 1,
+        }
+    }
+
+    fn for_interface(interface: &NetworkInterface) -> Self {
+        let has_mac = interface.mac.is_some_and(|mac| !mac.is_zero());
+        if Self::is_monitor_mode(interface) {
+            LinkType::Radiotap
+        } else if interface.is_loopback() {
+            LinkType::Loopback
+        } else if interface.is_point_to_point() || !has_mac {
+            LinkType::RawIp
+        } else {
+            LinkType::Ethernet
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn is_monitor_mode(interface: &NetworkInterface) -> bool {
+        std::fs::read_to_string(format!("/sys/class/net/{}/type", interface.name))
+            .is_ok_and(|device_type| device_type.trim() == Self::ARPHRD_IEEE80211_RADIOTAP)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn is_monitor_mode(_interface: &NetworkInterface) -> bool {
+        false
+    }
+
+    /// Detects the BSD null header used on loopback and macOS `utun` interfaces. The
+    /// family is in host byte order, so both orders are accepted; a bare IP header never
+    /// matches since its first byte carries the version.
+    fn has_null_header(frame: &[u8]) -> bool {
+        let Some(header) = frame.get(..Self::NULL_HEADER_LENGTH) else {
+            return false;
+        };
+        let header = [header[0], header[1], header[2], header[3]];
+        let families = [u32::from_le_bytes(header), u32::from_be_bytes(header)];
+        families
+            .iter()
+            .any(|family| Self::NULL_ADDRESS_FAMILIES.contains(family))
+    }
+}
+
+/// Settings of the datalink channels opened for each capture.
+#[derive(Clone, Copy)]
+pub struct CaptureOptions {
+    /// Bytes of each frame kept on the packet; headers are parsed from the whole frame.
+    pub snaplen: Option<usize>,
+    /// Also capture traffic addressed to other hosts on the segment.
+    pub promiscuous: bool,
+    /// The BPF buffer on macOS and the BSDs, where a larger one drops fewer packets in
+    /// bursts; on Linux, the largest frame that can be read in one go.
+    pub read_buffer_size: usize,
+    pub write_buffer_size: usize,
+    /// How long a read waits for a frame; also how long stopping or pausing can take.
+    pub read_timeout: Duration,
+    /// Keep only one of every N frames read from each interface; the rest are dropped
+    /// before being parsed.
+    pub sample: Option<u32>,
+    /// Ethernet frames end with their 4-byte FCS, as some NICs and drivers deliver them.
+    pub fcs: bool,
+}
+
+impl Default for CaptureOptions {
+    fn default() -> Self {
+        Self {
+            snaplen: None,
+            promiscuous: true,
+            read_buffer_size: Self::DEFAULT_BUFFER_SIZE,
+            write_buffer_size: Self::DEFAULT_BUFFER_SIZE,
+            read_timeout: Self::DEFAULT_READ_TIMEOUT,
+            sample: None,
+            fcs: false,
+        }
+    }
+}
+
+impl CaptureOptions {
+    pub const DEFAULT_BUFFER_SIZE: usize = 4096;
+    pub const BUFFER_SIZE_RANGE: (usize, usize) = (1024, 64 * 1024 * 1024);
+    pub const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(1);
+    pub const READ_TIMEOUT_MS_RANGE: (u64, u64) = (10, 10_000);
+}
+
 pub struct Sniffer {
-    pub network_interface: Option<NetworkInterface>,
+    /// Interfaces to capture from, one thread each, all feeding the same channel.
+    pub network_interfaces: Vec<NetworkInterface>,
     pub tx: Option<mpsc::Sender<Event>>,
     pub stop_signal: Arc<AtomicBool>,
     pub sniffer_paused: bool,
-    pub sniffer_handle: Option<JoinHandle<()>>,
-    pub packet_table_state: PacketTableState,
-    pub packets: Vec<CompletePacket>,
+    pub sniffer_handles: Vec<JoinHandle<()>>,
+    pub options: CaptureOptions,
+    /// Ports decoded as another protocol, read by the capture threads for every packet so
+    /// changes apply without restarting the capture.
+    pub decode_as: Arc<RwLock<DecodeAs>>,
+    /// Last packet id handed out, shared by the capture threads and kept across restarts
+    /// so ids stay unique in the table.
+    packet_ids: Arc<AtomicUsize>,
 }
 
 impl Sniffer {
+    const MAX_CONSECUTIVE_ERRORS: u32 = 10;
+    const ERROR_RETRY_DELAY: Duration = Duration::from_millis(100);
+    /// Enough for QinQ; anything deeper is almost certainly a malformed frame.
+    const MAX_VLAN_TAGS: usize = 4;
+    /// Tunnels inside tunnels decoded before giving up, so crafted nesting can't recurse
+    /// until the stack runs out.
+    const MAX_TUNNEL_DEPTH: usize = 4;
+
     pub fn new() -> Self {
         Sniffer {
-            network_interface: None,
+            network_interfaces: Vec::new(),
             tx: None,
             stop_signal: Arc::new(AtomicBool::new(false)),
             sniffer_paused: true,
-            sniffer_handle: None,
-            packet_table_state: PacketTableState::new(),
-            packets: Vec::new(),
+            sniffer_handles: Vec::new(),
+            options: CaptureOptions::default(),
+            decode_as: Arc::default(),
+            packet_ids: Arc::new(AtomicUsize::new(0)),
         }
     }
 
     pub fn stop(&mut self) {
         self.stop_signal.store(true, Ordering::Relaxed);
 
-        if let Some(handle) = self.sniffer_handle.take() {
+        for handle in self.sniffer_handles.drain(..) {
             let _ = handle.join();
         }
         self.sniffer_paused = true;
     }
 
     pub fn start(&mut self) {
-        let tx_to_sniffer = self.tx.clone();
-        if let Some(tx_to_sniffer) = tx_to_sniffer {
-            let interface = self.network_interface.clone();
-            if let Some(interface) = interface {
-                let stop_signal = Arc::new(AtomicBool::new(false));
-                self.stop_signal = stop_signal.clone();
-                let handle = thread::spawn(move || {
-                    Self::run(interface, tx_to_sniffer, stop_signal);
-                });
-
-                self.sniffer_handle = Some(handle);
-                self.sniffer_paused = false;
-            }
+        let Some(tx) = &self.tx else {
+            return;
+        };
+        if self.network_interfaces.is_empty() {
+            return;
         }
-    }
-
-    pub fn next_row(&mut self) {
-        self.packet_table_state.next_row(self.packets.len());
-    }
 
-    pub fn previous_row(&mut self) {
-        self.packet_table_state.previous_row(self.packets.len());
+        let stop_signal = Arc::new(AtomicBool::new(false));
+        self.stop_signal = stop_signal.clone();
+        for interface in self.network_interfaces.clone() {
+            let tx = tx.clone();
+            let stop_signal = stop_signal.clone();
+            let packet_ids = self.packet_ids.clone();
+            let options = self.options;
+            let decode_as = self.decode_as.clone();
+            self.sniffer_handles.push(thread::spawn(move || {
+                Self::run(interface, tx, stop_signal, packet_ids, options, decode_as);
+            }));
+        }
+        self.sniffer_paused = false;
     }
 
-    pub fn selected_packet_index(&self) -> Option<usize> {
-        self.packet_table_state.selected()
+    /// True once every capture thread has exited on its own, e.g. after an error.
+    pub fn is_finished(&self) -> bool {
+        !self.sniffer_handles.is_empty()
+            && self
+                .sniffer_handles
+                .iter()
+                .all(|handle| handle.is_finished())
     }
 
     fn run(
         network_interface: NetworkInterface,
         tx: mpsc::Sender<Event>,
         stop_signal: Arc<AtomicBool>,
+        packet_ids: Arc<AtomicUsize>,
+        options: CaptureOptions,
+        decode_as: Arc<RwLock<DecodeAs>>,
     ) {
-        let (_, mut receiver) = match pnet::datalink::channel(
+        if let Err(message) = Self::capture(
             &network_interface,
+            &tx,
+            &stop_signal,
+            &packet_ids,
+            options,
+            &decode_as,
+        ) {
+            let _ = tx.send(Event::SnifferError(message));
+        }
+        if !stop_signal.load(Ordering::Relaxed) {
+            let _ = tx.send(Event::SnifferStopped);
+        }
+    }
+
+    /// Captures until `stop_signal` is set or the app drops its receiver. Errors are
+    /// returned as translated messages ready to be shown to the user.
+    fn capture(
+        network_interface: &NetworkInterface,
+        tx: &mpsc::Sender<Event>,
+        stop_signal: &AtomicBool,
+        packet_ids: &AtomicUsize,
+        options: CaptureOptions,
+        decode_as: &RwLock<DecodeAs>,
+    ) -> Result<(), String> {
+        let (_, mut receiver) = match pnet::datalink::channel(
+            network_interface,
             pnet::datalink::Config {
-                write_buffer_size: 4096,
-                read_buffer_size: 4096,
-                read_timeout: Some(Duration::new(1, 0)),
+                write_buffer_size: options.write_buffer_size,
+                read_buffer_size: options.read_buffer_size,
+                read_timeout: Some(options.read_timeout),
                 write_timeout: None,
                 channel_type: ChannelType::Layer2,
                 bpf_fd_attempts: 1000,
                 linux_fanout: None,
-                promiscuous: true,
+                promiscuous: options.promiscuous,
                 socket_fd: None,
             },
         ) {
             Ok(Channel::Ethernet(tx, rx)) => (tx, rx),
             Ok(_) => {
-                println!("Tipo desconhecido de datalink channel",);
-                return;
+                return Err(strings::get("sniffer.unsupported_channel")
+                    .replace("{interface}", &network_interface.name));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                return Err(strings::get("sniffer.permission_denied")
+                    .replace("{interface}", &network_interface.name));
             }
-            Err(_) => {
-                println!("Erro ao criar datalink channel");
-                return;
+            Err(e) => {
+                return Err(strings::get("sniffer.channel_error")
+                    .replace("{interface}", &network_interface.name)
+                    .replace("{error}", &e.to_string()));
             }
         };
 
-        let mut packet_id = 0;
-
-        loop {
-            if stop_signal.load(Ordering::Relaxed) {
-                break;
-            }
+        let link_type = LinkType::for_interface(network_interface);
+        let mut consecutive_errors = 0;
+        let mut reassembler = Ipv4Reassembler::default();
+        let mut frames_read: u32 = 0;
 
+        while !stop_signal.load(Ordering::Relaxed) {
             match receiver.next() {
                 Ok(packet) => {
-                    packet_id += 1;
-                    let mut complete_packet = CompletePacket::new(packet_id);
-                    let ethernet_packet = EthernetPacket::new(packet);
-                    if let Some(ethernet_packet) = ethernet_packet {
-                        Self::handle_ethernet_packet(&ethernet_packet, &mut complete_packet);
-                    };
-                    tx.send(Event::PacketCaptured(complete_packet)).unwrap()
+                    consecutive_errors = 0;
+                    if let Some(sample) = options.sample {
+                        let keep = frames_read == 0;
+                        frames_read = (frames_read + 1) % sample;
+                        if !keep {
+                            continue;
+                        }
+                    }
+                    let decode_as = decode_as.read().unwrap_or_else(PoisonError::into_inner);
+                    let packet_id = packet_ids.fetch_add(1, Ordering::Relaxed) + 1;
+                    let mut complete_packet = CompletePacket::new(
+                        packet_id,
+                        &network_interface.name,
+                        packet,
+                        options.snaplen,
+                    );
+                    Self::handle_frame(
+                        link_type,
+                        packet,
+                        options.fcs,
+                        &decode_as,
+                        &mut complete_packet,
+                    );
+                    complete_packet.traffic_direction =
+                        TrafficDirection::classify(&complete_packet, network_interface);
+                    if let Some(fragment) = complete_packet.ipv4_fragment.take() {
+                        if let Some(datagram) = reassembler.push(fragment) {
+                            complete_packet.reassembled_fragments = Some(datagram.fragments);
+                            Self::handle_ip_next_header_protocols(
+                                &datagram.payload,
+                                datagram.protocol,
+                                &decode_as,
+                                &mut complete_packet,
+                            );
+                        }
+                    }
+                    if tx
+                        .send(Event::PacketCaptured(Box::new(complete_packet)))
+                        .is_err()
+                    {
+                        return Ok(());
+                    }
                 }
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => continue,
                 Err(e) => {
-                    if e.kind() == std::io::ErrorKind::TimedOut {
-                        continue;
+                    // A single read error can be transient; an interface that went down or
+                    // was unplugged keeps failing, so give up once the errors persist.
+                    consecutive_errors += 1;
+                    if consecutive_errors >= Self::MAX_CONSECUTIVE_ERRORS {
+                        return Err(strings::get("sniffer.capture_error")
+                            .replace("{interface}", &network_interface.name)
+                            .replace("{error}", &e.to_string()));
                     }
+                    thread::sleep(Self::ERROR_RETRY_DELAY);
                 }
             }
         }
+        Ok(())
+    }
+
+    /// Decodes `frame` into `complete_packet`. With `fcs`, Ethernet frames are decoded
+    /// without their last 4 bytes, which are checked as the frame check sequence.
+    pub fn handle_frame(
+        link_type: LinkType,
+        frame: &[u8],
+        fcs: bool,
+        decode_as: &DecodeAs,
+        complete_packet: &mut CompletePacket,
+    ) {
+        complete_packet.link_type = link_type;
+        match link_type {
+            LinkType::Loopback | LinkType::RawIp if LinkType::has_null_header(frame) => {
+                Self::handle_ip_packet(
+                    &frame[LinkType::NULL_HEADER_LENGTH..],
+                    decode_as,
+                    complete_packet,
+                );
+            }
+            LinkType::RawIp => Self::handle_ip_packet(frame, decode_as, complete_packet),
+            LinkType::Radiotap => Self::handle_radiotap_frame(frame, decode_as, complete_packet),
+            LinkType::Ethernet | LinkType::Loopback => {
+                let fcs_length = if fcs
+                    && link_type == LinkType::Ethernet
+                    && frame.len()
+                        >= EthernetPacket::minimum_packet_size() + EthernetPacketInfo::FCS_LENGTH
+                {
+                    EthernetPacketInfo::FCS_LENGTH
+                } else {
+                    0
+                };
+                let (frame, trailer) = frame.split_at(frame.len() - fcs_length);
+                let ethernet_packet = EthernetPacket::new(frame);
+                if let Some(ethernet_packet) = ethernet_packet {
+                    Self::handle_ethernet_packet(&ethernet_packet, decode_as, complete_packet);
+                }
+                if let (Some(PacketsData::EthernetPacket(ethernet)), Ok(trailer)) =
+                    (&mut complete_packet.layer_1, <[u8; 4]>::try_from(trailer))
+                {
+                    ethernet.fcs = Some(u32::from_le_bytes(trailer));
+                    ethernet.expected_fcs = Some(EthernetPacketInfo::crc32(frame));
+                }
+            }
+        }
+    }
+
+    /// Decodes the 802.11 frame behind the radiotap header, and what an unencrypted data
+    /// frame carries.
+    fn handle_radiotap_frame(
+        frame: &[u8],
+        decode_as: &DecodeAs,
+        complete_packet: &mut CompletePacket,
+    ) {
+        let Some(radiotap) = RadiotapInfo::parse(frame) else {
+            return;
+        };
+        let mut ieee80211_frame = &frame[radiotap.length..];
+        if radiotap.has_fcs() {
+            let length = ieee80211_frame
+                .len()
+                .saturating_sub(RadiotapInfo::FCS_LENGTH);
+            ieee80211_frame = &ieee80211_frame[..length];
+        }
+        let Some((mut ieee80211_packet, offset)) = Ieee80211PacketInfo::parse(ieee80211_frame)
+        else {
+            return;
+        };
+        ieee80211_packet.radiotap = Some(radiotap);
+        let ethertype = ieee80211_packet.ethertype;
+        complete_packet.set_layer1_packet(Some(PacketsData::Ieee80211Packet(ieee80211_packet)));
+        if let Some(ethertype) = ethertype {
+            Self::handle_ethertype(
+                ethertype,
+                &ieee80211_frame[offset..],
+                decode_as,
+                complete_packet,
+            );
+        }
+    }
+
+    /// Dispatches a packet without a link-layer header by its IP version nibble.
+    fn handle_ip_packet(packet: &[u8], decode_as: &DecodeAs, complete_packet: &mut CompletePacket) {
+        match packet.first().map(|byte| byte >> 4) {
+            Some(4) => {
+                let ipv4_packet = Ipv4Packet::new(packet);
+                if let Some(ipv4_packet) = ipv4_packet {
+                    Self::handle_ipv4_packet(&ipv4_packet, decode_as, complete_packet);
+                }
+            }
+            Some(6) => {
+                let ipv6_packet = Ipv6Packet::new(packet);
+                if let Some(ipv6_packet) = ipv6_packet {
+                    Self::handle_ipv6_packet(&ipv6_packet, decode_as, complete_packet);
+                }
+            }
+            _ => {}
+        }
     }
 
     fn handle_icmp_packet(icmp_packet: &IcmpPacket, complete_packet: &mut CompletePacket) {
@@ -170,21 +475,350 @@ impl Sniffer {
         ))));
     }
 
-    fn handle_tcp_packet(tcp_packet: &TcpPacket, complete_packet: &mut CompletePacket) {
-        complete_packet.set_layer3_packet(Some(PacketsData::TcpPacket(TcpPacketInfo::from(
-            tcp_packet,
-        ))));
+    fn handle_tcp_packet(
+        tcp_packet: &TcpPacket,
+        decode_as: &DecodeAs,
+        complete_packet: &mut CompletePacket,
+    ) {
+        let mut tcp_info = TcpPacketInfo::from(tcp_packet);
+        tcp_info.expected_checksum = match complete_packet.ip_addresses() {
+            Some((IpAddr::V4(source), IpAddr::V4(destination))) => {
+                Some(tcp::ipv4_checksum(tcp_packet, &source, &destination))
+            }
+            Some((IpAddr::V6(source), IpAddr::V6(destination))) => {
+                Some(tcp::ipv6_checksum(tcp_packet, &source, &destination))
+            }
+            _ => None,
+        };
+        complete_packet.set_layer3_packet(Some(PacketsData::TcpPacket(tcp_info)));
+        complete_packet.app_protocol_guess = app_protocol::guess(
+            Transport::Tcp,
+            tcp_packet.get_source(),
+            tcp_packet.get_destination(),
+            tcp_packet.payload(),
+        );
+        if let Some(protocol) = decode_as.get(
+            Transport::Tcp,
+            tcp_packet.get_source(),
+            tcp_packet.get_destination(),
+        ) {
+            Self::handle_app_protocol(
+                protocol,
+                Transport::Tcp,
+                tcp_packet.payload(),
+                decode_as,
+                complete_packet,
+            );
+            return;
+        }
+        // Recognized by its banner rather than its port, like the application guess.
+        if let Some(mut ssh_packet) = SshPacketInfo::parse(tcp_packet.payload()) {
+            ssh_packet.from_server = if tcp_packet.get_source() == SshPacketInfo::PORT {
+                Some(true)
+            } else if tcp_packet.get_destination() == SshPacketInfo::PORT {
+                Some(false)
+            } else {
+                None
+            };
+            Self::handle_ssh_packet(ssh_packet, complete_packet);
+            return;
+        }
+        if Self::uses_port(
+            tcp_packet.get_source(),
+            tcp_packet.get_destination(),
+            DnsPacketInfo::PORT,
+        ) {
+            let dns_packet = DnsPacketInfo::parse_tcp(tcp_packet.payload());
+            if let Some(dns_packet) = dns_packet {
+                Self::handle_dns_packet(dns_packet, complete_packet);
+            }
+        }
+        if Self::uses_port(
+            tcp_packet.get_source(),
+            tcp_packet.get_destination(),
+            HttpPacketInfo::PORT,
+        ) {
+            let http_packet = HttpPacketInfo::parse(tcp_packet.payload());
+            if let Some(http_packet) = http_packet {
+                Self::handle_http_packet(http_packet, complete_packet);
+            }
+        }
     }
 
-    fn handle_udp_packet(udp_packet: &UdpPacket, complete_packet: &mut CompletePacket) {
-        complete_packet.set_layer3_packet(Some(PacketsData::UdpPacket(UdpPacketInfo::from(
-            udp_packet,
-        ))));
+    fn handle_udp_packet(
+        udp_packet: &UdpPacket,
+        decode_as: &DecodeAs,
+        complete_packet: &mut CompletePacket,
+    ) {
+        let mut udp_info = UdpPacketInfo::from(udp_packet);
+        udp_info.expected_checksum = match complete_packet.ip_addresses() {
+            // A zero checksum means the sender didn't compute one, which IPv4 allows.
+            Some((IpAddr::V4(_), _)) if udp_packet.get_checksum() == 0 => None,
+            Some((IpAddr::V4(source), IpAddr::V4(destination))) => {
+                Some(udp::ipv4_checksum(udp_packet, &source, &destination))
+            }
+            Some((IpAddr::V6(source), IpAddr::V6(destination))) => {
+                Some(udp::ipv6_checksum(udp_packet, &source, &destination))
+            }
+            _ => None,
+        };
+        complete_packet.set_layer3_packet(Some(PacketsData::UdpPacket(udp_info)));
+        complete_packet.app_protocol_guess = app_protocol::guess(
+            Transport::Udp,
+            udp_packet.get_source(),
+            udp_packet.get_destination(),
+            udp_packet.payload(),
+        );
+        if let Some(protocol) = decode_as.get(
+            Transport::Udp,
+            udp_packet.get_source(),
+            udp_packet.get_destination(),
+        ) {
+            Self::handle_app_protocol(
+                protocol,
+                Transport::Udp,
+                udp_packet.payload(),
+                decode_as,
+                complete_packet,
+            );
+            return;
+        }
+        if Self::uses_port(
+            udp_packet.get_source(),
+            udp_packet.get_destination(),
+            DnsPacketInfo::PORT,
+        ) {
+            let dns_packet = DnsPacketInfo::parse(udp_packet.payload());
+            if let Some(dns_packet) = dns_packet {
+                Self::handle_dns_packet(dns_packet, complete_packet);
+            }
+        }
+        if Self::uses_port(
+            udp_packet.get_source(),
+            udp_packet.get_destination(),
+            DnsPacketInfo::MDNS_PORT,
+        ) {
+            let mdns_packet = DnsPacketInfo::parse(udp_packet.payload());
+            if let Some(mdns_packet) = mdns_packet {
+                Self::handle_mdns_packet(mdns_packet, complete_packet);
+            }
+        }
+        if Self::uses_port(
+            udp_packet.get_source(),
+            udp_packet.get_destination(),
+            SsdpPacketInfo::PORT,
+        ) {
+            let ssdp_packet = SsdpPacketInfo::parse(udp_packet.payload());
+            if let Some(ssdp_packet) = ssdp_packet {
+                Self::handle_ssdp_packet(ssdp_packet, complete_packet);
+            }
+        }
+        if Self::uses_port(
+            udp_packet.get_source(),
+            udp_packet.get_destination(),
+            RadiusPacketInfo::AUTH_PORT,
+        ) || Self::uses_port(
+            udp_packet.get_source(),
+            udp_packet.get_destination(),
+            RadiusPacketInfo::ACCOUNTING_PORT,
+        ) {
+            let radius_packet = RadiusPacketInfo::parse(udp_packet.payload());
+            if let Some(radius_packet) = radius_packet {
+                Self::handle_radius_packet(radius_packet, complete_packet);
+            }
+        }
+        if Self::uses_port(
+            udp_packet.get_source(),
+            udp_packet.get_destination(),
+            GtpPacketInfo::PORT,
+        ) {
+            let gtp_packet = GtpPacketInfo::parse(udp_packet.payload());
+            if let Some((gtp_packet, offset)) = gtp_packet {
+                Self::handle_gtp_packet(
+                    gtp_packet,
+                    &udp_packet.payload()[offset..],
+                    decode_as,
+                    complete_packet,
+                );
+            }
+        }
+        if Self::uses_port(
+            udp_packet.get_source(),
+            udp_packet.get_destination(),
+            VxlanPacketInfo::PORT,
+        ) {
+            let vxlan_packet = VxlanPacketInfo::parse(udp_packet.payload());
+            if let Some((vxlan_packet, offset)) = vxlan_packet {
+                Self::handle_vxlan_packet(
+                    vxlan_packet,
+                    &udp_packet.payload()[offset..],
+                    decode_as,
+                    complete_packet,
+                );
+            }
+        }
+    }
+
+    /// Runs the dissector "decode as" chose for the port, whatever its well-known port is.
+    fn handle_app_protocol(
+        protocol: AppProto,
+        transport: Transport,
+        payload: &[u8],
+        decode_as: &DecodeAs,
+        complete_packet: &mut CompletePacket,
+    ) {
+        match protocol {
+            AppProto::Dns => {
+                let dns_packet = match transport {
+                    Transport::Tcp => DnsPacketInfo::parse_tcp(payload),
+                    Transport::Udp => DnsPacketInfo::parse(payload),
+                };
+                if let Some(dns_packet) = dns_packet {
+                    Self::handle_dns_packet(dns_packet, complete_packet);
+                }
+            }
+            AppProto::Mdns => {
+                if let Some(mdns_packet) = DnsPacketInfo::parse(payload) {
+                    Self::handle_mdns_packet(mdns_packet, complete_packet);
+                }
+            }
+            AppProto::Http => {
+                if let Some(http_packet) = HttpPacketInfo::parse(payload) {
+                    Self::handle_http_packet(http_packet, complete_packet);
+                }
+            }
+            AppProto::Ssdp => {
+                if let Some(ssdp_packet) = SsdpPacketInfo::parse(payload) {
+                    Self::handle_ssdp_packet(ssdp_packet, complete_packet);
+                }
+            }
+            AppProto::Radius => {
+                if let Some(radius_packet) = RadiusPacketInfo::parse(payload) {
+                    Self::handle_radius_packet(radius_packet, complete_packet);
+                }
+            }
+            AppProto::Gtp => {
+                if let Some((gtp_packet, offset)) = GtpPacketInfo::parse(payload) {
+                    Self::handle_gtp_packet(
+                        gtp_packet,
+                        &payload[offset..],
+                        decode_as,
+                        complete_packet,
+                    );
+                }
+            }
+            AppProto::Vxlan => {
+                if let Some((vxlan_packet, offset)) = VxlanPacketInfo::parse(payload) {
+                    Self::handle_vxlan_packet(
+                        vxlan_packet,
+                        &payload[offset..],
+                        decode_as,
+                        complete_packet,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Decodes the subscriber packet of a G-PDU into the GTP layer. The handlers fill in
+    /// a scratch packet, so the tunneled layers don't replace the outer ones.
+    fn handle_gtp_packet(
+        mut gtp_packet: GtpPacketInfo,
+        payload: &[u8],
+        decode_as: &DecodeAs,
+        complete_packet: &mut CompletePacket,
+    ) {
+        if gtp_packet.message_type == GtpPacketInfo::G_PDU
+            && complete_packet.tunnel_depth < Self::MAX_TUNNEL_DEPTH
+        {
+            let mut inner_packet = CompletePacket::new(
+                complete_packet.id,
+                &complete_packet.interface,
+                payload,
+                Some(0),
+            );
+            inner_packet.tunnel_depth = complete_packet.tunnel_depth + 1;
+            Self::handle_ip_packet(payload, decode_as, &mut inner_packet);
+            gtp_packet.inner = [
+                inner_packet.layer_2,
+                inner_packet.layer_3,
+                inner_packet.layer_4,
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+        }
+        complete_packet.set_layer4_packet(Some(PacketsData::GtpPacket(gtp_packet)));
+    }
+
+    /// Decodes the tenant's Ethernet frame into the VXLAN layer, through a scratch packet
+    /// like GTP-U. VLAN tags inside the tunnel are skipped over but not kept.
+    fn handle_vxlan_packet(
+        mut vxlan_packet: VxlanPacketInfo,
+        payload: &[u8],
+        decode_as: &DecodeAs,
+        complete_packet: &mut CompletePacket,
+    ) {
+        let ethernet_packet = EthernetPacket::new(payload)
+            .filter(|_| complete_packet.tunnel_depth < Self::MAX_TUNNEL_DEPTH);
+        if let Some(ethernet_packet) = ethernet_packet {
+            let mut inner_packet = CompletePacket::new(
+                complete_packet.id,
+                &complete_packet.interface,
+                payload,
+                Some(0),
+            );
+            inner_packet.tunnel_depth = complete_packet.tunnel_depth + 1;
+            Self::handle_ethernet_packet(&ethernet_packet, decode_as, &mut inner_packet);
+            vxlan_packet.inner = [
+                inner_packet.layer_1,
+                inner_packet.layer_2,
+                inner_packet.layer_3,
+                inner_packet.layer_4,
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+        }
+        complete_packet.set_layer4_packet(Some(PacketsData::VxlanPacket(vxlan_packet)));
+    }
+
+    fn handle_sctp_packet(sctp_packet: SctpPacketInfo, complete_packet: &mut CompletePacket) {
+        complete_packet.set_layer3_packet(Some(PacketsData::SctpPacket(sctp_packet)));
+    }
+
+    fn handle_dns_packet(dns_packet: DnsPacketInfo, complete_packet: &mut CompletePacket) {
+        complete_packet.set_layer4_packet(Some(PacketsData::DnsPacket(dns_packet)));
+    }
+
+    fn handle_mdns_packet(mdns_packet: DnsPacketInfo, complete_packet: &mut CompletePacket) {
+        complete_packet.set_layer4_packet(Some(PacketsData::MdnsPacket(mdns_packet)));
+    }
+
+    fn handle_ssdp_packet(ssdp_packet: SsdpPacketInfo, complete_packet: &mut CompletePacket) {
+        complete_packet.set_layer4_packet(Some(PacketsData::SsdpPacket(ssdp_packet)));
+    }
+
+    fn handle_radius_packet(radius_packet: RadiusPacketInfo, complete_packet: &mut CompletePacket) {
+        complete_packet.set_layer4_packet(Some(PacketsData::RadiusPacket(radius_packet)));
+    }
+
+    fn handle_ssh_packet(ssh_packet: SshPacketInfo, complete_packet: &mut CompletePacket) {
+        complete_packet.set_layer4_packet(Some(PacketsData::SshPacket(ssh_packet)));
+    }
+
+    fn handle_http_packet(http_packet: HttpPacketInfo, complete_packet: &mut CompletePacket) {
+        complete_packet.set_layer4_packet(Some(PacketsData::HttpPacket(http_packet)));
+    }
+
+    fn uses_port(source: u16, destination: u16, port: u16) -> bool {
+        source == port || destination == port
     }
 
     fn handle_ip_next_header_protocols(
         packet: &[u8],
         protocol: IpNextHeaderProtocol,
+        decode_as: &DecodeAs,
         complete_packet: &mut CompletePacket,
     ) {
         match protocol {
@@ -203,37 +837,66 @@ impl Sniffer {
             IpNextHeaderProtocols::Tcp => {
                 let tcp_packet = TcpPacket::new(packet);
                 if let Some(tcp_packet) = tcp_packet {
-                    Self::handle_tcp_packet(&tcp_packet, complete_packet);
+                    Self::handle_tcp_packet(&tcp_packet, decode_as, complete_packet);
                 }
             }
             IpNextHeaderProtocols::Udp => {
                 let udp_packet = UdpPacket::new(packet);
                 if let Some(udp_packet) = udp_packet {
-                    Self::handle_udp_packet(&udp_packet, complete_packet);
+                    Self::handle_udp_packet(&udp_packet, decode_as, complete_packet);
+                }
+            }
+            IpNextHeaderProtocols::Sctp => {
+                let sctp_packet = SctpPacketInfo::parse(packet);
+                if let Some(sctp_packet) = sctp_packet {
+                    Self::handle_sctp_packet(sctp_packet, complete_packet);
                 }
             }
             _ => {}
         }
     }
 
-    fn handle_ipv6_packet(ipv6_packet: &Ipv6Packet, complete_packet: &mut CompletePacket) {
+    fn handle_ipv6_packet(
+        ipv6_packet: &Ipv6Packet,
+        decode_as: &DecodeAs,
+        complete_packet: &mut CompletePacket,
+    ) {
         complete_packet.set_layer2_packet(Some(PacketsData::Ipv6Packet(Ipv6PacketInfo::from(
             ipv6_packet,
         ))));
         Self::handle_ip_next_header_protocols(
             ipv6_packet.payload(),
             ipv6_packet.get_next_header(),
+            decode_as,
             complete_packet,
         );
     }
 
-    fn handle_ipv4_packet(ipv4_packet: &Ipv4Packet, complete_packet: &mut CompletePacket) {
-        complete_packet.set_layer2_packet(Some(PacketsData::Ipv4Packet(Ipv4PacketInfo::from(
-            ipv4_packet,
-        ))));
+    fn handle_ipv4_packet(
+        ipv4_packet: &Ipv4Packet,
+        decode_as: &DecodeAs,
+        complete_packet: &mut CompletePacket,
+    ) {
+        let ipv4_info = Ipv4PacketInfo::from(ipv4_packet);
+        if ipv4_info.is_fragment() {
+            // The transport layer is parsed once the whole datagram has been reassembled.
+            complete_packet.ipv4_fragment = Some(Ipv4Fragment {
+                source: ipv4_info.source,
+                destination: ipv4_info.destination,
+                identification: ipv4_info.identification,
+                protocol: ipv4_info.next_level_protocol,
+                offset: ipv4_info.fragment_offset_bytes(),
+                more_fragments: ipv4_info.more_fragments(),
+                payload: ipv4_packet.payload().to_vec(),
+            });
+            complete_packet.set_layer2_packet(Some(PacketsData::Ipv4Packet(ipv4_info)));
+            return;
+        }
+        complete_packet.set_layer2_packet(Some(PacketsData::Ipv4Packet(ipv4_info)));
         Self::handle_ip_next_header_protocols(
             ipv4_packet.payload(),
             ipv4_packet.get_next_level_protocol(),
+            decode_as,
             complete_packet,
         );
     }
@@ -244,46 +907,586 @@ impl Sniffer {
         ))));
     }
 
+    fn handle_lldp_packet(lldp_packet: LldpPacketInfo, complete_packet: &mut CompletePacket) {
+        complete_packet.set_layer2_packet(Some(PacketsData::LldpPacket(lldp_packet)));
+    }
+
     fn handle_ethernet_packet(
         ethernet_packet: &EthernetPacket,
+        decode_as: &DecodeAs,
         complete_packet: &mut CompletePacket,
     ) {
         complete_packet.set_layer1_packet(Some(PacketsData::EthernetPacket(
             EthernetPacketInfo::from(ethernet_packet),
         )));
-        match ethernet_packet.get_ethertype() {
+        Self::handle_ethertype(
+            ethernet_packet.get_ethertype(),
+            ethernet_packet.payload(),
+            decode_as,
+            complete_packet,
+        );
+    }
+
+    fn handle_ethertype(
+        ethertype: EtherType,
+        payload: &[u8],
+        decode_as: &DecodeAs,
+        complete_packet: &mut CompletePacket,
+    ) {
+        match ethertype {
             EtherTypes::Arp => {
-                let arp_packet = ArpPacket::new(ethernet_packet.payload());
+                let arp_packet = ArpPacket::new(payload);
                 if let Some(arp_packet) = arp_packet {
                     Self::handle_arp_packet(&arp_packet, complete_packet);
                 }
             }
+            EtherTypes::Lldp => {
+                let lldp_packet = LldpPacketInfo::parse(payload);
+                if let Some(lldp_packet) = lldp_packet {
+                    Self::handle_lldp_packet(lldp_packet, complete_packet);
+                }
+            }
             EtherTypes::Ipv4 => {
-                let ipv4_packet = Ipv4Packet::new(ethernet_packet.payload());
+                let ipv4_packet = Ipv4Packet::new(payload);
                 if let Some(ipv4_packet) = ipv4_packet {
-                    Self::handle_ipv4_packet(&ipv4_packet, complete_packet);
+                    Self::handle_ipv4_packet(&ipv4_packet, decode_as, complete_packet);
                 }
             }
             EtherTypes::Ipv6 => {
-                let ipv6_packet = Ipv6Packet::new(ethernet_packet.payload());
+                let ipv6_packet = Ipv6Packet::new(payload);
                 if let Some(ipv6_packet) = ipv6_packet {
-                    Self::handle_ipv6_packet(&ipv6_packet, complete_packet);
+                    Self::handle_ipv6_packet(&ipv6_packet, decode_as, complete_packet);
+                }
+            }
+            ethertype if MplsPacketInfo::is_mpls_ethertype(ethertype) => {
+                let mpls_packet = MplsPacketInfo::parse(payload, ethertype);
+                if let Some(mpls_packet) = mpls_packet {
+                    Self::handle_mpls_packet(mpls_packet, payload, decode_as, complete_packet);
+                }
+            }
+            ethertype if VlanPacketInfo::is_vlan_ethertype(ethertype) => {
+                if complete_packet.vlan_tags.len() >= Self::MAX_VLAN_TAGS {
+                    return;
+                }
+                let vlan_packet = VlanPacketInfo::parse(payload, ethertype);
+                if let Some(vlan_packet) = vlan_packet {
+                    Self::handle_vlan_packet(vlan_packet, payload, decode_as, complete_packet);
                 }
             }
             _ => {}
         }
     }
 
+    fn handle_vlan_packet(
+        vlan_packet: VlanPacketInfo,
+        payload: &[u8],
+        decode_as: &DecodeAs,
+        complete_packet: &mut CompletePacket,
+    ) {
+        let inner_ethertype = vlan_packet.ethertype;
+        complete_packet.vlan_tags.push(vlan_packet);
+        Self::handle_ethertype(
+            inner_ethertype,
+            &payload[VlanPacketInfo::HEADER_LENGTH..],
+            decode_as,
+            complete_packet,
+        );
+    }
+
+    /// MPLS doesn't say what it carries; IPv4 and IPv6 are recognised by their version
+    /// nibble, anything else (such as an Ethernet pseudowire) is left undecoded.
+    fn handle_mpls_packet(
+        mpls_packet: MplsPacketInfo,
+        payload: &[u8],
+        decode_as: &DecodeAs,
+        complete_packet: &mut CompletePacket,
+    ) {
+        let header_length = mpls_packet.header_length();
+        complete_packet.mpls = Some(mpls_packet);
+        Self::handle_ip_packet(&payload[header_length..], decode_as, complete_packet);
+    }
+
     pub fn register_event_handler(&mut self, tx: Sender<Event>) {
         self.tx = Some(tx);
     }
+}
 
-    pub fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) {
-        let widget = PacketTable::new(&self.packets, self.sniffer_paused.clone()).block(
-            Block::default()
-                .borders(Borders::ALL)
-        );
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv6Addr;
 
-        frame.render_stateful_widget(widget, area, &mut self.packet_table_state);
+    use super::*;
+
+    const MAC_A: [u8; 6] = [0x02, 0x00, 0x00, 0x00, 0x00, 0x0a];
+    const MAC_B: [u8; 6] = [0x02, 0x00, 0x00, 0x00, 0x00, 0x0b];
+    const IP_A: [u8; 4] = [192, 168, 1, 10];
+    const IP_B: [u8; 4] = [192, 168, 1, 1];
+
+    fn dissect(link_type: LinkType, frame: &[u8]) -> CompletePacket {
+        let mut packet = CompletePacket::new(1, "test", frame, None);
+        Sniffer::handle_frame(link_type, frame, false, &DecodeAs::default(), &mut packet);
+        packet
+    }
+
+    /// Dissects `frame` and formats it the ways the UI does, which reads the same fields.
+    fn dissect_and_describe(link_type: LinkType, frame: &[u8]) {
+        let packet = dissect(link_type, frame);
+        crate::clipboard::packet_summary(&packet);
+        crate::expert::ExpertAnalyzer::default().analyze(&packet);
+    }
+
+    fn ethernet(ethertype: u16, payload: &[u8]) -> Vec<u8> {
+        [&MAC_B[..], &MAC_A, &ethertype.to_be_bytes(), payload].concat()
+    }
+
+    fn ipv4(protocol: u8, payload: &[u8]) -> Vec<u8> {
+        let total_length = (20 + payload.len()) as u16;
+        let mut header = vec![0x45, 0x00];
+        header.extend(total_length.to_be_bytes());
+        header.extend([0x00, 0x01, 0x40, 0x00, 64, protocol, 0x00, 0x00]);
+        [&header[..], &IP_A, &IP_B, payload].concat()
+    }
+
+    fn ipv6(next_header: u8, payload: &[u8]) -> Vec<u8> {
+        let mut header = vec![0x60, 0x00, 0x00, 0x00];
+        header.extend((payload.len() as u16).to_be_bytes());
+        header.extend([next_header, 255]);
+        header.extend(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1).octets());
+        header.extend(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 2).octets());
+        [&header[..], payload].concat()
+    }
+
+    fn udp(source: u16, destination: u16, payload: &[u8]) -> Vec<u8> {
+        let length = (8 + payload.len()) as u16;
+        [
+            &source.to_be_bytes()[..],
+            &destination.to_be_bytes(),
+            &length.to_be_bytes(),
+            &[0, 0],
+            payload,
+        ]
+        .concat()
+    }
+
+    fn tcp(source: u16, destination: u16, payload: &[u8]) -> Vec<u8> {
+        [
+            &source.to_be_bytes()[..],
+            &destination.to_be_bytes(),
+            &[0, 0, 0, 1, 0, 0, 0, 0, 0x50, 0x18, 0xff, 0xff, 0, 0, 0, 0],
+            payload,
+        ]
+        .concat()
+    }
+
+    fn dns_query() -> Vec<u8> {
+        let mut message = vec![0x12, 0x34, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0];
+        message.extend(b"\x07example\x03com\x00");
+        message.extend([0, 1, 0, 1]);
+        message
+    }
+
+    fn radius_request() -> Vec<u8> {
+        let attributes = [
+            &[1, 7][..],
+            b"alice",
+            &[2, 18],
+            &[0xaa; 16],
+            &[4, 6, 10, 0, 0, 1],
+        ]
+        .concat();
+        let length = (20 + attributes.len()) as u16;
+        [&[1, 7][..], &length.to_be_bytes(), &[0x11; 16], &attributes].concat()
+    }
+
+    /// One frame of every protocol the sniffer decodes, with the layers expected.
+    fn sample_frames() -> Vec<(LinkType, Vec<u8>)> {
+        let neighbor_advert = [
+            &[136, 0, 0, 0, 0x60, 0, 0, 0][..],
+            &Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 2).octets(),
+            &[2, 1],
+            &MAC_B,
+        ]
+        .concat();
+        let icmp_unreachable = [
+            &[3, 3, 0, 0, 0, 0, 0, 0][..],
+            &ipv4(17, &udp(40000, 53, &[]))[..28],
+        ]
+        .concat();
+        let arp = [
+            &[0, 1, 0x08, 0x00, 6, 4, 0, 1][..],
+            &MAC_A,
+            &IP_A,
+            &[0; 6],
+            &IP_B,
+        ]
+        .concat();
+        let lldp = [
+            &[0x02, 0x07, 4][..],
+            &MAC_A,
+            &[0x04, 0x05, 5],
+            b"eth0",
+            &[0x06, 0x02, 0, 120, 0x0a, 0x06],
+            b"switch",
+            &[0, 0],
+        ]
+        .concat();
+        let sctp = [
+            &[0x0b, 0x59, 0x0b, 0x59, 0, 0, 0, 0, 0, 0, 0, 0][..],
+            &[1, 0, 0, 20],
+            &[0; 16],
+        ]
+        .concat();
+        let gtp_inner = ipv4(17, &udp(40000, 53, &dns_query()));
+        let gtp = [
+            &[0x30, 0xff][..],
+            &(gtp_inner.len() as u16).to_be_bytes(),
+            &[0, 0, 0, 1],
+            &gtp_inner,
+        ]
+        .concat();
+        // VNI 100 carrying a tenant's TCP segment.
+        let vxlan = [
+            &[0x08, 0, 0, 0, 0, 0, 100, 0][..],
+            &ethernet(0x0800, &ipv4(6, &tcp(40000, 443, &[]))),
+        ]
+        .concat();
+        let http = b"GET /index.html HTTP/1.1\r\nHost: example.com\r\n\r\n";
+        let ssdp = b"NOTIFY * HTTP/1.1\r\nNT: upnp:rootdevice\r\nNTS: ssdp:alive\r\n\r\n";
+        let dns_over_tcp = [&(dns_query().len() as u16).to_be_bytes()[..], &dns_query()].concat();
+        let radiotap_data = [
+            &[0, 0, 8, 0, 0, 0, 0, 0][..],
+            &[0x08, 0x01, 0, 0],
+            &MAC_B,
+            &MAC_A,
+            &MAC_B,
+            &[0, 0],
+            &[0xaa, 0xaa, 0x03, 0, 0, 0, 0x08, 0x00],
+            &ipv4(17, &udp(40000, 53, &dns_query())),
+        ]
+        .concat();
+
+        vec![
+            (
+                LinkType::Ethernet,
+                ethernet(0x0800, &ipv4(17, &udp(40000, 53, &dns_query()))),
+            ),
+            (
+                LinkType::Ethernet,
+                ethernet(0x0800, &ipv4(6, &tcp(40000, 53, &dns_over_tcp))),
+            ),
+            (
+                LinkType::Ethernet,
+                ethernet(0x0800, &ipv4(6, &tcp(40000, 80, http))),
+            ),
+            (
+                LinkType::Ethernet,
+                ethernet(0x0800, &ipv4(17, &udp(1900, 1900, ssdp))),
+            ),
+            (
+                LinkType::Ethernet,
+                ethernet(0x0800, &ipv4(17, &udp(5353, 5353, &dns_query()))),
+            ),
+            (
+                LinkType::Ethernet,
+                ethernet(0x0800, &ipv4(17, &udp(40000, 1812, &radius_request()))),
+            ),
+            (
+                LinkType::Ethernet,
+                ethernet(0x0800, &ipv4(17, &udp(2152, 2152, &gtp))),
+            ),
+            (
+                LinkType::Ethernet,
+                ethernet(0x0800, &ipv4(17, &udp(50000, 4789, &vxlan))),
+            ),
+            (
+                LinkType::Ethernet,
+                ethernet(
+                    0x0800,
+                    &ipv4(6, &tcp(22, 40000, b"SSH-2.0-OpenSSH_9.6\r\n")),
+                ),
+            ),
+            (LinkType::Ethernet, ethernet(0x0800, &ipv4(132, &sctp))),
+            (
+                LinkType::Ethernet,
+                ethernet(0x0800, &ipv4(1, &icmp_unreachable)),
+            ),
+            (
+                LinkType::Ethernet,
+                ethernet(0x86dd, &ipv6(58, &neighbor_advert)),
+            ),
+            (
+                LinkType::Ethernet,
+                ethernet(0x86dd, &ipv6(17, &udp(40000, 53, &dns_query()))),
+            ),
+            (LinkType::Ethernet, ethernet(0x0806, &arp)),
+            (LinkType::Ethernet, ethernet(0x88cc, &lldp)),
+            (
+                LinkType::Ethernet,
+                ethernet(
+                    0x8100,
+                    &[&[0x00, 0x64, 0x08, 0x00][..], &ipv4(6, &tcp(1, 2, &[]))].concat(),
+                ),
+            ),
+            (
+                LinkType::Ethernet,
+                ethernet(
+                    0x8847,
+                    &[&[0x00, 0x01, 0x01, 0x40][..], &ipv4(6, &tcp(1, 2, &[]))].concat(),
+                ),
+            ),
+            (LinkType::RawIp, ipv4(17, &udp(40000, 53, &dns_query()))),
+            (LinkType::Radiotap, radiotap_data),
+        ]
+    }
+
+    #[test]
+    fn decodes_every_layer_of_the_sample_frames() {
+        let layers = |packet: &CompletePacket| {
+            [
+                &packet.layer_1,
+                &packet.layer_2,
+                &packet.layer_3,
+                &packet.layer_4,
+            ]
+            .map(|layer| {
+                layer.as_ref().map(|layer| match layer {
+                    PacketsData::EthernetPacket(_) => "eth",
+                    PacketsData::Ieee80211Packet(_) => "wlan",
+                    PacketsData::ArpPacket(_) => "arp",
+                    PacketsData::LldpPacket(_) => "lldp",
+                    PacketsData::Ipv4Packet(_) => "ipv4",
+                    PacketsData::Ipv6Packet(_) => "ipv6",
+                    PacketsData::TcpPacket(_) => "tcp",
+                    PacketsData::UdpPacket(_) => "udp",
+                    PacketsData::IcmpPacket(_) => "icmp",
+                    PacketsData::Icmpv6Packet(_) => "icmpv6",
+                    PacketsData::SctpPacket(_) => "sctp",
+                    PacketsData::DnsPacket(_) => "dns",
+                    PacketsData::MdnsPacket(_) => "mdns",
+                    PacketsData::HttpPacket(_) => "http",
+                    PacketsData::SsdpPacket(_) => "ssdp",
+                    PacketsData::RadiusPacket(_) => "radius",
+                    PacketsData::SshPacket(_) => "ssh",
+                    PacketsData::GtpPacket(_) => "gtp",
+                    PacketsData::VxlanPacket(_) => "vxlan",
+                })
+            })
+        };
+        let expected = [
+            [Some("eth"), Some("ipv4"), Some("udp"), Some("dns")],
+            [Some("eth"), Some("ipv4"), Some("tcp"), Some("dns")],
+            [Some("eth"), Some("ipv4"), Some("tcp"), Some("http")],
+            [Some("eth"), Some("ipv4"), Some("udp"), Some("ssdp")],
+            [Some("eth"), Some("ipv4"), Some("udp"), Some("mdns")],
+            [Some("eth"), Some("ipv4"), Some("udp"), Some("radius")],
+            [Some("eth"), Some("ipv4"), Some("udp"), Some("gtp")],
+            [Some("eth"), Some("ipv4"), Some("udp"), Some("vxlan")],
+            [Some("eth"), Some("ipv4"), Some("tcp"), Some("ssh")],
+            [Some("eth"), Some("ipv4"), Some("sctp"), None],
+            [Some("eth"), Some("ipv4"), Some("icmp"), None],
+            [Some("eth"), Some("ipv6"), Some("icmpv6"), None],
+            [Some("eth"), Some("ipv6"), Some("udp"), Some("dns")],
+            [Some("eth"), Some("arp"), None, None],
+            [Some("eth"), Some("lldp"), None, None],
+            [Some("eth"), Some("ipv4"), Some("tcp"), None],
+            [Some("eth"), Some("ipv4"), Some("tcp"), None],
+            [None, Some("ipv4"), Some("udp"), Some("dns")],
+            [Some("wlan"), Some("ipv4"), Some("udp"), Some("dns")],
+        ];
+        let frames = sample_frames();
+        assert_eq!(frames.len(), expected.len());
+        for ((link_type, frame), expected) in frames.iter().zip(expected) {
+            assert_eq!(
+                layers(&dissect(*link_type, frame)),
+                expected,
+                "{frame:02x?}"
+            );
+        }
+    }
+
+    #[test]
+    fn decode_as_overrides_the_port() {
+        let http = b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n";
+        let frame = ethernet(0x0800, &ipv4(6, &tcp(50000, 8080, http)));
+        let decode = |decode_as: &DecodeAs| {
+            let mut packet = CompletePacket::new(1, "test", &frame, None);
+            Sniffer::handle_frame(LinkType::Ethernet, &frame, false, decode_as, &mut packet);
+            packet.layer_4
+        };
+
+        let mut decode_as = DecodeAs::default();
+        assert!(decode(&decode_as).is_none());
+        let (transport, port, protocol) = DecodeAs::parse_rule("TCP 8080 http").unwrap();
+        decode_as.set(transport, port, protocol);
+        assert!(matches!(
+            decode(&decode_as),
+            Some(PacketsData::HttpPacket(_))
+        ));
+        decode_as.set(Transport::Tcp, 8080, None);
+        assert!(decode(&decode_as).is_none());
+
+        assert!(DecodeAs::parse_rule("tcp 8080 radius").is_err());
+        assert!(DecodeAs::parse_rule("udp 99999 dns").is_err());
+        assert!(DecodeAs::parse_rule("8080 http").is_err());
+    }
+
+    #[test]
+    fn decodes_tags_and_tunnels() {
+        let frames = sample_frames();
+
+        let vlan = dissect(LinkType::Ethernet, &frames[15].1);
+        assert_eq!(vlan.vlan_tags.len(), 1);
+        assert_eq!(vlan.vlan_tags[0].vlan_id, 100);
+
+        let mpls = dissect(LinkType::Ethernet, &frames[16].1);
+        let labels = &mpls.mpls.expect("MPLS stack").labels;
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].label, 16);
+
+        let gtp = dissect(LinkType::Ethernet, &frames[6].1);
+        let Some(PacketsData::GtpPacket(gtp)) = gtp.layer_4 else {
+            panic!("expected GTP-U");
+        };
+        assert_eq!(gtp.teid, 1);
+        assert!(matches!(gtp.inner.last(), Some(PacketsData::DnsPacket(_))));
+
+        let vxlan = dissect(LinkType::Ethernet, &frames[7].1);
+        assert_eq!(vxlan.tunneled_ports(), Some((40000, 443)));
+        let Some(PacketsData::VxlanPacket(vxlan)) = vxlan.layer_4 else {
+            panic!("expected VXLAN");
+        };
+        assert_eq!(vxlan.vni, 100);
+        assert!(matches!(
+            vxlan.inner.as_slice(),
+            [
+                PacketsData::EthernetPacket(_),
+                PacketsData::Ipv4Packet(_),
+                PacketsData::TcpPacket(_)
+            ]
+        ));
+
+        let icmp = dissect(LinkType::Ethernet, &frames[10].1);
+        let Some(PacketsData::IcmpPacket(icmp)) = icmp.layer_3 else {
+            panic!("expected ICMP");
+        };
+        let original = icmp.original.expect("embedded packet");
+        assert_eq!(original.ports, Some((40000, 53)));
+    }
+
+    /// Every truncation and single-byte corruption of the sample frames, so the
+    /// dissectors are exercised well past the headers random bytes rarely get through.
+    #[test]
+    fn truncated_and_corrupted_frames_never_panic() {
+        for (link_type, frame) in sample_frames() {
+            for length in 0..frame.len() {
+                dissect_and_describe(link_type, &frame[..length]);
+            }
+            for index in 0..frame.len() {
+                for value in [
+                    0x00,
+                    0xff,
+                    frame[index] ^ 0x80,
+                    frame[index].wrapping_add(1),
+                ] {
+                    let mut corrupted = frame.clone();
+                    corrupted[index] = value;
+                    dissect_and_describe(link_type, &corrupted);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_frames_never_panic() {
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..100_000 {
+            let length = (next() % 200) as usize;
+            let frame: Vec<u8> = (0..length).map(|_| next() as u8).collect();
+            for link_type in [LinkType::Ethernet, LinkType::RawIp, LinkType::Radiotap] {
+                dissect_and_describe(link_type, &frame);
+            }
+        }
+    }
+
+    #[test]
+    fn the_fcs_is_stripped_before_decoding_and_checked() {
+        assert_eq!(EthernetPacketInfo::crc32(b"123456789"), 0xcbf4_3926);
+
+        let frame = ethernet(0x0800, &ipv4(17, &udp(5000, 5001, &[0; 32])));
+        let with_fcs = |fcs: u32| {
+            let mut packet = CompletePacket::new(1, "test", &frame, None);
+            let captured = [&frame[..], &fcs.to_le_bytes()].concat();
+            Sniffer::handle_frame(
+                LinkType::Ethernet,
+                &captured,
+                true,
+                &DecodeAs::default(),
+                &mut packet,
+            );
+            packet.length = captured.len();
+            packet
+        };
+
+        let good = with_fcs(EthernetPacketInfo::crc32(&frame));
+        let Some(PacketsData::EthernetPacket(ethernet)) = &good.layer_1 else {
+            panic!("not decoded as Ethernet");
+        };
+        assert_eq!(ethernet.payload_length, frame.len() - 14);
+        assert_eq!(ethernet.fcs, ethernet.expected_fcs);
+        assert!(matches!(&good.layer_3, Some(PacketsData::UdpPacket(udp)) if udp.length == 40));
+        let mut analyzer = crate::expert::ExpertAnalyzer::default();
+        assert!(analyzer.analyze(&good).is_empty());
+        let bad = analyzer.analyze(&with_fcs(0xdead_beef));
+        assert_eq!(bad.len(), 1);
+        assert!(bad[0].message.contains("0xdeadbeef"));
+    }
+
+    /// How many tunnel layers are nested in `packet`, the outermost included.
+    fn nested_tunnels(packet: &CompletePacket) -> usize {
+        let mut depth = 0;
+        let mut layer = packet.layer_4.as_ref();
+        while let Some(PacketsData::GtpPacket(GtpPacketInfo { inner, .. }))
+        | Some(PacketsData::VxlanPacket(VxlanPacketInfo { inner, .. })) = layer
+        {
+            depth += 1;
+            layer = inner.last();
+        }
+        depth
+    }
+
+    /// GTP-U inside GTP-U as deep as fits in a datagram, which used to recurse until the
+    /// stack overflowed.
+    #[test]
+    fn nested_gtp_tunnels_stop_at_the_depth_limit() {
+        let mut datagram = ipv4(17, &udp(40000, 53, &dns_query()));
+        for _ in 0..1500 {
+            let gtp = [
+                &[0x30, 0xff][..],
+                &(datagram.len() as u16).to_be_bytes(),
+                &[0, 0, 0, 1],
+                &datagram,
+            ]
+            .concat();
+            datagram = ipv4(17, &udp(2152, 2152, &gtp));
+        }
+        let packet = dissect(LinkType::Ethernet, &ethernet(0x0800, &datagram));
+        assert_eq!(nested_tunnels(&packet), Sniffer::MAX_TUNNEL_DEPTH + 1);
+    }
+
+    #[test]
+    fn nested_vxlan_tunnels_stop_at_the_depth_limit() {
+        let mut frame = ethernet(0x0800, &ipv4(17, &udp(40000, 53, &dns_query())));
+        for _ in 0..1200 {
+            let vxlan = [&[0x08, 0, 0, 0, 0, 0, 100, 0][..], &frame].concat();
+            frame = ethernet(0x0800, &ipv4(17, &udp(40000, 4789, &vxlan)));
+        }
+        let packet = dissect(LinkType::Ethernet, &frame);
+        assert_eq!(nested_tunnels(&packet), Sniffer::MAX_TUNNEL_DEPTH + 1);
     }
 }
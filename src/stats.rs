@@ -0,0 +1,168 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    net::IpAddr,
+    time::Duration,
+};
+
+use chrono::{DateTime, Local};
+
+use crate::{packet_data::CompletePacket, strings, widgets::charts::ChartWidget};
+
+/// Packets counted per wall-clock second, keeping only the most recent seconds.
+#[derive(Default)]
+pub struct PacketRate {
+    /// (Unix second, packets captured in it), oldest first.
+    buckets: VecDeque<(i64, u64)>,
+}
+
+impl PacketRate {
+    const KEPT_SECONDS: i64 = 120;
+
+    pub fn record(&mut self, packet: &CompletePacket) {
+        let second = packet.timestamp.timestamp();
+        match self.buckets.back_mut() {
+            Some((last, count)) if *last == second => *count += 1,
+            // Clocks can step back; count late packets in the newest bucket.
+            Some((last, count)) if *last > second => *count += 1,
+            _ => self.buckets.push_back((second, 1)),
+        }
+        while self
+            .buckets
+            .front()
+            .is_some_and(|(first, _)| second - first >= Self::KEPT_SECONDS)
+        {
+            self.buckets.pop_front();
+        }
+    }
+
+    /// Packets per second over the `seconds` seconds up to `now`, oldest first, with
+    /// zeros for seconds without traffic.
+    pub fn history(&self, now: DateTime<Local>, seconds: usize) -> Vec<u64> {
+        let now = now.timestamp();
+        let first = now - seconds as i64 + 1;
+        let mut history = vec![0; seconds];
+        for &(second, count) in &self.buckets {
+            if (first..=now).contains(&second) {
+                history[(second - first) as usize] = count;
+            }
+        }
+        history
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct TalkerTotals {
+    pub packets: u64,
+    pub bytes: u64,
+}
+
+/// Per-source IP totals, updated incrementally as packets are captured so the top
+/// talkers never need a full pass over the packet buffer.
+#[derive(Default)]
+pub struct TalkerStats {
+    by_source: HashMap<IpAddr, TalkerTotals>,
+}
+
+impl TalkerStats {
+    pub fn record(&mut self, packet: &CompletePacket) {
+        if let Some((source, _)) = packet.ip_addresses() {
+            let totals = self.by_source.entry(source).or_default();
+            totals.packets += 1;
+            totals.bytes += packet.length as u64;
+        }
+    }
+
+    pub fn top_by_bytes(&self, count: usize) -> Vec<(IpAddr, TalkerTotals)> {
+        self.top_by(count, |totals| totals.bytes)
+    }
+
+    pub fn top_by_packets(&self, count: usize) -> Vec<(IpAddr, TalkerTotals)> {
+        self.top_by(count, |totals| totals.packets)
+    }
+
+    fn top_by<F>(&self, count: usize, key: F) -> Vec<(IpAddr, TalkerTotals)>
+    where
+        F: Fn(&TalkerTotals) -> u64,
+    {
+        let mut talkers: Vec<(IpAddr, TalkerTotals)> = self
+            .by_source
+            .iter()
+            .map(|(ip, totals)| (*ip, *totals))
+            .collect();
+        talkers.sort_by(|(ip_a, a), (ip_b, b)| key(b).cmp(&key(a)).then(ip_a.cmp(ip_b)));
+        talkers.truncate(count);
+        talkers
+    }
+}
+
+/// Totals for a whole capture session, printed on exit with `--summary`.
+pub struct CaptureSummary {
+    pub duration: Duration,
+    pub packets: usize,
+    pub bytes: u64,
+    /// Packets per protocol, most common first.
+    pub protocols: Vec<(&'static str, usize)>,
+    pub top_talkers: Vec<(IpAddr, TalkerTotals)>,
+}
+
+impl CaptureSummary {
+    const TOP_TALKERS: usize = 5;
+    const OTHER_PROTOCOL: &'static str = "Other";
+
+    pub fn new(packets: &[CompletePacket], talker_stats: &TalkerStats, duration: Duration) -> Self {
+        let mut counts: HashMap<&'static str, usize> = HashMap::new();
+        for packet in packets {
+            let protocol = ChartWidget::get_protocol_name(packet).unwrap_or(Self::OTHER_PROTOCOL);
+            *counts.entry(protocol).or_insert(0) += 1;
+        }
+        let mut protocols: Vec<(&'static str, usize)> = counts.into_iter().collect();
+        protocols.sort_by(|(name_a, a), (name_b, b)| b.cmp(a).then(name_a.cmp(name_b)));
+
+        Self {
+            duration,
+            packets: packets.len(),
+            bytes: packets.iter().map(|packet| packet.length as u64).sum(),
+            protocols,
+            top_talkers: talker_stats.top_by_bytes(Self::TOP_TALKERS),
+        }
+    }
+}
+
+impl fmt::Display for CaptureSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.duration.as_secs();
+        writeln!(f, "{}", strings::get("summary.title"))?;
+        writeln!(
+            f,
+            "  {}: {:02}:{:02}:{:02}",
+            strings::get("summary.duration"),
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )?;
+        writeln!(f, "  {}: {}", strings::get("summary.packets"), self.packets)?;
+        writeln!(f, "  {}: {}", strings::get("summary.bytes"), self.bytes)?;
+
+        if !self.protocols.is_empty() {
+            writeln!(f, "  {}:", strings::get("summary.protocols"))?;
+            for (protocol, count) in &self.protocols {
+                writeln!(f, "    {protocol:<10} {count:>10}")?;
+            }
+        }
+        if !self.top_talkers.is_empty() {
+            writeln!(f, "  {}:", strings::get("summary.top_talkers"))?;
+            for (ip, totals) in &self.top_talkers {
+                writeln!(
+                    f,
+                    "    {:<39} {}",
+                    ip,
+                    strings::get("summary.talker")
+                        .replace("{packets}", &totals.packets.to_string())
+                        .replace("{bytes}", &totals.bytes.to_string())
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,1076 @@
+use std::{collections::HashMap, env, sync::OnceLock};
+
+use clap::ValueEnum;
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum Language {
+    #[value(name = "en")]
+    English,
+    #[value(name = "pt")]
+    Portuguese,
+}
+
+impl Language {
+    /// Picks the language from a locale such as `pt_BR.UTF-8`, defaulting to English.
+    pub fn from_env() -> Self {
+        match env::var("LANG") {
+            Ok(lang) if lang.starts_with("pt") => Language::Portuguese,
+            _ => Language::English,
+        }
+    }
+
+    fn table(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::English => ENGLISH,
+            Language::Portuguese => PORTUGUESE,
+        }
+    }
+}
+
+static STRINGS: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+
+pub fn init(language: Language) {
+    let mut strings: HashMap<&'static str, &'static str> = ENGLISH.iter().copied().collect();
+    strings.extend(language.table().iter().copied());
+    let _ = STRINGS.set(strings);
+}
+
+/// Resolves a UI string, falling back to English and then to the key itself.
+pub fn get(key: &'static str) -> &'static str {
+    STRINGS
+        .get()
+        .and_then(|strings| strings.get(key).copied())
+        .or_else(|| ENGLISH.iter().find(|(k, _)| *k == key).map(|(_, v)| *v))
+        .unwrap_or(key)
+}
+
+const ENGLISH: &[(&str, &str)] = &[
+    ("footer.quit", "quit"),
+    ("footer.navigate", "navigate"),
+    ("footer.first_last", "newest/oldest"),
+    ("footer.interface", "interface"),
+    ("footer.refresh", "refresh"),
+    ("footer.play_pause", "play/pause"),
+    ("footer.freeze", "freeze"),
+    ("footer.search", "search"),
+    ("footer.next_previous", "next/previous"),
+    ("footer.details", "details"),
+    ("footer.go_to_packet", "go to packet"),
+    ("footer.direction", "direction"),
+    ("footer.presets", "filter presets"),
+    ("footer.compact", "compact rows"),
+    ("footer.order", "order"),
+    ("action.quit", "Quit"),
+    ("action.next_row", "Select the next packet"),
+    ("action.previous_row", "Select the previous packet"),
+    ("action.first_row", "Select the first packet"),
+    ("action.last_row", "Select the last packet"),
+    ("action.page_down", "Page down"),
+    ("action.page_up", "Page up"),
+    ("action.next_interface", "Capture on the next interface"),
+    ("action.refresh_interfaces", "Refresh the interface list"),
+    ("action.toggle_sniffer", "Pause or resume the capture"),
+    ("action.toggle_freeze", "Freeze or unfreeze the packet list"),
+    ("action.search", "Search packets"),
+    ("action.next_match", "Next search match"),
+    ("action.previous_match", "Previous search match"),
+    ("action.filter", "Edit the display filter"),
+    ("action.toggle_dim", "Dim or hide packets the filter rejects"),
+    ("action.copy", "Copy the selected packet"),
+    ("action.conversations", "Show conversations"),
+    ("action.top_talkers", "Show or hide top talkers"),
+    ("action.chart_mode", "Switch the chart"),
+    ("action.toggle_name_resolution", "Show host names or addresses"),
+    ("action.toggle_popup", "Show packet details"),
+    ("action.shrink_top_panel", "Shrink the top panel"),
+    ("action.grow_top_panel", "Grow the top panel"),
+    ("action.shrink_chart", "Shrink the chart"),
+    ("action.grow_chart", "Grow the chart"),
+    ("action.toggle_chart", "Show or hide the chart"),
+    ("action.toggle_interfaces", "Show or hide the interfaces"),
+    ("action.expert_info", "Show expert info"),
+    ("action.toggle_bookmark", "Bookmark the selected packet"),
+    ("action.next_bookmark", "Next bookmark"),
+    ("action.previous_bookmark", "Previous bookmark"),
+    ("action.go_to_packet", "Go to packet by number"),
+    ("action.toggle_direction", "Filter by direction"),
+    ("action.filter_presets", "Open saved filters"),
+    ("action.toggle_compact", "Toggle compact rows"),
+    ("action.decode_as", "Decode a port as another protocol"),
+    ("action.event_log", "Show the event log"),
+    ("action.toggle_fullscreen", "Toggle a fullscreen packet table"),
+    ("action.columns", "Choose table columns"),
+    ("action.save_pcap", "Save filtered packets to pcap"),
+    ("action.compare", "Mark packet for comparison"),
+    ("action.toggle_order", "Toggle oldest or newest first"),
+    ("action.command_palette", "Open the command palette"),
+    ("action.follow_stream", "Follow the selected packet's UDP stream"),
+    ("action.comment", "Comment on the selected packet"),
+    ("action.cycle_theme", "Switch to the next color theme"),
+    ("action.toggle_protocol_1", "Show or hide TCP packets"),
+    ("action.toggle_protocol_2", "Show or hide UDP packets"),
+    ("action.toggle_protocol_3", "Show or hide ICMP packets"),
+    ("action.toggle_protocol_4", "Show or hide ICMPv6 packets"),
+    ("action.toggle_protocol_5", "Show or hide ARP packets"),
+    ("action.toggle_protocol_6", "Show or hide DNS packets"),
+    ("action.toggle_protocol_7", "Show or hide HTTP packets"),
+    ("action.toggle_protocol_8", "Show or hide SCTP packets"),
+    ("action.toggle_protocol_9", "Show or hide LLDP packets"),
+    ("action.sort_conversations", "Change the conversations' sort order"),
+    ("action.toggle_conversation_ports", "Split conversations by port"),
+    ("action.toggle_differences_only", "Show only the differing fields in a comparison"),
+    ("action.toggle_column", "Show or hide the selected column"),
+    ("action.move_column_down", "Move the selected column down"),
+    ("action.move_column_up", "Move the selected column up"),
+    ("action.save_preset", "Save the current filter as a preset"),
+    ("action.delete_preset", "Delete the selected saved preset"),
+    ("action.apply_preset_1", "Apply filter preset 1"),
+    ("action.apply_preset_2", "Apply filter preset 2"),
+    ("action.apply_preset_3", "Apply filter preset 3"),
+    ("action.apply_preset_4", "Apply filter preset 4"),
+    ("action.apply_preset_5", "Apply filter preset 5"),
+    ("action.apply_preset_6", "Apply filter preset 6"),
+    ("action.apply_preset_7", "Apply filter preset 7"),
+    ("action.apply_preset_8", "Apply filter preset 8"),
+    ("action.apply_preset_9", "Apply filter preset 9"),
+    ("action.expand_layer", "Expand the selected layer"),
+    ("action.collapse_layer", "Collapse the selected layer"),
+    ("action.toggle_layer", "Expand or collapse the selected layer"),
+    ("action.toggle_bytes_view", "Switch the bytes between hex and text"),
+    ("action.inspect_bytes", "Select bytes to inspect"),
+    ("action.toggle_all_bytes", "Show all bytes of the frame"),
+    ("action.maximize_popup", "Maximize the packet details"),
+    ("action.select_next_byte", "Extend the byte selection to the right"),
+    ("action.select_previous_byte", "Extend the byte selection to the left"),
+    ("action.restart_selection", "Restart the byte selection here"),
+    ("action.end_selection", "Finish the byte selection"),
+    ("palette.title", "Commands"),
+    ("palette.help", "type to search  ↑/↓: select  enter: run  esc: close"),
+    ("palette.no_match", "No command matches"),
+    ("footer.palette", "commands"),
+    ("footer.fullscreen", "fullscreen"),
+    ("footer.columns", "columns"),
+    ("footer.decode_as", "decode as"),
+    (
+        "status.json_out_error",
+        "Stopped writing packets to --json-out: {error}",
+    ),
+    (
+        "status.capture_limit",
+        "Capture limit reached: {packets} packets, {bytes} bytes in {seconds}s",
+    ),
+    ("columns.title", "Columns"),
+    ("columns.column", "Column"),
+    (
+        "columns.help",
+        "{toggle_column}: show/hide  {move_column_down}/{move_column_up}: move down/up  {columns}/esc: close",
+    ),
+    ("presets.title", "Filter presets"),
+    ("presets.name", "Name"),
+    ("presets.expression", "Filter"),
+    (
+        "presets.help",
+        "enter/{apply_preset_1}-{apply_preset_9}: apply  {save_preset}: save current filter  {delete_preset}: delete saved  {filter_presets}/esc: close",
+    ),
+    ("status.preset_applied", "Applied filter preset \"{name}\""),
+    (
+        "status.preset_saved",
+        "Saved the filter \"{filter}\" as a preset",
+    ),
+    (
+        "status.preset_exists",
+        "The filter \"{filter}\" is already a preset",
+    ),
+    (
+        "status.preset_no_filter",
+        "Set a display filter first to save it as a preset",
+    ),
+    (
+        "status.preset_not_saved",
+        "Only presets saved here can be deleted; edit the config file for the others",
+    ),
+    ("footer.go_to_prompt", "go to packet:"),
+    ("footer.save_pcap_prompt", "save filtered packets to:"),
+    ("footer.comment_prompt", "comment on packet {id}:"),
+    ("status.comment_set", "Comment saved on packet {id}"),
+    ("status.comment_removed", "Comment removed from packet {id}"),
+    (
+        "status.pcap_comments_dropped",
+        " ({count} comments left out: save as .pcapng to keep them)",
+    ),
+    ("footer.save_pcap", "save pcap"),
+    ("status.pcap_saved", "Saved {count} packets to {path}"),
+    (
+        "status.pcap_truncated",
+        " ({count} cut short by the snap length)",
+    ),
+    ("pcap.empty", "No packets pass the current filters; nothing saved"),
+    (
+        "pcap.not_retained",
+        "{count} packets kept none of their frame (snaplen = 0?); nothing saved",
+    ),
+    (
+        "pcap.mixed_links",
+        "Packets from {first} and {other} have different link types and can't share a .pcap file; save as .pcapng or narrow the filter",
+    ),
+    ("pcap.write_error", "Couldn't write {path}: {error}"),
+    (
+        "footer.decode_as_prompt",
+        "decode as (tcp|udp port protocol):",
+    ),
+    ("footer.filter", "filter"),
+    ("footer.dim", "dim/hide"),
+    ("footer.filter_prompt", "filter:"),
+    ("footer.copy", "copy"),
+    ("footer.conversations", "conversations"),
+    ("footer.top_talkers", "top talkers"),
+    ("footer.chart", "chart"),
+    ("footer.resolve", "names"),
+    ("footer.resize", "resize"),
+    ("footer.panels", "chart/interfaces"),
+    ("footer.expert_info", "expert info"),
+    ("footer.log", "event log"),
+    ("footer.bookmark", "bookmark"),
+    ("footer.bookmarks", "next/previous bookmark"),
+    ("footer.packet_rate", "{rate} pkt/s"),
+    (
+        "footer.interface_stats",
+        "{interface}: {received} rx, {dropped} dropped",
+    ),
+    ("footer.sampling", "sampling 1/{n}"),
+    ("table.title", "Packet list"),
+    ("summary.title", "Capture summary"),
+    ("summary.duration", "Duration"),
+    ("summary.packets", "Packets"),
+    ("summary.bytes", "Bytes"),
+    ("summary.protocols", "Protocols"),
+    ("summary.top_talkers", "Top talkers"),
+    ("summary.talker", "{packets} packets, {bytes} bytes"),
+    ("table.sniffer_paused", "Sniffer is paused."),
+    ("table.sniffer_running", "Sniffer is running."),
+    (
+        "table.sniffer_not_started",
+        "Capture not started, press {key} to begin.",
+    ),
+    ("table.frozen", "Frozen"),
+    ("table.direction", "Dir"),
+    ("table.direction_filter", "Showing: {direction}"),
+    ("direction.both", "inbound and outbound"),
+    ("direction.inbound", "inbound only"),
+    ("direction.outbound", "outbound only"),
+    ("table.captured", "{total} captured"),
+    ("table.captured_shown", "{shown} of {total} captured"),
+    ("table.filter", "Filter: {filter}"),
+    ("table.filter_dimmed", "Filter: {filter} (dimming the rest)"),
+    ("table.id", "ID"),
+    ("table.protocol", "Protocol"),
+    ("table.source", "Source"),
+    ("table.destination", "Destination"),
+    ("table.length", "Length"),
+    ("table.interface", "Iface"),
+    ("table.time", "Time"),
+    ("table.service", "Service"),
+    ("table.info", "Info"),
+    ("chart.title", "Captured Packets"),
+    ("chart.traffic", "{bytes}, avg {average} B"),
+    ("chart.sizes_title", "Packet Sizes (bytes)"),
+    ("interfaces.title", "Interfaces"),
+    ("interfaces.name", "Name"),
+    ("interfaces.mtu", "MTU"),
+    ("interfaces.mac", "MAC"),
+    ("interfaces.ipv4", "IPv4"),
+    ("interfaces.ipv6", "IPv6"),
+    (
+        "interfaces.none",
+        "No capturable interfaces found. Try --all-interfaces or run with elevated privileges, then press r to look again.",
+    ),
+    ("popup.title", "Packet Details"),
+    ("popup.captured_at", "Captured at"),
+    ("popup.comment", "Comment"),
+    ("popup.interface", "on"),
+    (
+        "popup.help",
+        "{next_row}/{previous_row}: layer  {toggle_layer}, {expand_layer}/{collapse_layer}: expand/collapse  {page_up}/{page_down}: TCP options  {toggle_bytes_view}: hex/text  {inspect_bytes}: inspect bytes  {toggle_all_bytes}: all bytes  {maximize_popup}: maximize  esc: close",
+    ),
+    (
+        "popup.selection_help",
+        "{select_previous_byte}/{select_next_byte}: byte  {next_row}/{previous_row}: line  {restart_selection}: restart range here  {end_selection}/esc: done",
+    ),
+    ("popup.inspector", "Selected bytes"),
+    ("popup.more_bytes", "… {hidden} more bytes (a: show all)"),
+    (
+        "popup.truncated",
+        "… {missing} more bytes not captured (snap length)",
+    ),
+    ("popup.bytes", "Bytes"),
+    ("popup.hex", "hex"),
+    ("popup.text", "text"),
+    (
+        "popup.bytes_partial",
+        "Bytes: {captured} of {length} bytes captured, {view}",
+    ),
+    ("popup.bytes_whole", "Bytes: {captured} bytes, {view}"),
+    ("popup.options", "Options"),
+    ("popup.tcp_options", "TCP Options"),
+    ("popup.option", "Option"),
+    ("popup.length", "Length"),
+    ("popup.value", "Value"),
+    ("popup.checksum_correct", "correct"),
+    ("popup.checksum_incorrect", "incorrect, expected {expected}"),
+    ("conversations.title", "Conversations"),
+    ("conversations.sorted_by", "sorted by"),
+    ("conversations.sort_bytes", "bytes"),
+    ("conversations.sort_packets", "packets"),
+    ("conversations.sort_duration", "duration"),
+    ("conversations.address_a", "Address A"),
+    ("conversations.address_b", "Address B"),
+    ("conversations.packets_a_b", "Packets A→B"),
+    ("conversations.packets_b_a", "Packets B→A"),
+    ("conversations.bytes_a_b", "Bytes A→B"),
+    ("conversations.bytes_b_a", "Bytes B→A"),
+    ("conversations.duration", "Duration"),
+    ("conversations.rtt", "RTT"),
+    ("geoip.invalid", "{path} is not a MaxMind DB file"),
+    ("geoip.disabled", "GeoIP annotation disabled: {error}"),
+    ("status.mouse_unavailable", "Mouse support unavailable: {error}"),
+    ("conversations.help", "{sort_conversations}: sort  {toggle_conversation_ports}: ports  {conversations}/esc: close"),
+    ("top_talkers.title", "Top Talkers"),
+    ("top_talkers.by_bytes", "By bytes"),
+    ("top_talkers.by_packets", "By packets"),
+    ("top_talkers.bytes", "Bytes"),
+    ("top_talkers.packets", "Pkts"),
+    ("expert.title", "Expert Info"),
+    (
+        "expert.counts",
+        "{errors} errors, {warnings} warnings, {notes} notes",
+    ),
+    ("expert.packet", "Packet"),
+    ("expert.severity", "Severity"),
+    ("expert.message", "Message"),
+    ("expert.help", "enter: go to packet  {expert_info}/esc: close"),
+    ("log.title", "Event Log"),
+    ("log.time", "Time"),
+    ("log.help", "{event_log}/esc: close"),
+    ("log.capturing", "Capturing on {interface}"),
+    ("expert.note", "Note"),
+    ("expert.warn", "Warning"),
+    ("expert.error", "Error"),
+    ("expert.retransmission", "TCP retransmission"),
+    ("expert.keep_alive", "TCP keep-alive"),
+    ("expert.reset", "TCP connection reset (RST)"),
+    ("expert.zero_window", "TCP zero window"),
+    ("expert.ttl_one", "TTL of 1, expires at the next router"),
+    (
+        "expert.over_mtu",
+        "Frame carries {payload} bytes, over the {mtu}-byte MTU of {interface} (jumbo frame or segmentation offload)",
+    ),
+    (
+        "expert.runt",
+        "Runt frame: {size} bytes, under the {minimum}-byte Ethernet minimum",
+    ),
+    ("expert.gratuitous_arp", "Gratuitous ARP for {ip}"),
+    (
+        "expert.bad_fcs",
+        "Bad frame check sequence {fcs}, expected {expected}: the frame is corrupt",
+    ),
+    (
+        "expert.duplicate_ip",
+        "Duplicate IP {ip} detected ({previous} vs {mac})",
+    ),
+    ("expert.icmp_error", "{protocol} error: {error}"),
+    ("expert.malformed", "Malformed {protocol}: header too short"),
+    (
+        "expert.truncated",
+        "Truncated {protocol}: {captured} of {expected} bytes",
+    ),
+    ("error_dialog.title", "Capture error"),
+    (
+        "app.too_small",
+        "Terminal too small (need at least {min_width}x{min_height}, have {width}x{height})",
+    ),
+    (
+        "error_dialog.help",
+        "esc: dismiss  i: next interface  q: quit",
+    ),
+    (
+        "sniffer.permission_denied",
+        "Permission denied opening {interface}: try running with sudo or granting cap_net_raw.",
+    ),
+    (
+        "sniffer.channel_error",
+        "Could not open {interface}: {error}",
+    ),
+    (
+        "sniffer.unsupported_channel",
+        "Unsupported datalink channel type on {interface}",
+    ),
+    (
+        "sniffer.capture_error",
+        "Capture on {interface} stopped: {error}. Press i to pick another interface.",
+    ),
+    ("status.search_no_match", "No packet matches \"{query}\""),
+    ("status.filter_invalid", "Invalid filter: {error}"),
+    ("status.bookmark_added", "Bookmarked packet {id}"),
+    (
+        "status.bookmark_removed",
+        "Removed the bookmark from packet {id}",
+    ),
+    ("status.no_bookmarks", "No bookmarked packet is shown"),
+    (
+        "status.packet_hidden",
+        "Packet {id} is hidden by the display filter or protocol toggles",
+    ),
+    ("status.packet_not_found", "Packet {id} not found"),
+    (
+        "status.compare_marked",
+        "Packet {id} marked for comparison; select another packet and compare again",
+    ),
+    ("status.compare_unmarked", "Comparison mark cleared"),
+    ("compare.title", "Packet comparison"),
+    ("compare.differences", "{count} differing fields"),
+    ("compare.field", "Field"),
+    ("compare.frame", "Frame"),
+    (
+        "compare.help",
+        "{next_row}/{previous_row}: move  {toggle_differences_only}: only differences/all fields  {compare}/esc: close",
+    ),
+    ("footer.compare", "compare"),
+    ("footer.follow", "follow"),
+    ("stream.title", "Follow UDP Stream: {client} ⇄ {server}"),
+    (
+        "stream.summary",
+        "{datagrams} datagrams, {sent} bytes from the client and {received} from the server",
+    ),
+    ("stream.help", "{next_row}/{previous_row}: scroll  {page_up}/{page_down}: page  {follow_stream}/esc: close"),
+    (
+        "status.not_udp",
+        "Select a UDP packet to follow its stream",
+    ),
+    (
+        "status.invalid_packet_id",
+        "\"{input}\" is not a packet number",
+    ),
+    (
+        "decode_as.missing_transport",
+        "a rule starts with tcp or udp, as in \"tcp 8080 http\"",
+    ),
+    (
+        "decode_as.missing_port",
+        "the transport must be followed by a port",
+    ),
+    (
+        "decode_as.unknown_protocol",
+        "\"{value}\" is not DNS, mDNS, HTTP, SSDP, RADIUS, GTP or VXLAN",
+    ),
+    (
+        "decode_as.wrong_transport",
+        "{protocol} can't be decoded over {transport}",
+    ),
+    (
+        "status.decode_as_invalid",
+        "Invalid decode as rule: {error}",
+    ),
+    (
+        "status.decode_as_set",
+        "New packets on {transport} port {port} are decoded as {protocol}",
+    ),
+    (
+        "status.decode_as_removed",
+        "New packets on {transport} port {port} are decoded by the usual ports again",
+    ),
+    ("filter.empty_negation", "\"!\" must be followed by a term"),
+    (
+        "filter.missing_port",
+        "\"port\" must be followed by a port number",
+    ),
+    ("filter.invalid_port", "\"{value}\" is not a valid port"),
+    (
+        "filter.missing_host",
+        "\"host\" must be followed by an IP address",
+    ),
+    (
+        "filter.invalid_host",
+        "\"{value}\" is not a valid IP address",
+    ),
+    (
+        "filter.missing_flag",
+        "\"flag\" must be followed by a TCP flag such as rst",
+    ),
+    (
+        "filter.invalid_flag",
+        "\"{value}\" is not a TCP flag (fin, syn, rst, psh, ack, urg, ece or cwr)",
+    ),
+    ("status.no_packet_selected", "No packet selected"),
+    (
+        "status.interface_not_found",
+        "Interface {interface} not found",
+    ),
+    (
+        "status.interfaces_refreshed",
+        "{count} interfaces available",
+    ),
+    (
+        "status.interface_gone",
+        "Interface {interface} is gone and no other is available",
+    ),
+    (
+        "settings.save_failed",
+        "Could not save settings to {path}: {error}",
+    ),
+    (
+        "status.interface_gone_switched",
+        "Interface {interface} is gone; switched to {new}",
+    ),
+    (
+        "status.resolve_disabled",
+        "Name resolution is disabled (--no-resolve)",
+    ),
+    ("status.theme", "Theme: {theme}"),
+    ("status.clipboard_copied", "Packet copied to the clipboard"),
+    (
+        "status.clipboard_error",
+        "Could not copy to the clipboard: {error}",
+    ),
+    ("status.clipboard_unavailable", "Clipboard is not available"),
+    ("status.config_read_error", "Could not read {path}: {error}"),
+    ("status.config_invalid", "Invalid config {path}: {error}"),
+    (
+        "status.config_out_of_range",
+        "{option} must be between {min} and {max}; using the default",
+    ),
+    (
+        "status.invalid_highlight_rule",
+        "Highlight rule \"{filter}\" ignored: {error}",
+    ),
+    ("keymap.unknown_key", "Unknown key \"{key}\" in [keys]"),
+    (
+        "keymap.conflict",
+        "Key {key} is bound to both {action} and {other}; using {action}",
+    ),
+];
+
+const PORTUGUESE: &[(&str, &str)] = &[
+    ("footer.quit", "sair"),
+    ("footer.navigate", "navegar"),
+    ("footer.first_last", "mais novo/mais antigo"),
+    ("footer.interface", "interface"),
+    ("footer.refresh", "atualizar"),
+    ("footer.play_pause", "play/pause"),
+    ("footer.freeze", "congelar"),
+    ("footer.search", "buscar"),
+    ("footer.next_previous", "próximo/anterior"),
+    ("footer.details", "detalhes"),
+    ("footer.go_to_packet", "ir para pacote"),
+    ("footer.direction", "direção"),
+    ("footer.presets", "filtros salvos"),
+    ("footer.compact", "linhas compactas"),
+    ("footer.order", "ordem"),
+    ("action.quit", "Sair"),
+    ("action.next_row", "Selecionar o próximo pacote"),
+    ("action.previous_row", "Selecionar o pacote anterior"),
+    ("action.first_row", "Selecionar o primeiro pacote"),
+    ("action.last_row", "Selecionar o último pacote"),
+    ("action.page_down", "Página abaixo"),
+    ("action.page_up", "Página acima"),
+    ("action.next_interface", "Capturar na próxima interface"),
+    ("action.refresh_interfaces", "Atualizar a lista de interfaces"),
+    ("action.toggle_sniffer", "Pausar ou retomar a captura"),
+    ("action.toggle_freeze", "Congelar ou descongelar a lista de pacotes"),
+    ("action.search", "Buscar pacotes"),
+    ("action.next_match", "Próximo resultado da busca"),
+    ("action.previous_match", "Resultado anterior da busca"),
+    ("action.filter", "Editar o filtro de exibição"),
+    ("action.toggle_dim", "Escurecer ou ocultar os pacotes fora do filtro"),
+    ("action.copy", "Copiar o pacote selecionado"),
+    ("action.conversations", "Mostrar conversas"),
+    ("action.top_talkers", "Mostrar ou ocultar os maiores emissores"),
+    ("action.chart_mode", "Trocar o gráfico"),
+    ("action.toggle_name_resolution", "Mostrar nomes ou endereços"),
+    ("action.toggle_popup", "Mostrar detalhes do pacote"),
+    ("action.shrink_top_panel", "Diminuir o painel superior"),
+    ("action.grow_top_panel", "Aumentar o painel superior"),
+    ("action.shrink_chart", "Diminuir o gráfico"),
+    ("action.grow_chart", "Aumentar o gráfico"),
+    ("action.toggle_chart", "Mostrar ou ocultar o gráfico"),
+    ("action.toggle_interfaces", "Mostrar ou ocultar as interfaces"),
+    ("action.expert_info", "Mostrar o diagnóstico"),
+    ("action.toggle_bookmark", "Marcar o pacote selecionado"),
+    ("action.next_bookmark", "Próxima marca"),
+    ("action.previous_bookmark", "Marca anterior"),
+    ("action.go_to_packet", "Ir para um pacote pelo número"),
+    ("action.toggle_direction", "Filtrar por direção"),
+    ("action.filter_presets", "Abrir os filtros salvos"),
+    ("action.toggle_compact", "Alternar linhas compactas"),
+    ("action.decode_as", "Decodificar uma porta como outro protocolo"),
+    ("action.event_log", "Mostrar o registro de eventos"),
+    ("action.toggle_fullscreen", "Alternar a tabela de pacotes em tela cheia"),
+    ("action.columns", "Escolher as colunas da tabela"),
+    ("action.save_pcap", "Salvar os pacotes filtrados em pcap"),
+    ("action.compare", "Marcar pacote para comparação"),
+    ("action.toggle_order", "Alternar mais antigos ou mais novos primeiro"),
+    ("action.command_palette", "Abrir a paleta de comandos"),
+    ("action.follow_stream", "Seguir o fluxo UDP do pacote selecionado"),
+    ("action.comment", "Comentar o pacote selecionado"),
+    ("action.cycle_theme", "Trocar para o próximo tema de cores"),
+    ("action.toggle_protocol_1", "Mostrar ou ocultar pacotes TCP"),
+    ("action.toggle_protocol_2", "Mostrar ou ocultar pacotes UDP"),
+    ("action.toggle_protocol_3", "Mostrar ou ocultar pacotes ICMP"),
+    ("action.toggle_protocol_4", "Mostrar ou ocultar pacotes ICMPv6"),
+    ("action.toggle_protocol_5", "Mostrar ou ocultar pacotes ARP"),
+    ("action.toggle_protocol_6", "Mostrar ou ocultar pacotes DNS"),
+    ("action.toggle_protocol_7", "Mostrar ou ocultar pacotes HTTP"),
+    ("action.toggle_protocol_8", "Mostrar ou ocultar pacotes SCTP"),
+    ("action.toggle_protocol_9", "Mostrar ou ocultar pacotes LLDP"),
+    ("action.sort_conversations", "Mudar a ordem das conversas"),
+    ("action.toggle_conversation_ports", "Separar as conversas por porta"),
+    ("action.toggle_differences_only", "Mostrar só os campos diferentes na comparação"),
+    ("action.toggle_column", "Mostrar ou ocultar a coluna selecionada"),
+    ("action.move_column_down", "Mover a coluna selecionada para baixo"),
+    ("action.move_column_up", "Mover a coluna selecionada para cima"),
+    ("action.save_preset", "Salvar o filtro atual"),
+    ("action.delete_preset", "Apagar o filtro salvo selecionado"),
+    ("action.apply_preset_1", "Aplicar o filtro salvo 1"),
+    ("action.apply_preset_2", "Aplicar o filtro salvo 2"),
+    ("action.apply_preset_3", "Aplicar o filtro salvo 3"),
+    ("action.apply_preset_4", "Aplicar o filtro salvo 4"),
+    ("action.apply_preset_5", "Aplicar o filtro salvo 5"),
+    ("action.apply_preset_6", "Aplicar o filtro salvo 6"),
+    ("action.apply_preset_7", "Aplicar o filtro salvo 7"),
+    ("action.apply_preset_8", "Aplicar o filtro salvo 8"),
+    ("action.apply_preset_9", "Aplicar o filtro salvo 9"),
+    ("action.expand_layer", "Expandir a camada selecionada"),
+    ("action.collapse_layer", "Recolher a camada selecionada"),
+    ("action.toggle_layer", "Expandir ou recolher a camada selecionada"),
+    ("action.toggle_bytes_view", "Alternar os bytes entre hexadecimal e texto"),
+    ("action.inspect_bytes", "Selecionar bytes para inspecionar"),
+    ("action.toggle_all_bytes", "Mostrar todos os bytes do quadro"),
+    ("action.maximize_popup", "Maximizar os detalhes do pacote"),
+    ("action.select_next_byte", "Estender a seleção de bytes para a direita"),
+    ("action.select_previous_byte", "Estender a seleção de bytes para a esquerda"),
+    ("action.restart_selection", "Recomeçar a seleção de bytes aqui"),
+    ("action.end_selection", "Concluir a seleção de bytes"),
+    ("palette.title", "Comandos"),
+    ("palette.help", "digite para buscar  ↑/↓: selecionar  enter: executar  esc: fechar"),
+    ("palette.no_match", "Nenhum comando encontrado"),
+    ("footer.palette", "comandos"),
+    ("footer.fullscreen", "tela cheia"),
+    ("footer.columns", "colunas"),
+    ("footer.decode_as", "decodificar como"),
+    (
+        "status.json_out_error",
+        "Pacotes não são mais gravados no --json-out: {error}",
+    ),
+    (
+        "status.capture_limit",
+        "Limite da captura atingido: {packets} pacotes, {bytes} bytes em {seconds}s",
+    ),
+    ("columns.title", "Colunas"),
+    ("columns.column", "Coluna"),
+    (
+        "columns.help",
+        "{toggle_column}: mostrar/ocultar  {move_column_down}/{move_column_up}: mover para baixo/cima  {columns}/esc: fechar",
+    ),
+    ("presets.title", "Filtros salvos"),
+    ("presets.name", "Nome"),
+    ("presets.expression", "Filtro"),
+    (
+        "presets.help",
+        "enter/{apply_preset_1}-{apply_preset_9}: aplicar  {save_preset}: salvar filtro atual  {delete_preset}: apagar salvo  {filter_presets}/esc: fechar",
+    ),
+    ("status.preset_applied", "Filtro salvo \"{name}\" aplicado"),
+    ("status.preset_saved", "Filtro \"{filter}\" salvo"),
+    (
+        "status.preset_exists",
+        "O filtro \"{filter}\" já está salvo",
+    ),
+    (
+        "status.preset_no_filter",
+        "Defina um filtro de exibição antes de salvá-lo",
+    ),
+    (
+        "status.preset_not_saved",
+        "Só os filtros salvos aqui podem ser apagados; os outros vêm do arquivo de configuração",
+    ),
+    ("footer.go_to_prompt", "ir para o pacote:"),
+    ("footer.save_pcap_prompt", "salvar pacotes filtrados em:"),
+    ("footer.comment_prompt", "comentário do pacote {id}:"),
+    ("status.comment_set", "Comentário salvo no pacote {id}"),
+    ("status.comment_removed", "Comentário removido do pacote {id}"),
+    (
+        "status.pcap_comments_dropped",
+        " ({count} comentários ficaram de fora: salve como .pcapng para mantê-los)",
+    ),
+    ("footer.save_pcap", "salvar pcap"),
+    ("status.pcap_saved", "{count} pacotes salvos em {path}"),
+    (
+        "status.pcap_truncated",
+        " ({count} cortados pelo snaplen)",
+    ),
+    ("pcap.empty", "Nenhum pacote passa pelos filtros atuais; nada foi salvo"),
+    (
+        "pcap.not_retained",
+        "{count} pacotes não guardaram nada do quadro (snaplen = 0?); nada foi salvo",
+    ),
+    (
+        "pcap.mixed_links",
+        "Pacotes de {first} e {other} têm tipos de enlace diferentes e não cabem no mesmo arquivo .pcap; salve como .pcapng ou restrinja o filtro",
+    ),
+    ("pcap.write_error", "Não foi possível gravar {path}: {error}"),
+    (
+        "footer.decode_as_prompt",
+        "decodificar como (tcp|udp porta protocolo):",
+    ),
+    ("footer.filter", "filtro"),
+    ("footer.dim", "escurecer/ocultar"),
+    ("footer.filter_prompt", "filtro:"),
+    ("footer.copy", "copiar"),
+    ("footer.conversations", "conversas"),
+    ("footer.top_talkers", "emissores"),
+    ("footer.chart", "gráfico"),
+    ("footer.resolve", "nomes"),
+    ("footer.resize", "redimensionar"),
+    ("footer.panels", "gráfico/interfaces"),
+    ("footer.expert_info", "diagnóstico"),
+    ("footer.log", "registro"),
+    ("footer.bookmark", "marcar"),
+    ("footer.bookmarks", "próxima/anterior marca"),
+    ("footer.packet_rate", "{rate} pct/s"),
+    (
+        "footer.interface_stats",
+        "{interface}: {received} recebidos, {dropped} descartados",
+    ),
+    ("footer.sampling", "amostragem 1/{n}"),
+    ("table.title", "Lista de pacotes"),
+    ("summary.title", "Resumo da captura"),
+    ("summary.duration", "Duração"),
+    ("summary.packets", "Pacotes"),
+    ("summary.bytes", "Bytes"),
+    ("summary.protocols", "Protocolos"),
+    ("summary.top_talkers", "Maiores emissores"),
+    ("summary.talker", "{packets} pacotes, {bytes} bytes"),
+    ("table.sniffer_paused", "Sniffer está pausado."),
+    ("table.sniffer_running", "Sniffer está rodando."),
+    (
+        "table.sniffer_not_started",
+        "Captura não iniciada, pressione {key} para começar.",
+    ),
+    ("table.frozen", "Congelado"),
+    ("table.direction", "Dir"),
+    ("table.direction_filter", "Mostrando: {direction}"),
+    ("direction.both", "entrada e saída"),
+    ("direction.inbound", "só entrada"),
+    ("direction.outbound", "só saída"),
+    ("table.captured", "{total} capturados"),
+    ("table.captured_shown", "{shown} de {total} capturados"),
+    ("table.filter", "Filtro: {filter}"),
+    (
+        "table.filter_dimmed",
+        "Filtro: {filter} (escurecendo o resto)",
+    ),
+    ("table.id", "ID"),
+    ("table.protocol", "Protocolo"),
+    ("table.source", "Origem"),
+    ("table.destination", "Destino"),
+    ("table.length", "Tamanho"),
+    ("table.interface", "Interf."),
+    ("table.time", "Hora"),
+    ("table.service", "Serviço"),
+    ("table.info", "Info"),
+    ("chart.title", "Pacotes Capturados"),
+    ("chart.traffic", "{bytes}, média {average} B"),
+    ("chart.sizes_title", "Tamanhos dos Pacotes (bytes)"),
+    ("interfaces.title", "Interfaces"),
+    ("interfaces.name", "Nome"),
+    ("interfaces.mtu", "MTU"),
+    ("interfaces.mac", "MAC"),
+    ("interfaces.ipv4", "IPv4"),
+    ("interfaces.ipv6", "IPv6"),
+    (
+        "interfaces.none",
+        "Nenhuma interface disponível para captura. Tente --all-interfaces ou execute com privilégios elevados, e pressione r para procurar de novo.",
+    ),
+    ("popup.title", "Detalhes do Pacote"),
+    ("popup.captured_at", "Capturado em"),
+    ("popup.comment", "Comentário"),
+    ("popup.interface", "na interface"),
+    (
+        "popup.help",
+        "{next_row}/{previous_row}: camada  {toggle_layer}, {expand_layer}/{collapse_layer}: expandir/recolher  {page_up}/{page_down}: opções TCP  {toggle_bytes_view}: hex/texto  {inspect_bytes}: inspecionar bytes  {toggle_all_bytes}: todos os bytes  {maximize_popup}: maximizar  esc: fechar",
+    ),
+    (
+        "popup.selection_help",
+        "{select_previous_byte}/{select_next_byte}: byte  {next_row}/{previous_row}: linha  {restart_selection}: recomeçar intervalo aqui  {end_selection}/esc: concluir",
+    ),
+    ("popup.inspector", "Bytes selecionados"),
+    ("popup.more_bytes", "… mais {hidden} bytes (a: mostrar todos)"),
+    (
+        "popup.truncated",
+        "… mais {missing} bytes não capturados (snap length)",
+    ),
+    ("popup.bytes", "Bytes"),
+    ("popup.hex", "hex"),
+    ("popup.text", "texto"),
+    (
+        "popup.bytes_partial",
+        "Bytes: {captured} de {length} bytes capturados, {view}",
+    ),
+    ("popup.bytes_whole", "Bytes: {captured} bytes, {view}"),
+    ("popup.options", "Opções"),
+    ("popup.tcp_options", "Opções TCP"),
+    ("popup.option", "Opção"),
+    ("popup.length", "Tamanho"),
+    ("popup.value", "Valor"),
+    ("popup.checksum_correct", "correto"),
+    ("popup.checksum_incorrect", "incorreto, esperado {expected}"),
+    ("conversations.title", "Conversas"),
+    ("conversations.sorted_by", "ordenado por"),
+    ("conversations.sort_bytes", "bytes"),
+    ("conversations.sort_packets", "pacotes"),
+    ("conversations.sort_duration", "duração"),
+    ("conversations.address_a", "Endereço A"),
+    ("conversations.address_b", "Endereço B"),
+    ("conversations.packets_a_b", "Pacotes A→B"),
+    ("conversations.packets_b_a", "Pacotes B→A"),
+    ("conversations.bytes_a_b", "Bytes A→B"),
+    ("conversations.bytes_b_a", "Bytes B→A"),
+    ("conversations.duration", "Duração"),
+    ("conversations.rtt", "RTT"),
+    ("geoip.invalid", "{path} não é um arquivo MaxMind DB"),
+    ("geoip.disabled", "Anotação GeoIP desativada: {error}"),
+    ("status.mouse_unavailable", "Suporte a mouse indisponível: {error}"),
+    ("conversations.help", "{sort_conversations}: ordenar  {toggle_conversation_ports}: portas  {conversations}/esc: fechar"),
+    ("top_talkers.title", "Maiores Emissores"),
+    ("top_talkers.by_bytes", "Por bytes"),
+    ("top_talkers.by_packets", "Por pacotes"),
+    ("top_talkers.bytes", "Bytes"),
+    ("top_talkers.packets", "Pcts"),
+    ("expert.title", "Diagnóstico"),
+    (
+        "expert.counts",
+        "{errors} erros, {warnings} avisos, {notes} notas",
+    ),
+    ("expert.packet", "Pacote"),
+    ("expert.severity", "Gravidade"),
+    ("expert.message", "Mensagem"),
+    ("expert.help", "enter: ir ao pacote  {expert_info}/esc: fechar"),
+    ("log.title", "Registro de Eventos"),
+    ("log.time", "Hora"),
+    ("log.help", "{event_log}/esc: fechar"),
+    ("log.capturing", "Capturando em {interface}"),
+    ("expert.note", "Nota"),
+    ("expert.warn", "Aviso"),
+    ("expert.error", "Erro"),
+    ("expert.retransmission", "Retransmissão TCP"),
+    ("expert.keep_alive", "Keep-alive TCP"),
+    ("expert.reset", "Conexão TCP reiniciada (RST)"),
+    ("expert.zero_window", "Janela TCP zerada"),
+    ("expert.ttl_one", "TTL 1, expira no próximo roteador"),
+    (
+        "expert.over_mtu",
+        "Quadro leva {payload} bytes, acima do MTU de {mtu} bytes de {interface} (quadro jumbo ou offload de segmentação)",
+    ),
+    (
+        "expert.runt",
+        "Quadro curto demais (runt): {size} bytes, abaixo do mínimo Ethernet de {minimum} bytes",
+    ),
+    ("expert.gratuitous_arp", "ARP gratuito para {ip}"),
+    (
+        "expert.bad_fcs",
+        "FCS incorreto {fcs}, esperado {expected}: o quadro está corrompido",
+    ),
+    (
+        "expert.duplicate_ip",
+        "IP duplicado {ip} detectado ({previous} e {mac})",
+    ),
+    ("expert.icmp_error", "Erro {protocol}: {error}"),
+    (
+        "expert.malformed",
+        "{protocol} malformado: cabeçalho curto demais",
+    ),
+    (
+        "expert.truncated",
+        "{protocol} truncado: {captured} de {expected} bytes",
+    ),
+    ("error_dialog.title", "Erro de captura"),
+    (
+        "app.too_small",
+        "Terminal pequeno demais (mínimo {min_width}x{min_height}, atual {width}x{height})",
+    ),
+    (
+        "error_dialog.help",
+        "esc: fechar  i: próxima interface  q: sair",
+    ),
+    (
+        "sniffer.permission_denied",
+        "Permissão negada ao abrir {interface}: tente executar com sudo ou conceder cap_net_raw.",
+    ),
+    (
+        "sniffer.channel_error",
+        "Não foi possível abrir {interface}: {error}",
+    ),
+    (
+        "sniffer.unsupported_channel",
+        "Tipo de canal de enlace não suportado em {interface}",
+    ),
+    (
+        "sniffer.capture_error",
+        "Captura em {interface} interrompida: {error}. Pressione i para escolher outra interface.",
+    ),
+    (
+        "status.search_no_match",
+        "Nenhum pacote encontrado para \"{query}\"",
+    ),
+    ("status.filter_invalid", "Filtro inválido: {error}"),
+    ("status.bookmark_added", "Pacote {id} marcado"),
+    ("status.bookmark_removed", "Marca removida do pacote {id}"),
+    ("status.no_bookmarks", "Nenhum pacote marcado visível"),
+    (
+        "status.packet_hidden",
+        "O pacote {id} está oculto pelo filtro de exibição ou pelos protocolos desligados",
+    ),
+    ("status.packet_not_found", "Pacote {id} não encontrado"),
+    (
+        "status.compare_marked",
+        "Pacote {id} marcado para comparação; selecione outro pacote e compare de novo",
+    ),
+    ("status.compare_unmarked", "Marca de comparação removida"),
+    ("compare.title", "Comparação de pacotes"),
+    ("compare.differences", "{count} campos diferentes"),
+    ("compare.field", "Campo"),
+    ("compare.frame", "Quadro"),
+    (
+        "compare.help",
+        "{next_row}/{previous_row}: mover  {toggle_differences_only}: só diferenças/todos os campos  {compare}/esc: fechar",
+    ),
+    ("footer.compare", "comparar"),
+    ("footer.follow", "seguir"),
+    ("stream.title", "Seguir Fluxo UDP: {client} ⇄ {server}"),
+    (
+        "stream.summary",
+        "{datagrams} datagramas, {sent} bytes do cliente e {received} do servidor",
+    ),
+    ("stream.help", "{next_row}/{previous_row}: rolar  {page_up}/{page_down}: página  {follow_stream}/esc: fechar"),
+    (
+        "status.not_udp",
+        "Selecione um pacote UDP para seguir seu fluxo",
+    ),
+    (
+        "status.invalid_packet_id",
+        "\"{input}\" não é um número de pacote",
+    ),
+    (
+        "decode_as.missing_transport",
+        "uma regra começa com tcp ou udp, como em \"tcp 8080 http\"",
+    ),
+    (
+        "decode_as.missing_port",
+        "o transporte deve ser seguido de uma porta",
+    ),
+    (
+        "decode_as.unknown_protocol",
+        "\"{value}\" não é DNS, mDNS, HTTP, SSDP, RADIUS, GTP ou VXLAN",
+    ),
+    (
+        "decode_as.wrong_transport",
+        "{protocol} não pode ser decodificado sobre {transport}",
+    ),
+    (
+        "status.decode_as_invalid",
+        "Regra de decodificação inválida: {error}",
+    ),
+    (
+        "status.decode_as_set",
+        "Novos pacotes na porta {port} {transport} são decodificados como {protocol}",
+    ),
+    (
+        "status.decode_as_removed",
+        "Novos pacotes na porta {port} {transport} voltam a ser decodificados pelas portas usuais",
+    ),
+    (
+        "filter.empty_negation",
+        "\"!\" deve ser seguido de um termo",
+    ),
+    (
+        "filter.missing_port",
+        "\"port\" deve ser seguido de um número de porta",
+    ),
+    ("filter.invalid_port", "\"{value}\" não é uma porta válida"),
+    (
+        "filter.missing_host",
+        "\"host\" deve ser seguido de um endereço IP",
+    ),
+    (
+        "filter.invalid_host",
+        "\"{value}\" não é um endereço IP válido",
+    ),
+    (
+        "filter.missing_flag",
+        "\"flag\" deve ser seguido de uma flag TCP como rst",
+    ),
+    (
+        "filter.invalid_flag",
+        "\"{value}\" não é uma flag TCP (fin, syn, rst, psh, ack, urg, ece ou cwr)",
+    ),
+    ("status.no_packet_selected", "Nenhum pacote selecionado"),
+    (
+        "status.interface_not_found",
+        "Interface {interface} não encontrada",
+    ),
+    (
+        "status.interfaces_refreshed",
+        "{count} interfaces disponíveis",
+    ),
+    (
+        "status.interface_gone",
+        "A interface {interface} sumiu e nenhuma outra está disponível",
+    ),
+    (
+        "settings.save_failed",
+        "Não foi possível salvar as configurações em {path}: {error}",
+    ),
+    (
+        "status.interface_gone_switched",
+        "A interface {interface} sumiu; trocada para {new}",
+    ),
+    (
+        "status.resolve_disabled",
+        "Resolução de nomes desativada (--no-resolve)",
+    ),
+    ("status.theme", "Tema: {theme}"),
+    (
+        "status.clipboard_copied",
+        "Pacote copiado para a área de transferência",
+    ),
+    (
+        "status.clipboard_error",
+        "Não foi possível copiar para a área de transferência: {error}",
+    ),
+    (
+        "status.clipboard_unavailable",
+        "Área de transferência indisponível",
+    ),
+    (
+        "status.config_read_error",
+        "Não foi possível ler {path}: {error}",
+    ),
+    (
+        "status.config_invalid",
+        "Configuração inválida em {path}: {error}",
+    ),
+    (
+        "status.config_out_of_range",
+        "{option} deve estar entre {min} e {max}; usando o padrão",
+    ),
+    (
+        "status.invalid_highlight_rule",
+        "Regra de destaque \"{filter}\" ignorada: {error}",
+    ),
+    (
+        "keymap.unknown_key",
+        "Tecla desconhecida \"{key}\" em [keys]",
+    ),
+    (
+        "keymap.conflict",
+        "A tecla {key} está associada a {action} e a {other}; usando {action}",
+    ),
+];
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use ratatui::style::{Color, Style};
+use serde::Deserialize;
+
+use crate::{expert::Severity, flows::FlowDirection};
+
+#[derive(Clone, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub border: Color,
+    pub title: Color,
+    pub highlight: Color,
+    pub dimmed: Color,
+    /// Rows and fields flagging a problem, such as a bad checksum.
+    pub error: Color,
+    /// Expert info warnings and notes; errors use `error`.
+    pub warning: Color,
+    pub note: Color,
+    /// Feedback on input that is fine as typed, such as a display filter that parses.
+    pub valid: Color,
+    /// What each side of a followed stream sent.
+    pub client: Color,
+    pub server: Color,
+    pub protocols: HashMap<String, Color>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            border: Color::Reset,
+            title: Color::Reset,
+            highlight: Color::Yellow,
+            dimmed: Color::DarkGray,
+            error: Color::Red,
+            warning: Color::Yellow,
+            note: Color::Cyan,
+            valid: Color::Green,
+            client: Color::LightRed,
+            server: Color::LightBlue,
+            protocols: Self::default_protocol_colors(),
+        }
+    }
+}
+
+impl Theme {
+    /// Themes the theme key cycles through, in order. "config" is the one from the config
+    /// file, or the default colors when it has no `[theme]`.
+    pub const NAMES: [&'static str; 3] = ["config", "light", "monochrome"];
+
+    /// The theme called `name` in `NAMES`, with `configured` standing for "config".
+    pub fn named(name: &str, configured: &Theme) -> Option<Self> {
+        match name {
+            "config" => Some(configured.clone()),
+            "light" => Some(Self::light()),
+            "monochrome" => Some(Self::monochrome()),
+            _ => None,
+        }
+    }
+
+    /// Darker colors that stay readable on a light terminal background.
+    fn light() -> Self {
+        let protocols = [
+            ("TCP", Color::Blue),
+            ("UDP", Color::Magenta),
+            ("SCTP", Color::Green),
+            ("ICMP", Color::Red),
+            ("ICMPV6", Color::Red),
+            ("ARP", Color::Green),
+            ("LLDP", Color::Blue),
+            ("802.11", Color::Green),
+            ("DNS", Color::Cyan),
+            ("MDNS", Color::Cyan),
+            ("HTTP", Color::Red),
+        ];
+        Self {
+            border: Color::Reset,
+            title: Color::Reset,
+            highlight: Color::Blue,
+            dimmed: Color::Gray,
+            error: Color::Red,
+            warning: Color::Magenta,
+            note: Color::Blue,
+            valid: Color::Green,
+            client: Color::Red,
+            server: Color::Blue,
+            protocols: protocols
+                .into_iter()
+                .map(|(protocol, color)| (protocol.to_string(), color))
+                .collect(),
+        }
+    }
+
+    /// The terminal's own colors, with only dimmed text and problems set apart.
+    fn monochrome() -> Self {
+        Self {
+            border: Color::Reset,
+            title: Color::Reset,
+            highlight: Color::White,
+            dimmed: Color::DarkGray,
+            error: Color::Reset,
+            warning: Color::Reset,
+            note: Color::Reset,
+            valid: Color::Reset,
+            client: Color::Reset,
+            server: Color::DarkGray,
+            protocols: HashMap::new(),
+        }
+    }
+
+    fn default_protocol_colors() -> HashMap<String, Color> {
+        [
+            ("TCP", Color::Cyan),
+            ("UDP", Color::Magenta),
+            ("SCTP", Color::LightGreen),
+            ("ICMP", Color::Yellow),
+            ("ICMPV6", Color::Yellow),
+            ("ARP", Color::Green),
+            ("LLDP", Color::Blue),
+            ("802.11", Color::Green),
+            ("MPLS", Color::LightYellow),
+            ("DNS", Color::LightBlue),
+            ("MDNS", Color::Blue),
+            ("SSDP", Color::LightCyan),
+            ("RADIUS", Color::LightYellow),
+            ("SSH", Color::LightGreen),
+            ("HTTP", Color::LightRed),
+            ("GTP-U", Color::LightMagenta),
+            ("VXLAN", Color::LightBlue),
+        ]
+        .into_iter()
+        .map(|(protocol, color)| (protocol.to_string(), color))
+        .collect()
+    }
+
+    /// Fills in the default color for every protocol the config file did not override.
+    pub fn with_default_protocols(mut self) -> Self {
+        self.protocols = self
+            .protocols
+            .into_iter()
+            .map(|(protocol, color)| (protocol.to_uppercase(), color))
+            .collect();
+        for (protocol, color) in Self::default_protocol_colors() {
+            self.protocols.entry(protocol).or_insert(color);
+        }
+        self
+    }
+
+    pub fn protocol_style(&self, protocol: &str) -> Style {
+        self.protocols
+            .get(&protocol.to_uppercase())
+            .map_or(Style::default(), |color| Style::default().fg(*color))
+    }
+
+    pub fn border_style(&self) -> Style {
+        Style::default().fg(self.border)
+    }
+
+    pub fn title_style(&self) -> Style {
+        Style::default().fg(self.title)
+    }
+
+    pub fn highlight_style(&self) -> Style {
+        Style::default().fg(self.highlight)
+    }
+
+    pub fn dimmed_style(&self) -> Style {
+        Style::default().fg(self.dimmed)
+    }
+
+    pub fn error_style(&self) -> Style {
+        Style::default().fg(self.error)
+    }
+
+    pub fn valid_style(&self) -> Style {
+        Style::default().fg(self.valid)
+    }
+
+    pub fn stream_style(&self, direction: FlowDirection) -> Style {
+        let color = match direction {
+            FlowDirection::ClientToServer => self.client,
+            FlowDirection::ServerToClient => self.server,
+        };
+        Style::default().fg(color)
+    }
+
+    pub fn severity_style(&self, severity: Severity) -> Style {
+        let color = match severity {
+            Severity::Error => self.error,
+            Severity::Warn => self.warning,
+            Severity::Note => self.note,
+        };
+        Style::default().fg(color)
+    }
+}
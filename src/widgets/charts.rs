@@ -1,32 +1,138 @@
-use crate::packet_data::{CompletePacket, PacketsData};
+use crate::{
+    filter::ProtocolToggles,
+    packet_data::{CompletePacket, PacketsData},
+    strings,
+    theme::Theme,
+};
 use ratatui::{
     style::{Modifier, Style},
-    widgets::{BarChart, Block, Borders},
+    text::Line,
+    widgets::{Bar, BarChart, BarGroup, Block, Borders},
     Frame,
 };
 use std::collections::HashMap;
 
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum ChartMode {
+    #[default]
+    Protocols,
+    Sizes,
+}
+
+impl ChartMode {
+    pub fn next(self) -> Self {
+        match self {
+            ChartMode::Protocols => ChartMode::Sizes,
+            ChartMode::Sizes => ChartMode::Protocols,
+        }
+    }
+}
+
 pub struct ChartWidget<'a> {
     packets: &'a [CompletePacket],
+    theme: &'a Theme,
+    mode: ChartMode,
+    protocol_toggles: Option<&'a ProtocolToggles>,
 }
 
 impl<'a> ChartWidget<'a> {
-    pub fn new(packets: &'a [CompletePacket]) -> Self {
-        Self { packets }
+    /// Upper bound (inclusive) and label of each packet size bucket; the last one is open.
+    const SIZE_BUCKETS: [(usize, &'static str); 7] = [
+        (64, "0-64"),
+        (128, "65-128"),
+        (256, "129-256"),
+        (512, "257-512"),
+        (1024, "513-1024"),
+        (1500, "1025-1500"),
+        (usize::MAX, "1501+"),
+    ];
+
+    pub fn new(packets: &'a [CompletePacket], theme: &'a Theme) -> Self {
+        Self {
+            packets,
+            theme,
+            mode: ChartMode::default(),
+            protocol_toggles: None,
+        }
+    }
+
+    /// Leaves out the packets of protocols switched off.
+    pub fn protocol_toggles(mut self, protocol_toggles: &'a ProtocolToggles) -> Self {
+        self.protocol_toggles = Some(protocol_toggles);
+        self
+    }
+
+    fn shown_packets(&self) -> impl Iterator<Item = &CompletePacket> {
+        self.packets.iter().filter(|packet| {
+            self.protocol_toggles
+                .is_none_or(|protocol_toggles| protocol_toggles.allows(packet))
+        })
+    }
+
+    pub fn mode(mut self, mode: ChartMode) -> Self {
+        self.mode = mode;
+        self
     }
 
     pub fn render(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
-        let protocol_counts = self.count_protocols();
-        let chart_data = self.build_chart_data(protocol_counts);
-        let barchart = self.build_barchart(chart_data);
+        let barchart = match self.mode {
+            ChartMode::Protocols => {
+                let protocol_counts = self.count_protocols();
+                let chart_data = self.build_chart_data(protocol_counts);
+                self.build_barchart(chart_data)
+            }
+            ChartMode::Sizes => self.build_size_histogram(),
+        };
 
         frame.render_widget(barchart, area);
     }
 
+    fn count_sizes(&self) -> [u64; 7] {
+        let mut counts = [0; 7];
+        for packet in self.shown_packets() {
+            let bucket = Self::SIZE_BUCKETS
+                .iter()
+                .position(|&(upper_bound, _)| packet.length <= upper_bound)
+                .unwrap_or(Self::SIZE_BUCKETS.len() - 1);
+            counts[bucket] += 1;
+        }
+        counts
+    }
+
+    fn build_size_histogram(&self) -> BarChart<'_> {
+        let counts = self.count_sizes();
+        let max_count = counts.iter().copied().max().unwrap_or(0);
+
+        let bars: Vec<Bar> = Self::SIZE_BUCKETS
+            .iter()
+            .zip(counts)
+            .map(|(&(_, label), count)| {
+                Bar::default()
+                    .label(label.into())
+                    .value(count)
+                    .style(self.theme.highlight_style())
+            })
+            .collect();
+
+        BarChart::default()
+            .block(
+                Block::default()
+                    .title(strings::get("chart.sizes_title"))
+                    .borders(Borders::ALL)
+                    .border_style(self.theme.border_style())
+                    .title_style(self.theme.title_style()),
+            )
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(9)
+            .value_style(Style::default().add_modifier(Modifier::BOLD))
+            .label_style(Style::default())
+            .max(max_count)
+    }
+
     fn count_protocols(&self) -> HashMap<&'static str, u32> {
         let mut protocol_counts = HashMap::new();
 
-        for packet in self.packets {
+        for packet in self.shown_packets() {
             if let Some(protocol_name) = Self::get_protocol_name(packet) {
                 *protocol_counts.entry(protocol_name).or_insert(0) += 1;
             }
@@ -35,11 +141,30 @@ impl<'a> ChartWidget<'a> {
         protocol_counts
     }
 
-    fn get_protocol_name(packet: &CompletePacket) -> Option<&'static str> {
-        if let Some(layer3) = &packet.layer_3 {
+    /// Names the innermost recognised protocol. VLAN tags are peeled off during capture,
+    /// so tagged traffic is counted by what it carries.
+    pub fn get_protocol_name(packet: &CompletePacket) -> Option<&'static str> {
+        if let Some(PacketsData::DnsPacket(_)) = &packet.layer_4 {
+            Some("DNS")
+        } else if let Some(PacketsData::MdnsPacket(_)) = &packet.layer_4 {
+            Some("mDNS")
+        } else if let Some(PacketsData::HttpPacket(_)) = &packet.layer_4 {
+            Some("HTTP")
+        } else if let Some(PacketsData::SsdpPacket(_)) = &packet.layer_4 {
+            Some("SSDP")
+        } else if let Some(PacketsData::RadiusPacket(_)) = &packet.layer_4 {
+            Some("RADIUS")
+        } else if let Some(PacketsData::SshPacket(_)) = &packet.layer_4 {
+            Some("SSH")
+        } else if let Some(PacketsData::GtpPacket(_)) = &packet.layer_4 {
+            Some("GTP-U")
+        } else if let Some(PacketsData::VxlanPacket(_)) = &packet.layer_4 {
+            Some("VXLAN")
+        } else if let Some(layer3) = &packet.layer_3 {
             match layer3 {
                 PacketsData::TcpPacket(_) => Some("TCP"),
                 PacketsData::UdpPacket(_) => Some("UDP"),
+                PacketsData::SctpPacket(_) => Some("SCTP"),
                 PacketsData::IcmpPacket(_) => Some("ICMP"),
                 PacketsData::Icmpv6Packet(_) => Some("ICMPv6"),
                 _ => None,
@@ -47,8 +172,13 @@ impl<'a> ChartWidget<'a> {
         } else if let Some(layer2) = &packet.layer_2 {
             match layer2 {
                 PacketsData::ArpPacket(_) => Some("ARP"),
+                PacketsData::LldpPacket(_) => Some("LLDP"),
                 _ => None,
             }
+        } else if packet.mpls.is_some() {
+            Some("MPLS")
+        } else if let Some(PacketsData::Ieee80211Packet(_)) = &packet.layer_1 {
+            Some("802.11")
         } else {
             None
         }
@@ -67,24 +197,115 @@ impl<'a> ChartWidget<'a> {
             .collect()
     }
 
-    fn build_barchart(&self, chart_data: Vec<(&'static str, u64)>) -> BarChart {
+    /// Each protocol's share of all captured packets, largest first, e.g. "TCP 80% UDP 15%".
+    fn protocol_shares(&self, chart_data: &[(&'static str, u64)]) -> String {
+        let total = self.shown_packets().count();
+        if total == 0 {
+            return String::new();
+        }
+
+        let mut shares = chart_data.to_vec();
+        shares.sort_by(|(name_a, a), (name_b, b)| b.cmp(a).then(name_a.cmp(name_b)));
+        shares
+            .iter()
+            .map(|&(name, count)| format!("{name} {:.0}%", count as f64 * 100.0 / total as f64))
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+
+    /// Total bytes and average packet length of the shown packets, e.g. "1.5 MB, avg 512 B".
+    fn traffic_totals(&self) -> String {
+        let (count, bytes) = self.shown_packets().fold((0, 0), |(count, bytes), packet| {
+            (count + 1, bytes + packet.length as u64)
+        });
+        if count == 0 {
+            return String::new();
+        }
+        strings::get("chart.traffic")
+            .replace("{bytes}", &Self::format_bytes(bytes))
+            .replace("{average}", &(bytes / count).to_string())
+    }
+
+    /// Bytes in the largest decimal unit that keeps the value at 1 or more.
+    fn format_bytes(bytes: u64) -> String {
+        const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+        if bytes < 1000 {
+            return format!("{bytes} B");
+        }
+        let mut value = bytes as f64 / 1000.0;
+        let mut unit = 0;
+        while value >= 1000.0 && unit < UNITS.len() - 1 {
+            value /= 1000.0;
+            unit += 1;
+        }
+        format!("{value:.1} {}", UNITS[unit])
+    }
+
+    fn build_barchart(&self, chart_data: Vec<(&'static str, u64)>) -> BarChart<'_> {
         let max_count = chart_data
             .iter()
             .map(|&(_, count)| count)
             .max()
             .unwrap_or(0);
 
+        let bars: Vec<Bar> = chart_data
+            .iter()
+            .map(|&(name, count)| {
+                Bar::default()
+                    .label(name.into())
+                    .value(count)
+                    .style(self.theme.protocol_style(name))
+            })
+            .collect();
+
         BarChart::default()
             .block(
                 Block::default()
-                    .title("Pacotes Capturados")
-                    .borders(Borders::ALL),
+                    .title(strings::get("chart.title"))
+                    .title(Line::from(self.traffic_totals()).right_aligned())
+                    .title_bottom(self.protocol_shares(&chart_data))
+                    .borders(Borders::ALL)
+                    .border_style(self.theme.border_style())
+                    .title_style(self.theme.title_style()),
             )
-            .data(&chart_data)
+            .data(BarGroup::default().bars(&bars))
             .bar_width(5)
-            .bar_style(Style::default())
             .value_style(Style::default().add_modifier(Modifier::BOLD))
             .label_style(Style::default())
             .max(max_count)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ChartWidget;
+    use crate::{
+        app_protocol::DecodeAs,
+        packet_data::CompletePacket,
+        sniffer::{LinkType, Sniffer},
+    };
+
+    #[test]
+    fn tagged_tcp_is_counted_as_tcp() {
+        // An 802.1Q tag for VLAN 100 in front of an IPv4 packet carrying a bare TCP ACK.
+        let mut frame = [[0x02, 0, 0, 0, 0, 0x0b], [0x02, 0, 0, 0, 0, 0x0a]].concat();
+        frame.extend([0x81, 0x00, 0x00, 100, 0x08, 0x00]);
+        frame.extend([0x45, 0, 0, 40, 0, 1, 0x40, 0, 64, 6, 0, 0]);
+        frame.extend([192, 168, 1, 10, 192, 168, 1, 1]);
+        frame.extend([
+            0xc3, 0x50, 0, 80, 0, 0, 0, 1, 0, 0, 0, 0, 0x50, 0x10, 0xff, 0xff,
+        ]);
+        frame.extend([0, 0, 0, 0]);
+
+        let mut packet = CompletePacket::new(1, "test", &frame, None);
+        Sniffer::handle_frame(
+            LinkType::Ethernet,
+            &frame,
+            false,
+            &DecodeAs::default(),
+            &mut packet,
+        );
+        assert_eq!(packet.vlan_tags.len(), 1);
+        assert_eq!(ChartWidget::get_protocol_name(&packet), Some("TCP"));
+    }
+}
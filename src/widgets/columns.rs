@@ -0,0 +1,74 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::Text,
+    widgets::{Block, Cell, Clear, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    keymap::Keymap,
+    strings,
+    theme::Theme,
+    widgets::{layout_helper::LayoutHelper, packet_table::Column},
+};
+
+/// Picker listing the packet table's columns in the order they're shown, followed by the
+/// hidden ones.
+pub struct ColumnsWidget<'a> {
+    columns: &'a [Column],
+    keymap: &'a Keymap,
+    theme: &'a Theme,
+}
+
+impl<'a> ColumnsWidget<'a> {
+    pub fn new(columns: &'a [Column], keymap: &'a Keymap, theme: &'a Theme) -> Self {
+        Self {
+            columns,
+            keymap,
+            theme,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, state: &mut TableState) {
+        let popup_area = LayoutHelper::create_centered_layout(area, 40, 50);
+
+        let header = ["", strings::get("columns.column")]
+            .into_iter()
+            .map(|title| Cell::from(Text::from(title).bold()))
+            .collect::<Row>();
+
+        let shown = self.columns.iter().map(|column| (*column, true));
+        let hidden = Column::hidden(self.columns).map(|column| (column, false));
+        let rows: Vec<Row> = shown
+            .chain(hidden)
+            .map(|(column, visible)| {
+                let row = Row::new(vec![
+                    if visible { "[x]" } else { "[ ]" }.to_string(),
+                    column.header().to_string(),
+                ]);
+                if visible {
+                    row
+                } else {
+                    row.style(self.theme.dimmed_style())
+                }
+            })
+            .collect();
+
+        let widths = [Constraint::Length(3), Constraint::Fill(1)];
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .highlight_style(self.theme.highlight_style().reversed())
+            .block(
+                Block::bordered()
+                    .title(strings::get("columns.title"))
+                    .title_bottom(self.keymap.fill_keys(strings::get("columns.help")))
+                    .border_style(self.theme.border_style())
+                    .title_style(self.theme.title_style()),
+            );
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_stateful_widget(table, popup_area, state);
+    }
+}
@@ -0,0 +1,120 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    keymap::{Action, KeyContext, Keymap},
+    strings,
+    theme::Theme,
+    widgets::layout_helper::LayoutHelper,
+};
+
+/// Every action the keys can run, searchable by what it does, with the key bound to it.
+pub struct CommandPaletteWidget<'a> {
+    keymap: &'a Keymap,
+    query: &'a str,
+    theme: &'a Theme,
+}
+
+impl<'a> CommandPaletteWidget<'a> {
+    pub fn new(keymap: &'a Keymap, query: &'a str, theme: &'a Theme) -> Self {
+        Self {
+            keymap,
+            query,
+            theme,
+        }
+    }
+
+    /// The packet list actions whose description contains the letters of `query` in
+    /// order, closest matches first; all of them, in their usual order, for an empty query.
+    pub fn matching_actions(query: &str) -> Vec<Action> {
+        let mut matches: Vec<(usize, Action)> = Action::all()
+            .filter(|&action| action != Action::CommandPalette)
+            .filter(|action| action.context() == KeyContext::PacketList)
+            .filter_map(|action| Some((Self::fuzzy_score(query, action.description())?, action)))
+            .collect();
+        matches.sort_by_key(|(score, _)| *score);
+        matches.into_iter().map(|(_, action)| action).collect()
+    }
+
+    /// How far apart the letters of `query` are found in `text`, ignoring case, counting
+    /// where the first one is and the letters skipped between them; `None` if they aren't
+    /// all there in order.
+    fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+        let text: Vec<char> = text.to_lowercase().chars().collect();
+        let mut position = 0;
+        let mut score = 0;
+        for (index, wanted) in query.to_lowercase().chars().enumerate() {
+            let found = text[position..].iter().position(|&c| c == wanted)?;
+            // The first letter may start anywhere, but only a word start scores nothing.
+            if index > 0 || (found + position > 0 && text[found + position - 1] != ' ') {
+                score += found;
+            }
+            position += found + 1;
+        }
+        Some(score)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, state: &mut TableState) {
+        let popup_area = LayoutHelper::create_centered_layout(area, 50, 60);
+        let block = Block::bordered()
+            .title(strings::get("palette.title"))
+            .title_bottom(strings::get("palette.help"))
+            .border_style(self.theme.border_style())
+            .title_style(self.theme.title_style());
+        let [query_area, list_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)])
+                .areas(block.inner(popup_area));
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::raw("> "),
+                Span::raw(self.query),
+                Span::raw("█"),
+            ])),
+            query_area,
+        );
+
+        let actions = Self::matching_actions(self.query);
+        if actions.is_empty() {
+            frame.render_widget(
+                Paragraph::new(strings::get("palette.no_match")).style(self.theme.dimmed_style()),
+                list_area,
+            );
+            return;
+        }
+        let rows: Vec<Row> = actions
+            .into_iter()
+            .map(|action| {
+                Row::new(vec![
+                    Span::from(action.description()),
+                    Span::from(self.keymap.key_label(action)).style(self.theme.dimmed_style()),
+                ])
+            })
+            .collect();
+        let table = Table::new(rows, [Constraint::Fill(1), Constraint::Length(12)])
+            .highlight_style(self.theme.highlight_style().reversed());
+        frame.render_stateful_widget(table, list_area, state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommandPaletteWidget;
+
+    #[test]
+    fn fuzzy_matches_rank_word_starts_and_close_letters_first() {
+        let score = CommandPaletteWidget::fuzzy_score;
+        assert_eq!(score("", "Quit"), Some(0));
+        assert_eq!(score("conv", "Show conversations"), Some(0));
+        assert_eq!(score("xyz", "Search packets"), None);
+        assert_eq!(score("pa", "Page up"), Some(0));
+        assert!(score("con", "Show conversations") < score("con", "Decode a port as another"));
+    }
+}
@@ -0,0 +1,292 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::{Span, Text},
+    widgets::{Block, Cell, Clear, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    geoip::GeoIp,
+    keymap::Keymap,
+    packet_data::CompletePacket,
+    strings,
+    theme::Theme,
+    widgets::{
+        layout_helper::LayoutHelper,
+        popup::{Field, PopupWidget},
+    },
+};
+
+/// A line of the comparison: the names of the layers at the same depth in both packets,
+/// or a field with each packet's value.
+enum ComparisonRow {
+    Layer([Option<&'static str>; 2]),
+    Field {
+        name: String,
+        values: [Option<Span<'static>>; 2],
+    },
+}
+
+impl ComparisonRow {
+    fn differs(&self) -> bool {
+        match self {
+            ComparisonRow::Layer([first, second]) => first != second,
+            ComparisonRow::Field { values, .. } => {
+                let [first, second] = values
+                    .each_ref()
+                    .map(|value| value.as_ref().map(|v| &v.content));
+                first != second
+            }
+        }
+    }
+}
+
+/// Two packets' fields side by side, layer by layer, with the rows that differ
+/// highlighted.
+pub struct ComparisonWidget<'a> {
+    packets: [&'a Option<CompletePacket>; 2],
+    keymap: &'a Keymap,
+    theme: &'a Theme,
+    verify_checksums: bool,
+    geoip: Option<&'a GeoIp>,
+    differences_only: bool,
+}
+
+impl<'a> ComparisonWidget<'a> {
+    pub fn new(
+        packets: [&'a Option<CompletePacket>; 2],
+        keymap: &'a Keymap,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            packets,
+            keymap,
+            theme,
+            verify_checksums: false,
+            geoip: None,
+            differences_only: false,
+        }
+    }
+
+    pub fn verify_checksums(mut self, verify_checksums: bool) -> Self {
+        self.verify_checksums = verify_checksums;
+        self
+    }
+
+    pub fn geoip(mut self, geoip: Option<&'a GeoIp>) -> Self {
+        self.geoip = geoip;
+        self
+    }
+
+    /// Leaves out the fields both packets agree on.
+    pub fn differences_only(mut self, differences_only: bool) -> Self {
+        self.differences_only = differences_only;
+        self
+    }
+
+    /// Rows the table shows, for keeping the selection in range.
+    pub fn row_count(&self) -> usize {
+        self.rows().len()
+    }
+
+    /// Capture details, then the layers at the same depth paired up. Fields of a pair
+    /// are matched by name, so an option or answer present in one packet only gets a row
+    /// of its own.
+    fn rows(&self) -> Vec<ComparisonRow> {
+        let layers = self.packets.map(|packet| {
+            let mut layers = vec![(strings::get("compare.frame"), Self::frame_fields(packet))];
+            layers.extend(
+                PopupWidget::new(packet, self.theme)
+                    .verify_checksums(self.verify_checksums)
+                    .geoip(self.geoip)
+                    .packet_fields(),
+            );
+            layers
+        });
+        let rows = Self::compare_layers(layers);
+        if !self.differences_only {
+            return rows;
+        }
+        rows.into_iter()
+            .filter(|row| matches!(row, ComparisonRow::Layer(_)) || row.differs())
+            .collect()
+    }
+
+    fn frame_fields(packet: &Option<CompletePacket>) -> Vec<Field> {
+        let Some(packet) = packet else {
+            return Vec::new();
+        };
+        vec![
+            Field::new(
+                strings::get("popup.captured_at"),
+                packet.timestamp.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            ),
+            Field::new(strings::get("table.interface"), packet.interface.clone()),
+            Field::new(strings::get("table.length"), packet.length.to_string()),
+        ]
+    }
+
+    fn compare_layers(layers: [Vec<(&'static str, Vec<Field>)>; 2]) -> Vec<ComparisonRow> {
+        let [first, second] = layers;
+        let depth = first.len().max(second.len());
+        let mut first = first.into_iter();
+        let mut second = second.into_iter();
+        let mut rows = Vec::new();
+        for _ in 0..depth {
+            let (first_name, first_fields) = first.next().unzip();
+            let (second_name, second_fields) = second.next().unzip();
+            rows.push(ComparisonRow::Layer([first_name, second_name]));
+
+            let mut unmatched: Vec<Option<Field>> = second_fields
+                .unwrap_or_default()
+                .into_iter()
+                .map(Some)
+                .collect();
+            for field in first_fields.unwrap_or_default() {
+                let other = unmatched
+                    .iter_mut()
+                    .find(|other| other.as_ref().is_some_and(|other| other.name == field.name))
+                    .and_then(Option::take);
+                rows.push(ComparisonRow::Field {
+                    name: field.name,
+                    values: [Some(field.value), other.map(|other| other.value)],
+                });
+            }
+            rows.extend(
+                unmatched
+                    .into_iter()
+                    .flatten()
+                    .map(|field| ComparisonRow::Field {
+                        name: field.name,
+                        values: [None, Some(field.value)],
+                    }),
+            );
+        }
+        rows
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, state: &mut TableState) {
+        let popup_area = LayoutHelper::create_centered_layout(area, 90, 85);
+
+        let ids = self.packets.map(|packet| {
+            packet
+                .as_ref()
+                .map(|packet| format!("#{}", packet.id))
+                .unwrap_or_default()
+        });
+        let header = [strings::get("compare.field"), &ids[0], &ids[1]]
+            .into_iter()
+            .map(|title| Cell::from(Text::from(title.to_string()).bold()))
+            .collect::<Row>();
+
+        let comparison = self.rows();
+        let differences = comparison
+            .iter()
+            .filter(|row| matches!(row, ComparisonRow::Field { .. }) && row.differs())
+            .count();
+        let rows: Vec<Row> = comparison
+            .into_iter()
+            .map(|row| {
+                let differs = row.differs();
+                match row {
+                    ComparisonRow::Layer(names) => {
+                        let style = self
+                            .theme
+                            .protocol_style(names[0].or(names[1]).unwrap_or(""));
+                        let [first, second] = names.map(|name| name.unwrap_or("-"));
+                        Row::new(vec![String::new(), first.to_string(), second.to_string()])
+                            .style(style.bold())
+                    }
+                    ComparisonRow::Field { name, values } => {
+                        let [first, second] =
+                            values.map(|value| value.unwrap_or_else(|| Span::from("-")));
+                        let row = Row::new(vec![
+                            Cell::from(Span::from(name).bold()),
+                            Cell::from(first),
+                            Cell::from(second),
+                        ]);
+                        if differs {
+                            row.style(self.theme.highlight_style())
+                        } else {
+                            row
+                        }
+                    }
+                }
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Length(24),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ];
+
+        let title = format!(
+            "{} ({})",
+            strings::get("compare.title"),
+            strings::get("compare.differences").replace("{count}", &differences.to_string())
+        );
+        let table = Table::new(rows, widths)
+            .header(header)
+            .column_spacing(2)
+            .highlight_style(self.theme.highlight_style().reversed())
+            .block(
+                Block::bordered()
+                    .title(title)
+                    .title_bottom(self.keymap.fill_keys(strings::get("compare.help")))
+                    .border_style(self.theme.border_style())
+                    .title_style(self.theme.title_style()),
+            );
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_stateful_widget(table, popup_area, state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ComparisonRow, ComparisonWidget};
+    use crate::widgets::popup::Field;
+
+    #[test]
+    fn fields_are_matched_by_name_within_a_layer() {
+        let first = vec![(
+            "TCP",
+            vec![
+                Field::new("Source Port", "443"),
+                Field::new("Option 1", "MSS 1460"),
+            ],
+        )];
+        let second = vec![
+            (
+                "TCP",
+                vec![
+                    Field::new("Window Size", "512"),
+                    Field::new("Source Port", "443"),
+                ],
+            ),
+            ("HTTP", vec![Field::new("Method", "GET")]),
+        ];
+
+        let rows = ComparisonWidget::compare_layers([first, second]);
+        let summary: Vec<(String, bool)> = rows
+            .iter()
+            .map(|row| match row {
+                ComparisonRow::Layer(names) => (format!("{names:?}"), row.differs()),
+                ComparisonRow::Field { name, .. } => (name.clone(), row.differs()),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (r#"[Some("TCP"), Some("TCP")]"#.to_string(), false),
+                ("Source Port".to_string(), false),
+                ("Option 1".to_string(), true),
+                ("Window Size".to_string(), true),
+                (r#"[None, Some("HTTP")]"#.to_string(), true),
+                ("Method".to_string(), true),
+            ]
+        );
+    }
+}
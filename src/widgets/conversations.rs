@@ -0,0 +1,228 @@
+use std::{collections::HashMap, net::IpAddr, time::Duration};
+
+use chrono::{DateTime, Local};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::Text,
+    widgets::{Block, Cell, Clear, Row, Table},
+    Frame,
+};
+
+use crate::{
+    flows::TcpRttEstimator, keymap::Keymap, packet_data::CompletePacket, strings, theme::Theme,
+    widgets::layout_helper::LayoutHelper,
+};
+
+pub type Endpoint = (IpAddr, Option<u16>);
+
+pub struct FlowStats {
+    pub packets_forward: u64,
+    pub packets_backward: u64,
+    pub bytes_forward: u64,
+    pub bytes_backward: u64,
+    pub first_seen: DateTime<Local>,
+    pub last_seen: DateTime<Local>,
+    /// The latest RTT estimate of the TCP connection.
+    pub rtt: Option<Duration>,
+}
+
+impl FlowStats {
+    fn new(timestamp: DateTime<Local>) -> Self {
+        Self {
+            packets_forward: 0,
+            packets_backward: 0,
+            bytes_forward: 0,
+            bytes_backward: 0,
+            first_seen: timestamp,
+            last_seen: timestamp,
+            rtt: None,
+        }
+    }
+
+    pub fn packets(&self) -> u64 {
+        self.packets_forward + self.packets_backward
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes_forward + self.bytes_backward
+    }
+
+    pub fn duration(&self) -> chrono::TimeDelta {
+        self.last_seen - self.first_seen
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum ConversationSort {
+    #[default]
+    Bytes,
+    Packets,
+    Duration,
+}
+
+impl ConversationSort {
+    pub fn next(self) -> Self {
+        match self {
+            ConversationSort::Bytes => ConversationSort::Packets,
+            ConversationSort::Packets => ConversationSort::Duration,
+            ConversationSort::Duration => ConversationSort::Bytes,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ConversationSort::Bytes => strings::get("conversations.sort_bytes"),
+            ConversationSort::Packets => strings::get("conversations.sort_packets"),
+            ConversationSort::Duration => strings::get("conversations.sort_duration"),
+        }
+    }
+}
+
+pub struct ConversationsWidget<'a> {
+    packets: &'a [CompletePacket],
+    keymap: &'a Keymap,
+    theme: &'a Theme,
+    sort: ConversationSort,
+    include_ports: bool,
+}
+
+impl<'a> ConversationsWidget<'a> {
+    pub fn new(
+        packets: &'a [CompletePacket],
+        keymap: &'a Keymap,
+        theme: &'a Theme,
+        sort: ConversationSort,
+        include_ports: bool,
+    ) -> Self {
+        Self {
+            packets,
+            keymap,
+            theme,
+            sort,
+            include_ports,
+        }
+    }
+
+    /// Groups packets by endpoint pair, with the lower endpoint always first so both
+    /// directions of a conversation land in the same entry.
+    pub fn build_conversations(&self) -> HashMap<(Endpoint, Endpoint), FlowStats> {
+        let mut conversations: HashMap<(Endpoint, Endpoint), FlowStats> = HashMap::new();
+
+        for packet in self.packets {
+            let Some((source_ip, destination_ip)) = packet.ip_addresses() else {
+                continue;
+            };
+            let (source_port, destination_port) = match packet.ports() {
+                Some((source, destination)) if self.include_ports => {
+                    (Some(source), Some(destination))
+                }
+                _ => (None, None),
+            };
+
+            let source = (source_ip, source_port);
+            let destination = (destination_ip, destination_port);
+            let forward = source <= destination;
+            let key = if forward {
+                (source, destination)
+            } else {
+                (destination, source)
+            };
+
+            let stats = conversations
+                .entry(key)
+                .or_insert_with(|| FlowStats::new(packet.timestamp));
+            if forward {
+                stats.packets_forward += 1;
+                stats.bytes_forward += packet.length as u64;
+            } else {
+                stats.packets_backward += 1;
+                stats.bytes_backward += packet.length as u64;
+            }
+            stats.first_seen = stats.first_seen.min(packet.timestamp);
+            stats.last_seen = stats.last_seen.max(packet.timestamp);
+            if packet.tcp_rtt.is_some() {
+                stats.rtt = packet.tcp_rtt;
+            }
+        }
+
+        conversations
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = LayoutHelper::create_centered_layout(area, 90, 80);
+
+        let mut conversations: Vec<_> = self.build_conversations().into_iter().collect();
+        conversations.sort_by(|(_, a), (_, b)| match self.sort {
+            ConversationSort::Bytes => b.bytes().cmp(&a.bytes()),
+            ConversationSort::Packets => b.packets().cmp(&a.packets()),
+            ConversationSort::Duration => b.duration().cmp(&a.duration()),
+        });
+
+        let header = [
+            strings::get("conversations.address_a"),
+            strings::get("conversations.address_b"),
+            strings::get("conversations.packets_a_b"),
+            strings::get("conversations.packets_b_a"),
+            strings::get("conversations.bytes_a_b"),
+            strings::get("conversations.bytes_b_a"),
+            strings::get("conversations.duration"),
+            strings::get("conversations.rtt"),
+        ]
+        .into_iter()
+        .map(|title| Cell::from(Text::from(title).bold()))
+        .collect::<Row>();
+
+        let rows: Vec<Row> = conversations
+            .iter()
+            .map(|((a, b), stats)| {
+                Row::new(vec![
+                    Self::format_endpoint(a),
+                    Self::format_endpoint(b),
+                    stats.packets_forward.to_string(),
+                    stats.packets_backward.to_string(),
+                    stats.bytes_forward.to_string(),
+                    stats.bytes_backward.to_string(),
+                    format!("{:.3}s", stats.duration().as_seconds_f64()),
+                    stats.rtt.map(TcpRttEstimator::format).unwrap_or_default(),
+                ])
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Length(9),
+        ];
+
+        let title = format!(
+            "{} ({}: {})",
+            strings::get("conversations.title"),
+            strings::get("conversations.sorted_by"),
+            self.sort.name()
+        );
+        let table = Table::new(rows, widths).header(header).block(
+            Block::bordered()
+                .title(title)
+                .title_bottom(self.keymap.fill_keys(strings::get("conversations.help")))
+                .border_style(self.theme.border_style())
+                .title_style(self.theme.title_style()),
+        );
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(table, popup_area);
+    }
+
+    fn format_endpoint((ip, port): &Endpoint) -> String {
+        match (ip, port) {
+            (IpAddr::V6(ip), Some(port)) => format!("[{ip}]:{port}"),
+            (ip, Some(port)) => format!("{ip}:{port}"),
+            (ip, None) => ip.to_string(),
+        }
+    }
+}
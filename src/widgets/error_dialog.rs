@@ -0,0 +1,37 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    widgets::{Block, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{strings, theme::Theme, widgets::layout_helper::LayoutHelper};
+
+pub struct ErrorDialog<'a> {
+    message: &'a str,
+    theme: &'a Theme,
+}
+
+impl<'a> ErrorDialog<'a> {
+    pub fn new(message: &'a str, theme: &'a Theme) -> Self {
+        Self { message, theme }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let dialog_area = LayoutHelper::create_centered_layout(area, 60, 20);
+
+        let dialog = Paragraph::new(self.message)
+            .wrap(Wrap { trim: true })
+            .centered()
+            .block(
+                Block::bordered()
+                    .title(strings::get("error_dialog.title"))
+                    .title_bottom(strings::get("error_dialog.help"))
+                    .border_style(Style::default().fg(Color::Red))
+                    .title_style(self.theme.title_style().bold()),
+            );
+
+        frame.render_widget(Clear, dialog_area);
+        frame.render_widget(dialog, dialog_area);
+    }
+}
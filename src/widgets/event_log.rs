@@ -0,0 +1,75 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::Text,
+    widgets::{Block, Cell, Clear, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    event_log::EventLog, keymap::Keymap, strings, theme::Theme,
+    widgets::layout_helper::LayoutHelper,
+};
+
+pub struct EventLogWidget<'a> {
+    event_log: &'a EventLog,
+    keymap: &'a Keymap,
+    theme: &'a Theme,
+}
+
+impl<'a> EventLogWidget<'a> {
+    pub fn new(event_log: &'a EventLog, keymap: &'a Keymap, theme: &'a Theme) -> Self {
+        Self {
+            event_log,
+            keymap,
+            theme,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, state: &mut TableState) {
+        let popup_area = LayoutHelper::create_centered_layout(area, 80, 70);
+
+        let header = [
+            strings::get("log.time"),
+            strings::get("expert.severity"),
+            strings::get("expert.message"),
+        ]
+        .into_iter()
+        .map(|title| Cell::from(Text::from(title).bold()))
+        .collect::<Row>();
+
+        let rows: Vec<Row> = self
+            .event_log
+            .entries()
+            .map(|entry| {
+                Row::new(vec![
+                    entry.timestamp.format("%H:%M:%S").to_string(),
+                    entry.severity.name().to_string(),
+                    entry.message.clone(),
+                ])
+                .style(self.theme.severity_style(entry.severity))
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Fill(1),
+        ];
+
+        let title = format!("{} ({})", strings::get("log.title"), self.event_log.len());
+        let table = Table::new(rows, widths)
+            .header(header)
+            .highlight_style(self.theme.highlight_style().reversed())
+            .block(
+                Block::bordered()
+                    .title(title)
+                    .title_bottom(self.keymap.fill_keys(strings::get("log.help")))
+                    .border_style(self.theme.border_style())
+                    .title_style(self.theme.title_style()),
+            );
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_stateful_widget(table, popup_area, state);
+    }
+}
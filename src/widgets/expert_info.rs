@@ -0,0 +1,94 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::Text,
+    widgets::{Block, Cell, Clear, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    expert::{ExpertInfo, Severity},
+    keymap::Keymap,
+    strings,
+    theme::Theme,
+    widgets::layout_helper::LayoutHelper,
+};
+
+pub struct ExpertInfoWidget<'a> {
+    findings: &'a [ExpertInfo],
+    keymap: &'a Keymap,
+    theme: &'a Theme,
+}
+
+impl<'a> ExpertInfoWidget<'a> {
+    pub fn new(findings: &'a [ExpertInfo], keymap: &'a Keymap, theme: &'a Theme) -> Self {
+        Self {
+            findings,
+            keymap,
+            theme,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, state: &mut TableState) {
+        let popup_area = LayoutHelper::create_centered_layout(area, 80, 70);
+
+        let header = [
+            strings::get("expert.packet"),
+            strings::get("expert.severity"),
+            strings::get("expert.message"),
+        ]
+        .into_iter()
+        .map(|title| Cell::from(Text::from(title).bold()))
+        .collect::<Row>();
+
+        // Newest first; the findings are kept in the order they were found.
+        let rows: Vec<Row> = self
+            .findings
+            .iter()
+            .rev()
+            .map(|finding| {
+                Row::new(vec![
+                    finding.packet_id.to_string(),
+                    finding.severity.name().to_string(),
+                    finding.message.clone(),
+                ])
+                .style(self.theme.severity_style(finding.severity))
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Fill(1),
+        ];
+
+        let count = |severity: Severity| {
+            self.findings
+                .iter()
+                .filter(|finding| finding.severity == severity)
+                .count()
+                .to_string()
+        };
+        let title = format!(
+            "{} ({})",
+            strings::get("expert.title"),
+            strings::get("expert.counts")
+                .replace("{errors}", &count(Severity::Error))
+                .replace("{warnings}", &count(Severity::Warn))
+                .replace("{notes}", &count(Severity::Note))
+        );
+        let table = Table::new(rows, widths)
+            .header(header)
+            .highlight_style(self.theme.highlight_style().reversed())
+            .block(
+                Block::bordered()
+                    .title(title)
+                    .title_bottom(self.keymap.fill_keys(strings::get("expert.help")))
+                    .border_style(self.theme.border_style())
+                    .title_style(self.theme.title_style()),
+            );
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_stateful_widget(table, popup_area, state);
+    }
+}
@@ -0,0 +1,77 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::Text,
+    widgets::{Block, Cell, Clear, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    filter::FilterPreset, keymap::Keymap, strings, theme::Theme,
+    widgets::layout_helper::LayoutHelper,
+};
+
+/// Picker listing the presets from the config file followed by the ones saved from it,
+/// numbered for the keys that apply them.
+pub struct FilterPresetsWidget<'a> {
+    presets: &'a [FilterPreset],
+    keymap: &'a Keymap,
+    theme: &'a Theme,
+}
+
+impl<'a> FilterPresetsWidget<'a> {
+    pub fn new(presets: &'a [FilterPreset], keymap: &'a Keymap, theme: &'a Theme) -> Self {
+        Self {
+            presets,
+            keymap,
+            theme,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, state: &mut TableState) {
+        let popup_area = LayoutHelper::create_centered_layout(area, 60, 50);
+
+        let header = [
+            "#",
+            strings::get("presets.name"),
+            strings::get("presets.expression"),
+        ]
+        .into_iter()
+        .map(|title| Cell::from(Text::from(title).bold()))
+        .collect::<Row>();
+
+        let rows: Vec<Row> = self
+            .presets
+            .iter()
+            .enumerate()
+            .map(|(index, preset)| {
+                let number = if index < 9 {
+                    (index + 1).to_string()
+                } else {
+                    String::new()
+                };
+                Row::new(vec![number, preset.name.clone(), preset.expression.clone()])
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Length(3),
+            Constraint::Length(20),
+            Constraint::Fill(1),
+        ];
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .highlight_style(self.theme.highlight_style().reversed())
+            .block(
+                Block::bordered()
+                    .title(strings::get("presets.title"))
+                    .title_bottom(self.keymap.fill_keys(strings::get("presets.help")))
+                    .border_style(self.theme.border_style())
+                    .title_style(self.theme.title_style()),
+            );
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_stateful_widget(table, popup_area, state);
+    }
+}
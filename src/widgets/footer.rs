@@ -1,24 +1,201 @@
+use crate::{
+    keymap::{Action, Keymap},
+    strings,
+    theme::Theme,
+};
 use ratatui::{
-    layout::Alignment, text::{Line, Span}, widgets::Paragraph, Frame
+    layout::{Alignment, Constraint, Layout},
+    text::{Line, Span},
+    widgets::{Paragraph, Sparkline},
+    Frame,
 };
-pub struct Footer;
+pub struct Footer<'a> {
+    keymap: &'a Keymap,
+    prompt: Option<String>,
+    prompt_feedback: Option<Result<(), String>>,
+    status: Option<String>,
+    interface_stats: Option<String>,
+    packet_rate: Option<&'a [u64]>,
+    theme: &'a Theme,
+}
 
-impl Footer {
-    pub fn new() -> Self {
-        Self {  }
+impl<'a> Footer<'a> {
+    pub fn new(keymap: &'a Keymap, theme: &'a Theme) -> Self {
+        Self {
+            keymap,
+            prompt: None,
+            prompt_feedback: None,
+            status: None,
+            interface_stats: None,
+            packet_rate: None,
+            theme,
+        }
+    }
+
+    /// Packets per second, oldest first, drawn as a sparkline at the left of the footer
+    /// with the latest rate next to it.
+    pub fn packet_rate(mut self, packet_rate: &'a [u64]) -> Self {
+        self.packet_rate = Some(packet_rate);
+        self
+    }
+
+    pub fn prompt(mut self, prompt: String) -> Self {
+        self.prompt = Some(prompt);
+        self
+    }
+
+    /// Marks the input of the prompt as valid, or shows why it isn't, after the cursor.
+    pub fn prompt_feedback(mut self, feedback: Result<(), String>) -> Self {
+        self.prompt_feedback = Some(feedback);
+        self
+    }
+
+    pub fn status(mut self, status: String) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn interface_stats(mut self, interface_stats: String) -> Self {
+        self.interface_stats = Some(interface_stats);
+        self
     }
 
     pub fn render(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let area = match self.packet_rate {
+            Some(packet_rate) => {
+                let rate = strings::get("footer.packet_rate").replace(
+                    "{rate}",
+                    &packet_rate.last().copied().unwrap_or(0).to_string(),
+                );
+                let [sparkline_area, rate_area, main_area] = Layout::horizontal([
+                    Constraint::Length(packet_rate.len() as u16),
+                    Constraint::Length(rate.chars().count() as u16 + 2),
+                    Constraint::Fill(1),
+                ])
+                .areas(area);
+                let sparkline = Sparkline::default()
+                    .data(packet_rate)
+                    .style(self.theme.highlight_style());
+                frame.render_widget(sparkline, sparkline_area);
+                frame.render_widget(Paragraph::new(format!(" {rate}")), rate_area);
+                main_area
+            }
+            None => area,
+        };
+        let area = match &self.interface_stats {
+            Some(interface_stats) => {
+                let [main_area, stats_area] = Layout::horizontal([
+                    Constraint::Fill(1),
+                    Constraint::Length(interface_stats.chars().count() as u16 + 1),
+                ])
+                .areas(area);
+                let stats_text = Paragraph::new(Line::from(interface_stats.as_str()))
+                    .alignment(Alignment::Right);
+                frame.render_widget(stats_text, stats_area);
+                main_area
+            }
+            None => area,
+        };
 
-    let footer_text = Paragraph::new(Line::from(vec![
-        Span::raw("q: sair  "),
-        Span::raw("j/k ou ↓/↑: navegar  "),
-        Span::raw("i: interface  "),
-        Span::raw("p: play/pause  "),
-        Span::raw("enter: detalhes"),
-    ]))
-    .alignment(Alignment::Center);
+        if let Some(prompt) = &self.prompt {
+            let mut spans = vec![Span::raw(prompt.as_str()), Span::raw("█")];
+            match &self.prompt_feedback {
+                Some(Ok(())) => spans.push(Span::styled("  ✓", self.theme.valid_style())),
+                Some(Err(error)) => spans.push(Span::styled(
+                    format!("  ✗ {error}"),
+                    self.theme.error_style(),
+                )),
+                None => {}
+            }
+            let prompt_text = Paragraph::new(Line::from(spans));
+            frame.render_widget(prompt_text, area);
+            return;
+        }
 
-    frame.render_widget(footer_text, area);
-}
+        if let Some(status) = &self.status {
+            let status_text =
+                Paragraph::new(Line::from(status.as_str())).alignment(Alignment::Center);
+            frame.render_widget(status_text, area);
+            return;
+        }
+
+        let hints: [(&[Action], &str); 35] = [
+            (&[Action::Quit], strings::get("footer.quit")),
+            (&[Action::CommandPalette], strings::get("footer.palette")),
+            (
+                &[Action::NextRow, Action::PreviousRow],
+                strings::get("footer.navigate"),
+            ),
+            (
+                &[Action::FirstRow, Action::LastRow],
+                strings::get("footer.first_last"),
+            ),
+            (&[Action::NextInterface], strings::get("footer.interface")),
+            (&[Action::RefreshInterfaces], strings::get("footer.refresh")),
+            (&[Action::ToggleSniffer], strings::get("footer.play_pause")),
+            (&[Action::ToggleFreeze], strings::get("footer.freeze")),
+            (&[Action::Search], strings::get("footer.search")),
+            (
+                &[Action::NextMatch, Action::PreviousMatch],
+                strings::get("footer.next_previous"),
+            ),
+            (&[Action::GoToPacket], strings::get("footer.go_to_packet")),
+            (&[Action::Filter], strings::get("footer.filter")),
+            (&[Action::FilterPresets], strings::get("footer.presets")),
+            (&[Action::DecodeAs], strings::get("footer.decode_as")),
+            (&[Action::ToggleDim], strings::get("footer.dim")),
+            (&[Action::ToggleDirection], strings::get("footer.direction")),
+            (&[Action::Copy], strings::get("footer.copy")),
+            (&[Action::SavePcap], strings::get("footer.save_pcap")),
+            (&[Action::ToggleBookmark], strings::get("footer.bookmark")),
+            (
+                &[Action::NextBookmark, Action::PreviousBookmark],
+                strings::get("footer.bookmarks"),
+            ),
+            (
+                &[Action::Conversations],
+                strings::get("footer.conversations"),
+            ),
+            (&[Action::TopTalkers], strings::get("footer.top_talkers")),
+            (&[Action::ExpertInfo], strings::get("footer.expert_info")),
+            (&[Action::EventLog], strings::get("footer.log")),
+            (&[Action::ChartMode], strings::get("footer.chart")),
+            (
+                &[Action::ToggleNameResolution],
+                strings::get("footer.resolve"),
+            ),
+            (&[Action::TogglePopup], strings::get("footer.details")),
+            (&[Action::Compare], strings::get("footer.compare")),
+            (&[Action::FollowStream], strings::get("footer.follow")),
+            (&[Action::ToggleCompact], strings::get("footer.compact")),
+            (&[Action::ToggleOrder], strings::get("footer.order")),
+            (
+                &[Action::ToggleFullscreen],
+                strings::get("footer.fullscreen"),
+            ),
+            (&[Action::Columns], strings::get("footer.columns")),
+            (
+                &[Action::ShrinkTopPanel, Action::GrowTopPanel],
+                strings::get("footer.resize"),
+            ),
+            (
+                &[Action::ToggleChart, Action::ToggleInterfaces],
+                strings::get("footer.panels"),
+            ),
+        ];
+        let spans: Vec<Span> = hints
+            .iter()
+            .map(|(actions, description)| {
+                let keys: Vec<String> = actions
+                    .iter()
+                    .map(|action| self.keymap.key_label(*action))
+                    .collect();
+                Span::raw(format!("{}: {description}  ", keys.join("/")))
+            })
+            .collect();
+
+        let footer_text = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
+
+        frame.render_widget(footer_text, area);
+    }
 }
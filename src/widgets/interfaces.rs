@@ -1,11 +1,14 @@
-use pnet::{datalink::NetworkInterface, util::MacAddr};
+use std::collections::HashMap;
+
+use crate::{strings, theme::Theme};
+use pnet::datalink::NetworkInterface;
 use ratatui::{
     layout::{Alignment, Constraint, Margin},
     style::Style,
     text::{Line, Span},
     widgets::{
-        Block, Borders, Cell, Padding, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table,
-        TableState,
+        Block, Borders, Cell, Padding, Paragraph, Row, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Table, TableState, Wrap,
     },
     Frame,
 };
@@ -13,19 +16,30 @@ use ratatui::{
 pub struct InterfacesWidget<'a> {
     interfaces: &'a [NetworkInterface],
     current_interface: &'a Option<NetworkInterface>,
+    mtus: Option<&'a HashMap<String, usize>>,
+    theme: &'a Theme,
 }
 
 impl<'a> InterfacesWidget<'a> {
     pub fn new(
         interfaces: &'a [NetworkInterface],
         current_interface: &'a Option<NetworkInterface>,
+        theme: &'a Theme,
     ) -> Self {
         Self {
             interfaces,
             current_interface,
+            mtus: None,
+            theme,
         }
     }
 
+    /// MTU of the interfaces by name, shown next to their names where known.
+    pub fn mtus(mut self, mtus: &'a HashMap<String, usize>) -> Self {
+        self.mtus = Some(mtus);
+        self
+    }
+
     pub fn render(
         &self,
         frame: &mut Frame,
@@ -33,6 +47,16 @@ impl<'a> InterfacesWidget<'a> {
         _table_state: &mut TableState,
         scroll_state: &mut ScrollbarState,
     ) {
+        if self.interfaces.is_empty() {
+            let message = Paragraph::new(strings::get("interfaces.none"))
+                .wrap(Wrap { trim: true })
+                .centered()
+                .style(self.theme.error_style())
+                .block(self.build_block());
+            frame.render_widget(message, area);
+            return;
+        }
+
         let table = self.build_table();
         frame.render_widget(table, area);
 
@@ -49,10 +73,17 @@ impl<'a> InterfacesWidget<'a> {
         );
     }
 
-    fn build_table(&self) -> Table {
-        let header = Row::new(vec!["", "Nome", "MAC", "Ipv4", "Ipv6"])
-            .style(Style::default())
-            .height(1);
+    fn build_table(&self) -> Table<'_> {
+        let header = Row::new(vec![
+            "",
+            strings::get("interfaces.name"),
+            strings::get("interfaces.mtu"),
+            strings::get("interfaces.mac"),
+            strings::get("interfaces.ipv4"),
+            strings::get("interfaces.ipv6"),
+        ])
+        .style(Style::default())
+        .height(1);
 
         let rows = self.build_interface_rows();
 
@@ -61,6 +92,7 @@ impl<'a> InterfacesWidget<'a> {
             [
                 Constraint::Length(1),
                 Constraint::Length(8),
+                Constraint::Length(5),
                 Constraint::Length(18),
                 Constraint::Length(14),
                 Constraint::Length(25),
@@ -71,19 +103,16 @@ impl<'a> InterfacesWidget<'a> {
         .column_spacing(1)
     }
 
-    fn build_interface_rows(&self) -> Vec<Row> {
+    fn build_interface_rows(&self) -> Vec<Row<'_>> {
         self.interfaces
             .iter()
             .map(|interface| self.build_interface_row(interface))
             .collect()
     }
 
-    fn build_interface_row(&self, interface: &NetworkInterface) -> Row {
-        let active = if self.is_current_interface(interface) {
-            ">"
-        } else {
-            ""
-        };
+    fn build_interface_row(&self, interface: &NetworkInterface) -> Row<'_> {
+        let is_current = self.is_current_interface(interface);
+        let active = if is_current { ">" } else { "" };
 
         let name = if cfg!(windows) {
             interface.description.clone()
@@ -91,7 +120,12 @@ impl<'a> InterfacesWidget<'a> {
             interface.name.clone()
         };
 
-        let mac = interface.mac.unwrap_or(MacAddr::default()).to_string();
+        let mtu = self
+            .mtus
+            .and_then(|mtus| mtus.get(&interface.name))
+            .map(|mtu| mtu.to_string())
+            .unwrap_or_default();
+        let mac = interface.mac.map(|mac| mac.to_string()).unwrap_or_default();
 
         let (ipv4_lines, ipv6_spans) = self.extract_ip_info(interface);
         let row_height = std::cmp::max(1, ipv4_lines.len() as u16);
@@ -99,20 +133,24 @@ impl<'a> InterfacesWidget<'a> {
         Row::new(vec![
             Cell::from(Span::styled(format!("{active:<1}"), Style::default())),
             Cell::from(Span::styled(format!("{name:<2}"), Style::default())),
+            Cell::from(mtu),
             Cell::from(mac),
             Cell::from(ipv4_lines),
             Cell::from(vec![Line::from(ipv6_spans)]),
         ])
         .height(row_height)
+        .style(if is_current {
+            self.theme.highlight_style()
+        } else {
+            Style::default()
+        })
     }
 
     fn is_current_interface(&self, interface: &NetworkInterface) -> bool {
-        self.current_interface
-            .as_ref()
-            .map_or(false, |current| current == interface)
+        self.current_interface.as_ref() == Some(interface)
     }
 
-    fn extract_ip_info(&self, interface: &NetworkInterface) -> (Vec<Line>, Vec<Span>) {
+    fn extract_ip_info(&self, interface: &NetworkInterface) -> (Vec<Line<'_>>, Vec<Span<'_>>) {
         let ipv4_lines: Vec<Line> = interface
             .ips
             .iter()
@@ -135,14 +173,14 @@ impl<'a> InterfacesWidget<'a> {
         (ipv4_lines, ipv6_spans)
     }
 
-    fn build_block(&self) -> Block {
+    fn build_block(&self) -> Block<'_> {
         Block::default()
             .title(Line::from(vec![Span::styled(
-                "Interfaces",
+                strings::get("interfaces.title"),
                 Style::default(),
             )]))
-            .border_style(Style::default())
-            .title_style(Style::default())
+            .border_style(self.theme.border_style())
+            .title_style(self.theme.title_style())
             .title_alignment(Alignment::Right)
             .borders(Borders::ALL)
             .padding(Padding::new(0, 0, 1, 0))
@@ -1,19 +1,109 @@
-use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use serde::{Deserialize, Serialize};
+
+/// Sizes of the main panels, set from the config file or resized interactively.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PanelLayout {
+    /// Height of the chart/interfaces row, as a percentage of the terminal.
+    pub top_percent: u16,
+    /// Width of the chart within the top row, as a percentage.
+    pub chart_percent: u16,
+    pub show_chart: bool,
+    pub show_interfaces: bool,
+    /// Gives the whole screen above the footer to the packet list, without changing the
+    /// panel sizes it returns to.
+    pub fullscreen: bool,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self {
+            top_percent: 35,
+            chart_percent: 50,
+            show_chart: true,
+            show_interfaces: true,
+            fullscreen: false,
+        }
+    }
+}
+
+impl PanelLayout {
+    const STEP: i16 = 5;
+    const TOP_PERCENT_RANGE: (u16, u16) = (10, 80);
+    const CHART_PERCENT_RANGE: (u16, u16) = (10, 90);
+    /// Below this many rows only the packet list and the footer are drawn.
+    const MIN_HEIGHT_FOR_TOP: u16 = 30;
+
+    /// Grows (or with a negative `steps`, shrinks) the top row.
+    pub fn resize_top(&mut self, steps: i16) {
+        let (min, max) = Self::TOP_PERCENT_RANGE;
+        self.top_percent = Self::resize(self.top_percent, steps, min, max);
+    }
+
+    /// Grows (or with a negative `steps`, shrinks) the chart at the interfaces' expense.
+    pub fn resize_chart(&mut self, steps: i16) {
+        let (min, max) = Self::CHART_PERCENT_RANGE;
+        self.chart_percent = Self::resize(self.chart_percent, steps, min, max);
+    }
+
+    fn resize(percent: u16, steps: i16, min: u16, max: u16) -> u16 {
+        percent
+            .saturating_add_signed(steps * Self::STEP)
+            .clamp(min, max)
+    }
+
+    fn has_top(&self) -> bool {
+        !self.fullscreen && (self.show_chart || self.show_interfaces)
+    }
+}
 
 pub struct LayoutHelper;
 
 impl LayoutHelper {
-    pub fn create_main_layout(area: Rect) -> (Rect, Rect, Rect) {
-        let vertical_layout =
-            Layout::vertical([Constraint::Percentage(35), Constraint::Percentage(60), Constraint::Percentage(5)]);
+    /// Splits the screen into the top row, the packet list and the footer. The top row is
+    /// left out in fullscreen mode, when both of its panels are hidden or when the terminal
+    /// is too short for it.
+    pub fn create_main_layout(area: Rect, layout: &PanelLayout) -> (Option<Rect>, Rect, Rect) {
+        if !layout.has_top() || area.height < PanelLayout::MIN_HEIGHT_FOR_TOP {
+            let [packets_area, footer_area] =
+                Layout::vertical([Constraint::Fill(1), Constraint::Percentage(5)]).areas(area);
+            return (None, packets_area, footer_area);
+        }
+
+        let vertical_layout = Layout::vertical([
+            Constraint::Percentage(layout.top_percent),
+            Constraint::Fill(1),
+            Constraint::Percentage(5),
+        ]);
         let [top_area, packets_area, footer_area] = vertical_layout.areas(area);
-        (top_area, packets_area, footer_area)
+        (Some(top_area), packets_area, footer_area)
+    }
+
+    pub fn create_centered_layout(area: Rect, percent_x: u16, percent_y: u16) -> Rect {
+        let vertical = Layout::vertical([Constraint::Percentage(percent_y)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Percentage(percent_x)]).flex(Flex::Center);
+
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+        area
     }
 
-    pub fn create_top_layout(area: Rect) -> (Rect, Rect) {
-        let horizontal_layout =
-            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]);
-        let [chart_area, interfaces_area] = horizontal_layout.areas(area);
-        (chart_area, interfaces_area)
+    /// Splits the top row between the chart and the interfaces; a hidden panel leaves
+    /// the whole row to the other.
+    pub fn create_top_layout(area: Rect, layout: &PanelLayout) -> (Option<Rect>, Option<Rect>) {
+        match (layout.show_chart, layout.show_interfaces) {
+            (true, true) => {
+                let horizontal_layout = Layout::horizontal([
+                    Constraint::Percentage(layout.chart_percent),
+                    Constraint::Fill(1),
+                ]);
+                let [chart_area, interfaces_area] = horizontal_layout.areas(area);
+                (Some(chart_area), Some(interfaces_area))
+            }
+            (true, false) => (Some(area), None),
+            (false, true) => (None, Some(area)),
+            (false, false) => (None, None),
+        }
     }
 }
@@ -1,6 +1,16 @@
 pub mod charts;
+pub mod columns;
+pub mod command_palette;
+pub mod comparison;
+pub mod conversations;
+pub mod error_dialog;
+pub mod event_log;
+pub mod expert_info;
+pub mod filter_presets;
+pub mod footer;
 pub mod interfaces;
 pub mod layout_helper;
 pub mod packet_table;
 pub mod popup;
-pub mod footer;
\ No newline at end of file
+pub mod stream;
+pub mod top_talkers;
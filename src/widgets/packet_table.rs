@@ -1,97 +1,569 @@
+use std::{collections::HashSet, net::IpAddr};
+
 use ratatui::{
     layout::{Alignment, Constraint, Margin, Rect},
-    text::Text,
+    style::{Style, Stylize},
+    text::{Line, Span, Text},
     widgets::{
         Block, Borders, Cell, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget,
         Table, TableState,
     },
 };
 
-use crate::packet_data::{CompletePacket, PacketsData};
+use pnet::packet::ethernet::EtherType;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app_protocol::classify_app_protocol,
+    filter::{DirectionFilter, DisplayFilter, HighlightRules, ProtocolToggles},
+    geoip::GeoIp,
+    packet_data::{CompletePacket, Icmpv6PacketInfo, PacketsData},
+    resolver::Resolver,
+    strings,
+    theme::Theme,
+};
 
 pub struct PacketTableState {
     pub table_state: TableState,
     pub scroll_state: ScrollbarState,
+    pub frozen: bool,
+    /// Whether `next_row` past the last row selects the first one and `previous_row`
+    /// before the first selects the last; otherwise both stop at the ends.
+    pub wrap: bool,
+    /// Packet rows that fit in the table when it was last drawn; 0 before the first draw.
+    pub viewport_rows: usize,
+    /// One line per packet instead of two, fitting twice as many packets on screen.
+    pub compact: bool,
+    /// The oldest packet on top and new ones added at the bottom, as most capture tools
+    /// list them, instead of the newest on top.
+    pub oldest_first: bool,
+    /// Rows in the table when it was last drawn plus the packets inserted since, to tell
+    /// whether the newest packet was selected when another one arrives.
+    pub packets_len: usize,
 }
 
 impl PacketTableState {
-    pub fn new() -> Self {
+    pub fn new(wrap: bool) -> Self {
         Self {
             table_state: TableState::default().with_selected(0),
             scroll_state: ScrollbarState::new(0),
+            frozen: false,
+            wrap,
+            viewport_rows: 0,
+            compact: false,
+            oldest_first: false,
+            packets_len: 0,
+        }
+    }
+
+    /// Freezing pins the view on the packets being read; unfreezing jumps back to the
+    /// newest packet.
+    pub fn toggle_frozen(&mut self, packets_len: usize) {
+        self.frozen = !self.frozen;
+        if !self.frozen {
+            self.select_newest(packets_len);
+        }
+    }
+
+    /// Selects the newest packet and scrolls to it: the first row, or the last one when
+    /// the table is oldest first.
+    pub fn select_newest(&mut self, packets_len: usize) {
+        if self.oldest_first {
+            self.select_last(packets_len);
+        } else {
+            *self.table_state.offset_mut() = 0;
+            self.select_first();
+        }
+    }
+
+    /// Called after a visible packet is captured. Oldest first, it is added below and
+    /// the selection follows it if it was on the newest packet. Newest first, it is
+    /// inserted above, and while frozen the selection and scroll offset move down with
+    /// the rows so the view doesn't shift.
+    pub fn packet_inserted(&mut self) {
+        self.packets_len += 1;
+        if self.oldest_first {
+            let following = self
+                .selected()
+                .is_some_and(|selected| selected + 2 == self.packets_len);
+            if following && !self.frozen {
+                self.select(self.packets_len - 1);
+            }
+            return;
+        }
+        if !self.frozen {
+            return;
+        }
+        if let Some(selected) = self.selected() {
+            self.select(selected + 1);
         }
+        *self.table_state.offset_mut() += 1;
     }
 
+    /// Selects the row below, towards older packets unless the table is oldest first. An
+    /// empty table keeps no selection.
     pub fn next_row(&mut self, packets_len: usize) {
+        if packets_len == 0 {
+            self.deselect();
+            return;
+        }
+        let last = packets_len - 1;
         let i = match self.table_state.selected() {
-            Some(i) => {
-                if packets_len == 0 || i >= packets_len - 1 {
-                    0
-                } else {
-                    i + 1
-                }
-            }
+            Some(i) if i >= last && self.wrap => 0,
+            Some(i) => (i + 1).min(last),
             None => 0,
         };
-        self.table_state.select(Some(i));
-        self.scroll_state = self.scroll_state.position(i);
+        self.select(i);
     }
 
+    /// Selects the row above, towards newer packets unless the table is oldest first. An
+    /// empty table keeps no selection.
     pub fn previous_row(&mut self, packets_len: usize) {
+        if packets_len == 0 {
+            self.deselect();
+            return;
+        }
+        let last = packets_len - 1;
         let i = match self.table_state.selected() {
-            Some(i) => {
-                if i == 0 {
-                    if packets_len > 0 {
-                        packets_len - 1
-                    } else {
-                        0
-                    }
-                } else {
-                    i - 1
-                }
-            }
+            Some(0) if self.wrap => last,
+            Some(i) => i.saturating_sub(1).min(last),
             None => 0,
         };
-        self.table_state.select(Some(i));
-        self.scroll_state = self.scroll_state.position(i);
+        self.select(i);
+    }
+
+    fn deselect(&mut self) {
+        self.table_state.select(None);
+        self.scroll_state = self.scroll_state.position(0);
+    }
+
+    /// Moves the selection by `rows` (negative moves up), stopping
+    /// at either end of the list instead of wrapping.
+    pub fn move_by(&mut self, rows: isize, packets_len: usize) {
+        if packets_len == 0 {
+            return;
+        }
+        let current = self.selected().unwrap_or(0);
+        let target = current.saturating_add_signed(rows).min(packets_len - 1);
+        self.select(target);
+    }
+
+    pub fn select_first(&mut self) {
+        self.select(0);
+    }
+
+    pub fn select_last(&mut self, packets_len: usize) {
+        self.select(packets_len.saturating_sub(1));
     }
 
     pub fn selected(&self) -> Option<usize> {
         self.table_state.selected()
     }
+
+    pub fn row_height(&self) -> u16 {
+        if self.compact {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Number of packet rows that fit in the table `area`, at least one.
+    pub fn visible_rows(&self, area: Rect) -> usize {
+        let body_height = area
+            .height
+            .saturating_sub(2 * PacketTable::BORDER_HEIGHT + PacketTable::HEADER_HEIGHT);
+        ((body_height / self.row_height()) as usize).max(1)
+    }
+
+    /// Maps a terminal row inside the table `area` to a packet index, skipping the block
+    /// border and the header.
+    pub fn row_at(&self, area: Rect, row: u16) -> Option<usize> {
+        let first_row = area.y + PacketTable::BORDER_HEIGHT + PacketTable::HEADER_HEIGHT;
+        let last_row = area.bottom().saturating_sub(PacketTable::BORDER_HEIGHT);
+        if row < first_row || row >= last_row {
+            return None;
+        }
+        let offset = ((row - first_row) / self.row_height()) as usize;
+        Some(self.table_state.offset() + offset)
+    }
+
+    pub fn select(&mut self, index: usize) {
+        self.table_state.select(Some(index));
+        self.scroll_state = self.scroll_state.position(index);
+    }
+
+    /// Selects a row that can be far from the current one, such as a search match or a
+    /// bookmark. A row outside the visible window is scrolled to the middle of it, where
+    /// ratatui would only bring it to the nearest edge.
+    pub fn jump_to(&mut self, index: usize) {
+        self.select(index);
+        let offset = self.table_state.offset();
+        let visible = offset..offset + self.viewport_rows;
+        if self.viewport_rows > 0 && !visible.contains(&index) {
+            *self.table_state.offset_mut() = index.saturating_sub(self.viewport_rows / 2);
+        }
+    }
+
+    /// Selects the next (or previous) packet matching `query`, wrapping around the list.
+    pub fn search(&mut self, packets: &[&CompletePacket], query: &str, backward: bool) -> bool {
+        let from_index = self.selected().unwrap_or(0);
+        let predicate = |packet: &CompletePacket| PacketTable::matches_query(packet, query);
+        let found = if backward {
+            self.find_previous(packets, predicate, from_index)
+        } else {
+            self.find_next(packets, predicate, from_index)
+        };
+
+        match found {
+            Some(index) => {
+                self.jump_to(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn find_next<P>(
+        &self,
+        packets: &[&CompletePacket],
+        predicate: P,
+        from_index: usize,
+    ) -> Option<usize>
+    where
+        P: Fn(&CompletePacket) -> bool,
+    {
+        let len = packets.len();
+        (1..=len)
+            .map(|offset| (from_index + offset) % len)
+            .find(|&i| predicate(packets[i]))
+    }
+
+    pub fn find_previous<P>(
+        &self,
+        packets: &[&CompletePacket],
+        predicate: P,
+        from_index: usize,
+    ) -> Option<usize>
+    where
+        P: Fn(&CompletePacket) -> bool,
+    {
+        let len = packets.len();
+        (1..=len)
+            .map(|offset| (from_index + len - offset) % len)
+            .find(|&i| predicate(packets[i]))
+    }
+}
+/// A column of the packet table. Which ones are shown, and in what order, comes from the
+/// config file or the column picker.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    Id,
+    Time,
+    Protocol,
+    Source,
+    Destination,
+    Length,
+    Direction,
+    Interface,
+    Service,
+    Info,
 }
+
+impl Column {
+    pub const ALL: [Column; 10] = [
+        Column::Id,
+        Column::Time,
+        Column::Protocol,
+        Column::Source,
+        Column::Destination,
+        Column::Length,
+        Column::Direction,
+        Column::Interface,
+        Column::Service,
+        Column::Info,
+    ];
+
+    pub const DEFAULTS: [Column; 8] = [
+        Column::Id,
+        Column::Protocol,
+        Column::Source,
+        Column::Destination,
+        Column::Length,
+        Column::Direction,
+        Column::Interface,
+        Column::Info,
+    ];
+
+    /// The columns not in `shown`, in their usual order.
+    pub fn hidden(shown: &[Column]) -> impl Iterator<Item = Column> + '_ {
+        Self::ALL
+            .into_iter()
+            .filter(move |column| !shown.contains(column))
+    }
+
+    pub fn header(self) -> &'static str {
+        match self {
+            Column::Id => strings::get("table.id"),
+            Column::Time => strings::get("table.time"),
+            Column::Protocol => strings::get("table.protocol"),
+            Column::Source => strings::get("table.source"),
+            Column::Destination => strings::get("table.destination"),
+            Column::Length => strings::get("table.length"),
+            Column::Direction => strings::get("table.direction"),
+            Column::Interface => strings::get("table.interface"),
+            Column::Service => strings::get("table.service"),
+            Column::Info => strings::get("table.info"),
+        }
+    }
+
+    fn width(self) -> Constraint {
+        match self {
+            Column::Id | Column::Length => Constraint::Length(8),
+            Column::Time => Constraint::Length(12),
+            Column::Protocol | Column::Interface | Column::Service => Constraint::Length(10),
+            Column::Source | Column::Destination => Constraint::Length(25),
+            Column::Direction => Constraint::Length(4),
+            Column::Info => Constraint::Fill(1),
+        }
+    }
+
+    /// The cell for `packet`, with `fields` being its row from `generate_ref_array` after
+    /// names and countries were filled in.
+    fn value(self, packet: &CompletePacket, fields: &[String; 5]) -> String {
+        match self {
+            Column::Id => fields[0].clone(),
+            Column::Time => packet.timestamp.format("%H:%M:%S%.3f").to_string(),
+            Column::Protocol => fields[1].clone(),
+            Column::Source => fields[2].clone(),
+            Column::Destination => fields[3].clone(),
+            Column::Length => fields[4].clone(),
+            Column::Direction => packet.traffic_direction.indicator().to_string(),
+            Column::Interface => packet.interface.clone(),
+            Column::Service => classify_app_protocol(packet)
+                .map(str::to_string)
+                .unwrap_or_default(),
+            Column::Info => PacketTable::info(packet),
+        }
+    }
+}
+
 pub struct PacketTable<'a> {
-    packets: &'a [CompletePacket],
+    packets: &'a [&'a CompletePacket],
     block: Option<Block<'a>>,
     sniffer_paused: bool,
+    start_key: Option<String>,
+    theme: &'a Theme,
+    resolver: Option<&'a Resolver>,
+    geoip: Option<&'a GeoIp>,
+    display_filter: Option<&'a DisplayFilter>,
+    dim_unmatched: bool,
+    verify_checksums: bool,
+    highlight_expert_info: bool,
+    highlight_rules: Option<&'a HighlightRules>,
+    bookmarks: Option<&'a HashSet<usize>>,
+    protocol_toggles: Option<(&'a ProtocolToggles, [String; 9])>,
+    total_captured: Option<usize>,
+    direction_filter: DirectionFilter,
+    columns: &'a [Column],
 }
 
 impl<'a> PacketTable<'a> {
-    pub fn new(packets: &'a [CompletePacket], sniffer_paused: bool) -> Self {
+    const BORDER_HEIGHT: u16 = 1;
+    const HEADER_HEIGHT: u16 = 1;
+    const BOOKMARK_MARKER: &'static str = "★ ";
+    const COMMENT_MARKER: &'static str = "✎ ";
+
+    pub fn new(packets: &'a [&'a CompletePacket], sniffer_paused: bool, theme: &'a Theme) -> Self {
         Self {
             packets,
             block: None,
             sniffer_paused,
+            start_key: None,
+            theme,
+            resolver: None,
+            geoip: None,
+            display_filter: None,
+            dim_unmatched: false,
+            verify_checksums: false,
+            highlight_expert_info: false,
+            highlight_rules: None,
+            bookmarks: None,
+            protocol_toggles: None,
+            total_captured: None,
+            direction_filter: DirectionFilter::Both,
+            columns: &Column::DEFAULTS,
+        }
+    }
+
+    /// The columns to show, in order. An empty list shows the default ones.
+    pub fn columns(mut self, columns: &'a [Column]) -> Self {
+        if !columns.is_empty() {
+            self.columns = columns;
+        }
+        self
+    }
+
+    /// Shows the active filter under the table. With `dim_unmatched`, `packets` holds every
+    /// packet and the ones failing the filter are greyed out instead of being left out.
+    pub fn display_filter(
+        mut self,
+        display_filter: Option<&'a DisplayFilter>,
+        dim_unmatched: bool,
+    ) -> Self {
+        self.display_filter = display_filter;
+        self.dim_unmatched = dim_unmatched;
+        self
+    }
+
+    /// Colors the rows of packets with a bad checksum.
+    pub fn verify_checksums(mut self, verify_checksums: bool) -> Self {
+        self.verify_checksums = verify_checksums;
+        self
+    }
+
+    /// Colors the rows of packets with expert info findings by their most severe one.
+    pub fn highlight_expert_info(mut self, highlight_expert_info: bool) -> Self {
+        self.highlight_expert_info = highlight_expert_info;
+        self
+    }
+
+    /// Colors the rows of packets matching one of `highlight_rules` by the first of them,
+    /// over their expert info and protocol colors.
+    pub fn highlight_rules(mut self, highlight_rules: &'a HighlightRules) -> Self {
+        self.highlight_rules = Some(highlight_rules);
+        self
+    }
+
+    /// Marks the ID of every bookmarked packet.
+    pub fn bookmarks(mut self, bookmarks: &'a HashSet<usize>) -> Self {
+        self.bookmarks = Some(bookmarks);
+        self
+    }
+
+    /// Shows the key and state of each protocol switch under the table, `keys` being the
+    /// keys bound to them in the order of `ProtocolToggles::PROTOCOLS`.
+    pub fn protocol_toggles(
+        mut self,
+        protocol_toggles: &'a ProtocolToggles,
+        keys: [String; 9],
+    ) -> Self {
+        self.protocol_toggles = Some((protocol_toggles, keys));
+        self
+    }
+
+    /// "1:TCP 2:UDP ...", with the protocols switched off dimmed and struck through.
+    fn protocol_toggles_legend(
+        theme: &Theme,
+        protocol_toggles: &ProtocolToggles,
+        keys: &[String; 9],
+    ) -> Line<'static> {
+        let spans: Vec<Span> = ProtocolToggles::PROTOCOLS
+            .iter()
+            .zip(keys)
+            .flat_map(|(protocol, key)| {
+                let style = if protocol_toggles.is_enabled(protocol) {
+                    theme.protocol_style(protocol)
+                } else {
+                    theme.dimmed_style().crossed_out()
+                };
+                [
+                    Span::raw(format!(" {key}:")),
+                    Span::styled(protocol.to_string(), style),
+                ]
+            })
+            .chain([Span::raw(" ")])
+            .collect();
+        Line::from(spans).right_aligned()
+    }
+
+    /// Names the direction packets are limited to, if any, under the table.
+    pub fn direction_filter(mut self, direction_filter: DirectionFilter) -> Self {
+        self.direction_filter = direction_filter;
+        self
+    }
+
+    /// Shows how many packets were captured in all, next to how many are listed.
+    pub fn total_captured(mut self, total_captured: usize) -> Self {
+        self.total_captured = Some(total_captured);
+        self
+    }
+
+    pub fn resolver(mut self, resolver: Option<&'a Resolver>) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    pub fn geoip(mut self, geoip: Option<&'a GeoIp>) -> Self {
+        self.geoip = geoip;
+        self
+    }
+
+    /// The addresses shown in the source/destination fields; GTP-U and VXLAN rows show
+    /// the tunneled packet's.
+    fn shown_addresses(complete_packet: &CompletePacket) -> Option<(IpAddr, IpAddr)> {
+        complete_packet
+            .tunneled_addresses()
+            .or_else(|| complete_packet.ip_addresses())
+    }
+
+    /// Swaps the IP addresses at the start of the source/destination fields for their
+    /// host names, when already resolved.
+    fn resolve_names(&self, complete_packet: &CompletePacket, fields: &mut [String; 5]) {
+        let addresses = Self::shown_addresses(complete_packet);
+        let (Some(resolver), Some((source, destination))) = (self.resolver, addresses) else {
+            return;
+        };
+
+        for (field, ip) in [(2, source), (3, destination)] {
+            if let Some(name) = resolver.lookup(ip) {
+                let ip = ip.to_string();
+                if let Some(rest) = fields[field].strip_prefix(ip.as_str()) {
+                    fields[field] = format!("{name}{rest}");
+                }
+            }
+        }
+    }
+
+    /// Appends the country code of public addresses to the source/destination fields.
+    fn annotate_countries(&self, complete_packet: &CompletePacket, fields: &mut [String; 5]) {
+        let addresses = Self::shown_addresses(complete_packet);
+        let (Some(geoip), Some((source, destination))) = (self.geoip, addresses) else {
+            return;
+        };
+
+        for (field, ip) in [(2, source), (3, destination)] {
+            if let Some(country) = geoip.lookup(ip).and_then(|info| info.country) {
+                fields[field] = format!("{} [{country}]", fields[field]);
+            }
         }
     }
 
+    /// Key that starts a capture that hasn't started yet, named in the title in place of
+    /// the paused notice. Set before `block`.
+    pub fn start_key(mut self, start_key: Option<String>) -> Self {
+        self.start_key = start_key;
+        self
+    }
+
     pub fn block(mut self, block: Block<'a>) -> Self {
+        let title = match &self.start_key {
+            Some(key) => strings::get("table.sniffer_not_started").replace("{key}", key),
+            None if self.sniffer_paused => strings::get("table.sniffer_paused").to_string(),
+            None => strings::get("table.sniffer_running").to_string(),
+        };
         self.block = Some(
             block
-                .title(format!(
-                    "Sniffer está {}",
-                    if self.sniffer_paused {
-                        "pausado."
-                    } else {
-                        "rodando."
-                    }
-                ))
-                .title_alignment(Alignment::Center),
+                .title(title)
+                .title_alignment(Alignment::Center)
+                .border_style(self.theme.border_style())
+                .title_style(self.theme.title_style()),
         );
         self
     }
 
-    fn generate_ref_array(&self, complete_packet: &CompletePacket) -> Option<[String; 5]> {
+    pub fn generate_ref_array(complete_packet: &CompletePacket) -> Option<[String; 5]> {
         if let Some(layer2) = &complete_packet.layer_2 {
             let (src_ip, dst_ip) = match layer2 {
                 PacketsData::ArpPacket(arp_packet) => {
@@ -103,6 +575,24 @@ impl<'a> PacketTable<'a> {
                         arp_packet.length.to_string(),
                     ]);
                 }
+                PacketsData::LldpPacket(lldp) => {
+                    let destination = match &complete_packet.layer_1 {
+                        Some(PacketsData::EthernetPacket(ethernet)) => {
+                            ethernet.destination.to_string()
+                        }
+                        _ => "".to_string(),
+                    };
+                    return Some([
+                        complete_packet.id.to_string(),
+                        "LLDP".to_string(),
+                        lldp.system_name
+                            .clone()
+                            .or_else(|| lldp.chassis_id.clone())
+                            .unwrap_or_default(),
+                        destination,
+                        lldp.length.to_string(),
+                    ]);
+                }
                 PacketsData::Ipv4Packet(ipv4) => {
                     (ipv4.source.to_string(), ipv4.destination.to_string())
                 }
@@ -112,6 +602,60 @@ impl<'a> PacketTable<'a> {
                 _ => ("".to_string(), "".to_string()),
             };
 
+            match &complete_packet.layer_4 {
+                Some(PacketsData::GtpPacket(gtp)) => {
+                    return Some(Self::tunnel_ref_array(
+                        complete_packet,
+                        "GTP-U",
+                        gtp.length as usize,
+                        src_ip,
+                        dst_ip,
+                    ));
+                }
+                Some(PacketsData::VxlanPacket(vxlan)) => {
+                    return Some(Self::tunnel_ref_array(
+                        complete_packet,
+                        "VXLAN",
+                        vxlan.length,
+                        src_ip,
+                        dst_ip,
+                    ));
+                }
+                _ => {}
+            }
+
+            let application = match &complete_packet.layer_4 {
+                Some(PacketsData::DnsPacket(dns)) => Some(("DNS", dns.length)),
+                Some(PacketsData::MdnsPacket(mdns)) => Some(("mDNS", mdns.length)),
+                Some(PacketsData::SsdpPacket(ssdp)) => Some(("SSDP", ssdp.length)),
+                Some(PacketsData::RadiusPacket(radius)) => Some(("RADIUS", radius.length)),
+                Some(PacketsData::SshPacket(ssh)) => Some(("SSH", ssh.length)),
+                _ => None,
+            };
+            if let (Some((protocol, length)), Some((src_port, dst_port))) =
+                (application, complete_packet.ports())
+            {
+                return Some([
+                    complete_packet.id.to_string(),
+                    protocol.to_string(),
+                    format!("{}:{}", src_ip, src_port),
+                    format!("{}:{}", dst_ip, dst_port),
+                    length.to_string(),
+                ]);
+            }
+
+            if let (Some(PacketsData::HttpPacket(http)), Some((src_port, dst_port))) =
+                (&complete_packet.layer_4, complete_packet.ports())
+            {
+                return Some([
+                    complete_packet.id.to_string(),
+                    "HTTP".to_string(),
+                    format!("{}:{}", src_ip, src_port),
+                    format!("{}:{}", dst_ip, dst_port),
+                    http.length.to_string(),
+                ]);
+            }
+
             if let Some(layer3) = &complete_packet.layer_3 {
                 match layer3 {
                     PacketsData::TcpPacket(tcp) => {
@@ -132,6 +676,15 @@ impl<'a> PacketTable<'a> {
                             udp.length.to_string(),
                         ]);
                     }
+                    PacketsData::SctpPacket(sctp) => {
+                        return Some([
+                            complete_packet.id.to_string(),
+                            "SCTP".to_string(),
+                            format!("{}:{}", src_ip, sctp.source),
+                            format!("{}:{}", dst_ip, sctp.destination),
+                            sctp.length.to_string(),
+                        ]);
+                    }
                     PacketsData::IcmpPacket(icmp) => {
                         return Some([
                             complete_packet.id.to_string(),
@@ -153,58 +706,364 @@ impl<'a> PacketTable<'a> {
                     _ => {}
                 }
             }
+
+            // IP carrying a protocol without a dissector, or a fragment whose datagram
+            // isn't complete yet.
+            match layer2 {
+                PacketsData::Ipv4Packet(ipv4) => {
+                    return Some([
+                        complete_packet.id.to_string(),
+                        "IPv4".to_string(),
+                        src_ip,
+                        dst_ip,
+                        ipv4.length.to_string(),
+                    ]);
+                }
+                PacketsData::Ipv6Packet(ipv6) => {
+                    return Some([
+                        complete_packet.id.to_string(),
+                        "IPv6".to_string(),
+                        src_ip,
+                        dst_ip,
+                        ipv6.length.to_string(),
+                    ]);
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(PacketsData::Ieee80211Packet(wifi)) = &complete_packet.layer_1 {
+            return Some([
+                complete_packet.id.to_string(),
+                "802.11".to_string(),
+                wifi.transmitter()
+                    .map(|mac| mac.to_string())
+                    .unwrap_or_default(),
+                wifi.receiver()
+                    .map(|mac| mac.to_string())
+                    .unwrap_or_default(),
+                wifi.length.to_string(),
+            ]);
+        }
+
+        if let Some(PacketsData::EthernetPacket(ethernet)) = &complete_packet.layer_1 {
+            let protocol = match &complete_packet.mpls {
+                Some(_) => "MPLS".to_string(),
+                None => Self::ethertype_name(ethernet.ethertype),
+            };
+            return Some([
+                complete_packet.id.to_string(),
+                protocol,
+                ethernet.source.to_string(),
+                ethernet.destination.to_string(),
+                ethernet.payload_length.to_string(),
+            ]);
         }
         None
     }
+
+    fn ethertype_name(ethertype: EtherType) -> String {
+        match ethertype.to_string().as_str() {
+            "unknown" => format!("0x{:04x}", ethertype.0),
+            name => name.to_uppercase(),
+        }
+    }
+
+    /// Short summary for the Info column: TCP direction, IPv4 fragmentation, VLAN tags, the
+    /// top MPLS label and application-level details, e.g. "C→S VLAN 10 GET example.com/".
+    pub fn info(complete_packet: &CompletePacket) -> String {
+        let direction = complete_packet
+            .tcp_direction
+            .map(|direction| direction.indicator().to_string())
+            .into_iter()
+            .chain(Self::fragment_info(complete_packet));
+        let vlans = complete_packet
+            .vlan_tags
+            .iter()
+            .map(|vlan| format!("VLAN {}", vlan.vlan_id))
+            .chain(
+                complete_packet
+                    .mpls
+                    .iter()
+                    .filter_map(|mpls| mpls.labels.first())
+                    .map(|label| format!("MPLS {}", label.label)),
+            );
+        let application = Self::application_info(complete_packet);
+        direction
+            .chain(vlans)
+            .chain((!application.is_empty()).then_some(application))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn fragment_info(complete_packet: &CompletePacket) -> Option<String> {
+        if let Some(fragments) = complete_packet.reassembled_fragments {
+            return Some(format!("[reassembled from {fragments} fragments]"));
+        }
+        match &complete_packet.layer_2 {
+            Some(PacketsData::Ipv4Packet(ipv4)) if ipv4.is_fragment() => Some(format!(
+                "[{} fragment, offset {}]",
+                ipv4.next_level_protocol.to_string().to_uppercase(),
+                ipv4.fragment_offset_bytes()
+            )),
+            _ => None,
+        }
+    }
+
+    /// Endpoints of the tunneled packet, or of the tunnel itself for GTP-U messages that
+    /// carry no subscriber traffic and VXLAN frames without IP.
+    fn tunnel_ref_array(
+        complete_packet: &CompletePacket,
+        protocol: &str,
+        length: usize,
+        src_ip: String,
+        dst_ip: String,
+    ) -> [String; 5] {
+        let (src_ip, dst_ip, ports) = match complete_packet.tunneled_addresses() {
+            Some((source, destination)) => (
+                source.to_string(),
+                destination.to_string(),
+                complete_packet.tunneled_ports(),
+            ),
+            None => (src_ip, dst_ip, complete_packet.ports()),
+        };
+        let (source, destination) = match ports {
+            Some((src_port, dst_port)) => (
+                format!("{src_ip}:{src_port}"),
+                format!("{dst_ip}:{dst_port}"),
+            ),
+            None => (src_ip, dst_ip),
+        };
+        [
+            complete_packet.id.to_string(),
+            protocol.to_string(),
+            source,
+            destination,
+            length.to_string(),
+        ]
+    }
+
+    fn application_info(complete_packet: &CompletePacket) -> String {
+        // Neighbor discovery names the neighbor being resolved and its MAC address.
+        if let Some(PacketsData::Icmpv6Packet(Icmpv6PacketInfo {
+            icmpv6_type,
+            ndp: Some(ndp),
+            ..
+        })) = &complete_packet.layer_3
+        {
+            return ndp.summary(*icmpv6_type);
+        }
+        match &complete_packet.layer_4 {
+            Some(PacketsData::HttpPacket(http)) => match (&http.method, http.status_code) {
+                (Some(method), _) => format!(
+                    "{method} {}{}",
+                    http.host.as_deref().unwrap_or_default(),
+                    http.path.as_deref().unwrap_or_default()
+                ),
+                (None, Some(status_code)) => format!(
+                    "{status_code} {}",
+                    http.reason.as_deref().unwrap_or_default()
+                ),
+                (None, None) => String::new(),
+            },
+            Some(PacketsData::SsdpPacket(ssdp)) => ssdp.summary(),
+            Some(PacketsData::RadiusPacket(radius)) => radius.summary(),
+            Some(PacketsData::SshPacket(ssh)) => ssh.summary(),
+            Some(PacketsData::GtpPacket(gtp)) => {
+                Self::tunnel_info(format!("TEID 0x{:08x}", gtp.teid), &gtp.inner)
+            }
+            Some(PacketsData::VxlanPacket(vxlan)) => {
+                Self::tunnel_info(format!("VNI {}", vxlan.vni), &vxlan.inner)
+            }
+            Some(_) => String::new(),
+            None if complete_packet.layer_2.is_none() => match &complete_packet.layer_1 {
+                Some(PacketsData::Ieee80211Packet(wifi)) => wifi.summary(),
+                _ => String::new(),
+            },
+            None => classify_app_protocol(complete_packet)
+                .map(str::to_string)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The tunnel's identifier followed by the innermost protocol it carries.
+    fn tunnel_info(id: String, inner: &[PacketsData]) -> String {
+        let inner_protocol = inner.iter().rev().find_map(|layer| match layer {
+            PacketsData::TcpPacket(_) => Some("TCP"),
+            PacketsData::UdpPacket(_) => Some("UDP"),
+            PacketsData::SctpPacket(_) => Some("SCTP"),
+            PacketsData::IcmpPacket(_) => Some("ICMP"),
+            PacketsData::Icmpv6Packet(_) => Some("ICMPv6"),
+            PacketsData::Ipv4Packet(_) => Some("IPv4"),
+            PacketsData::Ipv6Packet(_) => Some("IPv6"),
+            PacketsData::ArpPacket(_) => Some("ARP"),
+            _ => None,
+        });
+        match inner_protocol {
+            Some(inner_protocol) => format!("{id} {inner_protocol}"),
+            None => id,
+        }
+    }
+
+    pub fn matches_query(complete_packet: &CompletePacket, query: &str) -> bool {
+        let query = query.to_lowercase();
+        Self::generate_ref_array(complete_packet).is_some_and(|fields| {
+            fields
+                .iter()
+                .chain([&complete_packet.interface, &Self::info(complete_packet)])
+                .any(|field| field.to_lowercase().contains(&query))
+        })
+    }
 }
 
 impl<'a> StatefulWidget for PacketTable<'a> {
     type State = PacketTableState;
 
     fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer, state: &mut Self::State) {
-        let header = ["ID", "Protocolo", "Origem", "Destino", "Length"]
-            .into_iter()
-            .map(Cell::from)
+        // Don't leave empty space below the last packet after a jump near the end.
+        state.viewport_rows = state.visible_rows(area);
+        state.packets_len = self.packets.len();
+        let max_offset = self.packets.len().saturating_sub(state.viewport_rows);
+        let mut offset = state.table_state.offset().min(max_offset);
+        // Scroll to the selection the way ratatui would, so only the rows on screen are
+        // built: names and countries are looked up for them alone, and a long capture
+        // doesn't cost a row per packet every frame.
+        if let Some(selected) = state
+            .table_state
+            .selected()
+            .filter(|&selected| selected < self.packets.len())
+        {
+            if selected < offset {
+                offset = selected;
+            } else if selected >= offset + state.viewport_rows {
+                offset = selected + 1 - state.viewport_rows;
+            }
+        }
+        *state.table_state.offset_mut() = offset;
+        let window = offset..(offset + state.viewport_rows).min(self.packets.len());
+        let mut window_state = TableState::default().with_selected(
+            state
+                .table_state
+                .selected()
+                .map(|selected| selected.saturating_sub(offset)),
+        );
+
+        let header = self
+            .columns
+            .iter()
+            .map(|column| Cell::from(column.header()))
             .collect::<Row>();
 
-        let rows: Vec<Row> = self
-            .packets
+        let rows: Vec<Row> = self.packets[window]
             .iter()
             .filter_map(|data| {
-                self.generate_ref_array(data).map(|item| {
-                    item.into_iter()
-                        .map(|content| Cell::from(Text::from(format!("\n{content}\n"))))
+                Self::generate_ref_array(data).map(|mut item| {
+                    self.resolve_names(data, &mut item);
+                    self.annotate_countries(data, &mut item);
+                    if self
+                        .bookmarks
+                        .is_some_and(|bookmarks| bookmarks.contains(&data.id))
+                    {
+                        item[0] = format!("{}{}", Self::BOOKMARK_MARKER, item[0]);
+                    }
+                    if data.comment.is_some() {
+                        item[0] = format!("{}{}", Self::COMMENT_MARKER, item[0]);
+                    }
+                    let unmatched = self.dim_unmatched
+                        && self
+                            .display_filter
+                            .is_some_and(|filter| !filter.matches(data));
+                    let style = if unmatched {
+                        self.theme.dimmed_style()
+                    } else if self.verify_checksums && data.has_bad_checksum() {
+                        self.theme.error_style()
+                    } else if let Some(color) = self
+                        .highlight_rules
+                        .and_then(|highlight_rules| highlight_rules.color(data))
+                    {
+                        Style::default().fg(color)
+                    } else if let Some(severity) =
+                        data.expert_severity.filter(|_| self.highlight_expert_info)
+                    {
+                        self.theme.severity_style(severity)
+                    } else {
+                        self.theme.protocol_style(&item[1])
+                    };
+                    self.columns
+                        .iter()
+                        .map(|column| column.value(data, &item))
+                        .map(|content| {
+                            if state.compact {
+                                Cell::from(content)
+                            } else {
+                                Cell::from(Text::from(format!("\n{content}\n")))
+                            }
+                        })
                         .collect::<Row>()
-                        .height(2)
+                        .height(state.row_height())
+                        .style(style)
                 })
             })
             .collect();
 
-        let widths = [
-            Constraint::Length(8),
-            Constraint::Length(10),
-            Constraint::Length(25),
-            Constraint::Length(25),
-            Constraint::Length(8),
-        ];
+        let widths: Vec<Constraint> = self.columns.iter().map(|column| column.width()).collect();
 
         let bar = " > ";
+        let highlight_symbol = if state.compact {
+            Text::from(bar)
+        } else {
+            Text::from(vec!["".into(), bar.into(), bar.into(), "".into()])
+        };
         let mut table = Table::new(rows, widths)
             .header(header)
-            .highlight_symbol(Text::from(vec![
-                "".into(),
-                bar.into(),
-                bar.into(),
-                "".into(),
-            ]));
-
-        if let Some(block) = self.block {
+            .highlight_style(self.theme.highlight_style())
+            .highlight_symbol(highlight_symbol);
+
+        if let Some(mut block) = self.block {
+            if let Some(total_captured) = self.total_captured {
+                let count = if self.packets.len() == total_captured {
+                    strings::get("table.captured").to_string()
+                } else {
+                    strings::get("table.captured_shown")
+                        .replace("{shown}", &self.packets.len().to_string())
+                };
+                let count = count.replace("{total}", &total_captured.to_string());
+                block = block.title(Line::from(count).left_aligned());
+            }
+            if state.frozen {
+                block = block.title(Line::from(strings::get("table.frozen")).right_aligned());
+            }
+            if let Some(filter) = self.display_filter {
+                let label = if self.dim_unmatched {
+                    strings::get("table.filter_dimmed")
+                } else {
+                    strings::get("table.filter")
+                };
+                block = block.title_bottom(label.replace("{filter}", filter.expression()));
+            }
+            if self.direction_filter != DirectionFilter::Both {
+                block = block.title_bottom(
+                    strings::get("table.direction_filter")
+                        .replace("{direction}", self.direction_filter.name()),
+                );
+            }
+            if let Some((protocol_toggles, keys)) = &self.protocol_toggles {
+                block = block.title_bottom(Self::protocol_toggles_legend(
+                    self.theme,
+                    protocol_toggles,
+                    keys,
+                ));
+            }
             table = table.block(block);
         } else {
-            table = table.block(Block::new().borders(Borders::ALL).title("Lista de pacotes"));
+            table = table.block(
+                Block::new()
+                    .borders(Borders::ALL)
+                    .title(strings::get("table.title")),
+            );
         }
 
-        StatefulWidget::render(table, area, buf, &mut state.table_state.clone());
+        StatefulWidget::render(table, area, buf, &mut window_state);
         state.scroll_state = state.scroll_state.content_length(self.packets.len());
 
         let scrollbar = Scrollbar::default()
@@ -220,3 +1079,120 @@ impl<'a> StatefulWidget for PacketTable<'a> {
         StatefulWidget::render(scrollbar, scrollbar_area, buf, &mut state.scroll_state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Column, PacketTableState};
+
+    #[test]
+    fn hidden_columns_keep_their_usual_order() {
+        let shown = [Column::Info, Column::Id, Column::Source];
+        let hidden: Vec<Column> = Column::hidden(&shown).collect();
+        assert_eq!(
+            hidden,
+            [
+                Column::Time,
+                Column::Protocol,
+                Column::Destination,
+                Column::Length,
+                Column::Direction,
+                Column::Interface,
+                Column::Service,
+            ]
+        );
+        assert_eq!(Column::hidden(&Column::ALL).count(), 0);
+    }
+
+    #[test]
+    fn empty_table_keeps_no_selection() {
+        for wrap in [true, false] {
+            let mut state = PacketTableState::new(wrap);
+            state.next_row(0);
+            assert_eq!(state.selected(), None);
+            state.previous_row(0);
+            assert_eq!(state.selected(), None);
+        }
+    }
+
+    #[test]
+    fn single_row_stays_selected() {
+        for wrap in [true, false] {
+            let mut state = PacketTableState::new(wrap);
+            state.next_row(1);
+            assert_eq!(state.selected(), Some(0));
+            state.previous_row(1);
+            assert_eq!(state.selected(), Some(0));
+        }
+    }
+
+    #[test]
+    fn first_row_is_selected_after_the_table_empties() {
+        let mut state = PacketTableState::new(true);
+        state.next_row(0);
+        state.next_row(3);
+        assert_eq!(state.selected(), Some(0));
+    }
+
+    #[test]
+    fn oldest_first_follows_new_packets_only_from_the_newest_row() {
+        let mut state = PacketTableState::new(false);
+        state.oldest_first = true;
+        for _ in 0..3 {
+            state.packet_inserted();
+        }
+        assert_eq!(state.selected(), Some(2));
+
+        state.previous_row(3);
+        state.packet_inserted();
+        assert_eq!(state.selected(), Some(1));
+
+        state.select_newest(4);
+        state.frozen = true;
+        state.packet_inserted();
+        assert_eq!(state.selected(), Some(3));
+    }
+
+    #[test]
+    fn jumps_center_off_screen_rows() {
+        let mut state = PacketTableState::new(true);
+        state.viewport_rows = 10;
+        state.jump_to(5);
+        assert_eq!(state.table_state.offset(), 0);
+        state.jump_to(50);
+        assert_eq!(state.table_state.offset(), 45);
+        state.jump_to(3);
+        assert_eq!(state.table_state.offset(), 0);
+    }
+
+    #[test]
+    fn compact_rows_take_one_line() {
+        let area = ratatui::layout::Rect::new(0, 0, 80, 12);
+        let mut state = PacketTableState::new(true);
+        assert_eq!(state.visible_rows(area), 4);
+        assert_eq!(state.row_at(area, 4), Some(1));
+
+        state.compact = true;
+        assert_eq!(state.visible_rows(area), 9);
+        assert_eq!(state.row_at(area, 4), Some(2));
+        assert_eq!(state.row_at(area, 11), None);
+    }
+
+    #[test]
+    fn wrapping_goes_around_the_ends() {
+        let mut state = PacketTableState::new(true);
+        state.previous_row(3);
+        assert_eq!(state.selected(), Some(2));
+        state.next_row(3);
+        assert_eq!(state.selected(), Some(0));
+    }
+
+    #[test]
+    fn clamping_stops_at_the_ends() {
+        let mut state = PacketTableState::new(false);
+        state.previous_row(3);
+        assert_eq!(state.selected(), Some(0));
+        state.select(2);
+        state.next_row(3);
+        assert_eq!(state.selected(), Some(2));
+    }
+}
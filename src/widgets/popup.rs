@@ -1,507 +1,1792 @@
-use crate::packet_data::{CompletePacket, PacketsData};
+use crate::{
+    clipboard,
+    flows::{TcpConnectionState, TcpRttEstimator},
+    geoip::GeoIp,
+    keymap::Keymap,
+    packet_data::{
+        CompletePacket, IcmpPacketInfo, Icmpv6PacketInfo, MplsPacketInfo, NdpInfo, NdpOption,
+        PacketsData, VlanPacketInfo,
+    },
+    strings,
+    theme::Theme,
+    widgets::layout_helper::LayoutHelper,
+};
+use pnet::packet::{
+    arp::ArpOperation,
+    ethernet::EtherType,
+    icmpv6::{Icmpv6Type, Icmpv6Types},
+    ip::IpNextHeaderProtocol,
+    tcp::{TcpOption, TcpOptionNumbers},
+};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Style, Stylize},
-    text::Span,
-    widgets::{Block, Borders, Clear, Padding, Paragraph, Row, Table},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Row, Table, Wrap},
     Frame,
 };
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    ops::RangeInclusive,
+};
 
-pub struct PopupWidget<'a> {
-    packet: &'a Option<CompletePacket>,
+/// One node of the detail tree, outermost first.
+enum PopupLayer<'p> {
+    Data(&'p PacketsData),
+    Vlan(&'p VlanPacketInfo),
+    Mpls(&'p MplsPacketInfo),
+    /// Options of the TCP layer right above it, one row each.
+    TcpOptions(&'p [TcpOption]),
+    /// The captured frame, as a hex dump or as text, and the length it had on the wire.
+    Bytes(&'p [u8], usize),
 }
 
-impl<'a> PopupWidget<'a> {
-    pub fn new(packet: &'a Option<CompletePacket>) -> Self {
-        Self { packet }
+impl<'p> PopupLayer<'p> {
+    fn all(packet: &'p CompletePacket) -> Vec<Self> {
+        let mut layers: Vec<Self> = packet.layer_1.iter().map(PopupLayer::Data).collect();
+        layers.extend(packet.vlan_tags.iter().map(PopupLayer::Vlan));
+        layers.extend(packet.mpls.iter().map(PopupLayer::Mpls));
+        let data_layers = [&packet.layer_2, &packet.layer_3, &packet.layer_4]
+            .into_iter()
+            .flatten()
+            .chain(packet.tunneled_layers());
+        for layer in data_layers {
+            layers.push(PopupLayer::Data(layer));
+            if let PacketsData::TcpPacket(tcp) = layer {
+                if tcp
+                    .options
+                    .iter()
+                    .any(|option| PopupWidget::tcp_option(option).is_some())
+                {
+                    layers.push(PopupLayer::TcpOptions(&tcp.options));
+                }
+            }
+        }
+        if !packet.data.is_empty() {
+            layers.push(PopupLayer::Bytes(&packet.data, packet.length));
+        }
+        layers
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
-        let popup_area = self.calculate_popup_area(area, 80, 80);
+    fn summary(&self, bytes_view: BytesView) -> String {
+        match self {
+            PopupLayer::Data(layer) => clipboard::layer_summary(layer),
+            PopupLayer::Vlan(vlan) => clipboard::vlan_summary(vlan),
+            PopupLayer::Mpls(mpls) => clipboard::mpls_summary(mpls),
+            PopupLayer::TcpOptions(options) => {
+                let names: Vec<String> = options
+                    .iter()
+                    .filter_map(PopupWidget::tcp_option)
+                    .map(|(name, _)| name)
+                    .collect();
+                format!(
+                    "{}: {}",
+                    strings::get("popup.tcp_options"),
+                    names.join(", ")
+                )
+            }
+            PopupLayer::Bytes(bytes, length) => {
+                let view = match bytes_view {
+                    BytesView::Hex => strings::get("popup.hex"),
+                    BytesView::Text => strings::get("popup.text"),
+                };
+                let summary = if bytes.len() < *length {
+                    strings::get("popup.bytes_partial")
+                } else {
+                    strings::get("popup.bytes_whole")
+                };
+                summary
+                    .replace("{captured}", &bytes.len().to_string())
+                    .replace("{length}", &length.to_string())
+                    .replace("{view}", view)
+            }
+        }
+    }
+}
 
-        frame.render_widget(Clear, popup_area);
-        frame.render_widget(Block::bordered().title("Detalhes do Pacote"), popup_area);
+/// A row of a layer's detail table.
+pub struct Field {
+    pub name: String,
+    pub value: Span<'static>,
+}
 
-        if let Some(packet) = self.packet {
-            self.render_packet_layers(frame, popup_area, packet);
+impl Field {
+    pub fn new(name: impl Into<String>, value: impl Into<Span<'static>>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
         }
     }
+}
 
-    fn calculate_popup_area(&self, area: Rect, percent_x: u16, percent_y: u16) -> Rect {
-        use ratatui::layout::Flex;
+impl From<Field> for Row<'static> {
+    fn from(field: Field) -> Self {
+        Row::new(vec![
+            Span::styled(field.name, Style::new().bold()),
+            field.value,
+        ])
+    }
+}
 
-        let vertical = Layout::vertical([ratatui::layout::Constraint::Percentage(percent_y)])
-            .flex(Flex::Center);
-        let horizontal = Layout::horizontal([ratatui::layout::Constraint::Percentage(percent_x)])
-            .flex(Flex::Center);
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum BytesView {
+    #[default]
+    Hex,
+    Text,
+}
 
-        let [area] = vertical.areas(area);
-        let [area] = horizontal.areas(area);
-        area
+/// Frame bytes picked in the hex view, from where the range was started to the cursor.
+#[derive(Clone, Copy)]
+struct ByteSelection {
+    anchor: usize,
+    cursor: usize,
+}
+
+impl ByteSelection {
+    fn range(self) -> RangeInclusive<usize> {
+        self.anchor.min(self.cursor)..=self.anchor.max(self.cursor)
     }
+}
+
+/// Which layer of the detail tree has the cursor and which ones show their fields.
+#[derive(Default)]
+pub struct PopupState {
+    focused: usize,
+    expanded: Vec<bool>,
+    bytes_view: BytesView,
+    /// Whether the frame bytes are shown past the popup's byte limit.
+    all_bytes: bool,
+    /// Where the frame's bytes are in the tree and how many there are, if it kept any.
+    bytes_layer: Option<usize>,
+    byte_count: usize,
+    selection: Option<ByteSelection>,
+    /// First row shown of the TCP options tables, and the most rows any of them has.
+    options_scroll: usize,
+    option_count: usize,
+}
 
-    fn render_packet_layers(&self, frame: &mut Frame, area: Rect, packet: &CompletePacket) {
-        let layers_count = [&packet.layer_1, &packet.layer_2, &packet.layer_3]
+impl PopupState {
+    /// Starts with the cursor on the innermost protocol layer, which is the only one
+    /// expanded.
+    pub fn new(packet: &CompletePacket) -> Self {
+        let layers = PopupLayer::all(packet);
+        let focused = layers
+            .iter()
+            .rposition(|layer| !matches!(layer, PopupLayer::Bytes(..) | PopupLayer::TcpOptions(_)))
+            .unwrap_or_default();
+        let expanded = (0..layers.len()).map(|index| index == focused).collect();
+        let bytes_layer = layers
+            .iter()
+            .position(|layer| matches!(layer, PopupLayer::Bytes(..)));
+        let option_count = layers
             .iter()
-            .filter(|layer| layer.is_some())
-            .count();
+            .map(|layer| match layer {
+                PopupLayer::TcpOptions(options) => options.len(),
+                _ => 0,
+            })
+            .max()
+            .unwrap_or_default();
+        Self {
+            focused,
+            expanded,
+            bytes_view: BytesView::default(),
+            all_bytes: false,
+            bytes_layer,
+            byte_count: packet.data.len(),
+            selection: None,
+            options_scroll: 0,
+            option_count,
+        }
+    }
 
-        if layers_count == 0 {
+    /// Starts picking bytes at the first one, when the frame's bytes have the cursor.
+    /// They're expanded and shown in hex if they weren't.
+    pub fn start_selection(&mut self) {
+        let Some(bytes_layer) = self.bytes_layer else {
             return;
+        };
+        self.focused = bytes_layer;
+        self.expand();
+        self.bytes_view = BytesView::Hex;
+        self.selection = Some(ByteSelection {
+            anchor: 0,
+            cursor: 0,
+        });
+    }
+
+    pub fn end_selection(&mut self) {
+        self.selection = None;
+    }
+
+    pub fn is_selecting(&self) -> bool {
+        self.selection.is_some()
+    }
+
+    /// Starts the range over at the cursor.
+    pub fn restart_selection(&mut self) {
+        if let Some(selection) = &mut self.selection {
+            selection.anchor = selection.cursor;
         }
+    }
 
-        let constraints: Vec<Constraint> = (0..layers_count)
-            .map(|_| Constraint::Percentage(100 / layers_count as u16))
-            .collect();
+    /// Moves the cursor by `delta` bytes, extending the range, without leaving the bytes
+    /// shown under a popup byte limit of `max_bytes`.
+    pub fn move_selection(&mut self, delta: isize, max_bytes: usize) {
+        let shown = if self.all_bytes || max_bytes == 0 {
+            self.byte_count
+        } else {
+            self.byte_count.min(max_bytes)
+        };
+        if let Some(selection) = &mut self.selection {
+            selection.cursor = selection
+                .cursor
+                .saturating_add_signed(delta)
+                .min(shown.saturating_sub(1));
+        }
+    }
 
-        let vertical_layout = Layout::vertical(constraints);
-        let areas = vertical_layout.split(area);
+    /// Scrolls the TCP options tables by `delta` rows.
+    pub fn scroll_options(&mut self, delta: isize) {
+        self.options_scroll = self
+            .options_scroll
+            .saturating_add_signed(delta)
+            .min(self.option_count.saturating_sub(1));
+    }
 
-        let mut area_index = 0;
+    pub fn toggle_all_bytes(&mut self) {
+        self.all_bytes = !self.all_bytes;
+    }
 
-        // Render layers in order (Layer 1 -> Layer 2 -> Layer 3)
-        if packet.layer_1.is_some() {
-            self.render_layer(frame, areas[area_index], &packet.layer_1);
-            area_index += 1;
+    pub fn toggle_bytes_view(&mut self) {
+        self.selection = None;
+        self.bytes_view = match self.bytes_view {
+            BytesView::Hex => BytesView::Text,
+            BytesView::Text => BytesView::Hex,
+        };
+    }
+
+    pub fn next(&mut self) {
+        if self.focused + 1 < self.expanded.len() {
+            self.focused += 1;
         }
-        if packet.layer_2.is_some() {
-            self.render_layer(frame, areas[area_index], &packet.layer_2);
-            area_index += 1;
+    }
+
+    pub fn previous(&mut self) {
+        self.focused = self.focused.saturating_sub(1);
+    }
+
+    pub fn toggle(&mut self) {
+        if let Some(expanded) = self.expanded.get_mut(self.focused) {
+            *expanded = !*expanded;
         }
-        if packet.layer_3.is_some() {
-            self.render_layer(frame, areas[area_index], &packet.layer_3);
+    }
+
+    pub fn expand(&mut self) {
+        if let Some(expanded) = self.expanded.get_mut(self.focused) {
+            *expanded = true;
         }
     }
 
-    fn render_layer(&self, frame: &mut Frame, area: Rect, layer: &Option<PacketsData>) {
-        if let Some(packet_data) = layer {
-            match packet_data {
-                PacketsData::EthernetPacket(packet) => {
-                    self.render_ethernet_packet(frame, area, packet);
-                }
-                PacketsData::ArpPacket(packet) => {
-                    self.render_arp_packet(frame, area, packet);
-                }
-                PacketsData::Ipv4Packet(packet) => {
-                    self.render_ipv4_packet(frame, area, packet);
-                }
-                PacketsData::Ipv6Packet(packet) => {
-                    self.render_ipv6_packet(frame, area, packet);
-                }
-                PacketsData::TcpPacket(packet) => {
-                    self.render_tcp_packet(frame, area, packet);
-                }
-                PacketsData::UdpPacket(packet) => {
-                    self.render_udp_packet(frame, area, packet);
-                }
-                PacketsData::IcmpPacket(packet) => {
-                    self.render_icmp_packet(frame, area, packet);
-                }
-                PacketsData::Icmpv6Packet(packet) => {
-                    self.render_icmpv6_packet(frame, area, packet);
-                }
-            }
+    pub fn collapse(&mut self) {
+        if let Some(expanded) = self.expanded.get_mut(self.focused) {
+            *expanded = false;
         }
     }
 
-    fn create_packet_layout(&self, area: Rect) -> (Rect, Rect) {
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Length(10), Constraint::Fill(1)])
-            .margin(2)
-            .split(area);
-        (chunks[0], chunks[1])
+    fn is_expanded(&self, index: usize) -> bool {
+        self.expanded.get(index).copied().unwrap_or(false)
     }
+}
 
-    fn create_title_widget<'p>(&self, title: String, title_area: Rect) -> Paragraph {
-        Paragraph::new(title)
-            .bold()
-            .block(Block::new().padding(Padding::top({
-                if title_area.height % 2 == 0 {
-                    (title_area.height / 2).saturating_sub(1)
-                } else {
-                    title_area.height / 2
-                }
-            })))
+pub struct PopupWidget<'a> {
+    packet: &'a Option<CompletePacket>,
+    theme: &'a Theme,
+    keymap: Option<&'a Keymap>,
+    verify_checksums: bool,
+    geoip: Option<&'a GeoIp>,
+    size_percent: u16,
+    max_bytes: usize,
+    connection_state: Option<TcpConnectionState>,
+}
+
+impl<'a> PopupWidget<'a> {
+    pub const DEFAULT_SIZE_PERCENT: u16 = 80;
+    pub const SIZE_PERCENT_RANGE: (u16, u16) = (30, 98);
+    /// Size of the maximized popup, leaving a sliver of the table visible around it.
+    pub const MAXIMIZED_SIZE_PERCENT: u16 = 98;
+    pub const DEFAULT_MAX_BYTES: usize = 256;
+    /// Selected bytes the inspector spells out as ASCII.
+    const INSPECTED_ASCII_LENGTH: usize = 64;
+
+    pub fn new(packet: &'a Option<CompletePacket>, theme: &'a Theme) -> Self {
+        Self {
+            packet,
+            theme,
+            keymap: None,
+            verify_checksums: false,
+            geoip: None,
+            size_percent: Self::DEFAULT_SIZE_PERCENT,
+            max_bytes: Self::DEFAULT_MAX_BYTES,
+            connection_state: None,
+        }
+    }
+
+    /// Names the bound keys in the help line, which is left out without it.
+    pub fn keymap(mut self, keymap: &'a Keymap) -> Self {
+        self.keymap = Some(keymap);
+        self
+    }
+
+    /// How many frame bytes the hex and text views show until all are asked for; 0 shows
+    /// them all.
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Current state of the TCP connection of the packet, shown next to its state as of
+    /// the packet when they differ.
+    pub fn connection_state(mut self, connection_state: Option<TcpConnectionState>) -> Self {
+        self.connection_state = connection_state;
+        self
+    }
+
+    /// Percentage of the terminal's width and height the popup covers.
+    pub fn size_percent(mut self, size_percent: u16) -> Self {
+        self.size_percent = size_percent;
+        self
     }
 
-    fn render_ethernet_packet(
+    /// Shows the country and autonomous system of public addresses.
+    pub fn geoip(mut self, geoip: Option<&'a GeoIp>) -> Self {
+        self.geoip = geoip;
+        self
+    }
+
+    /// "Source Location"/"Destination Location" rows for the addresses the GeoIP databases
+    /// know about.
+    fn geoip_fields(&self, source: IpAddr, destination: IpAddr) -> Vec<Field> {
+        let Some(geoip) = self.geoip else {
+            return Vec::new();
+        };
+        [
+            ("Source Location", source),
+            ("Destination Location", destination),
+        ]
+        .into_iter()
+        .filter_map(|(label, ip)| {
+            let description = geoip.lookup(ip)?.describe()?;
+            Some(Field::new(label, description))
+        })
+        .collect()
+    }
+
+    /// Shows whether checksums match their recomputed value.
+    pub fn verify_checksums(mut self, verify_checksums: bool) -> Self {
+        self.verify_checksums = verify_checksums;
+        self
+    }
+
+    fn checksum_span(&self, checksum: u16, expected: Option<u16>) -> Span<'static> {
+        match expected.filter(|_| self.verify_checksums) {
+            Some(expected) if expected == checksum => Span::from(format!(
+                "0x{checksum:04x} ({})",
+                strings::get("popup.checksum_correct")
+            )),
+            Some(expected) => Span::styled(
+                format!(
+                    "0x{checksum:04x} ({})",
+                    strings::get("popup.checksum_incorrect")
+                        .replace("{expected}", &format!("0x{expected:04x}"))
+                ),
+                self.theme.error_style(),
+            ),
+            None => Span::from(format!("0x{checksum:04x}")),
+        }
+    }
+
+    /// The part of `area` a popup covering `percent` of each dimension takes.
+    pub fn area(area: Rect, percent: u16) -> Rect {
+        LayoutHelper::create_centered_layout(area, percent, percent)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, state: &PopupState) {
+        let popup_area = Self::area(area, self.size_percent);
+
+        let help = if state.is_selecting() {
+            strings::get("popup.selection_help")
+        } else {
+            strings::get("popup.help")
+        };
+        let block = Block::bordered()
+            .title(strings::get("popup.title"))
+            .title_bottom(
+                self.keymap
+                    .map(|keymap| keymap.fill_keys(help))
+                    .unwrap_or_default(),
+            )
+            .border_style(self.theme.border_style())
+            .title_style(self.theme.title_style());
+        let inner_area = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        if let Some(packet) = self.packet {
+            let header_height = if packet.comment.is_some() { 2 } else { 1 };
+            let [captured_area, layers_area] =
+                Layout::vertical([Constraint::Length(header_height), Constraint::Fill(1)])
+                    .areas(inner_area);
+            self.render_captured_at(frame, captured_area, packet);
+            self.render_packet_layers(frame, layers_area, packet, state);
+        }
+    }
+
+    /// Full date and local time of the capture, to line packets up with other logs, and
+    /// the interface it came from, above the analyst's comment if there is one.
+    fn render_captured_at(&self, frame: &mut Frame, area: Rect, packet: &CompletePacket) {
+        let line = Line::from(vec![
+            Span::styled(
+                format!("{}: ", strings::get("popup.captured_at")),
+                Style::new().bold(),
+            ),
+            Span::from(
+                packet
+                    .timestamp
+                    .format("%Y-%m-%d %H:%M:%S%.3f %:z")
+                    .to_string(),
+            ),
+            Span::from(format!(
+                " {} {}",
+                strings::get("popup.interface"),
+                packet.interface
+            )),
+        ]);
+        let mut text = Text::from(line);
+        if let Some(comment) = &packet.comment {
+            text.push_line(Line::from(vec![
+                Span::styled(
+                    format!("{}: ", strings::get("popup.comment")),
+                    Style::new().bold(),
+                ),
+                Span::styled(comment.clone(), self.theme.highlight_style()),
+            ]));
+        }
+        frame.render_widget(text, area);
+    }
+
+    /// Each layer gets a one-line header; expanded layers share the rest of the space
+    /// for their field tables.
+    fn render_packet_layers(
         &self,
         frame: &mut Frame,
         area: Rect,
-        packet: &crate::packet_data::EthernetPacketInfo,
+        packet: &CompletePacket,
+        state: &PopupState,
     ) {
-        let (title_area, data_area) = self.create_packet_layout(area);
-        let title = self.create_title_widget("Ethernet".to_string(), title_area);
+        let layers = PopupLayer::all(packet);
 
-        let widths = [Constraint::Length(23), Constraint::Fill(1)];
-        let rows = [
-            Row::new(vec![
-                Span::styled("Destination MAC", Style::new().bold()),
-                Span::from(packet.destination.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Source MAC", Style::new().bold()),
-                Span::from(packet.source.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("EtherType", Style::new().bold()),
-                Span::from(format!("{:?}", packet.ethertype)),
-            ]),
-            Row::new(vec![
-                Span::styled("Payload Length", Style::new().bold()),
-                Span::from(packet.payload.len().to_string()),
-            ]),
-        ];
+        let mut constraints = Vec::new();
+        for index in 0..layers.len() {
+            constraints.push(Constraint::Length(1));
+            if state.is_expanded(index) {
+                constraints.push(Constraint::Fill(1));
+            }
+        }
+        if !state.expanded.contains(&true) {
+            constraints.push(Constraint::Fill(1));
+        }
+        let areas = Layout::vertical(constraints).split(area);
 
-        let table = Table::new(rows, widths).column_spacing(2).block(
-            Block::default()
-                .borders(Borders::LEFT)
-                .border_style(Style::new().bold())
-                .border_type(ratatui::widgets::BorderType::Thick),
-        );
+        let mut area_index = 0;
+        for (index, layer) in layers.iter().enumerate() {
+            let expanded = state.is_expanded(index);
+            self.render_layer_header(
+                frame,
+                areas[area_index],
+                &layer.summary(state.bytes_view),
+                expanded,
+                index == state.focused,
+            );
+            area_index += 1;
+            if expanded {
+                match layer {
+                    PopupLayer::Data(data) => self.render_layer(frame, areas[area_index], data),
+                    PopupLayer::Vlan(vlan) => {
+                        self.render_fields(frame, areas[area_index], "VLAN", self.vlan_fields(vlan))
+                    }
+                    PopupLayer::Mpls(mpls) => {
+                        self.render_fields(frame, areas[area_index], "MPLS", self.mpls_fields(mpls))
+                    }
+                    PopupLayer::TcpOptions(options) => {
+                        self.render_tcp_options(frame, areas[area_index], options, state)
+                    }
+                    PopupLayer::Bytes(bytes, length) => {
+                        self.render_bytes(frame, areas[area_index], bytes, *length, state)
+                    }
+                }
+                area_index += 1;
+            }
+        }
+    }
 
-        frame.render_widget(table, data_area);
-        frame.render_widget(title, title_area);
+    fn render_layer_header(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        summary: &str,
+        expanded: bool,
+        focused: bool,
+    ) {
+        let protocol = summary.split(':').next().unwrap_or_default();
+        let marker = if expanded { "▼ " } else { "▶ " };
+        let mut header = Line::from(vec![
+            Span::raw(marker),
+            Span::styled(summary, self.theme.protocol_style(protocol)),
+        ]);
+        if focused {
+            header = header.patch_style(self.theme.highlight_style().reversed());
+        }
+        frame.render_widget(header, area);
     }
 
-    fn render_arp_packet(
+    fn render_bytes(
         &self,
         frame: &mut Frame,
         area: Rect,
-        packet: &crate::packet_data::ArpPacketInfo,
+        bytes: &[u8],
+        length: usize,
+        state: &PopupState,
     ) {
         let (title_area, data_area) = self.create_packet_layout(area);
-        let title = self.create_title_widget("ARP".to_string(), title_area);
+        let title = self.create_title_widget(strings::get("popup.bytes").to_string(), title_area);
 
-        let widths = [Constraint::Length(23), Constraint::Fill(1)];
-        let rows = [
-            Row::new(vec![
-                Span::styled("Operation", Style::new().bold()),
-                Span::from(format!("{:?}", packet.operation)),
-            ]),
-            Row::new(vec![
-                Span::styled("Sender MAC", Style::new().bold()),
-                Span::from(packet.sender_hw_addr.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Sender IP", Style::new().bold()),
-                Span::from(packet.sender_proto_addr.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Target MAC", Style::new().bold()),
-                Span::from(packet.target_hw_addr.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Target IP", Style::new().bold()),
-                Span::from(packet.target_proto_addr.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Hardware Type", Style::new().bold()),
-                Span::from(format!("{:?}", packet.hardware_type)),
-            ]),
-            Row::new(vec![
-                Span::styled("Protocol Type", Style::new().bold()),
-                Span::from(format!("{:?}", packet.protocol_type)),
-            ]),
-        ];
-
-        let table = Table::new(rows, widths).column_spacing(2).block(
+        let view = state.bytes_view;
+        let shown = if state.all_bytes || self.max_bytes == 0 {
+            bytes
+        } else {
+            &bytes[..bytes.len().min(self.max_bytes)]
+        };
+        let selection = state.selection.filter(|_| view == BytesView::Hex);
+        let mut text = match view {
+            BytesView::Hex => self.hex_dump(shown, selection.map(ByteSelection::range)),
+            BytesView::Text => self.payload_text(shown),
+        };
+        if shown.len() < bytes.len() {
+            // Past the byte limit: the rest is a key away.
+            text.push_line(Line::styled(
+                strings::get("popup.more_bytes")
+                    .replace("{hidden}", &(bytes.len() - shown.len()).to_string()),
+                self.theme.dimmed_style().italic(),
+            ));
+        }
+        if bytes.len() < length {
+            // Cut by the snap length: say so instead of letting the dump just stop.
+            text.push_line(Line::styled(
+                strings::get("popup.truncated")
+                    .replace("{missing}", &(length - bytes.len()).to_string()),
+                self.theme.dimmed_style().italic(),
+            ));
+        }
+        let mut paragraph = Paragraph::new(text);
+        if view == BytesView::Text {
+            paragraph = paragraph.wrap(Wrap { trim: false });
+        }
+        let data_area = match selection {
+            Some(selection) => {
+                let [dump_area, inspector_area] =
+                    Layout::vertical([Constraint::Fill(1), Constraint::Length(8)]).areas(data_area);
+                // Keep the cursor's line on screen as it moves down a long frame.
+                let cursor_line = (selection.cursor / 16) as u16;
+                let visible = dump_area.height.max(1);
+                paragraph = paragraph.scroll((cursor_line.saturating_sub(visible - 1), 0));
+                let range = selection.range();
+                let offset = *range.start();
+                let last = (*range.end()).min(shown.len().saturating_sub(1));
+                let selected = shown.get(offset..=last).unwrap_or_default();
+                self.render_inspector(frame, inspector_area, offset, selected);
+                dump_area
+            }
+            None => data_area,
+        };
+        let paragraph = paragraph.block(
             Block::default()
                 .borders(Borders::LEFT)
-                .border_style(Style::new().bold())
-                .border_type(ratatui::widgets::BorderType::Thick),
+                .border_style(self.theme.border_style().bold())
+                .border_type(ratatui::widgets::BorderType::Thick)
+                .padding(Padding::left(1)),
         );
 
-        frame.render_widget(table, data_area);
+        frame.render_widget(paragraph, data_area);
         frame.render_widget(title, title_area);
     }
 
-    fn render_ipv4_packet(
+    /// One row per option with its length and decoded value, scrolled on its own; only a
+    /// line of them when there's no room for the table's header and a row.
+    fn render_tcp_options(
         &self,
         frame: &mut Frame,
         area: Rect,
-        packet: &crate::packet_data::Ipv4PacketInfo,
+        options: &[TcpOption],
+        state: &PopupState,
     ) {
         let (title_area, data_area) = self.create_packet_layout(area);
-        let title = self.create_title_widget("IPv4".to_string(), title_area);
+        let title = self.create_title_widget(strings::get("popup.options").to_string(), title_area);
+        let block = Block::default()
+            .borders(Borders::LEFT)
+            .border_style(self.theme.protocol_style("TCP").bold())
+            .border_type(ratatui::widgets::BorderType::Thick);
+        let options: Vec<(&TcpOption, String, String)> = options
+            .iter()
+            .filter_map(|option| {
+                let (name, value) = Self::tcp_option(option)?;
+                Some((option, name, value))
+            })
+            .collect();
 
-        let widths = [Constraint::Length(23), Constraint::Fill(1)];
-        let rows = [
-            Row::new(vec![
-                Span::styled("Source IP", Style::new().bold()),
-                Span::from(packet.source.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Destination IP", Style::new().bold()),
-                Span::from(packet.destination.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Protocol", Style::new().bold()),
-                Span::from(format!("{:?}", packet.next_level_protocol)),
-            ]),
-            Row::new(vec![
-                Span::styled("Time To Live (TTL)", Style::new().bold()),
-                Span::from(packet.ttl.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Total Length", Style::new().bold()),
-                Span::from(packet.total_length.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Checksum", Style::new().bold()),
-                Span::from(format!("0x{:04x}", packet.checksum)),
-            ]),
-            Row::new(vec![
-                Span::styled("Identification", Style::new().bold()),
-                Span::from(packet.identification.to_string()),
-            ]),
+        if data_area.height < 2 {
+            let summary: Vec<String> = options
+                .iter()
+                .map(|(_, name, value)| format!("{name} {value}").trim_end().to_string())
+                .collect();
+            frame.render_widget(Paragraph::new(summary.join(", ")).block(block), data_area);
+            frame.render_widget(title, title_area);
+            return;
+        }
+
+        let visible = usize::from(data_area.height) - 1;
+        let first = state
+            .options_scroll
+            .min(options.len().saturating_sub(visible));
+        let rows = options
+            .iter()
+            .skip(first)
+            .take(visible)
+            .map(|(option, name, value)| {
+                Row::new(vec![
+                    Span::styled(name.clone(), Style::new().bold()),
+                    Span::from((option.data.len() + 2).to_string()),
+                    Span::from(value.clone()),
+                ])
+            });
+        let mut block = block;
+        if options.len() > visible {
+            block = block.title_bottom(
+                Line::from(format!(
+                    "{}-{}/{}",
+                    first + 1,
+                    (first + visible).min(options.len()),
+                    options.len()
+                ))
+                .style(self.theme.dimmed_style())
+                .right_aligned(),
+            );
+        }
+        let widths = [
+            Constraint::Length(16),
+            Constraint::Length(6),
+            Constraint::Fill(1),
         ];
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new([
+                    strings::get("popup.option"),
+                    strings::get("popup.length"),
+                    strings::get("popup.value"),
+                ])
+                .style(self.theme.dimmed_style()),
+            )
+            .column_spacing(2)
+            .block(block);
+        frame.render_widget(table, data_area);
+        frame.render_widget(title, title_area);
+    }
 
-        let table = Table::new(rows, widths).column_spacing(2).block(
+    fn render_inspector(&self, frame: &mut Frame, area: Rect, offset: usize, selected: &[u8]) {
+        let rows: Vec<Row> = Self::inspect_bytes(offset, selected)
+            .into_iter()
+            .map(Row::from)
+            .collect();
+        let table = Table::new(rows, [Constraint::Length(23), Constraint::Fill(1)]).block(
             Block::default()
-                .borders(Borders::LEFT)
-                .border_style(Style::new().bold())
-                .border_type(ratatui::widgets::BorderType::Thick),
+                .borders(Borders::TOP)
+                .border_style(self.theme.border_style())
+                .title(strings::get("popup.inspector"))
+                .title_style(self.theme.title_style()),
         );
+        frame.render_widget(table, area);
+    }
 
-        frame.render_widget(table, data_area);
-        frame.render_widget(title, title_area);
+    /// Classic 16-bytes-per-line dump: offset, hex bytes and their printable ASCII, with
+    /// the `selected` bytes highlighted in both.
+    fn hex_dump(&self, bytes: &[u8], selected: Option<RangeInclusive<usize>>) -> Text<'_> {
+        let style = |offset: usize| {
+            if selected
+                .as_ref()
+                .is_some_and(|range| range.contains(&offset))
+            {
+                self.theme.highlight_style().reversed()
+            } else {
+                Style::new()
+            }
+        };
+        let lines: Vec<Line> = bytes
+            .chunks(16)
+            .enumerate()
+            .map(|(index, chunk)| {
+                let start = index * 16;
+                let mut spans = vec![Span::styled(format!("{start:04x}  "), Style::new().bold())];
+                for (offset, byte) in (start..).zip(chunk) {
+                    spans.push(Span::styled(format!("{byte:02x}"), style(offset)));
+                    spans.push(Span::from(" "));
+                }
+                spans.push(Span::from(" ".repeat((16 - chunk.len()) * 3 + 1)));
+                spans.extend((start..).zip(chunk).map(|(offset, &byte)| {
+                    Span::styled(Self::printable(byte).to_string(), style(offset))
+                }));
+                Line::from(spans)
+            })
+            .collect();
+        Text::from(lines)
     }
 
-    fn render_ipv6_packet(
-        &self,
-        frame: &mut Frame,
-        area: Rect,
-        packet: &crate::packet_data::Ipv6PacketInfo,
-    ) {
+    fn printable(byte: u8) -> char {
+        if byte.is_ascii_graphic() || byte == b' ' {
+            byte as char
+        } else {
+            '·'
+        }
+    }
+
+    /// The selected bytes read as unsigned numbers of each size in both byte orders, as
+    /// an IPv4 address and as ASCII. Numbers take the first bytes of the selection and
+    /// sizes longer than it are left out.
+    fn inspect_bytes(offset: usize, selected: &[u8]) -> Vec<Field> {
+        let last = offset + selected.len().saturating_sub(1);
+        let mut fields = vec![Field::new(
+            "Offset",
+            format!("0x{offset:04x}-0x{last:04x} ({} bytes)", selected.len()),
+        )];
+        if let Some(byte) = selected.first() {
+            fields.push(Field::new("u8", byte.to_string()));
+        }
+        if let Some(bytes) = selected.first_chunk::<2>() {
+            fields.push(Field::new(
+                "u16 BE / LE",
+                format!(
+                    "{} / {}",
+                    u16::from_be_bytes(*bytes),
+                    u16::from_le_bytes(*bytes)
+                ),
+            ));
+        }
+        if let Some(bytes) = selected.first_chunk::<4>() {
+            fields.push(Field::new(
+                "u32 BE / LE",
+                format!(
+                    "{} / {}",
+                    u32::from_be_bytes(*bytes),
+                    u32::from_le_bytes(*bytes)
+                ),
+            ));
+            fields.push(Field::new("IPv4", Ipv4Addr::from(*bytes).to_string()));
+        }
+        if let Some(bytes) = selected.first_chunk::<8>() {
+            fields.push(Field::new(
+                "u64 BE / LE",
+                format!(
+                    "{} / {}",
+                    u64::from_be_bytes(*bytes),
+                    u64::from_le_bytes(*bytes)
+                ),
+            ));
+        }
+        fields.push(Field::new(
+            "ASCII",
+            selected
+                .iter()
+                .take(Self::INSPECTED_ASCII_LENGTH)
+                .map(|&byte| Self::printable(byte))
+                .collect::<String>(),
+        ));
+        fields
+    }
+
+    /// The bytes as UTF-8 text with line breaks kept, so text protocols such as HTTP or
+    /// SMTP read naturally. Control characters and invalid UTF-8 show as '·'.
+    fn payload_text(&self, payload: &[u8]) -> Text<'_> {
+        Text::from(Self::printable_text(payload))
+    }
+
+    /// `payload` as text, keeping line breaks and showing other control characters and
+    /// invalid UTF-8 as dots.
+    pub fn printable_text(payload: &[u8]) -> String {
+        let mut text = String::with_capacity(payload.len());
+        for chunk in payload.utf8_chunks() {
+            text.extend(chunk.valid().chars().map(|c| {
+                if c == '\n' || !c.is_control() {
+                    c
+                } else {
+                    '·'
+                }
+            }));
+            text.extend(chunk.invalid().iter().map(|_| '·'));
+        }
+        text
+    }
+
+    fn render_layer(&self, frame: &mut Frame, area: Rect, packet_data: &PacketsData) {
+        let (protocol, fields) = self.layer_fields(packet_data);
+        self.render_fields(frame, area, protocol, fields);
+    }
+
+    /// The protocol name and detail table rows of a decoded layer.
+    fn layer_fields(&self, packet_data: &PacketsData) -> (&'static str, Vec<Field>) {
+        match packet_data {
+            PacketsData::EthernetPacket(packet) => ("Ethernet", self.ethernet_fields(packet)),
+            PacketsData::Ieee80211Packet(packet) => ("802.11", self.ieee80211_fields(packet)),
+            PacketsData::ArpPacket(packet) => ("ARP", self.arp_fields(packet)),
+            PacketsData::LldpPacket(packet) => ("LLDP", self.lldp_fields(packet)),
+            PacketsData::Ipv4Packet(packet) => ("IPv4", self.ipv4_fields(packet)),
+            PacketsData::Ipv6Packet(packet) => ("IPv6", self.ipv6_fields(packet)),
+            PacketsData::TcpPacket(packet) => ("TCP", self.tcp_fields(packet)),
+            PacketsData::UdpPacket(packet) => ("UDP", self.udp_fields(packet)),
+            PacketsData::SctpPacket(packet) => ("SCTP", self.sctp_fields(packet)),
+            PacketsData::IcmpPacket(packet) => ("ICMP", self.icmp_fields(packet)),
+            PacketsData::Icmpv6Packet(packet) => ("ICMPv6", self.icmpv6_fields(packet)),
+            PacketsData::DnsPacket(packet) => ("DNS", self.dns_fields(packet)),
+            PacketsData::MdnsPacket(packet) => ("mDNS", self.dns_fields(packet)),
+            PacketsData::SsdpPacket(packet) => ("SSDP", self.ssdp_fields(packet)),
+            PacketsData::RadiusPacket(packet) => ("RADIUS", self.radius_fields(packet)),
+            PacketsData::SshPacket(packet) => ("SSH", self.ssh_fields(packet)),
+            PacketsData::HttpPacket(packet) => ("HTTP", self.http_fields(packet)),
+            PacketsData::GtpPacket(packet) => ("GTP-U", self.gtp_fields(packet)),
+            PacketsData::VxlanPacket(packet) => ("VXLAN", self.vxlan_fields(packet)),
+        }
+    }
+
+    /// The protocol name and detail table rows of every decoded layer of the packet,
+    /// outermost first, leaving out the raw bytes.
+    pub fn packet_fields(&self) -> Vec<(&'static str, Vec<Field>)> {
+        let Some(packet) = self.packet else {
+            return Vec::new();
+        };
+        PopupLayer::all(packet)
+            .into_iter()
+            .filter_map(|layer| match layer {
+                // Options stay with their TCP layer, so layers at the same depth still pair
+                // up when comparing a packet that has them with one that hasn't.
+                PopupLayer::Data(data @ PacketsData::TcpPacket(tcp)) => {
+                    let (protocol, mut fields) = self.layer_fields(data);
+                    fields.extend(
+                        tcp.options
+                            .iter()
+                            .filter_map(Self::tcp_option)
+                            .map(|(name, value)| Field::new(name, value)),
+                    );
+                    Some((protocol, fields))
+                }
+                PopupLayer::Data(data) => Some(self.layer_fields(data)),
+                PopupLayer::Vlan(vlan) => Some(("VLAN", self.vlan_fields(vlan))),
+                PopupLayer::Mpls(mpls) => Some(("MPLS", self.mpls_fields(mpls))),
+                PopupLayer::TcpOptions(_) | PopupLayer::Bytes(..) => None,
+            })
+            .collect()
+    }
+
+    /// The protocol name beside a table of the layer's fields, bordered in its color.
+    fn render_fields(&self, frame: &mut Frame, area: Rect, protocol: &str, fields: Vec<Field>) {
         let (title_area, data_area) = self.create_packet_layout(area);
-        let title = self.create_title_widget("IPv6".to_string(), title_area);
+        let title = self.create_title_widget(protocol.to_string(), title_area);
 
         let widths = [Constraint::Length(23), Constraint::Fill(1)];
-        let rows = [
-            Row::new(vec![
-                Span::styled("Source IP", Style::new().bold()),
-                Span::from(packet.source.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Destination IP", Style::new().bold()),
-                Span::from(packet.destination.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Next Header", Style::new().bold()),
-                Span::from(format!("{:?}", packet.next_header)),
-            ]),
-            Row::new(vec![
-                Span::styled("Traffic Class", Style::new().bold()),
-                Span::from(packet.traffic_class.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Flow Label", Style::new().bold()),
-                Span::from(packet.flow_label.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Payload Length", Style::new().bold()),
-                Span::from(packet.payload_length.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Hop Limit", Style::new().bold()),
-                Span::from(packet.hop_limit.to_string()),
-            ]),
-        ];
-
-        let table = Table::new(rows, widths).column_spacing(2).block(
-            Block::default()
-                .borders(Borders::LEFT)
-                .border_style(Style::new().bold())
-                .border_type(ratatui::widgets::BorderType::Thick),
-        );
+        let table = Table::new(fields.into_iter().map(Row::from), widths)
+            .column_spacing(2)
+            .block(
+                Block::default()
+                    .borders(Borders::LEFT)
+                    .border_style(self.theme.protocol_style(protocol).bold())
+                    .border_type(ratatui::widgets::BorderType::Thick),
+            );
 
         frame.render_widget(table, data_area);
         frame.render_widget(title, title_area);
     }
 
-    fn render_tcp_packet(
-        &self,
-        frame: &mut Frame,
-        area: Rect,
-        packet: &crate::packet_data::TcpPacketInfo,
-    ) {
-        let (title_area, data_area) = self.create_packet_layout(area);
-        let title = self.create_title_widget("TCP".to_string(), title_area);
+    fn create_packet_layout(&self, area: Rect) -> (Rect, Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(10), Constraint::Fill(1)])
+            .horizontal_margin(2)
+            .vertical_margin(1)
+            .split(area);
+        (chunks[0], chunks[1])
+    }
 
-        let widths = [Constraint::Length(23), Constraint::Fill(1)];
-        let rows = [
-            Row::new(vec![
-                Span::styled("Source Port", Style::new().bold()),
-                Span::from(packet.source.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Destination Port", Style::new().bold()),
-                Span::from(packet.destination.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Sequence Number", Style::new().bold()),
-                Span::from(packet.sequence.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Acknowledgement", Style::new().bold()),
-                Span::from(packet.acknowledgement.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Window Size", Style::new().bold()),
-                Span::from(packet.window.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Checksum", Style::new().bold()),
-                Span::from(format!("0x{:04x}", packet.checksum)),
-            ]),
-            Row::new(vec![
-                Span::styled("Flags (raw)", Style::new().bold()),
-                Span::from(packet.flags.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Urgent Pointer", Style::new().bold()),
-                Span::from(packet.urgent_ptr.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Options", Style::new().bold()),
-                Span::from(format!("{:?}", packet.options)),
-            ]),
-            Row::new(vec![
-                Span::styled("Payload Length", Style::new().bold()),
-                Span::from(packet.length.to_string()),
-            ]),
+    fn create_title_widget(&self, title: String, title_area: Rect) -> Paragraph<'_> {
+        Paragraph::new(title)
+            .bold()
+            .block(Block::new().padding(Padding::top({
+                if title_area.height.is_multiple_of(2) {
+                    (title_area.height / 2).saturating_sub(1)
+                } else {
+                    title_area.height / 2
+                }
+            })))
+    }
+
+    fn ethernet_fields(&self, packet: &crate::packet_data::EthernetPacketInfo) -> Vec<Field> {
+        let mut fields = vec![
+            Field::new("Destination MAC", packet.destination.to_string()),
+            Field::new("Source MAC", packet.source.to_string()),
+            Field::new("EtherType", Self::ethertype_description(packet.ethertype)),
+            Field::new("Payload Length", packet.payload_length.to_string()),
         ];
+        if let (Some(fcs), Some(expected)) = (packet.fcs, packet.expected_fcs) {
+            let value = if fcs == expected {
+                Span::from(format!("0x{fcs:08x} (correct)"))
+            } else {
+                Span::styled(
+                    format!("0x{fcs:08x} (incorrect, expected 0x{expected:08x})"),
+                    self.theme.error_style(),
+                )
+            };
+            fields.push(Field::new("FCS", value));
+        }
+        fields
+    }
 
-        let table = Table::new(rows, widths).column_spacing(2).block(
-            Block::default()
-                .borders(Borders::LEFT)
-                .border_style(Style::new().bold())
-                .border_type(ratatui::widgets::BorderType::Thick),
+    fn ieee80211_fields(&self, packet: &crate::packet_data::Ieee80211PacketInfo) -> Vec<Field> {
+        let row = |label: &'static str, value: String| Field::new(label, value);
+        let mut rows = vec![
+            row(
+                "Type / Subtype",
+                format!(
+                    "{} / {} ({}/{})",
+                    packet.type_name(),
+                    packet.subtype_name(),
+                    packet.frame_type,
+                    packet.subtype
+                ),
+            ),
+            row(
+                "Flags",
+                format!(
+                    "0x{:02x}{}",
+                    packet.flags,
+                    if packet.is_protected() {
+                        " (protected)"
+                    } else {
+                        ""
+                    }
+                ),
+            ),
+            row("Duration", format!("{} µs", packet.duration)),
+        ];
+        let labels = [
+            "Receiver Address",
+            "Transmitter Address",
+            "Address 3",
+            "Address 4",
+        ];
+        rows.extend(
+            labels
+                .into_iter()
+                .zip(&packet.addresses)
+                .map(|(label, address)| row(label, address.to_string())),
         );
+        if let Some(sequence) = packet.sequence {
+            rows.push(row("Sequence Number", sequence.to_string()));
+        }
+        if let Some(ssid) = &packet.ssid {
+            let ssid = if ssid.is_empty() {
+                "(hidden)".to_string()
+            } else {
+                ssid.clone()
+            };
+            rows.push(row("SSID", ssid));
+        }
+        if let Some(ethertype) = packet.ethertype {
+            rows.push(row("EtherType", Self::ethertype_description(ethertype)));
+        }
+        if let Some(radiotap) = &packet.radiotap {
+            if let Some(frequency) = radiotap.frequency {
+                let channel = radiotap
+                    .channel()
+                    .map_or("?".to_string(), |channel| channel.to_string());
+                rows.push(row("Channel", format!("{channel} ({frequency} MHz)")));
+            }
+            if let Some(signal) = radiotap.signal_dbm {
+                rows.push(row("Signal", format!("{signal} dBm")));
+            }
+            if let Some(noise) = radiotap.noise_dbm {
+                rows.push(row("Noise", format!("{noise} dBm")));
+            }
+            if let Some(rate) = radiotap.rate {
+                rows.push(row("Data Rate", format!("{:.1} Mb/s", rate as f32 / 2.0)));
+            }
+            rows.push(row("Radiotap Length", radiotap.length.to_string()));
+        }
+        rows.push(row("Frame Length", packet.length.to_string()));
+        rows
+    }
 
-        frame.render_widget(table, data_area);
-        frame.render_widget(title, title_area);
+    fn vlan_fields(&self, packet: &VlanPacketInfo) -> Vec<Field> {
+        vec![
+            Field::new("Tag Protocol", Self::ethertype_description(packet.tpid)),
+            Field::new("VLAN ID", packet.vlan_id.to_string()),
+            Field::new("Priority", packet.priority.to_string()),
+            Field::new("Drop Eligible", packet.drop_eligible.to_string()),
+            Field::new("EtherType", Self::ethertype_description(packet.ethertype)),
+        ]
     }
 
-    fn render_udp_packet(
-        &self,
-        frame: &mut Frame,
-        area: Rect,
-        packet: &crate::packet_data::UdpPacketInfo,
-    ) {
-        let (title_area, data_area) = self.create_packet_layout(area);
-        let title = self.create_title_widget("UDP".to_string(), title_area);
+    /// One row per label stack entry, top of the stack first.
+    fn mpls_fields(&self, packet: &MplsPacketInfo) -> Vec<Field> {
+        let mut rows = vec![Field::new(
+            "EtherType",
+            Self::ethertype_description(packet.ethertype),
+        )];
+        rows.extend(packet.labels.iter().enumerate().map(|(index, label)| {
+            let bottom_of_stack = if label.bottom_of_stack {
+                ", bottom of stack"
+            } else {
+                ""
+            };
+            Field::new(
+                format!("Label {}", index + 1),
+                format!(
+                    "{} (TC {}, TTL {}{bottom_of_stack})",
+                    label.label, label.traffic_class, label.ttl
+                ),
+            )
+        }));
+        rows.push(Field::new("Payload Length", packet.length.to_string()));
+        rows
+    }
 
-        let widths = [Constraint::Length(23), Constraint::Fill(1)];
-        let rows = [
-            Row::new(vec![
-                Span::styled("Source Port", Style::new().bold()),
-                Span::from(packet.source.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Destination Port", Style::new().bold()),
-                Span::from(packet.destination.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Length", Style::new().bold()),
-                Span::from(packet.length.to_string()),
-            ]),
-            Row::new(vec![
-                Span::styled("Checksum", Style::new().bold()),
-                Span::from(format!("0x{:04x}", packet.checksum)),
-            ]),
-        ];
+    /// EtherType name with its number, e.g. "IPv4 (0x0800)".
+    fn ethertype_description(ethertype: EtherType) -> String {
+        format!(
+            "{} (0x{:04x})",
+            Self::protocol_display_name(&ethertype.to_string()),
+            ethertype.0
+        )
+    }
 
-        let table = Table::new(rows, widths).column_spacing(2).block(
-            Block::default()
-                .borders(Borders::LEFT)
-                .border_style(Style::new().bold())
-                .border_type(ratatui::widgets::BorderType::Thick),
+    /// IP protocol name with its number, e.g. "TCP (6)"; unknown numbers are shown in hex.
+    fn ip_protocol_description(protocol: IpNextHeaderProtocol) -> String {
+        match protocol.to_string().as_str() {
+            "unknown" => format!("Unknown (0x{:02x})", protocol.0),
+            name => format!("{} ({})", Self::protocol_display_name(name), protocol.0),
+        }
+    }
+
+    /// pnet names protocols in CamelCase ("Ipv4", "Tcp"); use the usual spelling for the
+    /// common ones.
+    fn protocol_display_name(name: &str) -> String {
+        match name {
+            "unknown" => "Unknown",
+            "Ipv4" => "IPv4",
+            "Ipv6" => "IPv6",
+            "Arp" => "ARP",
+            "Rarp" => "RARP",
+            "Vlan" => "VLAN",
+            "Lldp" => "LLDP",
+            "Mpls" => "MPLS",
+            "Ptp" => "PTP",
+            "Tcp" => "TCP",
+            "Udp" => "UDP",
+            "Icmp" => "ICMP",
+            "Icmpv6" => "ICMPv6",
+            "Igmp" => "IGMP",
+            "Sctp" => "SCTP",
+            "Gre" => "GRE",
+            "Esp" => "ESP",
+            "Ah" => "AH",
+            other => other,
+        }
+        .to_string()
+    }
+
+    fn arp_fields(&self, packet: &crate::packet_data::ArpPacketInfo) -> Vec<Field> {
+        let (hw_label, proto_label) = (
+            if packet.sender_hw_addr.is_raw() {
+                "Hardware Address"
+            } else {
+                "MAC"
+            },
+            if packet.sender_proto_addr.is_raw() {
+                "Protocol Address"
+            } else {
+                "IP"
+            },
         );
+        vec![
+            Field::new(
+                "Operation",
+                Self::arp_operation_description(packet.operation),
+            ),
+            Field::new(
+                format!("Sender {hw_label}"),
+                packet.sender_hw_addr.to_string(),
+            ),
+            Field::new(
+                format!("Sender {proto_label}"),
+                packet.sender_proto_addr.to_string(),
+            ),
+            Field::new(
+                format!("Target {hw_label}"),
+                packet.target_hw_addr.to_string(),
+            ),
+            Field::new(
+                format!("Target {proto_label}"),
+                packet.target_proto_addr.to_string(),
+            ),
+            Field::new("Hardware Type", format!("{:?}", packet.hardware_type)),
+            Field::new(
+                "Protocol Type",
+                Self::ethertype_description(packet.protocol_type),
+            ),
+            Field::new("Hardware Address Length", packet.hw_addr_len.to_string()),
+            Field::new("Protocol Address Length", packet.proto_addr_len.to_string()),
+        ]
+    }
 
-        frame.render_widget(table, data_area);
-        frame.render_widget(title, title_area);
+    /// ARP and RARP operation name with its number, e.g. "Request (1)".
+    pub fn arp_operation_description(operation: ArpOperation) -> String {
+        let name = match operation.0 {
+            1 => "Request",
+            2 => "Reply",
+            3 => "RARP Request",
+            4 => "RARP Reply",
+            8 => "InARP Request",
+            9 => "InARP Reply",
+            _ => "Unknown",
+        };
+        format!("{name} ({})", operation.0)
     }
 
-    fn render_icmp_packet(
-        &self,
-        frame: &mut Frame,
-        area: Rect,
-        packet: &crate::packet_data::IcmpPacketInfo,
-    ) {
-        let (title_area, data_area) = self.create_packet_layout(area);
-        let title = self.create_title_widget("ICMP".to_string(), title_area);
+    fn lldp_fields(&self, packet: &crate::packet_data::LldpPacketInfo) -> Vec<Field> {
+        let optional = |value: &Option<String>| value.clone().unwrap_or("-".to_string());
+        vec![
+            Field::new("System Name", optional(&packet.system_name)),
+            Field::new("Chassis ID", optional(&packet.chassis_id)),
+            Field::new("Port ID", optional(&packet.port_id)),
+            Field::new("Port Description", optional(&packet.port_description)),
+            Field::new(
+                "Time To Live (TTL)",
+                packet.ttl.map_or("-".to_string(), |ttl| format!("{ttl}s")),
+            ),
+            Field::new("System Description", optional(&packet.system_description)),
+            Field::new(
+                "Capabilities",
+                packet
+                    .system_capabilities
+                    .map_or("-".to_string(), Self::lldp_capability_names),
+            ),
+            Field::new(
+                "Enabled Capabilities",
+                packet
+                    .enabled_capabilities
+                    .map_or("-".to_string(), Self::lldp_capability_names),
+            ),
+        ]
+    }
 
-        let widths = [Constraint::Length(23), Constraint::Fill(1)];
-        let rows = [
-            Row::new(vec![
-                Span::styled("Type", Style::new().bold()),
-                Span::from(format!("{:?}", packet.icmp_type)),
-            ]),
-            Row::new(vec![
-                Span::styled("Code", Style::new().bold()),
-                Span::from(format!("{:?}", packet.icmp_code)),
-            ]),
-            Row::new(vec![
-                Span::styled("Checksum", Style::new().bold()),
-                Span::from(format!("0x{:04x}", packet.checksum)),
-            ]),
-            Row::new(vec![
-                Span::styled("Payload Length", Style::new().bold()),
-                Span::from(packet.length.to_string()),
-            ]),
+    fn lldp_capability_names(capabilities: u16) -> String {
+        const NAMES: [&str; 8] = [
+            "Other",
+            "Repeater",
+            "Bridge",
+            "WLAN AP",
+            "Router",
+            "Telephone",
+            "DOCSIS",
+            "Station",
         ];
 
-        let table = Table::new(rows, widths).column_spacing(2).block(
-            Block::default()
-                .borders(Borders::LEFT)
-                .border_style(Style::new().bold())
-                .border_type(ratatui::widgets::BorderType::Thick),
-        );
+        let names: Vec<&str> = NAMES
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| capabilities & (1 << bit) != 0)
+            .map(|(_, name)| *name)
+            .collect();
 
-        frame.render_widget(table, data_area);
-        frame.render_widget(title, title_area);
+        if names.is_empty() {
+            "None".to_string()
+        } else {
+            names.join(", ")
+        }
     }
 
-    fn render_icmpv6_packet(
-        &self,
-        frame: &mut Frame,
-        area: Rect,
-        packet: &crate::packet_data::Icmpv6PacketInfo,
-    ) {
-        let (title_area, data_area) = self.create_packet_layout(area);
-        let title = self.create_title_widget("ICMPv6".to_string(), title_area);
+    fn ipv4_fields(&self, packet: &crate::packet_data::Ipv4PacketInfo) -> Vec<Field> {
+        let mut rows = vec![
+            Field::new("Version", packet.version.to_string()),
+            Field::new("Source IP", packet.source.to_string()),
+            Field::new("Destination IP", packet.destination.to_string()),
+            Field::new(
+                "Protocol",
+                Self::ip_protocol_description(packet.next_level_protocol),
+            ),
+            Field::new("Time To Live (TTL)", packet.ttl.to_string()),
+            Field::new("Total Length", packet.total_length.to_string()),
+            Field::new(
+                "Checksum",
+                self.checksum_span(packet.checksum, packet.expected_checksum),
+            ),
+            Field::new("Identification", packet.identification.to_string()),
+            Field::new(
+                "DSCP",
+                match packet.dscp_name() {
+                    Some(name) => format!("{name} ({})", packet.dscp),
+                    None => packet.dscp.to_string(),
+                },
+            ),
+            Field::new("ECN", format!("{} ({})", packet.ecn_name(), packet.ecn)),
+        ];
+        rows.extend(self.geoip_fields(packet.source.into(), packet.destination.into()));
+        rows
+    }
 
-        let widths = [Constraint::Length(23), Constraint::Fill(1)];
-        let rows = [
-            Row::new(vec![
-                Span::styled("Type", Style::new().bold()),
-                Span::from(format!("{:?}", packet.icmpv6_type)),
-            ]),
-            Row::new(vec![
-                Span::styled("Code", Style::new().bold()),
-                Span::from(format!("{:?}", packet.icmpv6_code)),
-            ]),
-            Row::new(vec![
-                Span::styled("Checksum", Style::new().bold()),
-                Span::from(format!("0x{:04x}", packet.checksum)),
-            ]),
-            Row::new(vec![
-                Span::styled("Payload Length", Style::new().bold()),
-                Span::from(packet.length.to_string()),
-            ]),
+    fn ipv6_fields(&self, packet: &crate::packet_data::Ipv6PacketInfo) -> Vec<Field> {
+        let mut rows = vec![
+            Field::new("Version", packet.version.to_string()),
+            Field::new("Source IP", packet.source.to_string()),
+            Field::new("Destination IP", packet.destination.to_string()),
+            Field::new(
+                "Next Header",
+                Self::ip_protocol_description(packet.next_header),
+            ),
+            Field::new("Traffic Class", packet.traffic_class.to_string()),
+            Field::new("Flow Label", packet.flow_label.to_string()),
+            Field::new("Payload Length", packet.payload_length.to_string()),
+            Field::new("Hop Limit", packet.hop_limit.to_string()),
         ];
+        rows.extend(self.geoip_fields(packet.source.into(), packet.destination.into()));
+        rows
+    }
 
-        let table = Table::new(rows, widths).column_spacing(2).block(
-            Block::default()
-                .borders(Borders::LEFT)
-                .border_style(Style::new().bold())
-                .border_type(ratatui::widgets::BorderType::Thick),
+    fn tcp_fields(&self, packet: &crate::packet_data::TcpPacketInfo) -> Vec<Field> {
+        let mut rows = vec![
+            Field::new("Source Port", packet.source.to_string()),
+            Field::new("Destination Port", packet.destination.to_string()),
+            Field::new("Sequence Number", packet.sequence.to_string()),
+            Field::new("Acknowledgement", packet.acknowledgement.to_string()),
+            Field::new("Window Size", packet.window.to_string()),
+            Field::new(
+                "Checksum",
+                self.checksum_span(packet.checksum, packet.expected_checksum),
+            ),
+            Field::new("Flags (raw)", packet.flags.to_string()),
+            Field::new("Reserved", packet.reserved.to_string()),
+            Field::new("Urgent Pointer", packet.urgent_ptr.to_string()),
+            Field::new("Payload Length", packet.length.to_string()),
+        ];
+        if let Some(rtt) = self.packet.as_ref().and_then(|packet| packet.tcp_rtt) {
+            rows.push(Field::new("RTT", TcpRttEstimator::format(rtt)));
+        }
+        if let Some(state) = self.packet.as_ref().and_then(|packet| packet.tcp_state) {
+            let value = match self.connection_state {
+                Some(current) if current != state => {
+                    format!("{} (now {})", state.name(), current.name())
+                }
+                _ => state.name().to_string(),
+            };
+            rows.push(Field::new("Connection State", value));
+        }
+        rows
+    }
+
+    /// Name and readable value of a TCP option; padding options (EOL/NOP) return `None`.
+    fn tcp_option(option: &TcpOption) -> Option<(String, String)> {
+        let data = &option.data;
+        let (name, value) = match option.number {
+            TcpOptionNumbers::EOL | TcpOptionNumbers::NOP => return None,
+            TcpOptionNumbers::MSS if data.len() == 2 => {
+                ("MSS", u16::from_be_bytes([data[0], data[1]]).to_string())
+            }
+            TcpOptionNumbers::WSCALE if data.len() == 1 => (
+                "Window Scale",
+                format!(
+                    "{} (x{})",
+                    data[0],
+                    1u32.checked_shl(data[0].into()).unwrap_or(0)
+                ),
+            ),
+            TcpOptionNumbers::SACK_PERMITTED => ("SACK Permitted", String::new()),
+            TcpOptionNumbers::SACK if data.len().is_multiple_of(8) => {
+                let blocks: Vec<String> = data
+                    .chunks_exact(8)
+                    .map(|block| {
+                        let left = u32::from_be_bytes([block[0], block[1], block[2], block[3]]);
+                        let right = u32::from_be_bytes([block[4], block[5], block[6], block[7]]);
+                        format!("{left}-{right}")
+                    })
+                    .collect();
+                ("SACK", blocks.join(", "))
+            }
+            TcpOptionNumbers::TIMESTAMPS if data.len() == 8 => (
+                "Timestamps",
+                format!(
+                    "TSval {} TSecr {}",
+                    u32::from_be_bytes([data[0], data[1], data[2], data[3]]),
+                    u32::from_be_bytes([data[4], data[5], data[6], data[7]])
+                ),
+            ),
+            number => {
+                let bytes: Vec<String> = data.iter().map(|byte| format!("{byte:02x}")).collect();
+                return Some((format!("Kind {}", number.0), bytes.join(" ")));
+            }
+        };
+        Some((name.to_string(), value))
+    }
+
+    fn udp_fields(&self, packet: &crate::packet_data::UdpPacketInfo) -> Vec<Field> {
+        vec![
+            Field::new("Source Port", packet.source.to_string()),
+            Field::new("Destination Port", packet.destination.to_string()),
+            Field::new("Length", packet.length.to_string()),
+            Field::new(
+                "Checksum",
+                self.checksum_span(packet.checksum, packet.expected_checksum),
+            ),
+        ]
+    }
+
+    fn sctp_fields(&self, packet: &crate::packet_data::SctpPacketInfo) -> Vec<Field> {
+        let mut rows = vec![
+            Field::new("Source Port", packet.source.to_string()),
+            Field::new("Destination Port", packet.destination.to_string()),
+            Field::new(
+                "Verification Tag",
+                format!("0x{:08x}", packet.verification_tag),
+            ),
+            Field::new("Checksum", format!("0x{:08x}", packet.checksum)),
+        ];
+        rows.extend(packet.chunks.iter().enumerate().map(|(index, chunk)| {
+            Field::new(
+                format!("Chunk {}", index + 1),
+                format!(
+                    "{} (flags 0x{:02x}, length {})",
+                    Self::sctp_chunk_name(chunk.chunk_type),
+                    chunk.flags,
+                    chunk.length
+                ),
+            )
+        }));
+        rows
+    }
+
+    fn sctp_chunk_name(chunk_type: u8) -> String {
+        match chunk_type {
+            0 => "DATA".to_string(),
+            1 => "INIT".to_string(),
+            2 => "INIT ACK".to_string(),
+            3 => "SACK".to_string(),
+            4 => "HEARTBEAT".to_string(),
+            5 => "HEARTBEAT ACK".to_string(),
+            6 => "ABORT".to_string(),
+            7 => "SHUTDOWN".to_string(),
+            8 => "SHUTDOWN ACK".to_string(),
+            9 => "ERROR".to_string(),
+            10 => "COOKIE ECHO".to_string(),
+            11 => "COOKIE ACK".to_string(),
+            14 => "SHUTDOWN COMPLETE".to_string(),
+            15 => "AUTH".to_string(),
+            64 => "I-DATA".to_string(),
+            128 => "ASCONF ACK".to_string(),
+            130 => "RE-CONFIG".to_string(),
+            192 => "FORWARD TSN".to_string(),
+            193 => "ASCONF".to_string(),
+            other => format!("TYPE{other}"),
+        }
+    }
+
+    fn icmp_fields(&self, packet: &IcmpPacketInfo) -> Vec<Field> {
+        let mut rows = vec![
+            Field::new("Type", packet.icmp_type.0.to_string()),
+            Field::new(
+                "Code",
+                format!(
+                    "{} ({})",
+                    packet.icmp_code.0,
+                    IcmpPacketInfo::describe_icmp(packet.icmp_type, packet.icmp_code)
+                ),
+            ),
+            Field::new(
+                "Checksum",
+                self.checksum_span(packet.checksum, packet.expected_checksum),
+            ),
+            Field::new("Payload Length", packet.length.to_string()),
+        ];
+        if let Some(original) = &packet.original {
+            rows.push(Field::new("Original Packet", original.to_string()));
+        }
+        rows
+    }
+
+    fn icmpv6_fields(&self, packet: &Icmpv6PacketInfo) -> Vec<Field> {
+        let mut rows = vec![
+            Field::new("Type", packet.icmpv6_type.0.to_string()),
+            Field::new(
+                "Code",
+                format!(
+                    "{} ({})",
+                    packet.icmpv6_code.0,
+                    Icmpv6PacketInfo::describe_icmpv6(packet.icmpv6_type, packet.icmpv6_code)
+                ),
+            ),
+            Field::new("Checksum", format!("0x{:04x}", packet.checksum)),
+            Field::new("Payload Length", packet.length.to_string()),
+        ];
+        if let Some(ndp) = &packet.ndp {
+            rows.extend(Self::ndp_fields(packet.icmpv6_type, ndp));
+        }
+        rows
+    }
+
+    fn ndp_fields(icmpv6_type: Icmpv6Type, ndp: &NdpInfo) -> Vec<Field> {
+        let row = |label: &'static str, value: String| Field::new(label, value);
+        let mut rows = vec![row(
+            "Message",
+            NdpInfo::message_name(icmpv6_type).to_string(),
+        )];
+        if let Some(target) = ndp.target {
+            rows.push(row("Target Address", target.to_string()));
+        }
+        if matches!(
+            icmpv6_type,
+            Icmpv6Types::RouterAdvert | Icmpv6Types::NeighborAdvert
+        ) {
+            let flags = ndp.flag_names(icmpv6_type);
+            let flags = if flags.is_empty() {
+                "None".to_string()
+            } else {
+                flags.join(", ")
+            };
+            rows.push(row("Flags", format!("0x{:02x} ({flags})", ndp.flags)));
+        }
+        if let Some(router) = &ndp.router {
+            rows.push(row("Cur Hop Limit", router.cur_hop_limit.to_string()));
+            rows.push(row("Router Lifetime", format!("{} s", router.lifetime)));
+            rows.push(row(
+                "Reachable Time",
+                format!("{} ms", router.reachable_time),
+            ));
+            rows.push(row("Retrans Timer", format!("{} ms", router.retrans_timer)));
+        }
+        for option in &ndp.options {
+            rows.push(match option {
+                NdpOption::SourceLinkLayerAddress(mac) => {
+                    row("Source Link-Layer Addr", mac.to_string())
+                }
+                NdpOption::TargetLinkLayerAddress(mac) => {
+                    row("Target Link-Layer Addr", mac.to_string())
+                }
+                NdpOption::PrefixInformation {
+                    prefix,
+                    prefix_length,
+                    on_link,
+                    autonomous,
+                    valid_lifetime,
+                    preferred_lifetime,
+                } => {
+                    let flags: Vec<&str> = [(*on_link, "on-link"), (*autonomous, "autonomous")]
+                        .into_iter()
+                        .filter_map(|(set, name)| set.then_some(name))
+                        .collect();
+                    row(
+                        "Prefix",
+                        format!(
+                            "{prefix}/{prefix_length} [{}], valid {} s, preferred {} s",
+                            flags.join(", "),
+                            valid_lifetime,
+                            preferred_lifetime
+                        ),
+                    )
+                }
+                NdpOption::Mtu(mtu) => row("MTU", mtu.to_string()),
+                NdpOption::Other {
+                    option_type,
+                    length,
+                } => row("Option", format!("type {option_type}, {length} bytes")),
+            });
+        }
+        if ndp.malformed_options {
+            rows.push(row("Options", "Malformed (bad option length)".to_string()));
+        }
+        rows
+    }
+
+    fn dns_fields(&self, packet: &crate::packet_data::DnsPacketInfo) -> Vec<Field> {
+        let mut rows = vec![
+            Field::new("Transaction ID", format!("0x{:04x}", packet.id)),
+            Field::new(
+                "Message Type",
+                if packet.is_response {
+                    "Response"
+                } else {
+                    "Query"
+                },
+            ),
+            Field::new("Transport", if packet.over_tcp { "TCP" } else { "UDP" }),
+            Field::new("Opcode", packet.opcode.to_string()),
+            Field::new(
+                "Response Code",
+                format!("{} ({})", Self::dns_rcode_name(packet.rcode), packet.rcode),
+            ),
+            Field::new("Flags (raw)", format!("0x{:04x}", packet.flags)),
+        ];
+
+        rows.extend(packet.questions.iter().map(|question| {
+            Field::new(
+                "Question",
+                format!(
+                    "{} {}",
+                    question.name,
+                    Self::dns_type_name(question.record_type)
+                ),
+            )
+        }));
+        rows.extend(packet.answers.iter().map(|answer| {
+            Field::new(
+                "Answer",
+                format!(
+                    "{} {} {} (TTL {}s)",
+                    answer.name,
+                    Self::dns_type_name(answer.record_type),
+                    answer.data,
+                    answer.ttl
+                ),
+            )
+        }));
+        rows.push(Field::new(
+            "Authority / Additional",
+            format!("{} / {}", packet.authority_count, packet.additional_count),
+        ));
+        rows
+    }
+
+    fn http_fields(&self, packet: &crate::packet_data::HttpPacketInfo) -> Vec<Field> {
+        let mut rows = Vec::new();
+        if let Some(method) = &packet.method {
+            rows.push(Field::new("Method", method.clone()));
+        }
+        if let Some(path) = &packet.path {
+            rows.push(Field::new("Path", path.clone()));
+        }
+        if let Some(status_code) = packet.status_code {
+            rows.push(Field::new(
+                "Status",
+                format!(
+                    "{} {}",
+                    status_code,
+                    packet.reason.as_deref().unwrap_or_default()
+                ),
+            ));
+        }
+        rows.push(Field::new("Version", packet.version.clone()));
+        if let Some(host) = &packet.host {
+            rows.push(Field::new("Host", host.clone()));
+        }
+        rows.push(Field::new("Length", packet.length.to_string()));
+        rows
+    }
+
+    fn ssdp_fields(&self, packet: &crate::packet_data::SsdpPacketInfo) -> Vec<Field> {
+        let mut rows = Vec::new();
+        if let Some(method) = &packet.method {
+            rows.push(Field::new("Method", method.clone()));
+        }
+        if let Some(status_code) = packet.status_code {
+            rows.push(Field::new("Status", status_code.to_string()));
+        }
+        let headers = [
+            ("Notification Sub Type", &packet.notification_sub_type),
+            (
+                if packet.method.as_deref() == Some("NOTIFY") {
+                    "Notification Type"
+                } else {
+                    "Search Target"
+                },
+                &packet.target,
+            ),
+            ("Unique Service Name", &packet.usn),
+            ("Location", &packet.location),
+            ("Server", &packet.server),
+        ];
+        for (label, value) in headers {
+            if let Some(value) = value {
+                rows.push(Field::new(label, value.clone()));
+            }
+        }
+        rows.push(Field::new("Length", packet.length.to_string()));
+        rows
+    }
+
+    fn radius_fields(&self, packet: &crate::packet_data::RadiusPacketInfo) -> Vec<Field> {
+        let code = format!(
+            "{} ({})",
+            crate::packet_data::RadiusPacketInfo::code_name(packet.code).unwrap_or("Unknown"),
+            packet.code
         );
+        let authenticator: String = packet
+            .authenticator
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        let mut rows = vec![
+            Field::new("Code", code),
+            Field::new("Identifier", packet.identifier.to_string()),
+            Field::new("Length", packet.declared_length.to_string()),
+            Field::new("Authenticator", authenticator),
+        ];
+        for attribute in &packet.attributes {
+            rows.push(Field::new(attribute.name(), attribute.display_value()));
+        }
+        if packet.malformed_attributes {
+            rows.push(Field::new(
+                "Attributes",
+                Span::styled("Malformed", self.theme.error_style()),
+            ));
+        }
+        rows
+    }
 
-        frame.render_widget(table, data_area);
-        frame.render_widget(title, title_area);
+    fn ssh_fields(&self, packet: &crate::packet_data::SshPacketInfo) -> Vec<Field> {
+        let mut rows = Vec::new();
+        if let Some(from_server) = packet.from_server {
+            rows.push(Field::new(
+                "Sent By",
+                if from_server { "Server" } else { "Client" },
+            ));
+        }
+        rows.extend([
+            Field::new("Protocol Version", packet.protocol_version.clone()),
+            Field::new("Software Version", packet.software_version.clone()),
+        ]);
+        if let Some(comments) = &packet.comments {
+            rows.push(Field::new("Comments", comments.clone()));
+        }
+        rows
+    }
+
+    fn gtp_fields(&self, packet: &crate::packet_data::GtpPacketInfo) -> Vec<Field> {
+        let mut rows = vec![
+            Field::new("Version", packet.version.to_string()),
+            Field::new(
+                "Message Type",
+                Self::gtp_message_type_description(packet.message_type),
+            ),
+            Field::new("TEID", format!("0x{:08x} ({})", packet.teid, packet.teid)),
+            Field::new("Length", packet.length.to_string()),
+        ];
+        if let Some(sequence) = packet.sequence {
+            rows.push(Field::new("Sequence Number", sequence.to_string()));
+        }
+        rows
+    }
+
+    fn vxlan_fields(&self, packet: &crate::packet_data::VxlanPacketInfo) -> Vec<Field> {
+        vec![
+            Field::new("Flags", format!("0x{:02x}", packet.flags)),
+            Field::new("VNI", format!("{} (0x{:06x})", packet.vni, packet.vni)),
+            Field::new("Inner Frame Length", packet.length.to_string()),
+        ]
+    }
+
+    /// GTP-U message name with its number, e.g. "G-PDU (255)".
+    pub fn gtp_message_type_description(message_type: u8) -> String {
+        let name = match message_type {
+            1 => "Echo Request",
+            2 => "Echo Response",
+            26 => "Error Indication",
+            31 => "Supported Extension Headers Notification",
+            254 => "End Marker",
+            255 => "G-PDU",
+            _ => "Unknown",
+        };
+        format!("{name} ({message_type})")
+    }
+
+    fn dns_type_name(record_type: u16) -> String {
+        match record_type {
+            1 => "A".to_string(),
+            2 => "NS".to_string(),
+            5 => "CNAME".to_string(),
+            6 => "SOA".to_string(),
+            12 => "PTR".to_string(),
+            15 => "MX".to_string(),
+            16 => "TXT".to_string(),
+            28 => "AAAA".to_string(),
+            33 => "SRV".to_string(),
+            41 => "OPT".to_string(),
+            65 => "HTTPS".to_string(),
+            255 => "ANY".to_string(),
+            other => format!("TYPE{other}"),
+        }
+    }
+
+    fn dns_rcode_name(rcode: u8) -> &'static str {
+        match rcode {
+            0 => "NoError",
+            1 => "FormErr",
+            2 => "ServFail",
+            3 => "NXDomain",
+            4 => "NotImp",
+            5 => "Refused",
+            _ => "Unknown",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pnet::packet::tcp::TcpOption;
+
+    use super::PopupWidget;
+
+    #[test]
+    fn tcp_options_are_split_into_name_and_value_without_padding() {
+        let options = [
+            TcpOption::mss(1460),
+            TcpOption::nop(),
+            TcpOption::wscale(7),
+            TcpOption::sack_perm(),
+            TcpOption::timestamp(100, 0),
+        ];
+        let rows: Vec<(String, String)> =
+            options.iter().filter_map(PopupWidget::tcp_option).collect();
+        assert_eq!(
+            rows,
+            [
+                ("MSS", "1460"),
+                ("Window Scale", "7 (x128)"),
+                ("SACK Permitted", ""),
+                ("Timestamps", "TSval 100 TSecr 0"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+    }
+
+    #[test]
+    fn inspector_reads_only_the_sizes_the_selection_covers() {
+        let fields = PopupWidget::inspect_bytes(0x1a, &[0xc0, 0xa8, 0x00, 0x01, 0x41]);
+        let fields: Vec<(String, String)> = fields
+            .into_iter()
+            .map(|field| (field.name, field.value.content.into_owned()))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("Offset", "0x001a-0x001e (5 bytes)"),
+                ("u8", "192"),
+                ("u16 BE / LE", "49320 / 43200"),
+                ("u32 BE / LE", "3232235521 / 16820416"),
+                ("IPv4", "192.168.0.1"),
+                ("ASCII", "····A"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+        );
     }
 }
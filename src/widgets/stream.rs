@@ -0,0 +1,111 @@
+use std::net::{IpAddr, SocketAddr};
+
+use ratatui::{
+    layout::Rect,
+    style::Stylize,
+    text::{Line, Text},
+    widgets::{Block, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    flows::{FlowDirection, StreamSegment},
+    keymap::Keymap,
+    strings,
+    theme::Theme,
+    widgets::{layout_helper::LayoutHelper, popup::PopupWidget},
+};
+
+/// The payloads of a flow one after the other, each under a line saying when it was sent
+/// and which way, colored by direction.
+pub struct StreamWidget<'a> {
+    /// Client first.
+    endpoints: [SocketAddr; 2],
+    segments: &'a [StreamSegment<'a>],
+    keymap: &'a Keymap,
+    theme: &'a Theme,
+}
+
+impl<'a> StreamWidget<'a> {
+    pub fn new(
+        endpoints: [(IpAddr, u16); 2],
+        segments: &'a [StreamSegment<'a>],
+        keymap: &'a Keymap,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            endpoints: endpoints.map(SocketAddr::from),
+            segments,
+            keymap,
+            theme,
+        }
+    }
+
+    /// Renders the stream from line `scroll`, which is kept within the text.
+    pub fn render(&self, frame: &mut Frame, area: Rect, scroll: &mut u16) {
+        let popup_area = LayoutHelper::create_centered_layout(area, 80, 80);
+        let [client, server] = self.endpoints;
+        let block = Block::bordered()
+            .title(
+                strings::get("stream.title")
+                    .replace("{client}", &client.to_string())
+                    .replace("{server}", &server.to_string()),
+            )
+            .title_bottom(self.keymap.fill_keys(strings::get("stream.help")))
+            .border_style(self.theme.border_style())
+            .title_style(self.theme.title_style());
+
+        let text = self.text();
+        *scroll =
+            (*scroll).min(u16::try_from(text.lines.len().saturating_sub(1)).unwrap_or(u16::MAX));
+        let paragraph = Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .scroll((*scroll, 0))
+            .block(block);
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(paragraph, popup_area);
+    }
+
+    fn text(&self) -> Text<'static> {
+        let bytes = |direction: FlowDirection| -> usize {
+            self.segments
+                .iter()
+                .filter(|segment| segment.direction == direction)
+                .map(|segment| segment.payload.len())
+                .sum()
+        };
+        let mut lines = vec![Line::from(
+            strings::get("stream.summary")
+                .replace("{datagrams}", &self.segments.len().to_string())
+                .replace("{sent}", &bytes(FlowDirection::ClientToServer).to_string())
+                .replace(
+                    "{received}",
+                    &bytes(FlowDirection::ServerToClient).to_string(),
+                ),
+        )
+        .style(self.theme.dimmed_style())];
+        for segment in self.segments {
+            let style = self.theme.stream_style(segment.direction);
+            let (sender, receiver) = match segment.direction {
+                FlowDirection::ClientToServer => (self.endpoints[0], self.endpoints[1]),
+                FlowDirection::ServerToClient => (self.endpoints[1], self.endpoints[0]),
+            };
+            lines.push(Line::default());
+            lines.push(
+                Line::from(format!(
+                    "{} {sender} → {receiver} ({} bytes)",
+                    segment.timestamp.format("%H:%M:%S%.3f"),
+                    segment.payload.len()
+                ))
+                .style(style.bold()),
+            );
+            lines.extend(
+                PopupWidget::printable_text(segment.payload)
+                    .lines()
+                    .map(|line| Line::from(line.to_string()).style(style)),
+            );
+        }
+        Text::from(lines)
+    }
+}
@@ -0,0 +1,105 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    text::Text,
+    widgets::{Block, Cell, Clear, Row, Table},
+    Frame,
+};
+
+use crate::{
+    stats::{TalkerStats, TalkerTotals},
+    strings,
+    theme::Theme,
+};
+
+pub struct TopTalkersWidget<'a> {
+    stats: &'a TalkerStats,
+    theme: &'a Theme,
+    count: usize,
+}
+
+impl<'a> TopTalkersWidget<'a> {
+    const WIDTH: u16 = 46;
+
+    pub fn new(stats: &'a TalkerStats, theme: &'a Theme, count: usize) -> Self {
+        Self {
+            stats,
+            theme,
+            count,
+        }
+    }
+
+    /// Renders the panel anchored to the bottom-right corner of `area`.
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let height = (2 * (self.count as u16 + 1) + 3).min(area.height);
+        let width = Self::WIDTH.min(area.width);
+        let panel_area = Rect::new(
+            area.right().saturating_sub(width),
+            area.bottom().saturating_sub(height),
+            width,
+            height,
+        );
+
+        let block = Block::bordered()
+            .title(strings::get("top_talkers.title"))
+            .border_style(self.theme.border_style())
+            .title_style(self.theme.title_style());
+        let inner = block.inner(panel_area);
+
+        frame.render_widget(Clear, panel_area);
+        frame.render_widget(block, panel_area);
+
+        let [bytes_area, packets_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)]).areas(inner);
+        frame.render_widget(
+            self.build_table(
+                strings::get("top_talkers.by_bytes"),
+                self.stats.top_by_bytes(self.count),
+            ),
+            bytes_area,
+        );
+        frame.render_widget(
+            self.build_table(
+                strings::get("top_talkers.by_packets"),
+                self.stats.top_by_packets(self.count),
+            ),
+            packets_area,
+        );
+    }
+
+    fn build_table(
+        &self,
+        title: &'static str,
+        talkers: Vec<(std::net::IpAddr, TalkerTotals)>,
+    ) -> Table<'static> {
+        let header = [
+            title,
+            strings::get("top_talkers.bytes"),
+            strings::get("top_talkers.packets"),
+        ]
+        .into_iter()
+        .map(|title| Cell::from(Text::from(title).bold()))
+        .collect::<Row>();
+
+        let rows: Vec<Row> = talkers
+            .into_iter()
+            .map(|(ip, totals)| {
+                Row::new(vec![
+                    ip.to_string(),
+                    totals.bytes.to_string(),
+                    totals.packets.to_string(),
+                ])
+            })
+            .collect();
+
+        Table::new(
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(10),
+                Constraint::Length(7),
+            ],
+        )
+        .header(header)
+    }
+}